@@ -26,6 +26,7 @@ import type {
   BackendSyncBootstrapResult,
   BackendSyncCycleResult,
   BackendSyncEngineStatusResult,
+  BackendSyncEventAudit,
   BackendSyncPairingSourceStatusResult,
   BackendSyncReconcileReadyResult,
   BackendSyncSnapshotUploadResult,
@@ -153,6 +154,28 @@ export const deviceSyncCancelSnapshotUpload =
     return invoke<BackendSyncBackgroundEngineResult>("device_sync_cancel_snapshot_upload");
   };
 
+export const deviceSyncSetActiveTeam = async (
+  teamId: string | null,
+): Promise<BackendSyncBackgroundEngineResult> => {
+  return invoke<BackendSyncBackgroundEngineResult>("set_active_team", { teamId });
+};
+
+export const setSnapshotCompression = async (enabled: boolean): Promise<void> => {
+  return invoke<void>("set_snapshot_compression", { enabled });
+};
+
+export const getSyncAuditLog = async (limit?: number): Promise<BackendSyncEventAudit[]> => {
+  return invoke<BackendSyncEventAudit[]>("get_sync_audit_log", { limit });
+};
+
+export const getSyncedAccountIds = async (): Promise<string[]> => {
+  return invoke<string[]>("get_synced_account_ids");
+};
+
+export const setSyncedAccountIds = async (accountIds: string[]): Promise<void> => {
+  return invoke<void>("set_synced_account_ids", { accountIds });
+};
+
 // Device Management Commands
 export const getDevice = async (deviceId?: string): Promise<Device> => {
   return invoke<Device>("get_device", { deviceId });
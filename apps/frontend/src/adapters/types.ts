@@ -220,6 +220,9 @@ export interface BackendSyncEngineStatusResult {
   nextRetryAt: string | null;
   lastCycleStatus: string | null;
   lastCycleDurationMs: number | null;
+  lastCyclePushedBytes: number | null;
+  lastCyclePulledBytes: number | null;
+  lastCycleEventsApplied: number | null;
   backgroundRunning: boolean;
   bootstrapRequired: boolean;
 }
@@ -304,6 +307,8 @@ export interface BackendSyncCycleResult {
   bootstrapSnapshotId: string | null;
   bootstrapSnapshotSeq: number | null;
   deadLetterCount: number;
+  pushedBytes: number;
+  pulledBytes: number;
 }
 
 export interface BackendSyncBackgroundEngineResult {
@@ -318,6 +323,16 @@ export interface BackendSyncSnapshotUploadResult {
   message: string;
 }
 
+/** What replay did with one remote event. */
+export interface BackendSyncEventAudit {
+  entity: string;
+  entityId: string;
+  eventId: string;
+  outcome: "applied" | "skipped_lww" | "rejected_validation";
+  reason: string | null;
+  recordedAt: string;
+}
+
 /**
  * Ephemeral key pair for secure pairing operations.
  */
@@ -237,6 +237,11 @@ export const COMMANDS: CommandMap = {
     method: "POST",
     path: "/connect/device/stop-background",
   },
+  set_active_team: { method: "POST", path: "/connect/device/active-team" },
+  set_snapshot_compression: { method: "POST", path: "/connect/device/snapshot-compression" },
+  get_sync_audit_log: { method: "GET", path: "/connect/device/audit-log" },
+  get_synced_account_ids: { method: "GET", path: "/connect/device/synced-account-ids" },
+  set_synced_account_ids: { method: "POST", path: "/connect/device/synced-account-ids" },
   device_sync_generate_snapshot_now: {
     method: "POST",
     path: "/connect/device/generate-snapshot",
@@ -1051,6 +1056,26 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       body = JSON.stringify(payload ?? {});
       break;
     }
+    case "set_active_team": {
+      const { teamId } = payload as { teamId: string | null };
+      body = JSON.stringify({ teamId });
+      break;
+    }
+    case "set_snapshot_compression": {
+      const { enabled } = payload as { enabled: boolean };
+      body = JSON.stringify({ enabled });
+      break;
+    }
+    case "get_sync_audit_log": {
+      const { limit } = (payload ?? {}) as { limit?: number };
+      if (limit !== undefined) url += `?limit=${limit}`;
+      break;
+    }
+    case "set_synced_account_ids": {
+      const { accountIds } = payload as { accountIds: string[] };
+      body = JSON.stringify({ accountIds });
+      break;
+    }
     // Wealthfolio Connect commands
     case "store_sync_session": {
       const { refreshToken } = payload as {
@@ -23,6 +23,7 @@ export type {
   BackendSyncBootstrapResult,
   BackendSyncCycleResult,
   BackendSyncEngineStatusResult,
+  BackendSyncEventAudit,
   BackendSyncReconcileReadyResult,
   BackendSyncSnapshotUploadResult,
   BackendSyncStateResult,
@@ -220,6 +221,7 @@ export {
   deviceSyncCancelSnapshotUpload,
   deviceSyncGenerateSnapshotNow,
   deviceSyncReconcileReadyState,
+  deviceSyncSetActiveTeam,
   deviceSyncStartBackgroundEngine,
   deviceSyncStopBackgroundEngine,
   enableDeviceSync,
@@ -233,7 +235,9 @@ export {
   getPlatforms,
   getSubscriptionPlans,
   getSubscriptionPlansPublic,
+  getSyncedAccountIds,
   getSyncedAccounts,
+  getSyncAuditLog,
   getSyncEngineStatus,
   getUserInfo,
   listBrokerAccounts,
@@ -242,6 +246,8 @@ export {
   reinitializeDeviceSync,
   resetTeamSync,
   restoreSyncSession,
+  setSnapshotCompression,
+  setSyncedAccountIds,
   revokeDevice,
   storeSyncSession,
   syncBootstrapSnapshotIfNeeded,
@@ -192,6 +192,9 @@ class SyncService {
     nextRetryAt: string | null;
     lastCycleStatus: string | null;
     lastCycleDurationMs: number | null;
+    lastCyclePushedBytes: number | null;
+    lastCyclePulledBytes: number | null;
+    lastCycleEventsApplied: number | null;
     backgroundRunning: boolean;
     bootstrapRequired: boolean;
   }> {
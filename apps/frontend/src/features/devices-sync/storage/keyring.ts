@@ -6,6 +6,8 @@ import { getSecret, setSecret, logger } from "@/adapters";
 
 // Storage key for sync identity in keychain
 const SYNC_IDENTITY_KEY = "sync_identity";
+// Keychain entry naming the active sync team (written by the backend)
+const ACTIVE_SYNC_TEAM_KEY = "sync_active_team";
 
 /**
  * Device sync identity stored in keychain as a single JSON object
@@ -21,6 +23,10 @@ export interface SyncIdentity {
   rootKey?: string;
   /** E2EE key version (epoch) */
   keyVersion?: number;
+  /** Root key replaced by the last rotation, kept so older events still decrypt */
+  previousRootKey?: string;
+  /** Key version of `previousRootKey` */
+  previousKeyVersion?: number;
   /** Device X25519 secret key (base64 encoded) */
   deviceSecretKey?: string;
   /** Device X25519 public key (base64 encoded) */
@@ -42,12 +48,21 @@ function migrateIdentity(data: Record<string, unknown>): SyncIdentity | null {
   return data as unknown as SyncIdentity;
 }
 
+/**
+ * Keychain key of the active team's sync identity. Mirrors the backend's
+ * `sync_identity_key`: scoped per team once one is active, unscoped otherwise.
+ */
+async function identityKey(): Promise<string> {
+  const teamId = (await getSecret(ACTIVE_SYNC_TEAM_KEY))?.trim();
+  return teamId ? `${SYNC_IDENTITY_KEY}:${teamId}` : SYNC_IDENTITY_KEY;
+}
+
 /**
  * Get the current sync identity from keychain
  */
 async function getIdentity(): Promise<SyncIdentity | null> {
   try {
-    const json = await getSecret(SYNC_IDENTITY_KEY);
+    const json = await getSecret(await identityKey());
     if (!json) return null;
     const data = JSON.parse(json);
     return migrateIdentity(data);
@@ -61,7 +76,7 @@ async function getIdentity(): Promise<SyncIdentity | null> {
  * Save the sync identity to keychain
  */
 async function saveIdentity(identity: SyncIdentity): Promise<void> {
-  await setSecret(SYNC_IDENTITY_KEY, JSON.stringify(identity));
+  await setSecret(await identityKey(), JSON.stringify(identity));
 }
 
 /**
@@ -87,12 +102,17 @@ export const syncStorage = {
 
   /**
    * Clear only the root key (used during key rotation or re-pairing).
+   * The cleared key is kept as the previous key so events encrypted under it
+   * still decrypt once new credentials are set.
    */
   async clearRootKey(): Promise<void> {
     const current = await getIdentity();
     if (current) {
-      const { rootKey: _, deviceSecretKey: __, devicePublicKey: ___, ...rest } = current;
-      await saveIdentity(rest as SyncIdentity);
+      const { rootKey, deviceSecretKey: __, devicePublicKey: ___, ...rest } = current;
+      await saveIdentity({
+        ...rest,
+        ...(rootKey && { previousRootKey: rootKey, previousKeyVersion: current.keyVersion }),
+      } as SyncIdentity);
     }
   },
 
@@ -117,8 +137,14 @@ export const syncStorage = {
     if (!current) {
       throw new Error("No sync identity exists. Set device nonce first.");
     }
+    // Keep the key being rotated out so pre-rotation events still decrypt.
+    const rotated = current.rootKey !== undefined && current.keyVersion !== keyVersion;
     await saveIdentity({
       ...current,
+      ...(rotated && {
+        previousRootKey: current.rootKey,
+        previousKeyVersion: current.keyVersion,
+      }),
       rootKey,
       keyVersion,
       ...(keypair && {
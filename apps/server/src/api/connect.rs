@@ -3,7 +3,10 @@
 //! This module provides REST endpoints for syncing broker accounts and activities
 //! from the Wealthfolio Connect cloud service.
 
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
 use axum::{
     extract::{Query, State},
@@ -25,11 +28,12 @@ use wealthfolio_connect::{
         BrokerApiClient, PlansResponse, SyncAccountsResponse, SyncActivitiesResponse,
         SyncConnectionsResponse, UserInfo,
     },
-    ensure_valid_access_token, fetch_subscription_plans_public, ConnectApiClient, SyncConfig,
-    SyncOrchestrator, SyncProgressPayload, SyncProgressReporter, SyncResult, TokenLifecycleConfig,
+    ensure_valid_access_token, fetch_subscription_plans_public, ConnectApiClient,
+    LifecycleTokenSource, RefreshingConnectClient, SyncConfig, SyncOrchestrator,
+    SyncProgressPayload, SyncProgressReporter, SyncResult, TokenLifecycleConfig,
     TokenLifecycleError, CLOUD_ACCESS_TOKEN_KEY, CLOUD_REFRESH_TOKEN_KEY,
 };
-use wealthfolio_core::accounts::TrackingMode;
+use wealthfolio_core::sync::SyncEventAudit;
 use wealthfolio_device_sync::{EnableSyncResult, SyncState, SyncStateResult};
 
 const DEVICE_ID_KEY: &str = "sync_device_id";
@@ -99,6 +103,19 @@ async fn create_connect_client(state: &AppState) -> ApiResult<ConnectApiClient>
     ConnectApiClient::new(&base_url, &token).map_err(|e| ApiError::Internal(e.to_string()))
 }
 
+/// Create a client for broker calls that re-mints the access token and
+/// retries once if the cloud API rejects it mid-sync.
+async fn create_refreshing_connect_client(state: &AppState) -> ApiResult<RefreshingConnectClient> {
+    let tokens = LifecycleTokenSource::new(
+        state.secret_store.clone(),
+        state.token_lifecycle.clone(),
+        token_lifecycle_config(),
+    );
+    Ok(create_connect_client(state)
+        .await?
+        .with_token_refresh(Arc::new(tokens)))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Request/Response Types
 // ─────────────────────────────────────────────────────────────────────────────
@@ -133,6 +150,9 @@ struct DeviceSyncEngineStatusResponse {
     next_retry_at: Option<String>,
     last_cycle_status: Option<String>,
     last_cycle_duration_ms: Option<i64>,
+    last_cycle_pushed_bytes: Option<i64>,
+    last_cycle_pulled_bytes: Option<i64>,
+    last_cycle_events_applied: Option<i64>,
     background_running: bool,
     bootstrap_required: bool,
 }
@@ -368,12 +388,30 @@ pub(crate) async fn mint_access_token(state: &AppState) -> ApiResult<String> {
     .map_err(map_token_lifecycle_error)
 }
 
+/// Refresh the cloud access token in the background so the first sync after
+/// launch does not wait on the auth round-trip. Concurrent warmups and syncs
+/// share a single refresh.
+pub fn spawn_access_token_warmup(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        match mint_access_token(&state).await {
+            Ok(_) => debug!("[Connect] Access token warmed"),
+            Err(err) => debug!("[Connect] Access token warmup skipped: {}", err),
+        }
+    });
+}
+
+async fn warm_sync_session(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    ensure_cloud_sync_enabled()?;
+    spawn_access_token_warmup(state);
+    Ok(StatusCode::NO_CONTENT)
+}
+
 fn map_token_lifecycle_error(err: TokenLifecycleError) -> ApiError {
     match err {
-        TokenLifecycleError::Unauthorized(message) => ApiError::Forbidden(message),
-        TokenLifecycleError::NotConfigured(message)
-        | TokenLifecycleError::RefreshFailed(message)
-        | TokenLifecycleError::Internal(message) => ApiError::Internal(message),
+        TokenLifecycleError::Unauthorized(message) => ApiError::SessionExpired(message),
+        TokenLifecycleError::NotConfigured(message) => ApiError::ConfigMissing(message),
+        TokenLifecycleError::RefreshFailed(message) => ApiError::Provider(message),
+        TokenLifecycleError::Internal(message) => ApiError::Internal(message),
     }
 }
 
@@ -387,13 +425,13 @@ async fn sync_broker_connections(
     ensure_connect_sync_enabled()?;
     info!("[Connect] Syncing broker connections...");
 
-    let client = create_connect_client(&state).await?;
+    let client = create_refreshing_connect_client(&state).await?;
 
     // Fetch connections from cloud using the shared client
     let connections = client
         .list_connections()
         .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+        .map_err(|e| ApiError::Provider(e.to_string()))?;
 
     info!(
         "[Connect] Fetched {} connections from cloud",
@@ -421,13 +459,13 @@ async fn sync_broker_accounts(
     ensure_connect_sync_enabled()?;
     info!("[Connect] Syncing broker accounts...");
 
-    let client = create_connect_client(&state).await?;
+    let client = create_refreshing_connect_client(&state).await?;
 
     // Fetch accounts from cloud using the shared client
     let accounts = client
         .list_accounts(None)
         .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+        .map_err(|e| ApiError::Provider(e.to_string()))?;
 
     info!("[Connect] Fetched {} accounts from cloud", accounts.len());
 
@@ -486,11 +524,17 @@ async fn sync_broker_data(State(state): State<Arc<AppState>>) -> StatusCode {
         }
     }
 
+    let Some(cancel) = state.broker_sync_runtime.try_start() else {
+        info!("[Connect] Broker sync skipped: a sync is already running");
+        return StatusCode::CONFLICT;
+    };
+
     info!("[Connect] Starting broker data sync (non-blocking)...");
 
     // Spawn background task to perform the sync
-    tokio::spawn(async move {
-        match perform_broker_sync(&state).await {
+    let runtime = state.broker_sync_runtime.clone();
+    let handle = tokio::spawn(async move {
+        match perform_broker_sync(&state, cancel).await {
             Ok(_result) => {
                 info!("[Connect] Broker sync completed successfully");
                 // Events are emitted by the orchestrator via EventBusProgressReporter
@@ -502,9 +546,22 @@ async fn sync_broker_data(State(state): State<Arc<AppState>>) -> StatusCode {
         }
     });
 
+    runtime.attach(handle);
+
     StatusCode::ACCEPTED
 }
 
+/// Ask the running background broker sync to stop at its next page boundary.
+/// Returns 404 when no sync is running.
+async fn cancel_broker_sync(State(state): State<Arc<AppState>>) -> StatusCode {
+    if state.broker_sync_runtime.cancel() {
+        info!("[Connect] Broker sync cancellation requested");
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
 /// Check if the current user's plan includes broker sync.
 /// Used by the scheduler to skip sync for basic-plan users.
 pub async fn has_broker_sync(state: &AppState) -> Result<bool, String> {
@@ -518,10 +575,16 @@ pub async fn has_broker_sync(state: &AppState) -> Result<bool, String> {
 /// Core broker sync logic - syncs connections, accounts, and activities from cloud to local DB.
 /// Uses the centralized SyncOrchestrator for full pagination support.
 /// Also used by the background scheduler for periodic syncs.
-pub async fn perform_broker_sync(state: &AppState) -> Result<SyncResult, String> {
+///
+/// The sync stops with an error once `cancel` is set; callers claim it from
+/// `state.broker_sync_runtime` so only one sync runs at a time.
+pub async fn perform_broker_sync(
+    state: &AppState,
+    cancel: Arc<AtomicBool>,
+) -> Result<SyncResult, String> {
     ensure_connect_sync_enabled().map_err(|e| e.to_string())?;
     // Create API client
-    let client = create_connect_client(state)
+    let client = create_refreshing_connect_client(state)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -531,7 +594,8 @@ pub async fn perform_broker_sync(state: &AppState) -> Result<SyncResult, String>
         state.connect_sync_service.clone(),
         reporter,
         SyncConfig::default(),
-    );
+    )
+    .with_cancel_flag(cancel);
 
     // Run the sync via the centralized orchestrator
     // Note: Asset enrichment is handled automatically via domain events (AssetsCreated)
@@ -544,201 +608,112 @@ async fn perform_broker_activities_only_sync(
     state: &AppState,
 ) -> Result<SyncActivitiesResponse, String> {
     ensure_connect_sync_enabled().map_err(|e| e.to_string())?;
-    let client = create_connect_client(state)
+    let client = create_refreshing_connect_client(state)
         .await
         .map_err(|e| e.to_string())?;
 
-    let synced_accounts = state
-        .connect_sync_service
-        .get_synced_accounts()
-        .map_err(|e| e.to_string())?;
-
-    let mut summary = SyncActivitiesResponse::default();
-    let end_date = chrono::Utc::now().date_naive();
-    let end_date_str = end_date.format("%Y-%m-%d").to_string();
-    let page_limit: i64 = 1000;
-
-    for account in synced_accounts {
-        if account.tracking_mode != TrackingMode::Transactions {
-            continue;
-        }
+    // Per-page progress only; start/complete events stay with the full sync.
+    let reporter = Arc::new(EventBusProgressReporter::new(state.event_bus.clone()));
+    let orchestrator = SyncOrchestrator::new(
+        state.connect_sync_service.clone(),
+        reporter,
+        SyncConfig::default(),
+    );
+    orchestrator.sync_activities_only(&client).await
+}
 
-        let Some(provider_account_id) = account.provider_account_id.clone() else {
-            continue;
-        };
+/// How long fetched subscription plans are served from memory.
+const PLANS_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
 
-        let account_id = account.id.clone();
-        let account_name = account.name.clone();
+/// Subscription plans change rarely, so they are cached instead of fetched
+/// on every request.
+struct PlansCache {
+    ttl: Duration,
+    entry: RwLock<Option<(Instant, PlansResponse)>>,
+}
 
-        if let Err(err) = state
-            .connect_sync_service
-            .mark_activity_sync_attempt(account_id.clone())
-            .await
-        {
-            error!(
-                "[Connect] Failed to mark activity sync attempt for {}: {}",
-                account_name, err
-            );
-            summary.accounts_failed += 1;
-            continue;
+impl PlansCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: RwLock::new(None),
         }
+    }
 
-        let start_date = state
-            .connect_sync_service
-            .get_activity_sync_state(&account_id)
-            .map_err(|e| e.to_string())?
-            .and_then(|s| s.last_successful_at)
-            .map(|dt| (dt.date_naive() - chrono::Days::new(1)).min(end_date))
-            .map(|d| d.format("%Y-%m-%d").to_string());
-
-        info!(
-            "[Connect] Activities-only sync for account '{}' (provider={}): {} -> {}",
-            account_name,
-            provider_account_id,
-            start_date.as_deref().unwrap_or("ALL"),
-            end_date_str
-        );
-
-        let mut offset: i64 = 0;
-        let mut account_upserted = 0usize;
-        let mut account_assets = 0usize;
-        let mut account_new_asset_ids: Vec<String> = Vec::new();
-        let mut account_failed = false;
-
-        loop {
-            let page = match client
-                .get_account_activities(
-                    &provider_account_id,
-                    start_date.as_deref(),
-                    Some(&end_date_str),
-                    Some(offset),
-                    Some(page_limit),
-                )
-                .await
-            {
-                Ok(page) => page,
-                Err(err) => {
-                    error!(
-                        "[Connect] Failed to fetch activities for {}: {}",
-                        account_name, err
-                    );
-                    let _ = state
-                        .connect_sync_service
-                        .finalize_activity_sync_failure(account_id.clone(), err.to_string(), None)
-                        .await;
-                    summary.accounts_failed += 1;
-                    account_failed = true;
-                    break;
-                }
-            };
-
-            let received = page.data.len() as i64;
-            if received == 0 {
-                break;
-            }
-
-            match state
-                .connect_sync_service
-                .upsert_account_activities(account_id.clone(), None, page.data)
-                .await
-            {
-                Ok((upserted, assets, new_asset_ids, _needs_review)) => {
-                    account_upserted += upserted;
-                    account_assets += assets;
-                    account_new_asset_ids.extend(new_asset_ids);
+    /// Cached plans if still fresh, otherwise the result of `fetch`, which is
+    /// cached on success. `refresh` skips the cached copy.
+    async fn get_or_fetch<F, Fut>(&self, refresh: bool, fetch: F) -> ApiResult<PlansResponse>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ApiResult<PlansResponse>>,
+    {
+        if !refresh {
+            let entry = self.entry.read().unwrap_or_else(|e| e.into_inner());
+            if let Some((fetched_at, plans)) = entry.as_ref() {
+                if fetched_at.elapsed() < self.ttl {
+                    return Ok(plans.clone());
                 }
-                Err(err) => {
-                    error!(
-                        "[Connect] Failed to upsert activities for {}: {}",
-                        account_name, err
-                    );
-                    let _ = state
-                        .connect_sync_service
-                        .finalize_activity_sync_failure(account_id.clone(), err.to_string(), None)
-                        .await;
-                    summary.accounts_failed += 1;
-                    account_failed = true;
-                    break;
-                }
-            }
-
-            let next_offset = offset + received;
-            let has_more = match page.pagination.as_ref() {
-                Some(p) => match p.has_more {
-                    Some(true) => true,
-                    Some(false) => false,
-                    None => {
-                        if let Some(total) = p.total {
-                            next_offset < total
-                        } else if let Some(limit) = p.limit {
-                            received >= limit
-                        } else {
-                            received >= page_limit
-                        }
-                    }
-                },
-                None => received >= page_limit,
-            };
-
-            offset = next_offset;
-
-            if !has_more {
-                break;
             }
         }
+        let plans = fetch().await?;
+        *self.entry.write().unwrap_or_else(|e| e.into_inner()) =
+            Some((Instant::now(), plans.clone()));
+        Ok(plans)
+    }
+}
 
-        if account_failed {
-            continue;
-        }
-
-        let now = chrono::Utc::now().to_rfc3339();
-        if let Err(err) = state
-            .connect_sync_service
-            .finalize_activity_sync_success(account_id.clone(), now, None)
-            .await
-        {
-            error!(
-                "[Connect] Failed to finalize activity sync success for {}: {}",
-                account_name, err
-            );
-            summary.accounts_failed += 1;
-            continue;
-        }
+fn plans_cache() -> &'static PlansCache {
+    static CACHE: OnceLock<PlansCache> = OnceLock::new();
+    CACHE.get_or_init(|| PlansCache::new(PLANS_CACHE_TTL))
+}
 
-        summary.accounts_synced += 1;
-        summary.activities_upserted += account_upserted;
-        summary.assets_inserted += account_assets;
-        summary.new_asset_ids.extend(account_new_asset_ids);
-    }
+fn public_plans_cache() -> &'static PlansCache {
+    static CACHE: OnceLock<PlansCache> = OnceLock::new();
+    CACHE.get_or_init(|| PlansCache::new(PLANS_CACHE_TTL))
+}
 
-    Ok(summary)
+#[derive(Debug, Default, Deserialize)]
+struct PlansQuery {
+    /// Bypass the cache and fetch plans from the cloud API.
+    #[serde(default)]
+    refresh: bool,
 }
 
 async fn get_subscription_plans(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<PlansQuery>,
 ) -> ApiResult<Json<PlansResponse>> {
     ensure_cloud_sync_enabled()?;
-    info!("[Connect] Getting subscription plans...");
-
-    let client = create_connect_client(&state).await?;
 
-    let plans = client
-        .get_subscription_plans()
-        .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let plans = plans_cache()
+        .get_or_fetch(query.refresh, || async {
+            info!("[Connect] Getting subscription plans...");
+            create_connect_client(&state)
+                .await?
+                .get_subscription_plans()
+                .await
+                .map_err(|e| ApiError::Provider(e.to_string()))
+        })
+        .await?;
 
     Ok(Json(plans))
 }
 
-async fn get_subscription_plans_public() -> ApiResult<Json<PlansResponse>> {
+async fn get_subscription_plans_public(
+    Query(query): Query<PlansQuery>,
+) -> ApiResult<Json<PlansResponse>> {
     ensure_cloud_sync_enabled()?;
-    info!("[Connect] Getting subscription plans (public)...");
 
     let base_url = cloud_api_base_url()?;
 
-    let plans = fetch_subscription_plans_public(&base_url)
-        .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let plans = public_plans_cache()
+        .get_or_fetch(query.refresh, || async {
+            info!("[Connect] Getting subscription plans (public)...");
+            fetch_subscription_plans_public(&base_url)
+                .await
+                .map_err(|e| ApiError::Provider(e.to_string()))
+        })
+        .await?;
 
     Ok(Json(plans))
 }
@@ -751,7 +726,7 @@ async fn get_user_info(State(state): State<Arc<AppState>>) -> ApiResult<Json<Use
     let user_info = client
         .get_user_info()
         .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+        .map_err(|e| ApiError::Provider(e.to_string()))?;
 
     Ok(Json(user_info))
 }
@@ -766,12 +741,12 @@ async fn list_broker_connections(
     ensure_connect_sync_enabled()?;
     info!("[Connect] Listing broker connections from cloud...");
 
-    let client = create_connect_client(&state).await?;
+    let client = create_refreshing_connect_client(&state).await?;
 
     let connections = client
         .list_connections()
         .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+        .map_err(|e| ApiError::Provider(e.to_string()))?;
 
     info!("[Connect] Found {} broker connections", connections.len());
     Ok(Json(connections))
@@ -783,12 +758,12 @@ async fn list_broker_accounts(
     ensure_connect_sync_enabled()?;
     info!("[Connect] Listing broker accounts from cloud...");
 
-    let client = create_connect_client(&state).await?;
+    let client = create_refreshing_connect_client(&state).await?;
 
     let accounts = client
         .list_accounts(None)
         .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+        .map_err(|e| ApiError::Provider(e.to_string()))?;
 
     info!("[Connect] Found {} broker accounts", accounts.len());
     Ok(Json(accounts))
@@ -1027,6 +1002,9 @@ async fn get_device_sync_engine_status(
         next_retry_at: status.next_retry_at,
         last_cycle_status: status.last_cycle_status,
         last_cycle_duration_ms: status.last_cycle_duration_ms,
+        last_cycle_pushed_bytes: status.last_cycle_pushed_bytes,
+        last_cycle_pulled_bytes: status.last_cycle_pulled_bytes,
+        last_cycle_events_applied: status.last_cycle_events_applied,
         background_running: status.background_running,
         bootstrap_required: status.bootstrap_required,
     }))
@@ -1078,20 +1056,7 @@ async fn bootstrap_device_snapshot(
         .await
         .map_err(ApiError::Internal)?;
 
-    // Start the background sync engine whenever this device is READY.
-    let should_start_engine = if let Ok(token) = mint_access_token(&state).await {
-        state
-            .device_enroll_service
-            .get_sync_state(&token)
-            .await
-            .map(|sync_state| sync_state.state == SyncState::Ready)
-            .unwrap_or(false)
-    } else {
-        false
-    };
-    if should_start_engine {
-        let _ = device_sync_engine::ensure_background_engine_started(Arc::clone(&state)).await;
-    }
+    device_sync_engine::ensure_background_engine_started_if_ready(Arc::clone(&state)).await;
 
     Ok(Json(DeviceSyncBootstrapResponse {
         status: result.status,
@@ -1167,6 +1132,99 @@ async fn stop_device_sync_background_engine(
     }))
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceSyncActiveTeamRequest {
+    team_id: Option<String>,
+}
+
+async fn set_device_sync_active_team(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<DeviceSyncActiveTeamRequest>,
+) -> ApiResult<Json<DeviceSyncBackgroundResponse>> {
+    ensure_device_sync_enabled()?;
+    let background_running = device_sync_engine::set_active_team(state, body.team_id)
+        .await
+        .map_err(ApiError::BadRequest)?;
+    Ok(Json(DeviceSyncBackgroundResponse {
+        status: if background_running {
+            "started".to_string()
+        } else {
+            "stopped".to_string()
+        },
+        message: if background_running {
+            "Device sync background engine restarted for the active team".to_string()
+        } else {
+            "Active team updated; background engine is not running".to_string()
+        },
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceSyncSnapshotCompressionRequest {
+    enabled: bool,
+}
+
+async fn set_device_sync_snapshot_compression(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<DeviceSyncSnapshotCompressionRequest>,
+) -> ApiResult<StatusCode> {
+    ensure_device_sync_enabled()?;
+    device_sync_engine::set_snapshot_compression(&state, body.enabled)
+        .await
+        .map_err(ApiError::Internal)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DeviceSyncAuditLogQuery {
+    limit: Option<i64>,
+}
+
+/// Recent outcomes of remote events (applied, skipped by LWW, rejected), newest first.
+async fn get_device_sync_audit_log(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<DeviceSyncAuditLogQuery>,
+) -> ApiResult<Json<Vec<SyncEventAudit>>> {
+    ensure_device_sync_enabled()?;
+    let entries = state
+        .app_sync_repository
+        .list_recent_audit(query.limit.unwrap_or(200))
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    Ok(Json(entries))
+}
+
+/// Accounts this device syncs; empty means every account.
+async fn get_device_sync_account_ids(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<String>>> {
+    ensure_device_sync_enabled()?;
+    let account_ids = state
+        .app_sync_repository
+        .get_synced_account_ids()
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    Ok(Json(account_ids))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceSyncAccountIdsRequest {
+    account_ids: Vec<String>,
+}
+
+async fn set_device_sync_account_ids(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<DeviceSyncAccountIdsRequest>,
+) -> ApiResult<StatusCode> {
+    ensure_device_sync_enabled()?;
+    state
+        .app_sync_repository
+        .set_synced_account_ids(body.account_ids)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn generate_device_snapshot_now(
     State(state): State<Arc<AppState>>,
 ) -> ApiResult<Json<DeviceSyncSnapshotUploadResponse>> {
@@ -1204,11 +1262,13 @@ pub fn router() -> Router<Arc<AppState>> {
         .route("/connect/session", delete(clear_sync_session))
         .route("/connect/session/status", get(get_sync_session_status))
         .route("/connect/session/restore", get(restore_sync_session))
+        .route("/connect/session/warm", post(warm_sync_session))
         // List operations (fetch from cloud without syncing)
         .route("/connect/connections", get(list_broker_connections))
         .route("/connect/accounts", get(list_broker_accounts))
         // Unified sync (non-blocking, emits SSE events)
         .route("/connect/sync", post(sync_broker_data))
+        .route("/connect/sync/cancel", post(cancel_broker_sync))
         // Individual sync operations (kept for backwards compatibility)
         .route("/connect/sync/connections", post(sync_broker_connections))
         .route("/connect/sync/accounts", post(sync_broker_accounts))
@@ -1262,6 +1322,19 @@ pub fn router() -> Router<Arc<AppState>> {
             "/connect/device/stop-background",
             post(stop_device_sync_background_engine),
         )
+        .route(
+            "/connect/device/active-team",
+            post(set_device_sync_active_team),
+        )
+        .route(
+            "/connect/device/snapshot-compression",
+            post(set_device_sync_snapshot_compression),
+        )
+        .route("/connect/device/audit-log", get(get_device_sync_audit_log))
+        .route(
+            "/connect/device/synced-account-ids",
+            get(get_device_sync_account_ids).post(set_device_sync_account_ids),
+        )
         .route(
             "/connect/device/generate-snapshot",
             post(generate_device_snapshot_now),
@@ -1281,6 +1354,47 @@ mod tests {
         let _router = router();
     }
 
+    #[tokio::test]
+    async fn plans_cache_fetches_once_within_ttl() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let cache = PlansCache::new(PLANS_CACHE_TTL);
+        let fetches = AtomicUsize::new(0);
+        let counter = &fetches;
+        let fetch = || async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(PlansResponse { plans: Vec::new() })
+        };
+
+        cache.get_or_fetch(false, fetch).await.unwrap();
+        cache.get_or_fetch(false, fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        cache.get_or_fetch(true, fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        let expired = PlansCache::new(Duration::ZERO);
+        expired.get_or_fetch(false, fetch).await.unwrap();
+        expired.get_or_fetch(false, fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn plans_cache_does_not_cache_errors() {
+        let cache = PlansCache::new(PLANS_CACHE_TTL);
+        let failed = cache
+            .get_or_fetch(false, || async {
+                Err(ApiError::Internal("upstream down".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+        let plans = cache
+            .get_or_fetch(false, || async { Ok(PlansResponse { plans: Vec::new() }) })
+            .await
+            .unwrap();
+        assert!(plans.plans.is_empty());
+    }
+
     #[test]
     fn reconcile_response_mapping_preserves_fields() {
         let source = device_sync_engine::SyncReconcileReadyStateResult {
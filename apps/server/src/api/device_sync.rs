@@ -27,7 +27,6 @@ use wealthfolio_device_sync::{
 
 // Storage keys (without prefix - the SecretStore adds "wealthfolio_" prefix)
 const DEVICE_ID_KEY: &str = "sync_device_id";
-const SYNC_IDENTITY_KEY: &str = "sync_identity";
 
 fn cloud_api_base_url() -> String {
     crate::features::cloud_api_base_url().unwrap_or_default()
@@ -41,7 +40,9 @@ async fn get_access_token(state: &AppState) -> ApiResult<String> {
 /// Get the device ID from secret store.
 fn get_device_id(state: &AppState) -> Option<String> {
     // Preferred source: sync_identity (used by DeviceEnrollService).
-    match state.secret_store.get_secret(SYNC_IDENTITY_KEY) {
+    let identity_key =
+        wealthfolio_device_sync::active_sync_identity_key(state.secret_store.as_ref());
+    match state.secret_store.get_secret(&identity_key) {
         Ok(Some(identity_json)) => match serde_json::from_str::<SyncIdentity>(&identity_json) {
             Ok(identity) => {
                 if let Some(device_id) = identity.device_id {
@@ -211,6 +212,7 @@ async fn register_device(
         platform: body.platform,
         os_version: body.os_version,
         app_version: body.app_version,
+        metadata: None,
     };
 
     let result = client
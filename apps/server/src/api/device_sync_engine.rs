@@ -9,14 +9,16 @@ use uuid::Uuid;
 
 use crate::main_lib::AppState;
 use wealthfolio_core::events::DomainEvent;
-use wealthfolio_core::sync::APP_SYNC_TABLES;
+use wealthfolio_core::settings::SettingsServiceTrait;
+use wealthfolio_core::sync::{APP_SYNC_TABLES, SNAPSHOT_SCHEMA_VERSION};
+use wealthfolio_device_sync::crypto::verify_sha256_checksum;
 use wealthfolio_device_sync::engine::{
     self, CredentialStore, OutboxStore, ReadyReconcileStore, ReplayEvent, ReplayStore,
     SyncIdentity, SyncTransport, TransportError,
 };
 use wealthfolio_device_sync::{
-    DeviceSyncClient, ReconcileReadyStateResponse, SyncPullResponse, SyncPushRequest,
-    SyncPushResponse, SyncState,
+    DeviceSyncClient, ReconcileReadyStateResponse, SnapshotEncoding, SyncPullResponse,
+    SyncPushRequest, SyncPushResponse, SyncState, SNAPSHOT_COMPRESSION_SETTING_KEY,
 };
 
 fn transport_err_from_sync(e: wealthfolio_device_sync::DeviceSyncError) -> TransportError {
@@ -32,7 +34,6 @@ fn transport_err_from_sync(e: wealthfolio_device_sync::DeviceSyncError) -> Trans
 }
 use wealthfolio_storage_sqlite::sync::{SqliteSyncEngineDbPorts, SyncTableRowCount};
 
-const SYNC_IDENTITY_KEY: &str = "sync_identity";
 static MIN_SNAPSHOT_CREATED_AT: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
 static READY_STATE_OVERWRITE_APPROVALS: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
 static PAIRING_OVERWRITE_APPROVALS: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
@@ -88,6 +89,9 @@ pub struct SyncEngineStatusResult {
     pub next_retry_at: Option<String>,
     pub last_cycle_status: Option<String>,
     pub last_cycle_duration_ms: Option<i64>,
+    pub last_cycle_pushed_bytes: Option<i64>,
+    pub last_cycle_pulled_bytes: Option<i64>,
+    pub last_cycle_events_applied: Option<i64>,
     pub background_running: bool,
     pub bootstrap_required: bool,
 }
@@ -181,9 +185,11 @@ fn create_client() -> DeviceSyncClient {
 }
 
 fn get_sync_identity_from_store(state: &AppState) -> Option<SyncIdentity> {
+    let identity_key =
+        wealthfolio_device_sync::active_sync_identity_key(state.secret_store.as_ref());
     let raw = state
         .secret_store
-        .get_secret(SYNC_IDENTITY_KEY)
+        .get_secret(&identity_key)
         .ok()
         .flatten()?;
     let identity: wealthfolio_device_sync::SyncIdentity = serde_json::from_str(&raw).ok()?;
@@ -191,6 +197,8 @@ fn get_sync_identity_from_store(state: &AppState) -> Option<SyncIdentity> {
         device_id: identity.device_id,
         root_key: identity.root_key,
         key_version: identity.key_version,
+        previous_root_key: identity.previous_root_key,
+        previous_key_version: identity.previous_key_version,
     })
 }
 
@@ -311,38 +319,6 @@ async fn persist_device_config_from_identity(
     }
 }
 
-fn encrypt_sync_payload(
-    plaintext_payload: &str,
-    identity: &SyncIdentity,
-    payload_key_version: i32,
-) -> Result<String, String> {
-    let root_key = identity
-        .root_key
-        .as_ref()
-        .ok_or_else(|| "Sync root key is not configured".to_string())?;
-    let key_version = payload_key_version.max(1) as u32;
-    let dek = wealthfolio_device_sync::crypto::derive_dek(root_key, key_version)
-        .map_err(|e| format!("Failed to derive event DEK: {}", e))?;
-    wealthfolio_device_sync::crypto::encrypt(&dek, plaintext_payload)
-        .map_err(|e| format!("Failed to encrypt sync payload: {}", e))
-}
-
-fn decrypt_sync_payload(
-    encrypted_payload: &str,
-    identity: &SyncIdentity,
-    payload_key_version: i32,
-) -> Result<String, String> {
-    let root_key = identity
-        .root_key
-        .as_ref()
-        .ok_or_else(|| "Sync root key is not configured".to_string())?;
-    let key_version = payload_key_version.max(1) as u32;
-    let dek = wealthfolio_device_sync::crypto::derive_dek(root_key, key_version)
-        .map_err(|e| format!("Failed to derive event DEK: {}", e))?;
-    wealthfolio_device_sync::crypto::decrypt(&dek, encrypted_payload)
-        .map_err(|e| format!("Failed to decrypt sync payload: {}", e))
-}
-
 fn is_sqlite_image(bytes: &[u8]) -> bool {
     bytes.starts_with(b"SQLite format 3\0")
 }
@@ -437,6 +413,10 @@ impl OutboxStore for ServerEnginePorts {
     async fn has_pending_outbox(&self) -> Result<bool, String> {
         self.db.has_pending_outbox().await
     }
+
+    async fn compact_pending_outbox(&self) -> Result<usize, String> {
+        self.db.compact_pending_outbox().await
+    }
 }
 
 #[async_trait]
@@ -457,11 +437,14 @@ impl ReplayStore for ServerEnginePorts {
         self.db.set_cursor(cursor).await
     }
 
-    async fn apply_remote_events_lww_batch(
+    async fn apply_remote_events_lww_batch_with_cursor(
         &self,
         events: Vec<ReplayEvent>,
+        new_cursor: i64,
     ) -> Result<usize, String> {
-        self.db.apply_remote_events_lww_batch(events).await
+        self.db
+            .apply_remote_events_lww_batch_with_cursor(events, new_cursor)
+            .await
     }
 
     async fn apply_remote_event_lww(&self, event: ReplayEvent) -> Result<bool, String> {
@@ -483,14 +466,43 @@ impl ReplayStore for ServerEnginePorts {
             .await
     }
 
+    async fn mark_cycle_transfer_stats(
+        &self,
+        pushed_bytes: usize,
+        pulled_bytes: usize,
+        events_applied: usize,
+    ) -> Result<(), String> {
+        self.db
+            .mark_cycle_transfer_stats(pushed_bytes, pulled_bytes, events_applied)
+            .await
+    }
+
     async fn mark_engine_error(&self, message: String) -> Result<(), String> {
         self.db.mark_engine_error(message).await
     }
 
+    async fn record_skipped_event(
+        &self,
+        entity: wealthfolio_core::sync::SyncEntity,
+        entity_id: String,
+        event_id: String,
+        reason: String,
+    ) -> Result<(), String> {
+        self.db
+            .record_skipped_event(entity, entity_id, event_id, reason)
+            .await
+    }
+
     async fn prune_applied_events_up_to_seq(&self, seq: i64) -> Result<(), String> {
         self.db.prune_applied_events_up_to_seq(seq).await
     }
 
+    async fn prune_applied_events_older_than(&self, cutoff_rfc3339: String) -> Result<(), String> {
+        self.db
+            .prune_applied_events_older_than(cutoff_rfc3339)
+            .await
+    }
+
     async fn get_engine_status(&self) -> Result<wealthfolio_core::sync::SyncEngineStatus, String> {
         self.db.get_engine_status().await
     }
@@ -596,7 +608,7 @@ impl CredentialStore for ServerEnginePorts {
         identity: &SyncIdentity,
         payload_key_version: i32,
     ) -> Result<String, String> {
-        encrypt_sync_payload(plaintext_payload, identity, payload_key_version)
+        engine::encrypt_sync_payload(plaintext_payload, identity, payload_key_version)
     }
 
     fn decrypt_sync_payload(
@@ -605,7 +617,7 @@ impl CredentialStore for ServerEnginePorts {
         identity: &SyncIdentity,
         payload_key_version: i32,
     ) -> Result<String, String> {
-        decrypt_sync_payload(encrypted_payload, identity, payload_key_version)
+        engine::decrypt_sync_payload(encrypted_payload, identity, payload_key_version)
     }
 }
 
@@ -633,6 +645,9 @@ pub async fn get_engine_status(state: &Arc<AppState>) -> Result<SyncEngineStatus
         next_retry_at: status.next_retry_at,
         last_cycle_status: status.last_cycle_status,
         last_cycle_duration_ms: status.last_cycle_duration_ms,
+        last_cycle_pushed_bytes: status.last_cycle_pushed_bytes,
+        last_cycle_pulled_bytes: status.last_cycle_pulled_bytes,
+        last_cycle_events_applied: status.last_cycle_events_applied,
         background_running,
         bootstrap_required,
     })
@@ -851,6 +866,101 @@ pub async fn ensure_background_engine_stopped(state: Arc<AppState>) -> Result<()
     Ok(())
 }
 
+/// Start the background sync engine when this device is READY.
+pub async fn ensure_background_engine_started_if_ready(state: Arc<AppState>) {
+    let ready = match crate::api::connect::mint_access_token(&state).await {
+        Ok(token) => state
+            .device_enroll_service
+            .get_sync_state(&token)
+            .await
+            .map(|sync_state| sync_state.state == SyncState::Ready)
+            .unwrap_or(false),
+        Err(_) => false,
+    };
+    if ready {
+        let _ = ensure_background_engine_started(state).await;
+    }
+}
+
+/// Opt this device in or out of gzip-compressing the snapshots it uploads.
+pub async fn set_snapshot_compression(state: &AppState, enabled: bool) -> Result<(), String> {
+    state
+        .settings_service
+        .set_setting_value(
+            SNAPSHOT_COMPRESSION_SETTING_KEY,
+            if enabled { "true" } else { "false" },
+        )
+        .await
+        .map_err(|e| format!("Failed to save snapshot compression: {}", e))
+}
+
+/// Switch the sync identity in use to the one stored for `team_id` (`None`
+/// selects the unscoped identity). Switching to another team is refused
+/// while local changes are still waiting to be pushed; otherwise the local
+/// sync state is reset and the device bootstraps from the new team's
+/// snapshot. Returns whether the background engine is running afterwards.
+pub async fn set_active_team(
+    state: Arc<AppState>,
+    team_id: Option<String>,
+) -> Result<bool, String> {
+    ensure_device_sync_enabled()?;
+    let secret_store = state.secret_store.as_ref();
+    if wealthfolio_device_sync::is_active_sync_team_change(secret_store, team_id.as_deref()) {
+        let unpushed = state
+            .app_sync_repository
+            .count_unpushed_outbox()
+            .map_err(|e| format!("Failed to read the sync outbox: {}", e))?;
+        if unpushed > 0 {
+            return Err(format!(
+                "{} local change(s) have not been pushed yet. Sync before switching teams.",
+                unpushed
+            ));
+        }
+    }
+
+    let was_running = state.device_sync_runtime.is_background_running().await;
+    ensure_background_engine_stopped(Arc::clone(&state)).await?;
+    let changed = wealthfolio_device_sync::set_active_sync_team(secret_store, team_id.as_deref())
+        .map_err(|e| format!("Failed to set active team: {}", e))?;
+    tracing::info!(
+        "[DeviceSync] Active sync team set to {}",
+        team_id.as_deref().unwrap_or("<none>")
+    );
+
+    if changed {
+        // Cursor, outbox and entity metadata belong to the previous team.
+        state
+            .app_sync_repository
+            .reset_sync_state()
+            .await
+            .map_err(|e| format!("Failed to reset sync state: {}", e))?;
+        tracing::info!("[DeviceSync] Local sync state reset for the new team");
+        let enrolled = get_sync_identity_from_store(&state)
+            .and_then(|identity| identity.device_id)
+            .is_some();
+        if enrolled {
+            match sync_bootstrap_snapshot_if_needed(Arc::clone(&state)).await {
+                Ok(_) => ensure_background_engine_started_if_ready(Arc::clone(&state)).await,
+                Err(err) => {
+                    tracing::warn!("[DeviceSync] Bootstrap after team switch failed: {}", err)
+                }
+            }
+        }
+    } else if was_running {
+        ensure_background_engine_started(Arc::clone(&state)).await?;
+    }
+    Ok(state.device_sync_runtime.is_background_running().await)
+}
+
+fn snapshot_generation_in_progress_result() -> SyncSnapshotUploadResult {
+    SyncSnapshotUploadResult {
+        status: "in_progress".to_string(),
+        snapshot_id: None,
+        oplog_seq: None,
+        message: "Snapshot generation already in progress".to_string(),
+    }
+}
+
 fn snapshot_upload_cancelled_result(message: &str) -> SyncSnapshotUploadResult {
     SyncSnapshotUploadResult {
         status: "cancelled".to_string(),
@@ -1137,11 +1247,10 @@ pub async fn sync_bootstrap_snapshot_if_needed(
         }
     };
 
-    const LOCAL_SCHEMA_VERSION: i32 = 1;
-    if latest.schema_version > LOCAL_SCHEMA_VERSION {
+    if latest.schema_version > SNAPSHOT_SCHEMA_VERSION {
         return Err(format!(
             "Snapshot schema version {} is newer than local version {}. Please update the app.",
-            latest.schema_version, LOCAL_SCHEMA_VERSION
+            latest.schema_version, SNAPSHOT_SCHEMA_VERSION
         ));
     }
 
@@ -1197,23 +1306,18 @@ pub async fn sync_bootstrap_snapshot_if_needed(
             return Err(err.to_string());
         }
     };
-    let actual_checksum = sha256_checksum(&blob);
-    if headers.checksum != actual_checksum {
-        return Err(format!(
-            "Snapshot checksum mismatch (download header): expected={}, got={}",
-            headers.checksum, actual_checksum
-        ));
-    }
-    if let Some(expected_checksum) = latest_checksum.as_ref() {
-        if expected_checksum != &actual_checksum {
-            return Err(format!(
-                "Snapshot checksum mismatch (latest metadata): expected={}, got={}",
-                expected_checksum, actual_checksum
-            ));
-        }
+    // Nothing below may touch the database until the blob is verified.
+    verify_sha256_checksum(&headers.checksum, &blob)
+        .map_err(|e| format!("Snapshot checksum mismatch (download header): {}", e))?;
+    if let Some(expected_checksum) = latest_checksum.as_deref() {
+        verify_sha256_checksum(expected_checksum, &blob)
+            .map_err(|e| format!("Snapshot checksum mismatch (latest metadata): {}", e))?;
     }
 
     let sqlite_image = decode_snapshot_sqlite_payload(blob, &identity)?;
+    let sqlite_image = SnapshotEncoding::detect(&sqlite_image)
+        .decode(&sqlite_image)
+        .map_err(|e| format!("Failed to decode snapshot image: {}", e))?;
     let temp_snapshot_path =
         std::env::temp_dir().join(format!("wf_snapshot_server_{}.db", Uuid::new_v4()));
     std::fs::write(&temp_snapshot_path, sqlite_image)
@@ -1235,6 +1339,7 @@ pub async fn sync_bootstrap_snapshot_if_needed(
     let restore_result = sync_repo
         .restore_snapshot_tables_from_file(
             snapshot_path_str,
+            headers.schema_version,
             tables_to_restore,
             snapshot_oplog_seq,
             device_id.clone(),
@@ -1270,6 +1375,12 @@ pub async fn generate_snapshot_now(
     state: Arc<AppState>,
 ) -> Result<SyncSnapshotUploadResult, String> {
     ensure_device_sync_enabled()?;
+    // Held until this generation returns, so a second trigger neither
+    // duplicates the export nor resets the running upload's cancel flag.
+    let Some(_generation) = state.device_sync_runtime.try_begin_snapshot_generation() else {
+        tracing::info!("[DeviceSync] Snapshot generation already in progress; skipping");
+        return Ok(snapshot_generation_in_progress_result());
+    };
     state
         .device_sync_runtime
         .snapshot_upload_cancelled
@@ -1333,9 +1444,20 @@ pub async fn generate_snapshot_now(
         }
     }
 
+    let encoding = SnapshotEncoding::for_upload(
+        state
+            .settings_service
+            .get_setting_value(SNAPSHOT_COMPRESSION_SETTING_KEY)
+            .ok()
+            .flatten()
+            .as_deref(),
+    );
     let sqlite_bytes = state
         .app_sync_repository
-        .export_snapshot_sqlite_image(APP_SYNC_TABLES.iter().map(|v| v.to_string()).collect())
+        .export_snapshot_sqlite_image_encoded(
+            APP_SYNC_TABLES.iter().map(|v| v.to_string()).collect(),
+            encoding,
+        )
         .await
         .map_err(|e| format!("Failed to export snapshot SQLite image: {}", e))?;
     if state
@@ -1350,10 +1472,10 @@ pub async fn generate_snapshot_now(
 
     let encoded_snapshot = BASE64_STANDARD.encode(sqlite_bytes);
     let encrypted_snapshot_payload =
-        encrypt_sync_payload(&encoded_snapshot, &identity, key_version)?;
+        engine::encrypt_sync_payload(&encoded_snapshot, &identity, key_version)?;
     let payload = encrypted_snapshot_payload.into_bytes();
     let checksum = sha256_checksum(&payload);
-    let metadata_payload = encrypt_sync_payload(
+    let metadata_payload = engine::encrypt_sync_payload(
         &serde_json::json!({
             "schemaVersion": 1,
             "coversTables": APP_SYNC_TABLES,
@@ -1373,13 +1495,14 @@ pub async fn generate_snapshot_now(
     );
     let upload_headers = wealthfolio_device_sync::SnapshotUploadHeaders {
         event_id: Some(Uuid::now_v7().to_string()),
-        schema_version: 1,
+        schema_version: SNAPSHOT_SCHEMA_VERSION,
         covers_tables: APP_SYNC_TABLES.iter().map(|v| v.to_string()).collect(),
         size_bytes: payload.len() as i64,
         checksum,
         metadata_payload,
         payload_key_version: key_version,
         base_seq,
+        encoding,
     };
 
     let upload_result = create_client()
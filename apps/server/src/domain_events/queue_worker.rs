@@ -511,7 +511,9 @@ async fn perform_broker_sync(
     secret_store: Arc<dyn SecretStore>,
     token_lifecycle: Arc<TokenLifecycleState>,
 ) -> Result<wealthfolio_connect::SyncResult, String> {
-    use wealthfolio_connect::{ConnectApiClient, SyncConfig, SyncOrchestrator};
+    use wealthfolio_connect::{
+        ConnectApiClient, LifecycleTokenSource, SyncConfig, SyncOrchestrator,
+    };
 
     if !crate::features::connect_sync_enabled() {
         return Err("Connect sync feature is disabled in this build.".to_string());
@@ -525,6 +527,9 @@ async fn perform_broker_sync(
     if !client.has_broker_sync().await.map_err(|e| e.to_string())? {
         return Err("Plan does not include broker sync".to_string());
     }
+    // Long syncs can outlive the token; re-mint and retry once on 401.
+    let tokens = LifecycleTokenSource::new(secret_store, token_lifecycle, token_lifecycle_config());
+    let client = client.with_token_refresh(Arc::new(tokens));
 
     // Create progress reporter and orchestrator
     let reporter = Arc::new(EventBusProgressReporter::new(event_bus));
@@ -23,6 +23,15 @@ pub enum ApiError {
     Unauthorized(String),
     #[error("{0}")]
     Forbidden(String),
+    /// The cloud session is gone and the user has to sign in again.
+    #[error("{0}")]
+    SessionExpired(String),
+    /// Required server configuration (e.g. auth endpoints) is missing.
+    #[error("{0}")]
+    ConfigMissing(String),
+    /// An upstream provider such as the Connect cloud API failed.
+    #[error("{0}")]
+    Provider(String),
     #[error("{0}")]
     Internal(String),
     // Surface the underlying error message to help debugging during development
@@ -32,10 +41,31 @@ pub enum ApiError {
 
 #[derive(Serialize)]
 struct ErrorBody {
+    /// HTTP status code.
     code: u16,
+    /// Stable machine-readable error code, see [`ApiError::error_code`].
+    error_code: &'static str,
     message: String,
 }
 
+impl ApiError {
+    /// Stable code clients can match on instead of the message.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ApiError::Core(CoreError::ConstraintViolation(_)) => "conflict",
+            ApiError::Core(CoreError::Validation(_)) => "validation",
+            ApiError::Core(_) | ApiError::BadRequest(_) => "bad_request",
+            ApiError::NotFound => "not_found",
+            ApiError::NotImplemented(_) => "not_implemented",
+            ApiError::Unauthorized(_) | ApiError::SessionExpired(_) => "session_expired",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::ConfigMissing(_) => "config_missing",
+            ApiError::Provider(_) => "provider_error",
+            ApiError::Internal(_) | ApiError::Anyhow(_) => "internal",
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, msg) = match &self {
@@ -48,12 +78,17 @@ impl IntoResponse for ApiError {
             ApiError::NotImplemented(reason) => (StatusCode::NOT_IMPLEMENTED, reason.clone()),
             ApiError::BadRequest(reason) => (StatusCode::BAD_REQUEST, reason.clone()),
             ApiError::Unauthorized(reason) => (StatusCode::UNAUTHORIZED, reason.clone()),
-            ApiError::Forbidden(reason) => (StatusCode::FORBIDDEN, reason.clone()),
-            ApiError::Internal(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason.clone()),
+            ApiError::Forbidden(reason) | ApiError::SessionExpired(reason) => {
+                (StatusCode::FORBIDDEN, reason.clone())
+            }
+            ApiError::ConfigMissing(reason)
+            | ApiError::Provider(reason)
+            | ApiError::Internal(reason) => (StatusCode::INTERNAL_SERVER_ERROR, reason.clone()),
             ApiError::Anyhow(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
         let body = Json(ErrorBody {
             code: status.as_u16(),
+            error_code: self.error_code(),
             message: msg,
         });
         (status, body).into_response()
@@ -67,3 +102,48 @@ impl From<ProviderApiError> for ApiError {
         ApiError::BadRequest(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body_json(err: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = err.into_response();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        (status, serde_json::from_slice(&bytes).expect("json body"))
+    }
+
+    #[tokio::test]
+    async fn unauthorized_serializes_session_expired_code() {
+        let (status, body) = body_json(ApiError::Unauthorized("Session expired".to_string())).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], 401);
+        assert_eq!(body["error_code"], "session_expired");
+        assert_eq!(body["message"], "Session expired");
+    }
+
+    #[tokio::test]
+    async fn connect_errors_keep_status_and_add_code() {
+        let (status, body) = body_json(ApiError::ConfigMissing("no auth url".to_string())).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], 500);
+        assert_eq!(body["error_code"], "config_missing");
+
+        let (status, body) = body_json(ApiError::SessionExpired("sign in".to_string())).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], 403);
+        assert_eq!(body["error_code"], "session_expired");
+
+        assert_eq!(
+            ApiError::Provider("503".to_string()).error_code(),
+            "provider_error"
+        );
+        assert_eq!(
+            ApiError::Internal("boom".to_string()).error_code(),
+            "internal"
+        );
+    }
+}
@@ -23,7 +23,10 @@ use wealthfolio_device_sync::SyncState;
 #[cfg(feature = "device-sync")]
 fn is_expected_startup_token_warmup_error(err: &crate::error::ApiError) -> bool {
     match err {
-        crate::error::ApiError::Unauthorized(_) | crate::error::ApiError::Forbidden(_) => true,
+        crate::error::ApiError::Unauthorized(_)
+        | crate::error::ApiError::Forbidden(_)
+        | crate::error::ApiError::SessionExpired(_)
+        | crate::error::ApiError::ConfigMissing(_) => true,
         crate::error::ApiError::Internal(message) => {
             message.contains("No refresh token configured")
                 || message.contains("Auth refresh configuration is missing")
@@ -105,6 +108,11 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // Device sync warms the token above; otherwise warm it for the first broker sync.
+    if features::connect_sync_enabled() && !features::device_sync_enabled() {
+        api::connect::spawn_access_token_warmup(state.clone());
+    }
+
     // Start background broker sync scheduler (4-hour interval)
     scheduler::start_broker_sync_scheduler(state.clone());
 
@@ -10,8 +10,8 @@ use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 use wealthfolio_ai::{AiProviderService, AiProviderServiceTrait, ChatConfig, ChatService};
 use wealthfolio_connect::{
-    BrokerSyncService, BrokerSyncServiceTrait, CoreImportRunRepositoryAdapter,
-    ImportRunRepositoryTrait, TokenLifecycleState,
+    BrokerSyncRuntimeState, BrokerSyncService, BrokerSyncServiceTrait,
+    CoreImportRunRepositoryAdapter, ImportRunRepositoryTrait, TokenLifecycleState,
 };
 use wealthfolio_core::addons::{AddonService, AddonServiceTrait};
 use wealthfolio_core::{
@@ -89,6 +89,8 @@ pub struct AppState {
     pub alternative_asset_service: Arc<dyn AlternativeAssetServiceTrait + Send + Sync>,
     pub addon_service: Arc<dyn AddonServiceTrait + Send + Sync>,
     pub connect_sync_service: Arc<dyn BrokerSyncServiceTrait + Send + Sync>,
+    /// Background broker sync started by `/connect/sync` or the scheduler.
+    pub broker_sync_runtime: Arc<BrokerSyncRuntimeState>,
     pub ai_provider_service: Arc<dyn AiProviderServiceTrait + Send + Sync>,
     pub ai_chat_service: Arc<ChatService<ServerAiEnvironment>>,
     pub data_root: String,
@@ -404,6 +406,11 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
 
     let event_bus = EventBus::new(256);
     let device_sync_runtime = Arc::new(DeviceSyncRuntimeState::new());
+    // Snapshot exports interrupted by a crash leave their temp image behind;
+    // anything a day old is no longer in use.
+    wealthfolio_storage_sqlite::sync::cleanup_orphaned_snapshot_temp_files(
+        std::time::Duration::from_secs(24 * 60 * 60),
+    );
     let token_lifecycle = Arc::new(TokenLifecycleState::new());
 
     // Domain event sink - Phase 2: Start the worker now that all services are ready
@@ -458,6 +465,7 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         alternative_asset_service,
         addon_service,
         connect_sync_service,
+        broker_sync_runtime: Arc::new(BrokerSyncRuntimeState::new()),
         ai_provider_service,
         ai_chat_service,
         data_root,
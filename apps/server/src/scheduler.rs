@@ -86,7 +86,14 @@ async fn run_scheduled_sync(state: &Arc<AppState>) {
     // - Emits broker:sync-start, broker:sync-complete, broker:sync-error events via SSE
     // - Handles subscription validation internally
     // - Syncs connections, accounts, activities, and holdings
-    match perform_broker_sync(state).await {
+    let Some(cancel) = state.broker_sync_runtime.try_start() else {
+        debug!("Scheduled sync skipped: a broker sync is already running");
+        return;
+    };
+    let result = perform_broker_sync(state, cancel.clone()).await;
+    state.broker_sync_runtime.finish(&cancel);
+
+    match result {
         Ok(result) => {
             let activities_count = result
                 .activities_synced
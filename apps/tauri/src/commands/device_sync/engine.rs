@@ -7,8 +7,8 @@ use async_trait::async_trait;
 use crate::context::ServiceContext;
 use wealthfolio_core::events::DomainEvent;
 use wealthfolio_device_sync::engine::{
-    CredentialStore, OutboxStore, ReplayEvent, ReplayStore, SyncIdentity, SyncTransport,
-    TransportError,
+    CredentialStore, OutboxStore, ReplayEvent, ReplayStore, SyncIdentity, SyncThrottleConfig,
+    SyncTransport, TransportError,
 };
 use wealthfolio_device_sync::{
     ReconcileReadyStateResponse, SyncPullResponse, SyncPushRequest, SyncPushResponse, SyncState,
@@ -57,6 +57,8 @@ impl TauriEnginePorts {
             device_id: identity.device_id.clone(),
             root_key: identity.root_key.clone(),
             key_version: identity.key_version,
+            previous_root_key: identity.previous_root_key.clone(),
+            previous_key_version: identity.previous_key_version,
         }
     }
 }
@@ -104,6 +106,10 @@ impl OutboxStore for TauriEnginePorts {
     async fn has_pending_outbox(&self) -> Result<bool, String> {
         self.db.has_pending_outbox().await
     }
+
+    async fn compact_pending_outbox(&self) -> Result<usize, String> {
+        self.db.compact_pending_outbox().await
+    }
 }
 
 #[async_trait]
@@ -124,11 +130,14 @@ impl ReplayStore for TauriEnginePorts {
         self.db.set_cursor(cursor).await
     }
 
-    async fn apply_remote_events_lww_batch(
+    async fn apply_remote_events_lww_batch_with_cursor(
         &self,
         events: Vec<ReplayEvent>,
+        new_cursor: i64,
     ) -> Result<usize, String> {
-        self.db.apply_remote_events_lww_batch(events).await
+        self.db
+            .apply_remote_events_lww_batch_with_cursor(events, new_cursor)
+            .await
     }
 
     async fn apply_remote_event_lww(&self, event: ReplayEvent) -> Result<bool, String> {
@@ -150,14 +159,43 @@ impl ReplayStore for TauriEnginePorts {
             .await
     }
 
+    async fn mark_cycle_transfer_stats(
+        &self,
+        pushed_bytes: usize,
+        pulled_bytes: usize,
+        events_applied: usize,
+    ) -> Result<(), String> {
+        self.db
+            .mark_cycle_transfer_stats(pushed_bytes, pulled_bytes, events_applied)
+            .await
+    }
+
     async fn mark_engine_error(&self, message: String) -> Result<(), String> {
         self.db.mark_engine_error(message).await
     }
 
+    async fn record_skipped_event(
+        &self,
+        entity: wealthfolio_core::sync::SyncEntity,
+        entity_id: String,
+        event_id: String,
+        reason: String,
+    ) -> Result<(), String> {
+        self.db
+            .record_skipped_event(entity, entity_id, event_id, reason)
+            .await
+    }
+
     async fn prune_applied_events_up_to_seq(&self, seq: i64) -> Result<(), String> {
         self.db.prune_applied_events_up_to_seq(seq).await
     }
 
+    async fn prune_applied_events_older_than(&self, cutoff_rfc3339: String) -> Result<(), String> {
+        self.db
+            .prune_applied_events_older_than(cutoff_rfc3339)
+            .await
+    }
+
     async fn get_engine_status(&self) -> Result<wealthfolio_core::sync::SyncEngineStatus, String> {
         self.db.get_engine_status().await
     }
@@ -229,16 +267,22 @@ impl SyncTransport for TauriEnginePorts {
             .await
             .map_err(transport_err_from_sync)
     }
+
+    fn sync_throttle(&self) -> SyncThrottleConfig {
+        self.context.device_sync_runtime().sync_throttle()
+    }
+
+    fn take_snapshot_transfer_bytes(&self) -> usize {
+        self.context
+            .device_sync_runtime()
+            .take_snapshot_transfer_bytes()
+    }
 }
 
 #[async_trait]
 impl CredentialStore for TauriEnginePorts {
     fn get_sync_identity(&self) -> Option<SyncIdentity> {
-        get_sync_identity_from_store().map(|identity| SyncIdentity {
-            device_id: identity.device_id,
-            root_key: identity.root_key,
-            key_version: identity.key_version,
-        })
+        get_sync_identity_from_store().map(|identity| identity.to_engine_identity())
     }
 
     fn get_access_token(&self) -> Result<String, String> {
@@ -314,6 +358,8 @@ pub(super) async fn run_sync_cycle(
         bootstrap_snapshot_id: result.bootstrap_snapshot_id,
         bootstrap_snapshot_seq: result.bootstrap_snapshot_seq,
         dead_letter_count: result.dead_letter_count,
+        pushed_bytes: result.pushed_bytes,
+        pulled_bytes: result.pulled_bytes,
     })
 }
 
@@ -5,6 +5,7 @@
 
 mod engine;
 mod snapshot;
+mod support_bundle;
 
 use async_trait::async_trait;
 use log::{debug, info};
@@ -17,17 +18,20 @@ use tauri::{AppHandle, State};
 use crate::context::ServiceContext;
 use crate::secret_store::KeyringSecretStore;
 use wealthfolio_core::secrets::SecretStore;
+use wealthfolio_core::settings::SettingsServiceTrait;
+use wealthfolio_core::sync::{SyncEventAudit, SyncOutboxEvent};
 use wealthfolio_device_sync::engine as shared_sync_engine;
 use wealthfolio_device_sync::{
     ClaimPairingRequest, ClaimPairingResponse, CommitInitializeKeysRequest,
     CommitInitializeKeysResponse, CommitRotateKeysRequest, CommitRotateKeysResponse,
     CompletePairingRequest, CompletePairingResponse, ConfirmPairingRequest, ConfirmPairingResponse,
-    CreatePairingRequest, CreatePairingResponse, Device, DevicePlatform, DeviceSyncClient,
-    EnrollDeviceResponse, GetPairingResponse, InitializeKeysResult, PairingMessagesResponse,
-    RegisterDeviceRequest, ResetTeamSyncResponse, RotateKeysResponse, SuccessResponse,
+    CreatePairingRequest, CreatePairingResponse, Device, DeviceListPage, DeviceListQuery,
+    DevicePlatform, DeviceSyncClient, EnrollDeviceResponse, GetPairingResponse,
+    InitializeKeysResult, PairingMessagesResponse, PairingState, RegisterDeviceRequest,
+    ResetTeamSyncResponse, RotateKeysResponse, SnapshotOpInfo, SuccessResponse, TrustState,
     UpdateDeviceRequest,
 };
-use wealthfolio_storage_sqlite::sync::SyncTableRowCount;
+use wealthfolio_storage_sqlite::sync::{SyncDryRunSummary, SyncEntityLag, SyncTableRowCount};
 
 // Re-export public items consumed by lib.rs
 pub use engine::{ensure_background_engine_started, ensure_background_engine_stopped};
@@ -52,12 +56,28 @@ pub(crate) struct SyncIdentity {
     device_id: Option<String>,
     root_key: Option<String>,
     key_version: Option<i32>,
+    #[serde(default)]
+    previous_root_key: Option<String>,
+    #[serde(default)]
+    previous_key_version: Option<i32>,
+}
+
+impl SyncIdentity {
+    fn to_engine_identity(&self) -> shared_sync_engine::SyncIdentity {
+        shared_sync_engine::SyncIdentity {
+            device_id: self.device_id.clone(),
+            root_key: self.root_key.clone(),
+            key_version: self.key_version,
+            previous_root_key: self.previous_root_key.clone(),
+            previous_key_version: self.previous_key_version,
+        }
+    }
 }
 
 fn get_sync_identity_from_store() -> Option<SyncIdentity> {
-    const SYNC_IDENTITY_KEY: &str = "sync_identity";
+    let identity_key = wealthfolio_device_sync::active_sync_identity_key(&KeyringSecretStore);
 
-    match KeyringSecretStore.get_secret(SYNC_IDENTITY_KEY) {
+    match KeyringSecretStore.get_secret(&identity_key) {
         Ok(Some(json)) => match serde_json::from_str::<SyncIdentity>(&json) {
             Ok(identity) => {
                 if let Some(ref device_id) = identity.device_id {
@@ -284,10 +304,46 @@ pub struct SyncEngineStatusResult {
     pub next_retry_at: Option<String>,
     pub last_cycle_status: Option<String>,
     pub last_cycle_duration_ms: Option<i64>,
+    pub last_cycle_pushed_bytes: Option<i64>,
+    pub last_cycle_pulled_bytes: Option<i64>,
+    pub last_cycle_events_applied: Option<i64>,
     pub background_running: bool,
     pub bootstrap_required: bool,
 }
 
+/// One line of [`SyncHealthReport`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncHealthCheckItem {
+    pub ok: bool,
+    pub message: String,
+}
+
+impl SyncHealthCheckItem {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            ok: true,
+            message: message.into(),
+        }
+    }
+
+    fn failed(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncHealthReport {
+    pub api_url: SyncHealthCheckItem,
+    pub access_token: SyncHealthCheckItem,
+    pub identity: SyncHealthCheckItem,
+    pub background_engine: SyncHealthCheckItem,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncPairingSourceStatusResult {
@@ -325,6 +381,8 @@ pub struct SyncCycleResult {
     pub bootstrap_snapshot_id: Option<String>,
     pub bootstrap_snapshot_seq: Option<i64>,
     pub dead_letter_count: usize,
+    pub pushed_bytes: usize,
+    pub pulled_bytes: usize,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -425,6 +483,8 @@ impl shared_sync_engine::ReadyReconcileStore for TauriReadyReconcileRunner {
             bootstrap_snapshot_id: result.bootstrap_snapshot_id,
             bootstrap_snapshot_seq: result.bootstrap_snapshot_seq,
             dead_letter_count: result.dead_letter_count,
+            pushed_bytes: result.pushed_bytes,
+            pulled_bytes: result.pulled_bytes,
         })
     }
 
@@ -455,15 +515,11 @@ fn encrypt_sync_payload(
     identity: &SyncIdentity,
     payload_key_version: i32,
 ) -> Result<String, String> {
-    let root_key = identity
-        .root_key
-        .as_ref()
-        .ok_or_else(|| "Sync root key is not configured".to_string())?;
-    let key_version = payload_key_version.max(1) as u32;
-    let dek = wealthfolio_device_sync::crypto::derive_dek(root_key, key_version)
-        .map_err(|e| format!("Failed to derive event DEK: {}", e))?;
-    wealthfolio_device_sync::crypto::encrypt(&dek, plaintext_payload)
-        .map_err(|e| format!("Failed to encrypt sync payload: {}", e))
+    shared_sync_engine::encrypt_sync_payload(
+        plaintext_payload,
+        &identity.to_engine_identity(),
+        payload_key_version,
+    )
 }
 
 fn decrypt_sync_payload(
@@ -471,15 +527,11 @@ fn decrypt_sync_payload(
     identity: &SyncIdentity,
     payload_key_version: i32,
 ) -> Result<String, String> {
-    let root_key = identity
-        .root_key
-        .as_ref()
-        .ok_or_else(|| "Sync root key is not configured".to_string())?;
-    let key_version = payload_key_version.max(1) as u32;
-    let dek = wealthfolio_device_sync::crypto::derive_dek(root_key, key_version)
-        .map_err(|e| format!("Failed to derive event DEK: {}", e))?;
-    wealthfolio_device_sync::crypto::decrypt(&dek, encrypted_payload)
-        .map_err(|e| format!("Failed to decrypt sync payload: {}", e))
+    shared_sync_engine::decrypt_sync_payload(
+        encrypted_payload,
+        &identity.to_engine_identity(),
+        payload_key_version,
+    )
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -581,17 +633,79 @@ fn get_os_version_impl() -> Option<String> {
     None
 }
 
-#[cfg(not(any(
-    target_os = "macos",
-    target_os = "windows",
-    target_os = "linux",
-    target_os = "ios",
-    target_os = "android"
-)))]
+#[cfg(target_os = "freebsd")]
+fn get_os_version_impl() -> Option<String> {
+    // Userland version (e.g. "14.1-RELEASE-p3"); the kernel may lag behind.
+    Command::new("freebsd-version")
+        .arg("-u")
+        .output()
+        .ok()
+        .and_then(|o| {
+            if o.status.success() {
+                String::from_utf8(o.stdout).ok()
+            } else {
+                None
+            }
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(uname_release)
+}
+
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "ios",
+        target_os = "android",
+        target_os = "freebsd"
+    ))
+))]
+fn get_os_version_impl() -> Option<String> {
+    uname_release()
+}
+
+/// Kernel release reported by `uname -r`, for Unix systems without a better
+/// source.
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "ios",
+        target_os = "android"
+    ))
+))]
+fn uname_release() -> Option<String> {
+    Command::new("uname")
+        .arg("-r")
+        .output()
+        .ok()
+        .and_then(|o| {
+            if o.status.success() {
+                String::from_utf8(o.stdout).ok()
+            } else {
+                None
+            }
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
 fn get_os_version_impl() -> Option<String> {
     None
 }
 
+fn get_hostname() -> Option<String> {
+    hostname::get()
+        .ok()
+        .and_then(|name| name.into_string().ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
 fn get_app_version() -> Option<String> {
     Some(env!("CARGO_PKG_VERSION").to_string())
 }
@@ -620,13 +734,20 @@ pub async fn enroll_device(
         platform, os_version, app_version
     );
 
+    let hostname = get_hostname();
+    if hostname.is_none() {
+        debug!("[DeviceSync] Hostname unavailable; enrolling without it");
+    }
+
     let request = RegisterDeviceRequest {
         device_nonce,
         display_name,
         platform,
         os_version,
         app_version,
-    };
+        metadata: None,
+    }
+    .with_hostname(hostname.as_deref());
 
     let result = client
         .enroll_device(&token, request)
@@ -685,6 +806,28 @@ pub async fn list_devices(
     Ok(devices)
 }
 
+/// Lists one page of devices, optionally only those in `trust_state`.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_devices_page(
+    scope: Option<String>,
+    trust_state: Option<TrustState>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<DeviceListPage, String> {
+    let token = get_access_token(state.inner()).await?;
+    let query = DeviceListQuery {
+        scope,
+        trust_state,
+        limit,
+        offset,
+    };
+    create_client()?
+        .list_devices_page(&token, &query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn update_device(
     device_id: String,
@@ -711,6 +854,23 @@ pub async fn update_device(
         .map_err(|e| e.to_string())
 }
 
+/// Local device id for commands that act on this device.
+fn require_local_device_id(device_id: Option<String>) -> Result<String, String> {
+    device_id.ok_or_else(|| {
+        "No device ID configured for this device. Enable sync to enroll it first.".to_string()
+    })
+}
+
+/// Renames this device, resolving its id from the keyring.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn rename_this_device(
+    display_name: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SuccessResponse, String> {
+    let device_id = require_local_device_id(get_device_id_from_store())?;
+    update_device(device_id, Some(display_name), state).await
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn delete_device(
     device_id: String,
@@ -877,6 +1037,9 @@ pub async fn sync_engine_status(
         next_retry_at: status.next_retry_at,
         last_cycle_status: status.last_cycle_status,
         last_cycle_duration_ms: status.last_cycle_duration_ms,
+        last_cycle_pushed_bytes: status.last_cycle_pushed_bytes,
+        last_cycle_pulled_bytes: status.last_cycle_pulled_bytes,
+        last_cycle_events_applied: status.last_cycle_events_applied,
         background_running,
         bootstrap_required,
     })
@@ -979,6 +1142,162 @@ pub async fn device_sync_stop_background_engine(
     })
 }
 
+/// Switch the sync identity in use to the one stored for `team_id` (`None`
+/// selects the unscoped identity). A running background engine is restarted
+/// so its next cycle runs under the new identity. Switching to another team
+/// is refused while local changes are still waiting to be pushed.
+#[tauri::command]
+pub async fn set_active_team(
+    team_id: Option<String>,
+    handle: AppHandle,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SyncBackgroundEngineResult, String> {
+    let context = Arc::clone(state.inner());
+    if wealthfolio_device_sync::is_active_sync_team_change(&KeyringSecretStore, team_id.as_deref())
+    {
+        let unpushed = context
+            .app_sync_repository()
+            .count_unpushed_outbox()
+            .map_err(|e| format!("Failed to read the sync outbox: {}", e))?;
+        if unpushed > 0 {
+            return Err(format!(
+                "{} local change(s) have not been pushed yet. Sync before switching teams.",
+                unpushed
+            ));
+        }
+    }
+    let was_running = context.device_sync_runtime().is_background_running().await;
+    ensure_background_engine_stopped(Arc::clone(&context)).await?;
+
+    let changed =
+        wealthfolio_device_sync::set_active_sync_team(&KeyringSecretStore, team_id.as_deref())
+            .map_err(|e| format!("Failed to set active team: {}", e))?;
+    info!(
+        "[DeviceSync] Active sync team set to {}",
+        team_id.as_deref().unwrap_or("<none>")
+    );
+
+    if changed {
+        // Cursor, outbox and entity metadata belong to the previous team.
+        // Pushing them under the new team's keys, or pulling the new team
+        // from the old cursor, would mix the two; start over from a snapshot.
+        context
+            .app_sync_repository()
+            .reset_sync_state()
+            .await
+            .map_err(|e| format!("Failed to reset sync state: {}", e))?;
+        info!("[DeviceSync] Local sync state reset for the new team");
+        let enrolled = get_sync_identity_from_store()
+            .and_then(|identity| identity.device_id)
+            .is_some();
+        if enrolled {
+            // Starts the background engine once the device is ready.
+            if let Err(err) = device_sync_bootstrap_snapshot_if_needed(handle, state).await {
+                log::warn!("[DeviceSync] Bootstrap after team switch failed: {}", err);
+            }
+        }
+    } else if was_running {
+        ensure_background_engine_started(Arc::clone(&context)).await?;
+    }
+    let background_running = context.device_sync_runtime().is_background_running().await;
+    Ok(SyncBackgroundEngineResult {
+        status: if background_running {
+            "started".to_string()
+        } else {
+            "stopped".to_string()
+        },
+        message: if background_running {
+            "Device sync background engine restarted for the active team".to_string()
+        } else {
+            "Active team updated; background engine is not running".to_string()
+        },
+    })
+}
+
+/// Settings key for the persisted sync throttle.
+const SYNC_THROTTLE_SETTINGS_KEY: &str = "device_sync_throttle";
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredSyncThrottle {
+    max_bytes_per_cycle: Option<u64>,
+    min_cycle_interval_secs: Option<u64>,
+}
+
+impl StoredSyncThrottle {
+    fn to_config(&self) -> shared_sync_engine::SyncThrottleConfig {
+        shared_sync_engine::SyncThrottleConfig {
+            max_bytes_per_cycle: self
+                .max_bytes_per_cycle
+                .map(|bytes| usize::try_from(bytes).unwrap_or(usize::MAX)),
+            min_cycle_interval: std::time::Duration::from_secs(
+                self.min_cycle_interval_secs.unwrap_or(0),
+            ),
+        }
+    }
+}
+
+/// Throttle saved by `set_sync_throttle`, or unthrottled when none is stored.
+pub fn load_sync_throttle(
+    settings_service: &dyn SettingsServiceTrait,
+) -> shared_sync_engine::SyncThrottleConfig {
+    settings_service
+        .get_setting_value(SYNC_THROTTLE_SETTINGS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|value| serde_json::from_str::<StoredSyncThrottle>(&value).ok())
+        .unwrap_or_default()
+        .to_config()
+}
+
+/// Limit background sync traffic, e.g. on metered connections. `None` for
+/// either value removes that limit. Applies from the next cycle and is kept
+/// across restarts.
+#[tauri::command]
+pub async fn set_sync_throttle(
+    max_bytes_per_cycle: Option<u64>,
+    min_cycle_interval_secs: Option<u64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    let stored = StoredSyncThrottle {
+        max_bytes_per_cycle,
+        min_cycle_interval_secs,
+    };
+    let json = serde_json::to_string(&stored)
+        .map_err(|e| format!("Failed to serialize sync throttle: {}", e))?;
+    state
+        .settings_service()
+        .set_setting_value(SYNC_THROTTLE_SETTINGS_KEY, &json)
+        .await
+        .map_err(|e| format!("Failed to save sync throttle: {}", e))?;
+
+    let config = stored.to_config();
+    info!("[DeviceSync] Sync throttle set to {:?}", config);
+    state
+        .inner()
+        .device_sync_runtime()
+        .set_sync_throttle(config);
+    Ok(())
+}
+
+/// Opt this device in or out of gzip-compressing the snapshots it uploads.
+/// Restores detect compression themselves, so either setting reads any
+/// snapshot.
+#[tauri::command]
+pub async fn set_snapshot_compression(
+    enabled: bool,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    state
+        .settings_service()
+        .set_setting_value(
+            wealthfolio_device_sync::SNAPSHOT_COMPRESSION_SETTING_KEY,
+            if enabled { "true" } else { "false" },
+        )
+        .await
+        .map_err(|e| format!("Failed to save snapshot compression: {}", e))
+}
+
 #[tauri::command]
 pub async fn device_sync_generate_snapshot_now(
     handle: AppHandle,
@@ -1002,6 +1321,26 @@ pub async fn device_sync_cancel_snapshot_upload(
     })
 }
 
+#[tauri::command]
+pub async fn device_sync_list_in_flight_snapshots() -> Result<Vec<SnapshotOpInfo>, String> {
+    Ok(DeviceSyncClient::list_in_flight_snapshots().await)
+}
+
+/// Cancels one in-flight snapshot upload, identified by the key from
+/// `device_sync_list_in_flight_snapshots`.
+#[tauri::command]
+pub async fn device_sync_cancel_in_flight_snapshot(
+    dedupe_key: String,
+) -> Result<SyncBackgroundEngineResult, String> {
+    if !DeviceSyncClient::cancel_in_flight_snapshot(&dedupe_key).await {
+        return Err(format!("No snapshot upload in flight for {}", dedupe_key));
+    }
+    Ok(SyncBackgroundEngineResult {
+        status: "cancelled".to_string(),
+        message: "Snapshot upload cancelled".to_string(),
+    })
+}
+
 #[tauri::command]
 pub async fn device_sync_engine_status(
     state: State<'_, Arc<ServiceContext>>,
@@ -1009,6 +1348,239 @@ pub async fn device_sync_engine_status(
     sync_engine_status(state).await
 }
 
+/// Writes a zip of redacted sync diagnostics to `path` for attaching to bug reports.
+#[tauri::command]
+pub async fn device_sync_export_support_bundle(
+    path: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    let sync_repo = state.app_sync_repository();
+    let identity = get_sync_identity_from_store();
+    let data = support_bundle::SupportBundleData {
+        engine: sync_repo.get_engine_status().map_err(|e| e.to_string())?,
+        background_running: state
+            .inner()
+            .device_sync_runtime()
+            .is_background_running()
+            .await,
+        outbox: sync_repo
+            .list_outbox_diagnostics(500)
+            .map_err(|e| e.to_string())?,
+        table_states: sync_repo.list_table_states().map_err(|e| e.to_string())?,
+        device_configs: sync_repo.list_device_configs().map_err(|e| e.to_string())?,
+        device_id: identity.as_ref().and_then(|i| i.device_id.clone()),
+        identity_key_version: identity.as_ref().and_then(|i| i.key_version),
+        root_key_present: identity.as_ref().is_some_and(|i| i.root_key.is_some()),
+        app_version: get_app_version(),
+        os_version: get_os_version(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let file = std::fs::File::create(&path)
+        .map_err(|e| format!("Failed to create support bundle at {}: {}", path, e))?;
+    support_bundle::write_support_bundle(file, &data)?;
+    info!("[DeviceSync] Exported support bundle to {}", path);
+    Ok(())
+}
+
+/// Writes an UNENCRYPTED SQLite copy of the synced tables to `path` as an
+/// offline backup that does not depend on the cloud or the sync keys.
+#[tauri::command]
+pub async fn device_sync_export_plaintext_snapshot(
+    path: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    log::warn!(
+        "[DeviceSync] Exporting an UNENCRYPTED snapshot of synced data to {}. Anyone with access to this file can read it.",
+        path
+    );
+    let bytes = state
+        .app_sync_repository()
+        .export_plaintext_snapshot_to_file(path.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    info!(
+        "[DeviceSync] Exported plaintext snapshot ({} bytes) to {}",
+        bytes, path
+    );
+    Ok(())
+}
+
+/// Whether the stored sync identity can run sync: it needs a device id from
+/// enrollment and a root key from key setup or pairing.
+fn identity_health(identity: Option<&SyncIdentity>) -> SyncHealthCheckItem {
+    let Some(identity) = identity else {
+        return SyncHealthCheckItem::failed("No sync identity stored. Enable sync first.");
+    };
+    match (identity.device_id.is_some(), identity.root_key.is_some()) {
+        (true, true) => SyncHealthCheckItem::ok(format!(
+            "Device enrolled with key version {}",
+            identity.key_version.unwrap_or_default()
+        )),
+        (false, _) => SyncHealthCheckItem::failed("Device is not enrolled (no device id)."),
+        (true, false) => SyncHealthCheckItem::failed(
+            "Device is enrolled but has no root key. Pair with a trusted device.",
+        ),
+    }
+}
+
+/// Checks each piece of configuration device sync depends on and reports
+/// them together, instead of failing on the first one at some call site.
+#[tauri::command]
+pub async fn device_sync_health_check(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SyncHealthReport, String> {
+    let api_url = match cloud_api_base_url() {
+        Ok(url) => SyncHealthCheckItem::ok(url),
+        Err(err) => SyncHealthCheckItem::failed(err),
+    };
+    let access_token = match get_access_token(state.inner()).await {
+        Ok(_) => SyncHealthCheckItem::ok("Access token available"),
+        Err(err) => SyncHealthCheckItem::failed(err),
+    };
+    let identity = identity_health(get_sync_identity_from_store().as_ref());
+    let background_engine = if state
+        .inner()
+        .device_sync_runtime()
+        .is_background_running()
+        .await
+    {
+        SyncHealthCheckItem::ok("Background sync is running")
+    } else {
+        SyncHealthCheckItem::failed("Background sync is not running")
+    };
+    Ok(SyncHealthReport {
+        api_url,
+        access_token,
+        identity,
+        background_engine,
+    })
+}
+
+/// Reports how many events the next sync cycle would push and pull, without
+/// pushing, applying remote events or advancing the cursor.
+#[tauri::command]
+pub async fn sync_dry_run(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SyncDryRunSummary, String> {
+    let identity = get_sync_identity_from_store()
+        .ok_or_else(|| "No sync identity configured. Please enable sync first.".to_string())?;
+    let device_id = identity
+        .device_id
+        .ok_or_else(|| "No device ID configured".to_string())?;
+    let token = get_access_token(state.inner()).await?;
+    let server_cursor = create_client()?
+        .get_events_cursor(&token, &device_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .cursor;
+    state
+        .app_sync_repository()
+        .sync_dry_run(server_cursor)
+        .map_err(|e| e.to_string())
+}
+
+/// Per-entity sync lag: pending local edits and the last applied remote seq.
+#[tauri::command]
+pub async fn device_sync_entity_lag(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SyncEntityLag>, String> {
+    state
+        .app_sync_repository()
+        .entity_sync_lag()
+        .map_err(|e| e.to_string())
+}
+
+/// Pulls one window of the remote event log and reports each event's entity,
+/// operation, seq and whether its payload decrypts. Read-only: nothing is
+/// applied and the local cursor does not move.
+#[tauri::command]
+pub async fn device_sync_pull_window(
+    since: Option<i64>,
+    limit: Option<i64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<shared_sync_engine::PullWindowSummary, String> {
+    let identity = get_sync_identity_from_store()
+        .ok_or_else(|| "No sync identity configured. Please enable sync first.".to_string())?;
+    let device_id = identity
+        .device_id
+        .clone()
+        .ok_or_else(|| "No device ID configured".to_string())?;
+    let token = get_access_token(state.inner()).await?;
+    let response = create_client()?
+        .pull_events(&token, &device_id, since, limit.map(|value| value as i32))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(shared_sync_engine::summarize_pull_window(
+        &response,
+        |event| decrypt_sync_payload(&event.payload, &identity, event.payload_key_version),
+    ))
+}
+
+/// Recent outcomes of remote events (applied, skipped by LWW, rejected), newest first.
+#[tauri::command]
+pub async fn get_sync_audit_log(
+    limit: Option<i64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SyncEventAudit>, String> {
+    state
+        .app_sync_repository()
+        .list_recent_audit(limit.unwrap_or(200))
+        .map_err(|e| e.to_string())
+}
+
+/// Accounts this device syncs; empty means every account.
+#[tauri::command]
+pub async fn get_synced_account_ids(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<String>, String> {
+    state
+        .app_sync_repository()
+        .get_synced_account_ids()
+        .map_err(|e| e.to_string())
+}
+
+/// Restricts which accounts this device syncs; an empty list syncs all of them.
+/// Events skipped for accounts added back are pulled again on the next cycle.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_synced_account_ids(
+    account_ids: Vec<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    state
+        .app_sync_repository()
+        .set_synced_account_ids(account_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists outbox events that exhausted their retries or were rejected, oldest first.
+#[tauri::command]
+pub async fn list_dead_sync_events(
+    limit: Option<i64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SyncOutboxEvent>, String> {
+    state
+        .app_sync_repository()
+        .list_dead_outbox(limit.unwrap_or(500))
+        .map_err(|e| e.to_string())
+}
+
+/// Moves dead outbox events back to pending so the next cycle pushes them again.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn requeue_dead_sync_events(
+    event_ids: Vec<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<usize, String> {
+    let requeued = state
+        .app_sync_repository()
+        .requeue_dead_outbox(event_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    info!("[DeviceSync] Requeued {} dead outbox events", requeued);
+    Ok(requeued)
+}
+
 #[tauri::command]
 pub async fn device_sync_pairing_source_status(
     state: State<'_, Arc<ServiceContext>>,
@@ -1080,6 +1652,25 @@ pub async fn device_sync_bootstrap_snapshot_if_needed(
     Ok(result)
 }
 
+/// Recover from a wedged sync: stop the engine, discard local replication
+/// state (outbox, entity metadata, applied events, table state, cursor) and
+/// re-bootstrap from the latest snapshot. App data is left untouched.
+#[tauri::command]
+pub async fn device_sync_force_resync(
+    handle: AppHandle,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SyncBootstrapResult, String> {
+    let context = Arc::clone(state.inner());
+    ensure_background_engine_stopped(Arc::clone(&context)).await?;
+    context
+        .app_sync_repository()
+        .reset_sync_state()
+        .await
+        .map_err(|e| format!("Failed to reset sync state: {}", e))?;
+    info!("[DeviceSync] Local sync state reset; re-bootstrapping");
+    device_sync_bootstrap_snapshot_if_needed(handle, state).await
+}
+
 #[tauri::command]
 pub async fn device_sync_trigger_cycle(
     state: State<'_, Arc<ServiceContext>>,
@@ -1258,6 +1849,38 @@ pub async fn get_pairing_messages(
         .map_err(|e| e.to_string())
 }
 
+/// Current step of a pairing session from this device's point of view.
+/// The claimer also polls the session messages to detect the key bundle.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn pairing_state(
+    pairing_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<PairingState, String> {
+    debug!("[DeviceSync] Resolving pairing state: {}", pairing_id);
+
+    let token = get_access_token(state.inner()).await?;
+    let device_id =
+        get_device_id_from_store().ok_or_else(|| "No device ID configured".to_string())?;
+    let client = create_client()?;
+
+    let session = client
+        .get_pairing(&token, &device_id, &pairing_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let session_state = PairingState::from_session(&session, chrono::Utc::now());
+    let is_claimer = session.claimer_device_id.as_deref() == Some(device_id.as_str());
+    if !is_claimer || !(session_state.is_in_progress() || session_state == PairingState::Completed)
+    {
+        return Ok(session_state);
+    }
+
+    let messages = client
+        .get_pairing_messages(&token, &device_id, &pairing_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(PairingState::from_messages(&messages))
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfirmPairingWithBootstrapResult {
@@ -1823,6 +2446,54 @@ pub async fn confirm_pairing(
 mod tests {
     use super::*;
 
+    fn identity(device_id: Option<&str>, root_key: Option<&str>) -> SyncIdentity {
+        SyncIdentity {
+            device_id: device_id.map(str::to_string),
+            root_key: root_key.map(str::to_string),
+            key_version: Some(3),
+            previous_root_key: None,
+            previous_key_version: None,
+        }
+    }
+
+    #[test]
+    fn rename_this_device_requires_local_device_id() {
+        let err = require_local_device_id(None).unwrap_err();
+        assert!(err.contains("No device ID configured"));
+        assert_eq!(
+            require_local_device_id(Some("device-1".to_string())).unwrap(),
+            "device-1"
+        );
+    }
+
+    #[test]
+    fn identity_health_requires_device_id_and_root_key() {
+        assert!(!identity_health(None).ok);
+        assert!(!identity_health(Some(&identity(None, Some("root")))).ok);
+
+        let missing_key = identity_health(Some(&identity(Some("device-1"), None)));
+        assert!(!missing_key.ok);
+        assert!(missing_key.message.contains("root key"));
+
+        let ready = identity_health(Some(&identity(Some("device-1"), Some("root"))));
+        assert!(ready.ok);
+        assert!(ready.message.contains("key version 3"));
+    }
+
+    #[cfg(all(
+        unix,
+        not(any(
+            target_os = "macos",
+            target_os = "linux",
+            target_os = "ios",
+            target_os = "android"
+        ))
+    ))]
+    #[test]
+    fn bsd_and_other_unix_report_os_version() {
+        assert!(get_os_version_impl().is_some_and(|version| !version.is_empty()));
+    }
+
     #[test]
     fn pairing_already_approved_error_is_detected() {
         let err = wealthfolio_device_sync::DeviceSyncError::api_structured(
@@ -11,8 +11,9 @@ use uuid::Uuid;
 use crate::context::ServiceContext;
 use crate::events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload};
 use wealthfolio_core::quotes::MarketSyncMode;
-use wealthfolio_core::sync::APP_SYNC_TABLES;
-use wealthfolio_device_sync::SyncState;
+use wealthfolio_core::sync::{APP_SYNC_TABLES, SNAPSHOT_SCHEMA_VERSION};
+use wealthfolio_device_sync::crypto::verify_sha256_checksum;
+use wealthfolio_device_sync::{SnapshotEncoding, SyncState, SNAPSHOT_COMPRESSION_SETTING_KEY};
 
 use super::{
     clear_min_snapshot_created_at_from_store, create_client, encrypt_sync_payload,
@@ -196,6 +197,15 @@ fn emit_snapshot_upload_progress(
     }
 }
 
+fn snapshot_generation_in_progress_result() -> SyncSnapshotUploadResult {
+    SyncSnapshotUploadResult {
+        status: "in_progress".to_string(),
+        snapshot_id: None,
+        oplog_seq: None,
+        message: "Snapshot generation already in progress".to_string(),
+    }
+}
+
 fn snapshot_upload_cancelled_result(message: &str) -> SyncSnapshotUploadResult {
     SyncSnapshotUploadResult {
         status: "cancelled".to_string(),
@@ -454,11 +464,10 @@ pub async fn sync_bootstrap_snapshot_if_needed(
         }
     }
 
-    const LOCAL_SCHEMA_VERSION: i32 = 1;
-    if latest.schema_version > LOCAL_SCHEMA_VERSION {
+    if latest.schema_version > SNAPSHOT_SCHEMA_VERSION {
         return Err(format!(
             "Snapshot schema version {} is newer than local version {}. Please update the app.",
-            latest.schema_version, LOCAL_SCHEMA_VERSION
+            latest.schema_version, SNAPSHOT_SCHEMA_VERSION
         ));
     }
 
@@ -496,6 +505,9 @@ pub async fn sync_bootstrap_snapshot_if_needed(
             return Err(err.to_string());
         }
     };
+    context
+        .device_sync_runtime()
+        .record_snapshot_transfer(blob.len());
     debug!(
         "[DeviceSync] Snapshot download response headers: schema_version={} tables={} checksum={} blob_size={}",
         headers.schema_version,
@@ -504,23 +516,18 @@ pub async fn sync_bootstrap_snapshot_if_needed(
         blob.len()
     );
 
-    let actual_checksum = sha256_checksum(&blob);
-    if headers.checksum != actual_checksum {
-        return Err(format!(
-            "Snapshot checksum mismatch (download header): expected={}, got={}",
-            headers.checksum, actual_checksum
-        ));
-    }
-    if let Some(expected_checksum) = latest_checksum.as_ref() {
-        if expected_checksum != &actual_checksum {
-            return Err(format!(
-                "Snapshot checksum mismatch (latest metadata): expected={}, got={}",
-                expected_checksum, actual_checksum
-            ));
-        }
+    // Nothing below may touch the database until the blob is verified.
+    verify_sha256_checksum(&headers.checksum, &blob)
+        .map_err(|e| format!("Snapshot checksum mismatch (download header): {}", e))?;
+    if let Some(expected_checksum) = latest_checksum.as_deref() {
+        verify_sha256_checksum(expected_checksum, &blob)
+            .map_err(|e| format!("Snapshot checksum mismatch (latest metadata): {}", e))?;
     }
 
     let sqlite_image = decode_snapshot_sqlite_payload(blob, &identity)?;
+    let sqlite_image = SnapshotEncoding::detect(&sqlite_image)
+        .decode(&sqlite_image)
+        .map_err(|e| format!("Failed to decode snapshot image: {}", e))?;
     let temp_snapshot_path =
         std::env::temp_dir().join(format!("wf_snapshot_{}.db", Uuid::new_v4()));
     std::fs::write(&temp_snapshot_path, sqlite_image)
@@ -542,6 +549,7 @@ pub async fn sync_bootstrap_snapshot_if_needed(
     let restore_result = sync_repo
         .restore_snapshot_tables_from_file(
             snapshot_path_str,
+            headers.schema_version,
             tables_to_restore,
             snapshot_oplog_seq,
             device_id.clone(),
@@ -578,6 +586,15 @@ pub async fn generate_snapshot_now_internal(
     handle: Option<&AppHandle>,
     context: Arc<ServiceContext>,
 ) -> Result<SyncSnapshotUploadResult, String> {
+    // Held until this generation returns, so a second trigger neither
+    // duplicates the export nor resets the running upload's cancel flag.
+    let Some(_generation) = context
+        .device_sync_runtime()
+        .try_begin_snapshot_generation()
+    else {
+        info!("[DeviceSync] Snapshot generation already in progress; skipping");
+        return Ok(snapshot_generation_in_progress_result());
+    };
     context
         .device_sync_runtime()
         .snapshot_upload_cancelled
@@ -655,9 +672,20 @@ pub async fn generate_snapshot_now_internal(
         }
     }
 
+    let encoding = SnapshotEncoding::for_upload(
+        context
+            .settings_service()
+            .get_setting_value(SNAPSHOT_COMPRESSION_SETTING_KEY)
+            .ok()
+            .flatten()
+            .as_deref(),
+    );
     let sqlite_bytes = context
         .app_sync_repository()
-        .export_snapshot_sqlite_image(APP_SYNC_TABLES.iter().map(|v| v.to_string()).collect())
+        .export_snapshot_sqlite_image_encoded(
+            APP_SYNC_TABLES.iter().map(|v| v.to_string()).collect(),
+            encoding,
+        )
         .await
         .map_err(|e| format!("Failed to export snapshot SQLite image: {}", e))?;
     emit_snapshot_upload_progress(handle, "exported", 35, "Snapshot exported");
@@ -672,7 +700,7 @@ pub async fn generate_snapshot_now_internal(
         ));
     }
 
-    // Base64-encode the raw SQLite bytes before encryption because the crypto
+    // Base64-encode the gzipped SQLite image before encryption because the crypto
     // module operates on UTF-8 strings (encrypt/decrypt take &str). Binary-mode
     // encryption would avoid this overhead but isn't supported by the current API.
     let encoded_snapshot = BASE64_STANDARD.encode(sqlite_bytes);
@@ -698,13 +726,14 @@ pub async fn generate_snapshot_now_internal(
     );
     let upload_headers = wealthfolio_device_sync::SnapshotUploadHeaders {
         event_id: Some(Uuid::now_v7().to_string()),
-        schema_version: 1,
+        schema_version: SNAPSHOT_SCHEMA_VERSION,
         covers_tables: APP_SYNC_TABLES.iter().map(|v| v.to_string()).collect(),
         size_bytes: payload.len() as i64,
         checksum,
         metadata_payload,
         payload_key_version: key_version,
         base_seq,
+        encoding,
     };
     let checksum_prefix = upload_headers
         .checksum
@@ -721,6 +750,7 @@ pub async fn generate_snapshot_now_internal(
     );
 
     let runtime = context.device_sync_runtime();
+    let upload_size = payload.len();
     let upload_result = create_client()?
         .upload_snapshot_with_cancel_flag(
             &token,
@@ -730,6 +760,7 @@ pub async fn generate_snapshot_now_internal(
             Some(&runtime.snapshot_upload_cancelled),
         )
         .await;
+    runtime.record_snapshot_transfer(upload_size);
     let response = match upload_result {
         Ok(value) => value,
         Err(err) => {
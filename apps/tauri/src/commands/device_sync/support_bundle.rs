@@ -0,0 +1,301 @@
+//! Support bundle export for device sync diagnostics.
+//!
+//! The bundle is a zip of JSON documents describing local sync state. It is meant
+//! to be attached to bug reports, so it never contains decrypted payloads, error
+//! messages that may echo payload content, or any key material.
+
+use std::io::{Seek, Write};
+
+use serde::Serialize;
+use wealthfolio_core::sync::SyncEngineStatus;
+use wealthfolio_storage_sqlite::sync::app_sync::{SyncDeviceConfigDB, SyncTableStateDB};
+use wealthfolio_storage_sqlite::sync::SyncOutboxDiagnostic;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+const BUNDLE_FORMAT_VERSION: i32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const ENGINE_STATUS_ENTRY: &str = "engine_status.json";
+const OUTBOX_ENTRY: &str = "outbox.json";
+const TABLE_STATES_ENTRY: &str = "table_states.json";
+const LAST_CYCLE_ENTRY: &str = "last_cycle.json";
+const KEY_AUDIT_ENTRY: &str = "key_audit.json";
+
+/// Inputs for a support bundle, collected by the command before writing.
+pub(super) struct SupportBundleData {
+    pub engine: SyncEngineStatus,
+    pub background_running: bool,
+    pub outbox: Vec<SyncOutboxDiagnostic>,
+    pub table_states: Vec<SyncTableStateDB>,
+    pub device_configs: Vec<SyncDeviceConfigDB>,
+    pub device_id: Option<String>,
+    pub identity_key_version: Option<i32>,
+    pub root_key_present: bool,
+    pub app_version: Option<String>,
+    pub os_version: Option<String>,
+    pub generated_at: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleManifest<'a> {
+    format_version: i32,
+    generated_at: &'a str,
+    app_version: Option<&'a str>,
+    os_version: Option<&'a str>,
+    platform: &'a str,
+    entries: &'a [&'a str],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EngineStatusEntry<'a> {
+    cursor: i64,
+    background_running: bool,
+    consecutive_failures: i32,
+    outbox_events: usize,
+    tables: usize,
+    last_push_at: Option<&'a str>,
+    last_pull_at: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LastCycleEntry<'a> {
+    last_cycle_status: Option<&'a str>,
+    last_cycle_duration_ms: Option<i64>,
+    last_push_at: Option<&'a str>,
+    last_pull_at: Option<&'a str>,
+    consecutive_failures: i32,
+    next_retry_at: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyAuditEntry<'a> {
+    device_id: Option<&'a str>,
+    identity_key_version: Option<i32>,
+    root_key_present: bool,
+    devices: Vec<KeyAuditDevice<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyAuditDevice<'a> {
+    device_id: &'a str,
+    key_version: Option<i32>,
+    trust_state: &'a str,
+    last_bootstrap_at: Option<&'a str>,
+    min_snapshot_created_at: Option<&'a str>,
+}
+
+/// Writes the support bundle zip to `writer`.
+pub(super) fn write_support_bundle<W: Write + Seek>(
+    writer: W,
+    data: &SupportBundleData,
+) -> Result<(), String> {
+    let entries = [
+        MANIFEST_ENTRY,
+        ENGINE_STATUS_ENTRY,
+        OUTBOX_ENTRY,
+        TABLE_STATES_ENTRY,
+        LAST_CYCLE_ENTRY,
+        KEY_AUDIT_ENTRY,
+    ];
+    let engine = &data.engine;
+
+    let mut zip = ZipWriter::new(writer);
+    write_json_entry(
+        &mut zip,
+        MANIFEST_ENTRY,
+        &BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            generated_at: &data.generated_at,
+            app_version: data.app_version.as_deref(),
+            os_version: data.os_version.as_deref(),
+            platform: std::env::consts::OS,
+            entries: &entries,
+        },
+    )?;
+    write_json_entry(
+        &mut zip,
+        ENGINE_STATUS_ENTRY,
+        &EngineStatusEntry {
+            cursor: engine.cursor,
+            background_running: data.background_running,
+            consecutive_failures: engine.consecutive_failures,
+            outbox_events: data.outbox.len(),
+            tables: data.table_states.len(),
+            last_push_at: engine.last_push_at.as_deref(),
+            last_pull_at: engine.last_pull_at.as_deref(),
+        },
+    )?;
+    write_json_entry(&mut zip, OUTBOX_ENTRY, &data.outbox)?;
+    write_json_entry(&mut zip, TABLE_STATES_ENTRY, &data.table_states)?;
+    write_json_entry(
+        &mut zip,
+        LAST_CYCLE_ENTRY,
+        &LastCycleEntry {
+            last_cycle_status: engine.last_cycle_status.as_deref(),
+            last_cycle_duration_ms: engine.last_cycle_duration_ms,
+            last_push_at: engine.last_push_at.as_deref(),
+            last_pull_at: engine.last_pull_at.as_deref(),
+            consecutive_failures: engine.consecutive_failures,
+            next_retry_at: engine.next_retry_at.as_deref(),
+        },
+    )?;
+    write_json_entry(
+        &mut zip,
+        KEY_AUDIT_ENTRY,
+        &KeyAuditEntry {
+            device_id: data.device_id.as_deref(),
+            identity_key_version: data.identity_key_version,
+            root_key_present: data.root_key_present,
+            devices: data
+                .device_configs
+                .iter()
+                .map(|config| KeyAuditDevice {
+                    device_id: &config.device_id,
+                    key_version: config.key_version,
+                    trust_state: &config.trust_state,
+                    last_bootstrap_at: config.last_bootstrap_at.as_deref(),
+                    min_snapshot_created_at: config.min_snapshot_created_at.as_deref(),
+                })
+                .collect(),
+        },
+    )?;
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize support bundle: {}", e))?;
+    Ok(())
+}
+
+fn write_json_entry<W: Write + Seek, T: Serialize + ?Sized>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    value: &T,
+) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    zip.start_file(name, SimpleFileOptions::default())
+        .map_err(|e| format!("Failed to add {} to support bundle: {}", name, e))?;
+    zip.write_all(&json)
+        .map_err(|e| format!("Failed to write {} to support bundle: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use std::io::{Cursor, Read};
+    use wealthfolio_device_sync::crypto::generate_root_key;
+    use zip::ZipArchive;
+
+    fn sample_data(root_key: &str) -> SupportBundleData {
+        SupportBundleData {
+            engine: SyncEngineStatus {
+                cursor: 42,
+                last_push_at: Some("2026-01-02T03:04:05Z".to_string()),
+                last_pull_at: Some("2026-01-02T03:04:06Z".to_string()),
+                last_error: Some(format!("decrypt failed with key {}", root_key)),
+                consecutive_failures: 2,
+                next_retry_at: Some("2026-01-02T03:05:00Z".to_string()),
+                last_cycle_status: Some("error".to_string()),
+                last_cycle_duration_ms: Some(1234),
+                last_cycle_pushed_bytes: Some(2048),
+                last_cycle_pulled_bytes: Some(0),
+                last_cycle_events_applied: Some(0),
+            },
+            background_running: true,
+            outbox: vec![SyncOutboxDiagnostic {
+                event_id: "evt-1".to_string(),
+                entity: "account".to_string(),
+                entity_id: "acc-1".to_string(),
+                op: "update".to_string(),
+                status: "pending".to_string(),
+                payload_key_version: 3,
+                retry_count: 1,
+                next_retry_at: None,
+                last_error_code: Some("network".to_string()),
+                created_at: "2026-01-02T03:00:00Z".to_string(),
+            }],
+            table_states: vec![SyncTableStateDB {
+                table_name: "accounts".to_string(),
+                enabled: 1,
+                last_snapshot_restore_at: None,
+                last_incremental_apply_at: Some("2026-01-02T03:04:06Z".to_string()),
+            }],
+            device_configs: vec![SyncDeviceConfigDB {
+                device_id: "device-1".to_string(),
+                key_version: Some(3),
+                trust_state: "trusted".to_string(),
+                last_bootstrap_at: Some("2026-01-01T00:00:00Z".to_string()),
+                min_snapshot_created_at: None,
+            }],
+            device_id: Some("device-1".to_string()),
+            identity_key_version: Some(3),
+            root_key_present: true,
+            app_version: Some("1.0.0".to_string()),
+            os_version: Some("14.0".to_string()),
+            generated_at: "2026-01-02T03:10:00Z".to_string(),
+        }
+    }
+
+    fn read_entries(bytes: Vec<u8>) -> Vec<(String, String)> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).expect("open zip");
+        (0..archive.len())
+            .map(|index| {
+                let mut file = archive.by_index(index).expect("zip entry");
+                let mut contents = String::new();
+                file.read_to_string(&mut contents).expect("read entry");
+                (file.name().to_string(), contents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn support_bundle_contains_expected_entries_without_key_material() {
+        let root_key = generate_root_key();
+        let mut buffer = Cursor::new(Vec::new());
+        write_support_bundle(&mut buffer, &sample_data(&root_key)).expect("write bundle");
+
+        let entries = read_entries(buffer.into_inner());
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                MANIFEST_ENTRY,
+                ENGINE_STATUS_ENTRY,
+                OUTBOX_ENTRY,
+                TABLE_STATES_ENTRY,
+                LAST_CYCLE_ENTRY,
+                KEY_AUDIT_ENTRY,
+            ]
+        );
+
+        for (name, contents) in &entries {
+            assert!(serde_json::from_str::<serde_json::Value>(contents).is_ok());
+            assert!(
+                !contents.contains(&root_key),
+                "{} leaked the root key",
+                name
+            );
+            for token in contents
+                .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')))
+                .filter(|token| token.len() >= 43)
+            {
+                let decoded = base64::engine::general_purpose::STANDARD.decode(token);
+                assert!(
+                    !matches!(decoded, Ok(bytes) if bytes.len() == 32),
+                    "{} contains a root-key-like value",
+                    name
+                );
+            }
+        }
+
+        let key_audit = &entries[5].1;
+        assert!(key_audit.contains("\"rootKeyPresent\": true"));
+        assert!(!key_audit.contains("rootKey\""));
+    }
+}
@@ -312,6 +312,15 @@ pub async fn initialize_context(
         app_version,
     ));
     let device_sync_runtime = Arc::new(DeviceSyncRuntimeState::new());
+    #[cfg(feature = "device-sync")]
+    device_sync_runtime.set_sync_throttle(crate::commands::device_sync::load_sync_throttle(
+        settings_service.as_ref(),
+    ));
+    // Snapshot exports interrupted by a crash leave their temp image behind;
+    // anything a day old is no longer in use.
+    wealthfolio_storage_sqlite::sync::cleanup_orphaned_snapshot_temp_files(
+        std::time::Duration::from_secs(24 * 60 * 60),
+    );
 
     // Health service for portfolio health diagnostics
     let health_dismissal_repository =
@@ -460,8 +460,12 @@ pub fn run() {
             #[cfg(feature = "device-sync")]
             commands::device_sync::list_devices,
             #[cfg(feature = "device-sync")]
+            commands::device_sync::list_devices_page,
+            #[cfg(feature = "device-sync")]
             commands::device_sync::update_device,
             #[cfg(feature = "device-sync")]
+            commands::device_sync::rename_this_device,
+            #[cfg(feature = "device-sync")]
             commands::device_sync::delete_device,
             #[cfg(feature = "device-sync")]
             commands::device_sync::revoke_device,
@@ -479,8 +483,38 @@ pub fn run() {
             #[cfg(feature = "device-sync")]
             commands::device_sync::device_sync_bootstrap_snapshot_if_needed,
             #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_force_resync,
+            #[cfg(feature = "device-sync")]
             commands::device_sync::device_sync_engine_status,
             #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_export_support_bundle,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_export_plaintext_snapshot,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::sync_dry_run,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_entity_lag,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_health_check,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_pull_window,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::set_active_team,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::set_sync_throttle,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::set_snapshot_compression,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::get_sync_audit_log,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::get_synced_account_ids,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::set_synced_account_ids,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::list_dead_sync_events,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::requeue_dead_sync_events,
+            #[cfg(feature = "device-sync")]
             commands::device_sync::device_sync_pairing_source_status,
             #[cfg(feature = "device-sync")]
             commands::device_sync::device_sync_bootstrap_overwrite_check,
@@ -496,6 +530,10 @@ pub fn run() {
             commands::device_sync::device_sync_generate_snapshot_now,
             #[cfg(feature = "device-sync")]
             commands::device_sync::device_sync_cancel_snapshot_upload,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_list_in_flight_snapshots,
+            #[cfg(feature = "device-sync")]
+            commands::device_sync::device_sync_cancel_in_flight_snapshot,
             // Pairing (Issuer - Trusted Device)
             #[cfg(feature = "device-sync")]
             commands::device_sync::create_pairing,
@@ -512,6 +550,7 @@ pub fn run() {
             commands::device_sync::claim_pairing,
             #[cfg(feature = "device-sync")]
             commands::device_sync::get_pairing_messages,
+            commands::device_sync::pairing_state,
             #[cfg(feature = "device-sync")]
             commands::device_sync::confirm_pairing,
             // Composite pairing endpoints
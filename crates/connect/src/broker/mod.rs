@@ -2,11 +2,13 @@ pub mod mapping;
 mod models;
 pub mod orchestrator;
 pub mod progress;
+mod runtime;
 mod service;
 mod traits;
 
 pub use models::*;
-pub use orchestrator::{SyncConfig, SyncOrchestrator};
+pub use orchestrator::{SyncConfig, SyncOrchestrator, SYNC_CANCELLED_MESSAGE};
 pub use progress::{NoOpProgressReporter, SyncProgressPayload, SyncProgressReporter, SyncStatus};
+pub use runtime::BrokerSyncRuntimeState;
 pub use service::BrokerSyncService;
 pub use traits::*;
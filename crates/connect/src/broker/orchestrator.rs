@@ -4,17 +4,30 @@
 //! by both Tauri (desktop) and Axum (web) platforms.
 
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::stream::{self, StreamExt};
 use log::{debug, error, info, warn};
 
 use super::models::{
-    HoldingsDiff, NewAccountInfo, SyncActivitiesResponse, SyncHoldingsResponse, SyncResult,
+    AccountUniversalActivity, HoldingsDiff, NewAccountInfo, SyncActivitiesResponse,
+    SyncHoldingsResponse, SyncResult,
 };
 use super::progress::{SyncProgressPayload, SyncProgressReporter, SyncStatus};
 use super::traits::{BrokerApiClient, BrokerSyncServiceTrait};
-use crate::broker_ingest::{ImportRunMode, ImportRunStatus, ImportRunSummary};
-use wealthfolio_core::accounts::TrackingMode;
+use crate::broker_ingest::{
+    ActivityHighWaterMark, ImportRunMode, ImportRunStatus, ImportRunSummary,
+};
+use wealthfolio_core::accounts::{Account, TrackingMode};
+
+/// Error message returned when a sync stops because it was cancelled.
+pub const SYNC_CANCELLED_MESSAGE: &str = "Sync cancelled";
+
+/// Days re-fetched before the high-water mark, so activities the broker posts
+/// late with an earlier date are still picked up.
+const MARK_LOOKBACK_DAYS: u64 = 3;
 
 /// Configuration for sync operations.
 #[derive(Debug, Clone)]
@@ -23,6 +36,9 @@ pub struct SyncConfig {
     pub page_limit: i64,
     /// Maximum number of pages to fetch per account (safety limit).
     pub max_pages: usize,
+    /// Maximum number of accounts whose activities are synced concurrently
+    /// by [`SyncOrchestrator::sync_activities_only`].
+    pub account_concurrency: usize,
 }
 
 impl Default for SyncConfig {
@@ -30,10 +46,22 @@ impl Default for SyncConfig {
         Self {
             page_limit: 1000,
             max_pages: 10_000,
+            account_concurrency: 4,
         }
     }
 }
 
+/// Result of an activities-only sync for one account.
+enum ActivitiesOnlyOutcome {
+    Skipped,
+    Failed,
+    Synced {
+        upserted: usize,
+        assets: usize,
+        new_asset_ids: Vec<String>,
+    },
+}
+
 /// Orchestrates broker data synchronization.
 ///
 /// This struct encapsulates the sync logic previously duplicated in
@@ -54,6 +82,7 @@ pub struct SyncOrchestrator<P: SyncProgressReporter> {
     sync_service: Arc<dyn BrokerSyncServiceTrait>,
     progress_reporter: Arc<P>,
     config: SyncConfig,
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl<P: SyncProgressReporter> SyncOrchestrator<P> {
@@ -67,6 +96,23 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
             sync_service,
             progress_reporter,
             config,
+            cancel: None,
+        }
+    }
+
+    /// Stop syncing at the next page or account boundary once `cancel` is set.
+    /// The sync then fails with [`SYNC_CANCELLED_MESSAGE`].
+    pub fn with_cancel_flag(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<(), String> {
+        match &self.cancel {
+            Some(cancel) if cancel.load(Ordering::Acquire) => {
+                Err(SYNC_CANCELLED_MESSAGE.to_string())
+            }
+            _ => Ok(()),
         }
     }
 
@@ -251,6 +297,8 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
         let mut holdings_summary = SyncHoldingsResponse::default();
 
         for account in synced_accounts {
+            self.check_cancelled()?;
+
             let Some(broker_account_id) = account.provider_account_id.clone() else {
                 continue;
             };
@@ -732,6 +780,258 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
         Ok((diff, assets_created, new_asset_ids))
     }
 
+    /// Sync only brokerage activities for existing TRANSACTIONS accounts.
+    ///
+    /// Unlike [`Self::sync_all`] this skips connections, accounts and holdings,
+    /// creates no import runs and reports no sync start or completion, only
+    /// per-page progress for each account. Accounts are synced
+    /// concurrently, up to `account_concurrency` at a time; an account that
+    /// fails is counted in `accounts_failed` without affecting the others.
+    pub async fn sync_activities_only(
+        &self,
+        api_client: &dyn BrokerApiClient,
+    ) -> Result<SyncActivitiesResponse, String> {
+        let synced_accounts = self
+            .sync_service
+            .get_synced_accounts()
+            .map_err(|e| e.to_string())?;
+
+        let end_date = chrono::Utc::now().date_naive();
+        let mut outcomes = stream::iter(synced_accounts)
+            .map(|account| self.sync_account_activities_only(api_client, account, end_date))
+            .buffer_unordered(self.config.account_concurrency.max(1));
+
+        let mut summary = SyncActivitiesResponse::default();
+        let mut first_error: Option<String> = None;
+        while let Some(outcome) = outcomes.next().await {
+            match outcome {
+                Ok(ActivitiesOnlyOutcome::Skipped) => {}
+                Ok(ActivitiesOnlyOutcome::Failed) => summary.accounts_failed += 1,
+                Ok(ActivitiesOnlyOutcome::Synced {
+                    upserted,
+                    assets,
+                    new_asset_ids,
+                }) => {
+                    summary.accounts_synced += 1;
+                    summary.activities_upserted += upserted;
+                    summary.assets_inserted += assets;
+                    summary.new_asset_ids.extend(new_asset_ids);
+                }
+                // Let in-flight accounts finish before reporting the error.
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(summary),
+        }
+    }
+
+    /// Activities-only sync for one account. `Err` is reserved for failures
+    /// that abort the whole sync; per-account failures are finalized here and
+    /// reported as [`ActivitiesOnlyOutcome::Failed`].
+    async fn sync_account_activities_only(
+        &self,
+        api_client: &dyn BrokerApiClient,
+        account: Account,
+        end_date: chrono::NaiveDate,
+    ) -> Result<ActivitiesOnlyOutcome, String> {
+        if account.tracking_mode != TrackingMode::Transactions {
+            return Ok(ActivitiesOnlyOutcome::Skipped);
+        }
+        let Some(provider_account_id) = account.provider_account_id.clone() else {
+            return Ok(ActivitiesOnlyOutcome::Skipped);
+        };
+        let account_id = account.id;
+        let account_name = account.name;
+        self.check_cancelled()?;
+
+        if let Err(err) = self
+            .sync_service
+            .mark_activity_sync_attempt(account_id.clone())
+            .await
+        {
+            error!(
+                "Failed to mark activity sync attempt for {}: {}",
+                account_name, err
+            );
+            return Ok(ActivitiesOnlyOutcome::Failed);
+        }
+
+        let sync_state = self
+            .sync_service
+            .get_activity_sync_state(&account_id)
+            .map_err(|e| e.to_string())?;
+        let stored_mark = sync_state
+            .as_ref()
+            .and_then(|s| s.get_checkpoint::<ActivityHighWaterMark>());
+        // With a high-water mark, start a few days before the later of the
+        // mark and the last success: a quiet account keeps an old mark but has
+        // nothing new before its last success, and the lookback catches late
+        // postings dated before either. Without a mark, re-fetch a day of
+        // overlap before the last success. Re-fetched activities are deduped
+        // by the upsert.
+        let last_success = sync_state.and_then(|s| s.last_successful_at);
+        let start_date = match stored_mark {
+            Some(mark) => Some(
+                last_success
+                    .map_or(mark.last_activity_at, |at| at.max(mark.last_activity_at))
+                    .date_naive()
+                    - chrono::Days::new(MARK_LOOKBACK_DAYS),
+            ),
+            None => last_success.map(|dt| dt.date_naive() - chrono::Days::new(1)),
+        }
+        .map(|d| d.min(end_date))
+        .map(|d| d.format("%Y-%m-%d").to_string());
+        let end_date_str = end_date.format("%Y-%m-%d").to_string();
+
+        info!(
+            "Activities-only sync for account '{}' (provider={}): {} -> {}",
+            account_name,
+            provider_account_id,
+            start_date.as_deref().unwrap_or("ALL"),
+            end_date_str
+        );
+
+        let page_limit = self.config.page_limit;
+        let mut offset: i64 = 0;
+        let mut upserted_total = 0usize;
+        let mut assets_total = 0usize;
+        let mut new_asset_ids: Vec<String> = Vec::new();
+        let mut mark = stored_mark;
+        let mut pages_fetched: usize = 0;
+        let mut fetched_total: usize = 0;
+
+        loop {
+            if let Err(err) = self.check_cancelled() {
+                let _ = self
+                    .sync_service
+                    .finalize_activity_sync_failure(account_id.clone(), err.clone(), None)
+                    .await;
+                return Err(err);
+            }
+
+            let page = match api_client
+                .get_account_activities(
+                    &provider_account_id,
+                    start_date.as_deref(),
+                    Some(&end_date_str),
+                    Some(offset),
+                    Some(page_limit),
+                )
+                .await
+            {
+                Ok(page) => page,
+                Err(err) => {
+                    error!("Failed to fetch activities for {}: {}", account_name, err);
+                    let _ = self
+                        .sync_service
+                        .finalize_activity_sync_failure(account_id.clone(), err.to_string(), None)
+                        .await;
+                    return Ok(ActivitiesOnlyOutcome::Failed);
+                }
+            };
+
+            let received = page.data.len() as i64;
+            if received == 0 {
+                break;
+            }
+            pages_fetched += 1;
+            fetched_total += page.data.len();
+
+            mark = advance_high_water_mark(mark, &page.data);
+            // The mark only narrows the fetch window. Brokers post late or
+            // amended activities dated at or before it, so every returned
+            // activity is upserted; the upsert is idempotent.
+            match self
+                .sync_service
+                .upsert_account_activities(account_id.clone(), None, page.data)
+                .await
+            {
+                Ok((upserted, assets, ids, _needs_review)) => {
+                    upserted_total += upserted;
+                    assets_total += assets;
+                    new_asset_ids.extend(ids);
+                    self.progress_reporter.report_progress(
+                        SyncProgressPayload::new(&account_id, &account_name, SyncStatus::Syncing)
+                            .with_page(pages_fetched)
+                            .with_activities_fetched(fetched_total)
+                            .with_activities_upserted(upserted_total)
+                            .with_message(format!(
+                                "Synced {} activities (page {})",
+                                upserted_total, pages_fetched
+                            )),
+                    );
+                }
+                Err(err) => {
+                    error!("Failed to upsert activities for {}: {}", account_name, err);
+                    let _ = self
+                        .sync_service
+                        .finalize_activity_sync_failure(account_id.clone(), err.to_string(), None)
+                        .await;
+                    return Ok(ActivitiesOnlyOutcome::Failed);
+                }
+            }
+
+            let next_offset = offset + received;
+            let has_more = match page.pagination.as_ref() {
+                Some(p) => match p.has_more {
+                    Some(has_more) => has_more,
+                    None => {
+                        if let Some(total) = p.total {
+                            next_offset < total
+                        } else if let Some(limit) = p.limit {
+                            received >= limit
+                        } else {
+                            received >= page_limit
+                        }
+                    }
+                },
+                None => received >= page_limit,
+            };
+            offset = next_offset;
+
+            if !has_more {
+                break;
+            }
+        }
+
+        if let Some(mark) = mark.filter(|mark| Some(*mark) != stored_mark) {
+            if let Err(err) = self
+                .sync_service
+                .save_activity_high_water_mark(account_id.clone(), mark)
+                .await
+            {
+                warn!(
+                    "Failed to save activity high-water mark for {}: {}",
+                    account_name, err
+                );
+            }
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        if let Err(err) = self
+            .sync_service
+            .finalize_activity_sync_success(account_id, now, None)
+            .await
+        {
+            error!(
+                "Failed to finalize activity sync success for {}: {}",
+                account_name, err
+            );
+            return Ok(ActivitiesOnlyOutcome::Failed);
+        }
+
+        Ok(ActivitiesOnlyOutcome::Synced {
+            upserted: upserted_total,
+            assets: assets_total,
+            new_asset_ids,
+        })
+    }
+
     /// Sync activities for a single account with full pagination.
     ///
     /// Returns (fetched, inserted, assets_created, needs_review, new_asset_ids).
@@ -758,6 +1058,8 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
         let mut all_new_asset_ids: Vec<String> = Vec::new();
 
         loop {
+            self.check_cancelled()?;
+
             // Check max pages limit
             if pages_fetched >= self.config.max_pages {
                 return Err(format!(
@@ -918,14 +1220,564 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
     }
 }
 
+/// Time of a broker activity: its trade date, falling back to the settlement
+/// date. Date-only values are too coarse for a high-water mark and yield `None`.
+fn activity_timestamp(activity: &AccountUniversalActivity) -> Option<DateTime<Utc>> {
+    let value = activity
+        .trade_date
+        .as_deref()
+        .or(activity.settlement_date.as_deref())?
+        .trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Raise `mark` to the newest timestamped activity in `activities`.
+fn advance_high_water_mark(
+    mark: Option<ActivityHighWaterMark>,
+    activities: &[AccountUniversalActivity],
+) -> Option<ActivityHighWaterMark> {
+    activities
+        .iter()
+        .filter_map(activity_timestamp)
+        .map(|last_activity_at| ActivityHighWaterMark { last_activity_at })
+        .chain(mark)
+        .max_by_key(|mark| mark.last_activity_at)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::broker::models::{
+        AccountUniversalActivity, BrokerAccount, BrokerBrokerage, BrokerConnection,
+        BrokerHoldingsResponse, HoldingsBalance, HoldingsOptionPosition, HoldingsPosition,
+        PaginatedUniversalActivity, PaginationDetails, SyncAccountsResponse,
+        SyncConnectionsResponse,
+    };
+    use crate::broker::progress::NoOpProgressReporter;
+    use crate::broker_ingest::{BrokerSyncState, ImportRun};
+    use crate::platform::Platform;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use wealthfolio_core::errors::{Error, Result as CoreResult};
+
+    /// Serves `per_account` activities for every provider account, one page
+    /// at a time, and fails accounts listed in `failing`.
+    struct StubApiClient {
+        per_account: usize,
+        failing: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl BrokerApiClient for StubApiClient {
+        async fn list_connections(&self) -> CoreResult<Vec<BrokerConnection>> {
+            unimplemented!()
+        }
+        async fn list_accounts(&self, _: Option<Vec<String>>) -> CoreResult<Vec<BrokerAccount>> {
+            unimplemented!()
+        }
+        async fn list_brokerages(&self) -> CoreResult<Vec<BrokerBrokerage>> {
+            unimplemented!()
+        }
+        async fn get_account_activities(
+            &self,
+            account_id: &str,
+            _: Option<&str>,
+            _: Option<&str>,
+            offset: Option<i64>,
+            _: Option<i64>,
+        ) -> CoreResult<PaginatedUniversalActivity> {
+            if self.failing.contains(&account_id) {
+                return Err(Error::Unexpected("broker unavailable".to_string()));
+            }
+            let offset = offset.unwrap_or(0) as usize;
+            let data = if offset < self.per_account {
+                vec![AccountUniversalActivity::default()]
+            } else {
+                Vec::new()
+            };
+            Ok(PaginatedUniversalActivity {
+                data,
+                pagination: Some(PaginationDetails {
+                    offset: Some(offset as i64),
+                    limit: Some(1),
+                    total: Some(self.per_account as i64),
+                    has_more: None,
+                }),
+            })
+        }
+        async fn get_account_holdings(&self, _: &str) -> CoreResult<BrokerHoldingsResponse> {
+            unimplemented!()
+        }
+    }
+
+    /// Serves `data` dated on or after the start date as a single page and
+    /// records the requested start dates.
+    #[derive(Default)]
+    struct SinglePageApiClient {
+        data: Vec<AccountUniversalActivity>,
+        start_dates: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl BrokerApiClient for SinglePageApiClient {
+        async fn list_connections(&self) -> CoreResult<Vec<BrokerConnection>> {
+            unimplemented!()
+        }
+        async fn list_accounts(&self, _: Option<Vec<String>>) -> CoreResult<Vec<BrokerAccount>> {
+            unimplemented!()
+        }
+        async fn list_brokerages(&self) -> CoreResult<Vec<BrokerBrokerage>> {
+            unimplemented!()
+        }
+        async fn get_account_activities(
+            &self,
+            _: &str,
+            start_date: Option<&str>,
+            _: Option<&str>,
+            offset: Option<i64>,
+            _: Option<i64>,
+        ) -> CoreResult<PaginatedUniversalActivity> {
+            self.start_dates
+                .lock()
+                .unwrap()
+                .push(start_date.map(str::to_string));
+            let data = if offset.unwrap_or(0) == 0 {
+                self.data
+                    .iter()
+                    .filter(|activity| {
+                        let date = activity.trade_date.as_deref().unwrap_or_default();
+                        start_date.is_none_or(|start| date.get(..10) >= Some(start))
+                    })
+                    .cloned()
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Ok(PaginatedUniversalActivity {
+                data,
+                pagination: Some(PaginationDetails {
+                    offset,
+                    limit: None,
+                    total: None,
+                    has_more: Some(false),
+                }),
+            })
+        }
+        async fn get_account_holdings(&self, _: &str) -> CoreResult<BrokerHoldingsResponse> {
+            unimplemented!()
+        }
+    }
+
+    /// Records finalized accounts and reports each upserted activity as one
+    /// new asset.
+    #[derive(Default)]
+    struct StubSyncService {
+        accounts: Vec<Account>,
+        sync_state: Option<BrokerSyncState>,
+        succeeded: Mutex<Vec<String>>,
+        failed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl BrokerSyncServiceTrait for StubSyncService {
+        async fn sync_connections(
+            &self,
+            _: Vec<BrokerConnection>,
+        ) -> CoreResult<SyncConnectionsResponse> {
+            unimplemented!()
+        }
+        async fn sync_accounts(&self, _: Vec<BrokerAccount>) -> CoreResult<SyncAccountsResponse> {
+            unimplemented!()
+        }
+        fn get_synced_accounts(&self) -> CoreResult<Vec<Account>> {
+            Ok(self.accounts.clone())
+        }
+        fn get_platforms(&self) -> CoreResult<Vec<Platform>> {
+            unimplemented!()
+        }
+        fn get_activity_sync_state(&self, _: &str) -> CoreResult<Option<BrokerSyncState>> {
+            Ok(self.sync_state.clone())
+        }
+        async fn mark_activity_sync_attempt(&self, _: String) -> CoreResult<()> {
+            Ok(())
+        }
+        async fn upsert_account_activities(
+            &self,
+            account_id: String,
+            _: Option<String>,
+            activities: Vec<AccountUniversalActivity>,
+        ) -> CoreResult<(usize, usize, Vec<String>, usize)> {
+            let ids = activities
+                .iter()
+                .map(|_| format!("{}-asset", account_id))
+                .collect();
+            Ok((activities.len(), activities.len(), ids, 0))
+        }
+        async fn finalize_activity_sync_success(
+            &self,
+            account_id: String,
+            _: String,
+            _: Option<String>,
+        ) -> CoreResult<()> {
+            self.succeeded.lock().unwrap().push(account_id);
+            Ok(())
+        }
+        async fn save_activity_high_water_mark(
+            &self,
+            _: String,
+            _: ActivityHighWaterMark,
+        ) -> CoreResult<()> {
+            Ok(())
+        }
+        async fn finalize_activity_sync_failure(
+            &self,
+            account_id: String,
+            _: String,
+            _: Option<String>,
+        ) -> CoreResult<()> {
+            self.failed.lock().unwrap().push(account_id);
+            Ok(())
+        }
+        async fn finalize_activity_sync_needs_review(
+            &self,
+            _: String,
+            _: String,
+            _: Option<String>,
+        ) -> CoreResult<()> {
+            unimplemented!()
+        }
+        fn get_all_sync_states(&self) -> CoreResult<Vec<BrokerSyncState>> {
+            unimplemented!()
+        }
+        fn get_import_runs(&self, _: Option<&str>, _: i64, _: i64) -> CoreResult<Vec<ImportRun>> {
+            unimplemented!()
+        }
+        async fn create_import_run(&self, _: &str, _: ImportRunMode) -> CoreResult<ImportRun> {
+            unimplemented!()
+        }
+        async fn finalize_import_run(
+            &self,
+            _: &str,
+            _: ImportRunSummary,
+            _: ImportRunStatus,
+            _: Option<String>,
+        ) -> CoreResult<()> {
+            unimplemented!()
+        }
+        async fn save_broker_holdings(
+            &self,
+            _: String,
+            _: Vec<HoldingsBalance>,
+            _: Vec<HoldingsPosition>,
+            _: Vec<HoldingsOptionPosition>,
+        ) -> CoreResult<(HoldingsDiff, usize, Vec<String>)> {
+            unimplemented!()
+        }
+    }
+
+    fn transactions_account(id: &str, provider_account_id: &str) -> Account {
+        Account {
+            id: id.to_string(),
+            name: id.to_string(),
+            provider_account_id: Some(provider_account_id.to_string()),
+            tracking_mode: TrackingMode::Transactions,
+            ..Default::default()
+        }
+    }
+
+    fn orchestrator(service: Arc<StubSyncService>) -> SyncOrchestrator<NoOpProgressReporter> {
+        SyncOrchestrator::new(
+            service,
+            Arc::new(NoOpProgressReporter),
+            SyncConfig {
+                page_limit: 1,
+                ..SyncConfig::default()
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_activities_only_sync_sums_independent_accounts() {
+        let service = Arc::new(StubSyncService {
+            accounts: vec![
+                transactions_account("acc-1", "broker-1"),
+                transactions_account("acc-2", "broker-2"),
+                Account {
+                    tracking_mode: TrackingMode::Holdings,
+                    ..transactions_account("acc-3", "broker-3")
+                },
+            ],
+            ..Default::default()
+        });
+        let client = StubApiClient {
+            per_account: 3,
+            failing: Vec::new(),
+        };
+
+        let summary = orchestrator(service.clone())
+            .sync_activities_only(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.accounts_synced, 2);
+        assert_eq!(summary.accounts_failed, 0);
+        assert_eq!(summary.activities_upserted, 6);
+        assert_eq!(summary.assets_inserted, 6);
+        assert_eq!(summary.new_asset_ids.len(), 6);
+        let mut succeeded = service.succeeded.lock().unwrap().clone();
+        succeeded.sort();
+        assert_eq!(succeeded, vec!["acc-1", "acc-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_activities_only_sync_isolates_failed_account() {
+        let service = Arc::new(StubSyncService {
+            accounts: vec![
+                transactions_account("acc-1", "broker-1"),
+                transactions_account("acc-2", "broker-2"),
+            ],
+            ..Default::default()
+        });
+        let client = StubApiClient {
+            per_account: 2,
+            failing: vec!["broker-1"],
+        };
+
+        let summary = orchestrator(service.clone())
+            .sync_activities_only(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.accounts_synced, 1);
+        assert_eq!(summary.accounts_failed, 1);
+        assert_eq!(summary.activities_upserted, 2);
+        assert_eq!(*service.failed.lock().unwrap(), vec!["acc-1"]);
+        assert_eq!(*service.succeeded.lock().unwrap(), vec!["acc-2"]);
+    }
+
+    /// Captures every progress payload and counts start/complete calls.
+    #[derive(Default)]
+    struct CapturingReporter {
+        progress: Mutex<Vec<SyncProgressPayload>>,
+        lifecycle_calls: Mutex<usize>,
+    }
+
+    impl SyncProgressReporter for CapturingReporter {
+        fn report_progress(&self, payload: SyncProgressPayload) {
+            self.progress.lock().unwrap().push(payload);
+        }
+        fn report_sync_start(&self) {
+            *self.lifecycle_calls.lock().unwrap() += 1;
+        }
+        fn report_sync_complete(&self, _: &SyncResult) {
+            *self.lifecycle_calls.lock().unwrap() += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_activities_only_sync_reports_progress_per_page() {
+        let service = Arc::new(StubSyncService {
+            accounts: vec![transactions_account("acc-1", "broker-1")],
+            ..Default::default()
+        });
+        let reporter = Arc::new(CapturingReporter::default());
+        let client = StubApiClient {
+            per_account: 3,
+            failing: Vec::new(),
+        };
+
+        SyncOrchestrator::new(
+            service,
+            reporter.clone(),
+            SyncConfig {
+                page_limit: 1,
+                ..SyncConfig::default()
+            },
+        )
+        .sync_activities_only(&client)
+        .await
+        .unwrap();
+
+        let progress = reporter.progress.lock().unwrap();
+        let pages: Vec<_> = progress
+            .iter()
+            .map(|p| (p.current_page, p.activities_upserted))
+            .collect();
+        assert_eq!(pages, vec![(1, 1), (2, 2), (3, 3)]);
+        assert!(progress.iter().all(|p| p.account_name == "acc-1"));
+        assert_eq!(*reporter.lifecycle_calls.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_sync_stops_before_fetching() {
+        let service = Arc::new(StubSyncService {
+            accounts: vec![transactions_account("acc-1", "broker-1")],
+            ..Default::default()
+        });
+        let client = StubApiClient {
+            per_account: 2,
+            failing: Vec::new(),
+        };
+
+        let result = orchestrator(service.clone())
+            .with_cancel_flag(Arc::new(AtomicBool::new(true)))
+            .sync_activities_only(&client)
+            .await;
+
+        assert_eq!(result.unwrap_err(), SYNC_CANCELLED_MESSAGE);
+        assert!(service.succeeded.lock().unwrap().is_empty());
+    }
 
     #[test]
     fn test_sync_config_default() {
         let config = SyncConfig::default();
         assert_eq!(config.page_limit, 1000);
         assert_eq!(config.max_pages, 10_000);
+        assert_eq!(config.account_concurrency, 4);
+    }
+
+    fn dated(trade_date: Option<&str>, settlement_date: Option<&str>) -> AccountUniversalActivity {
+        AccountUniversalActivity {
+            trade_date: trade_date.map(str::to_string),
+            settlement_date: settlement_date.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn test_activities_at_or_before_the_mark_are_still_upserted() {
+        let mut sync_state = BrokerSyncState::new("acc-1".to_string(), "broker".to_string());
+        sync_state
+            .set_checkpoint(&ActivityHighWaterMark {
+                last_activity_at: at("2024-03-02T07:30:00Z"),
+            })
+            .unwrap();
+        let service = Arc::new(StubSyncService {
+            accounts: vec![transactions_account("acc-1", "broker-1")],
+            sync_state: Some(sync_state),
+            ..Default::default()
+        });
+        let client = SinglePageApiClient {
+            data: vec![
+                // Posted late, dated before the mark.
+                dated(Some("2024-03-01T23:00:00Z"), None),
+                // Amended activity at the mark itself.
+                dated(Some("2024-03-02T07:30:00Z"), None),
+                dated(Some("2024-03-03T00:00:00Z"), None),
+            ],
+            ..Default::default()
+        };
+
+        let summary = orchestrator(service)
+            .sync_activities_only(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.activities_upserted, 3);
+        assert_eq!(
+            client
+                .start_dates
+                .lock()
+                .unwrap()
+                .first()
+                .cloned()
+                .flatten(),
+            Some("2024-02-28".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_starts_at_last_success_when_the_mark_is_older() {
+        let mut sync_state = BrokerSyncState::new("acc-1".to_string(), "broker".to_string());
+        sync_state
+            .set_checkpoint(&ActivityHighWaterMark {
+                last_activity_at: at("2024-03-02T07:30:00Z"),
+            })
+            .unwrap();
+        sync_state.last_successful_at = Some(at("2024-03-10T12:00:00Z"));
+        let service = Arc::new(StubSyncService {
+            accounts: vec![transactions_account("acc-1", "broker-1")],
+            sync_state: Some(sync_state),
+            ..Default::default()
+        });
+        let client = SinglePageApiClient::default();
+
+        orchestrator(service)
+            .sync_activities_only(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client.start_dates.lock().unwrap().as_slice(),
+            [Some("2024-03-07".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_late_activity_dated_before_the_mark_is_fetched() {
+        let mut sync_state = BrokerSyncState::new("acc-1".to_string(), "broker".to_string());
+        sync_state
+            .set_checkpoint(&ActivityHighWaterMark {
+                last_activity_at: at("2024-03-10T07:30:00Z"),
+            })
+            .unwrap();
+        sync_state.last_successful_at = Some(at("2024-03-10T12:00:00Z"));
+        let service = Arc::new(StubSyncService {
+            accounts: vec![transactions_account("acc-1", "broker-1")],
+            sync_state: Some(sync_state),
+            ..Default::default()
+        });
+        let client = SinglePageApiClient {
+            data: vec![
+                // Already synced before the lookback window.
+                dated(Some("2024-03-01T10:00:00Z"), None),
+                // Posted after the mark was set, dated two days before it.
+                dated(Some("2024-03-08T15:00:00Z"), None),
+            ],
+            ..Default::default()
+        };
+
+        let summary = orchestrator(service)
+            .sync_activities_only(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.activities_upserted, 1);
+    }
+
+    #[test]
+    fn test_high_water_mark_is_newest_timestamped_activity() {
+        let page = vec![
+            dated(Some("2024-03-01T10:00:00Z"), None),
+            dated(Some("2024-03-02T09:30:00+02:00"), None),
+            // Date-only values are ignored, even when later.
+            dated(Some("2024-03-05"), None),
+            dated(None, Some("2024-03-01 12:00:00")),
+            dated(None, None),
+        ];
+
+        let mark = advance_high_water_mark(None, &page).unwrap();
+        assert_eq!(mark.last_activity_at, at("2024-03-02T07:30:00Z"));
+
+        let later = ActivityHighWaterMark {
+            last_activity_at: at("2024-04-01T00:00:00Z"),
+        };
+        assert_eq!(advance_high_water_mark(Some(later), &page), Some(later));
+        assert_eq!(
+            advance_high_water_mark(None, &[dated(Some("2024-03-05"), None)]),
+            None
+        );
     }
 }
@@ -49,6 +49,9 @@ pub struct SyncProgressPayload {
     pub current_page: usize,
     /// Total activities fetched so far
     pub activities_fetched: usize,
+    /// Total activities upserted so far
+    #[serde(default)]
+    pub activities_upserted: usize,
     /// Optional status message
     pub message: Option<String>,
 }
@@ -66,6 +69,7 @@ impl SyncProgressPayload {
             status: status.to_string(),
             current_page: 0,
             activities_fetched: 0,
+            activities_upserted: 0,
             message: None,
         }
     }
@@ -82,6 +86,12 @@ impl SyncProgressPayload {
         self
     }
 
+    /// Set the activities upserted count.
+    pub fn with_activities_upserted(mut self, count: usize) -> Self {
+        self.activities_upserted = count;
+        self
+    }
+
     /// Set an optional message.
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
         self.message = Some(message.into());
@@ -0,0 +1,154 @@
+//! Tracking of the background broker sync task.
+//!
+//! Hosts spawn the full broker sync in a background task. `BrokerSyncRuntimeState`
+//! keeps that task's handle and cancellation flag so the sync can be stopped
+//! and so a second trigger does not race with one that is still running.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::task::JoinHandle;
+
+struct RunningSync {
+    cancel: Arc<AtomicBool>,
+    /// `None` until [`BrokerSyncRuntimeState::attach`], or for a sync awaited
+    /// in place and released with [`BrokerSyncRuntimeState::finish`].
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RunningSync {
+    fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_none_or(|handle| !handle.is_finished())
+    }
+}
+
+/// The background broker sync, if one has been started.
+#[derive(Default)]
+pub struct BrokerSyncRuntimeState {
+    current: Mutex<Option<RunningSync>>,
+}
+
+impl BrokerSyncRuntimeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claim the slot for a new sync and return its cancellation flag, or
+    /// `None` if a sync is already running.
+    ///
+    /// Pass the flag to [`SyncOrchestrator::with_cancel_flag`](super::SyncOrchestrator::with_cancel_flag)
+    /// and hand the spawned task to [`Self::attach`].
+    pub fn try_start(&self) -> Option<Arc<AtomicBool>> {
+        let mut current = self
+            .current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if current.as_ref().is_some_and(RunningSync::is_running) {
+            return None;
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        *current = Some(RunningSync {
+            cancel: cancel.clone(),
+            handle: None,
+        });
+        Some(cancel)
+    }
+
+    /// Record the task running the sync claimed by [`Self::try_start`].
+    pub fn attach(&self, handle: JoinHandle<()>) {
+        if let Some(running) = self
+            .current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_mut()
+        {
+            running.handle = Some(handle);
+        }
+    }
+
+    /// Release the slot claimed by [`Self::try_start`] for a sync that was
+    /// awaited in place rather than spawned.
+    pub fn finish(&self, cancel: &Arc<AtomicBool>) {
+        let mut current = self
+            .current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if current
+            .as_ref()
+            .is_some_and(|running| Arc::ptr_eq(&running.cancel, cancel))
+        {
+            *current = None;
+        }
+    }
+
+    /// Ask the running sync to stop at its next page boundary. Returns `false`
+    /// if no sync is running.
+    pub fn cancel(&self) -> bool {
+        let current = self
+            .current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match current.as_ref() {
+            Some(running) if running.is_running() => {
+                running.cancel.store(true, Ordering::Release);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .is_some_and(RunningSync::is_running)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_refuses_concurrent_start_until_task_finishes() {
+        let state = BrokerSyncRuntimeState::new();
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+
+        let cancel = state.try_start().expect("first start");
+        assert!(state.try_start().is_none(), "claimed slot blocks a start");
+        state.attach(tokio::spawn(async move {
+            let _ = released.await;
+        }));
+        assert!(state.is_running());
+        assert!(state.try_start().is_none(), "running task blocks a start");
+
+        assert!(state.cancel());
+        assert!(cancel.load(Ordering::Acquire));
+
+        release.send(()).unwrap();
+        while state.is_running() {
+            tokio::task::yield_now().await;
+        }
+        assert!(!state.cancel());
+        let next = state.try_start().expect("start after finish");
+        assert!(!next.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn test_finish_releases_in_place_sync() {
+        let state = BrokerSyncRuntimeState::new();
+        let first = state.try_start().expect("first start");
+        assert!(state.try_start().is_none());
+
+        // A stale flag does not release someone else's run.
+        state.finish(&Arc::new(AtomicBool::new(false)));
+        assert!(state.is_running());
+
+        state.finish(&first);
+        assert!(!state.is_running());
+        assert!(state.try_start().is_some());
+    }
+}
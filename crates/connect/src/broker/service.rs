@@ -12,8 +12,9 @@ use super::models::{
 };
 use super::traits::{BrokerSyncServiceTrait, PlatformRepositoryTrait};
 use crate::broker_ingest::{
-    BrokerSyncState, BrokerSyncStateRepositoryTrait, ImportRun, ImportRunMode,
-    ImportRunRepositoryTrait, ImportRunStatus, ImportRunSummary, ImportRunType, ReviewMode,
+    ActivityHighWaterMark, BrokerSyncState, BrokerSyncStateRepositoryTrait, ImportRun,
+    ImportRunMode, ImportRunRepositoryTrait, ImportRunStatus, ImportRunSummary, ImportRunType,
+    ReviewMode,
 };
 use crate::platform::Platform;
 use chrono::{DateTime, Months, Utc};
@@ -29,7 +30,7 @@ use wealthfolio_core::assets::{
     build_option_metadata, parse_crypto_pair_symbol, parse_symbol_with_exchange_suffix, AssetKind,
     AssetServiceTrait, AssetSpec, InstrumentType,
 };
-use wealthfolio_core::errors::Result;
+use wealthfolio_core::errors::{Error, Result};
 use wealthfolio_core::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink};
 use wealthfolio_core::portfolio::snapshot::{
     AccountStateSnapshot, Position, SnapshotRepositoryTrait, SnapshotServiceTrait, SnapshotSource,
@@ -521,6 +522,22 @@ impl BrokerSyncServiceTrait for BrokerSyncService {
             .await
     }
 
+    async fn save_activity_high_water_mark(
+        &self,
+        account_id: String,
+        mark: ActivityHighWaterMark,
+    ) -> Result<()> {
+        let checkpoint = serde_json::to_value(mark)
+            .map_err(|e| Error::Unexpected(format!("Failed to encode checkpoint: {}", e)))?;
+        self.brokers_sync_state_repository
+            .upsert_checkpoint(
+                account_id,
+                DEFAULT_BROKERAGE_PROVIDER.to_string(),
+                checkpoint,
+            )
+            .await
+    }
+
     async fn finalize_activity_sync_failure(
         &self,
         account_id: String,
@@ -7,7 +7,7 @@ use super::models::{
     BrokerHoldingsResponse, HoldingsBalance, HoldingsDiff, HoldingsOptionPosition,
     HoldingsPosition, PaginatedUniversalActivity, SyncAccountsResponse, SyncConnectionsResponse,
 };
-use crate::broker_ingest::BrokerSyncState;
+use crate::broker_ingest::{ActivityHighWaterMark, BrokerSyncState};
 use crate::broker_ingest::{ImportRun, ImportRunMode, ImportRunStatus, ImportRunSummary};
 use crate::platform::Platform;
 use wealthfolio_core::accounts::Account;
@@ -110,6 +110,13 @@ pub trait BrokerSyncServiceTrait: Send + Sync {
         import_run_id: Option<String>,
     ) -> Result<()>;
 
+    /// Store the activity high-water mark for an account.
+    async fn save_activity_high_water_mark(
+        &self,
+        account_id: String,
+        mark: ActivityHighWaterMark,
+    ) -> Result<()>;
+
     /// Finalize an activity sync as failed for an account.
     async fn finalize_activity_sync_failure(
         &self,
@@ -5,7 +5,7 @@ mod models;
 
 pub use core_adapter::CoreImportRunRepositoryAdapter;
 pub use models::{
-    BrokerSyncState, BrokerSyncStateRepositoryTrait, ImportRun, ImportRunMode,
-    ImportRunRepositoryTrait, ImportRunStatus, ImportRunSummary, ImportRunType,
+    ActivityHighWaterMark, BrokerSyncState, BrokerSyncStateRepositoryTrait, ImportRun,
+    ImportRunMode, ImportRunRepositoryTrait, ImportRunStatus, ImportRunSummary, ImportRunType,
     PlaidInvestmentsCheckpoint, PlaidSyncCheckpoint, ReviewMode, SnapTradeCheckpoint, SyncStatus,
 };
@@ -89,6 +89,14 @@ pub struct SnapTradeCheckpoint {
     pub lookback_days: u32,
 }
 
+/// Latest activity already synced for a broker account. Incremental activity
+/// syncs only import activities strictly newer than this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityHighWaterMark {
+    pub last_activity_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PlaidSyncCheckpoint {
@@ -259,6 +267,13 @@ pub trait BrokerSyncStateRepositoryTrait: Send + Sync {
         warning: String,
         import_run_id: Option<String>,
     ) -> Result<()>;
+    /// Replace the stored checkpoint, creating the sync state if needed.
+    async fn upsert_checkpoint(
+        &self,
+        account_id: String,
+        provider: String,
+        checkpoint: Value,
+    ) -> Result<()>;
     fn get_all(&self) -> Result<Vec<BrokerSyncState>>;
 }
 
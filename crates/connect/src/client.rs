@@ -7,8 +7,12 @@
 use async_trait::async_trait;
 use log::{debug, info};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
+use tokio::sync::Mutex;
 
 use crate::broker::{
     BrokerAccount, BrokerBrokerage, BrokerConnection, BrokerConnectionBrokerage,
@@ -116,6 +120,23 @@ struct ApiErrorResponse {
     message: Option<String>,
 }
 
+/// Error for a non-success response, keeping the status so callers can
+/// recognise it (see [`is_unauthorized`]).
+fn status_error(status: StatusCode, message: &str) -> Error {
+    Error::Api {
+        status: status.as_u16(),
+        message: message.chars().take(200).collect(),
+    }
+}
+
+/// Whether `error` is the cloud API rejecting the access token (HTTP 401).
+pub fn is_unauthorized(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Api { status, .. } if *status == StatusCode::UNAUTHORIZED.as_u16()
+    )
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Connect API Client
 // ─────────────────────────────────────────────────────────────────────────────
@@ -206,13 +227,9 @@ impl ConnectApiClient {
                     .message
                     .or(err.error)
                     .unwrap_or_else(|| format!("HTTP {}", status));
-                return Err(Error::Unexpected(format!("API error: {}", msg)));
+                return Err(status_error(status, &msg));
             }
-            return Err(Error::Unexpected(format!(
-                "API error {}: {}",
-                status,
-                body.chars().take(200).collect::<String>()
-            )));
+            return Err(status_error(status, &body));
         }
 
         serde_json::from_str(&body)
@@ -410,11 +427,7 @@ impl BrokerApiClient for ConnectApiClient {
             .map_err(|e| Error::Unexpected(format!("Failed to read response: {}", e)))?;
 
         if !status.is_success() {
-            return Err(Error::Unexpected(format!(
-                "API error {}: {}",
-                status,
-                body.chars().take(200).collect::<String>()
-            )));
+            return Err(status_error(status, &body));
         }
 
         let api_response: ApiConnectionsResponse = serde_json::from_str(&body).map_err(|e| {
@@ -490,11 +503,7 @@ impl BrokerApiClient for ConnectApiClient {
             .map_err(|e| Error::Unexpected(format!("Failed to read response: {}", e)))?;
 
         if !status.is_success() {
-            return Err(Error::Unexpected(format!(
-                "API error {}: {}",
-                status,
-                body.chars().take(200).collect::<String>()
-            )));
+            return Err(status_error(status, &body));
         }
 
         let api_response: ApiAccountsResponse = serde_json::from_str(&body).map_err(|e| {
@@ -534,6 +543,134 @@ impl BrokerApiClient for ConnectApiClient {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Token-Refreshing Client
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Supplies access tokens to a [`RefreshingConnectClient`].
+#[async_trait]
+pub trait AccessTokenSource: Send + Sync {
+    /// A valid access token, minted if none is cached.
+    async fn access_token(&self) -> Result<String>;
+
+    /// Forget the cached token so the next [`Self::access_token`] mints a new one.
+    async fn invalidate(&self);
+}
+
+/// A [`BrokerApiClient`] that outlives the access token it started with.
+///
+/// A `ConnectApiClient` keeps the token it was built with, which can expire
+/// during a long sync. When the cloud API answers 401, this client
+/// invalidates the cached token, mints a fresh one, rebuilds the inner client
+/// and retries the call once.
+pub struct RefreshingConnectClient {
+    base_url: String,
+    tokens: Arc<dyn AccessTokenSource>,
+    client: RwLock<Arc<ConnectApiClient>>,
+    refresh_lock: Mutex<()>,
+}
+
+impl ConnectApiClient {
+    /// Retry broker calls once with a freshly minted token on 401.
+    pub fn with_token_refresh(self, tokens: Arc<dyn AccessTokenSource>) -> RefreshingConnectClient {
+        RefreshingConnectClient {
+            base_url: self.base_url.clone(),
+            tokens,
+            client: RwLock::new(Arc::new(self)),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl RefreshingConnectClient {
+    /// The client currently in use.
+    pub fn client(&self) -> Arc<ConnectApiClient> {
+        self.client
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Run `call`, and on 401 run it once more against a rebuilt client.
+    async fn with_refresh<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        F: Fn(Arc<ConnectApiClient>) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T>> + Send,
+    {
+        let client = self.client();
+        match call(client.clone()).await {
+            Err(err) if is_unauthorized(&err) => {
+                info!("[ConnectApi] Access token rejected, retrying with a fresh token");
+                call(self.refresh(&client).await?).await
+            }
+            result => result,
+        }
+    }
+
+    /// Replace `stale` with a client holding a fresh token. Calls that failed
+    /// concurrently share the client built by the first of them.
+    async fn refresh(&self, stale: &Arc<ConnectApiClient>) -> Result<Arc<ConnectApiClient>> {
+        let _guard = self.refresh_lock.lock().await;
+        let current = self.client();
+        if !Arc::ptr_eq(&current, stale) {
+            return Ok(current);
+        }
+
+        self.tokens.invalidate().await;
+        let token = self.tokens.access_token().await?;
+        let client = Arc::new(ConnectApiClient::new(&self.base_url, &token)?);
+        *self
+            .client
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = client.clone();
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl BrokerApiClient for RefreshingConnectClient {
+    async fn list_connections(&self) -> Result<Vec<BrokerConnection>> {
+        self.with_refresh(|client| async move { client.list_connections().await })
+            .await
+    }
+
+    async fn list_accounts(
+        &self,
+        authorization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<BrokerAccount>> {
+        self.with_refresh(|client| {
+            let authorization_ids = authorization_ids.clone();
+            async move { client.list_accounts(authorization_ids).await }
+        })
+        .await
+    }
+
+    async fn list_brokerages(&self) -> Result<Vec<BrokerBrokerage>> {
+        self.client().list_brokerages().await
+    }
+
+    async fn get_account_activities(
+        &self,
+        account_id: &str,
+        start_date: Option<&str>,
+        end_date: Option<&str>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<PaginatedUniversalActivity> {
+        self.with_refresh(|client| async move {
+            ConnectApiClient::get_account_activities(
+                &client, account_id, start_date, end_date, offset, limit,
+            )
+            .await
+        })
+        .await
+    }
+
+    async fn get_account_holdings(&self, account_id: &str) -> Result<BrokerHoldingsResponse> {
+        self.client().get_account_holdings(account_id).await
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Public (Unauthenticated) API Functions
 // ─────────────────────────────────────────────────────────────────────────────
@@ -575,11 +712,7 @@ pub async fn fetch_subscription_plans_public(base_url: &str) -> Result<PlansResp
         .map_err(|e| Error::Unexpected(format!("Failed to read response: {}", e)))?;
 
     if !status.is_success() {
-        return Err(Error::Unexpected(format!(
-            "API error {}: {}",
-            status,
-            body.chars().take(200).collect::<String>()
-        )));
+        return Err(status_error(status, &body));
     }
 
     serde_json::from_str(&body)
@@ -601,4 +734,110 @@ mod tests {
         let client = ConnectApiClient::new("https://api.wealthfolio.app/", "test-token").unwrap();
         assert_eq!(client.base_url, "https://api.wealthfolio.app");
     }
+
+    #[test]
+    fn test_is_unauthorized_uses_status_not_message() {
+        assert!(is_unauthorized(&status_error(StatusCode::UNAUTHORIZED, "")));
+        assert!(!is_unauthorized(&status_error(
+            StatusCode::FORBIDDEN,
+            "API error 401 Unauthorized"
+        )));
+        assert!(!is_unauthorized(&Error::Unexpected(
+            "API error 401 Unauthorized: expired".to_string()
+        )));
+    }
+
+    /// Token source that mints `token-1`, `token-2`, ... on demand.
+    #[derive(Default)]
+    struct CountingTokenSource {
+        minted: std::sync::atomic::AtomicUsize,
+        invalidated: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AccessTokenSource for CountingTokenSource {
+        async fn access_token(&self) -> Result<String> {
+            let n = self
+                .minted
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(format!("token-{}", n + 1))
+        }
+
+        async fn invalidate(&self) {
+            self.invalidated
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    /// Cloud API that rejects every bearer token except `accepted_token`.
+    fn start_api_server(accepted_token: &'static str) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).to_ascii_lowercase();
+                let (status, json) =
+                    if request.contains(&format!("authorization: bearer {}", accepted_token)) {
+                        (200, r#"{"accounts":[{"id":"acc-1","name":"Brokerage"}]}"#)
+                    } else {
+                        (401, r#"{"error":"unauthorized","message":"Token expired"}"#)
+                    };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    json.len(),
+                    json
+                );
+            }
+        });
+        base_url
+    }
+
+    #[tokio::test]
+    async fn test_retries_once_with_fresh_token_on_unauthorized() {
+        use std::sync::atomic::Ordering;
+
+        let base_url = start_api_server("token-1");
+        let tokens = Arc::new(CountingTokenSource::default());
+        let client = ConnectApiClient::new(&base_url, "expired")
+            .unwrap()
+            .with_token_refresh(tokens.clone());
+
+        let accounts = client.list_accounts(None).await.expect("retry succeeds");
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].id.as_deref(), Some("acc-1"));
+        assert_eq!(tokens.invalidated.load(Ordering::SeqCst), 1);
+        assert_eq!(tokens.minted.load(Ordering::SeqCst), 1);
+
+        // The rebuilt client is kept for later calls.
+        client.list_accounts(None).await.expect("fresh client");
+        assert_eq!(tokens.minted.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_one_retry() {
+        use std::sync::atomic::Ordering;
+
+        let base_url = start_api_server("never-minted");
+        let tokens = Arc::new(CountingTokenSource::default());
+        let client = ConnectApiClient::new(&base_url, "expired")
+            .unwrap()
+            .with_token_refresh(tokens.clone());
+
+        let err = client.list_accounts(None).await.unwrap_err();
+        assert!(is_unauthorized(&err), "unexpected error: {}", err);
+        assert_eq!(tokens.minted.load(Ordering::SeqCst), 1);
+    }
 }
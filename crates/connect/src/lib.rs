@@ -14,23 +14,26 @@ pub mod token_lifecycle;
 #[cfg(feature = "broker")]
 pub use broker::{
     AccountUniversalActivity, BrokerAccount, BrokerApiClient, BrokerBrokerage, BrokerConnection,
-    BrokerSyncService, BrokerSyncServiceTrait, NoOpProgressReporter, PaginatedUniversalActivity,
-    PlanLimitValue, PlanLimits, PlanPricing, PlansResponse, PlatformRepositoryTrait,
-    SubscriptionPlan, SyncAccountsResponse, SyncActivitiesResponse, SyncConfig,
-    SyncConnectionsResponse, SyncOrchestrator, SyncProgressPayload, SyncProgressReporter,
-    SyncResult, SyncStatus, UserInfo, UserTeam,
+    BrokerSyncRuntimeState, BrokerSyncService, BrokerSyncServiceTrait, NoOpProgressReporter,
+    PaginatedUniversalActivity, PlanLimitValue, PlanLimits, PlanPricing, PlansResponse,
+    PlatformRepositoryTrait, SubscriptionPlan, SyncAccountsResponse, SyncActivitiesResponse,
+    SyncConfig, SyncConnectionsResponse, SyncOrchestrator, SyncProgressPayload,
+    SyncProgressReporter, SyncResult, SyncStatus, UserInfo, UserTeam, SYNC_CANCELLED_MESSAGE,
 };
 
 // Re-export the HTTP client and public functions
-pub use client::{fetch_subscription_plans_public, ConnectApiClient, DEFAULT_CLOUD_API_URL};
+pub use client::{
+    fetch_subscription_plans_public, is_unauthorized, AccessTokenSource, ConnectApiClient,
+    RefreshingConnectClient, DEFAULT_CLOUD_API_URL,
+};
 pub use token_lifecycle::{
-    ensure_valid_access_token, TokenLifecycleConfig, TokenLifecycleError, TokenLifecycleState,
-    CLOUD_ACCESS_TOKEN_KEY, CLOUD_REFRESH_TOKEN_KEY,
+    ensure_valid_access_token, LifecycleTokenSource, TokenLifecycleConfig, TokenLifecycleError,
+    TokenLifecycleState, CLOUD_ACCESS_TOKEN_KEY, CLOUD_REFRESH_TOKEN_KEY,
 };
 
 pub use broker_ingest::{
-    BrokerSyncState, BrokerSyncStateRepositoryTrait, CoreImportRunRepositoryAdapter, ImportRun,
-    ImportRunMode, ImportRunRepositoryTrait, ImportRunStatus, ImportRunSummary, ImportRunType,
-    ReviewMode,
+    ActivityHighWaterMark, BrokerSyncState, BrokerSyncStateRepositoryTrait,
+    CoreImportRunRepositoryAdapter, ImportRun, ImportRunMode, ImportRunRepositoryTrait,
+    ImportRunStatus, ImportRunSummary, ImportRunType, ReviewMode,
 };
 pub use platform::Platform;
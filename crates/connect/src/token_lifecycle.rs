@@ -1,10 +1,16 @@
+use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
+use log::debug;
 use serde::Deserialize;
 use tokio::sync::{Mutex, RwLock};
+use wealthfolio_core::errors::Error;
 use wealthfolio_core::secrets::SecretStore;
 
+use crate::client::AccessTokenSource;
+
 pub const CLOUD_REFRESH_TOKEN_KEY: &str = "sync_refresh_token";
 pub const CLOUD_ACCESS_TOKEN_KEY: &str = "sync_access_token";
 
@@ -90,6 +96,8 @@ struct RefreshTokenResponse {
 struct RefreshErrorResponse {
     error: Option<String>,
     error_description: Option<String>,
+    error_code: Option<String>,
+    msg: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,7 +131,7 @@ pub async fn ensure_valid_access_token(
         ));
     }
 
-    let refresh_token = secret_store
+    let mut refresh_token = secret_store
         .get_secret(CLOUD_REFRESH_TOKEN_KEY)
         .map_err(|e| TokenLifecycleError::Internal(format!("Failed to read refresh token: {}", e)))?
         .ok_or_else(|| {
@@ -132,7 +140,24 @@ pub async fn ensure_valid_access_token(
             )
         })?;
 
-    let response = refresh_access_token(&refresh_token, config).await;
+    let mut response = refresh_access_token(&refresh_token, config).await;
+    if response
+        .as_ref()
+        .is_err_and(RefreshRequestError::is_token_reused)
+    {
+        // A concurrent refresh rotated the token first. Retry once with the
+        // rotated token it stored before treating the session as expired.
+        let stored = secret_store
+            .get_secret(CLOUD_REFRESH_TOKEN_KEY)
+            .ok()
+            .flatten()
+            .filter(|stored| *stored != refresh_token);
+        if let Some(rotated) = stored {
+            debug!("Refresh token was already rotated; retrying with the stored token");
+            refresh_token = rotated;
+            response = refresh_access_token(&refresh_token, config).await;
+        }
+    }
     match response {
         Ok(response) => {
             let rotated_refresh = response
@@ -173,6 +198,45 @@ pub async fn ensure_valid_access_token(
     }
 }
 
+/// [`AccessTokenSource`] that mints tokens from the stored refresh token via
+/// [`ensure_valid_access_token`].
+pub struct LifecycleTokenSource {
+    secret_store: Arc<dyn SecretStore>,
+    state: Arc<TokenLifecycleState>,
+    config: Option<TokenLifecycleConfig>,
+}
+
+impl LifecycleTokenSource {
+    pub fn new(
+        secret_store: Arc<dyn SecretStore>,
+        state: Arc<TokenLifecycleState>,
+        config: Option<TokenLifecycleConfig>,
+    ) -> Self {
+        Self {
+            secret_store,
+            state,
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl AccessTokenSource for LifecycleTokenSource {
+    async fn access_token(&self) -> wealthfolio_core::errors::Result<String> {
+        ensure_valid_access_token(
+            self.secret_store.as_ref(),
+            self.state.as_ref(),
+            self.config.as_ref(),
+        )
+        .await
+        .map_err(|e| Error::Unexpected(e.to_string()))
+    }
+
+    async fn invalidate(&self) {
+        self.state.clear_cache().await;
+    }
+}
+
 pub fn is_access_token_fresh(token: &str, now: SystemTime, expiry_buffer_secs: u64) -> bool {
     let Some(exp) = parse_jwt_exp(token) else {
         return false;
@@ -269,14 +333,22 @@ async fn refresh_access_token(
         let parsed = serde_json::from_str::<RefreshErrorResponse>(&body).ok();
         let error_code = parsed
             .as_ref()
-            .and_then(|value| value.error.clone())
+            .and_then(|value| value.error_code.clone().or(value.error.clone()))
             .unwrap_or_default();
         let error_message = parsed
             .as_ref()
-            .and_then(|value| value.error_description.clone().or(value.error.clone()))
+            .and_then(|value| {
+                value
+                    .error_description
+                    .clone()
+                    .or(value.msg.clone())
+                    .or(value.error.clone())
+            })
             .unwrap_or_else(|| format!("HTTP {}: {}", status, body));
         let invalid = is_session_invalid(status.as_u16(), &error_code, &error_message);
-        return Err(RefreshRequestError::new(invalid, error_message));
+        let mut error = RefreshRequestError::new(invalid, error_message);
+        error.token_reused = is_refresh_token_reused(&error_code, &error.message);
+        return Err(error);
     }
 
     serde_json::from_str::<RefreshTokenResponse>(&body).map_err(|e| {
@@ -301,9 +373,16 @@ fn is_session_invalid(status: u16, error_code: &str, message: &str) -> bool {
         || lower.contains("invalid grant")
 }
 
+/// Supabase rejects a refresh token that another refresh already rotated.
+fn is_refresh_token_reused(error_code: &str, message: &str) -> bool {
+    error_code.eq_ignore_ascii_case("refresh_token_already_used")
+        || message.to_ascii_lowercase().contains("already used")
+}
+
 #[derive(Debug)]
 struct RefreshRequestError {
     session_invalid: bool,
+    token_reused: bool,
     message: String,
 }
 
@@ -311,6 +390,7 @@ impl RefreshRequestError {
     fn new(session_invalid: bool, message: String) -> Self {
         Self {
             session_invalid,
+            token_reused: false,
             message,
         }
     }
@@ -318,6 +398,10 @@ impl RefreshRequestError {
     fn is_session_invalid(&self) -> bool {
         self.session_invalid
     }
+
+    fn is_token_reused(&self) -> bool {
+        self.token_reused
+    }
 }
 
 #[cfg(test)]
@@ -369,4 +453,193 @@ mod tests {
         ));
         assert!(is_session_invalid(401, "", "unauthorized"));
     }
+
+    #[derive(Default)]
+    struct MemorySecretStore {
+        secrets: std::sync::Mutex<std::collections::HashMap<String, String>>,
+    }
+
+    impl SecretStore for MemorySecretStore {
+        fn set_secret(&self, service: &str, secret: &str) -> wealthfolio_core::errors::Result<()> {
+            self.secrets
+                .lock()
+                .unwrap()
+                .insert(service.to_string(), secret.to_string());
+            Ok(())
+        }
+
+        fn get_secret(&self, service: &str) -> wealthfolio_core::errors::Result<Option<String>> {
+            Ok(self.secrets.lock().unwrap().get(service).cloned())
+        }
+
+        fn delete_secret(&self, service: &str) -> wealthfolio_core::errors::Result<()> {
+            self.secrets.lock().unwrap().remove(service);
+            Ok(())
+        }
+    }
+
+    /// Auth server that answers each refresh request body with
+    /// `respond(body) -> (status, json)` and counts the requests it served.
+    fn start_refresh_server<F>(
+        respond: F,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>)
+    where
+        F: Fn(&str) -> (u16, String) + Send + 'static,
+    {
+        use std::io::{Read, Write};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = std::sync::Arc::new(AtomicUsize::new(0));
+        let served = hits.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read headers, then as much body as Content-Length announces.
+                let body = loop {
+                    let Ok(n) = stream.read(&mut buf) else {
+                        break String::new();
+                    };
+                    if n == 0 {
+                        break String::new();
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    let Some(header_end) = text.find("\r\n\r\n") else {
+                        continue;
+                    };
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break text[header_end + 4..].to_string();
+                    }
+                };
+                served.fetch_add(1, Ordering::SeqCst);
+                let (status, json) = respond(&body);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    json.len(),
+                    json
+                );
+            }
+        });
+        (base_url, hits)
+    }
+
+    fn token_response(access_token: &str, refresh_token: &str) -> String {
+        format!(
+            r#"{{"access_token":"{}","refresh_token":"{}","expires_in":3600}}"#,
+            access_token, refresh_token
+        )
+    }
+
+    fn fresh_jwt() -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("current time after epoch")
+            .as_secs() as i64;
+        fake_jwt_with_exp(now + 3600)
+    }
+
+    #[tokio::test]
+    async fn warmed_token_is_served_from_cache() {
+        use std::sync::atomic::Ordering;
+
+        let token = fresh_jwt();
+        let response = token_response(&token, "rotated");
+        let (auth_url, hits) = start_refresh_server(move |_| (200, response.clone()));
+        let config = TokenLifecycleConfig::new(auth_url, "publishable".to_string());
+        let store = MemorySecretStore::default();
+        store
+            .set_secret(CLOUD_REFRESH_TOKEN_KEY, "refresh")
+            .unwrap();
+        let state = TokenLifecycleState::new();
+
+        let warmed = ensure_valid_access_token(&store, &state, Some(&config))
+            .await
+            .expect("warm");
+        assert_eq!(warmed, token);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let cached = ensure_valid_access_token(&store, &state, Some(&config))
+            .await
+            .expect("cached");
+        assert_eq!(cached, token);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            store
+                .get_secret(CLOUD_REFRESH_TOKEN_KEY)
+                .unwrap()
+                .as_deref(),
+            Some("rotated")
+        );
+    }
+
+    #[tokio::test]
+    async fn reused_refresh_token_retries_with_rotated_token() {
+        use std::sync::atomic::Ordering;
+
+        let store = std::sync::Arc::new(MemorySecretStore::default());
+        store.set_secret(CLOUD_REFRESH_TOKEN_KEY, "stale").unwrap();
+        let token = fresh_jwt();
+        let response = token_response(&token, "next");
+        let peer_store = store.clone();
+        let (auth_url, hits) = start_refresh_server(move |body| {
+            if body.contains(r#""refresh_token":"stale""#) {
+                // The winning refresh has already stored its rotation.
+                peer_store
+                    .set_secret(CLOUD_REFRESH_TOKEN_KEY, "rotated-by-peer")
+                    .unwrap();
+                (
+                    400,
+                    r#"{"code":400,"error_code":"refresh_token_already_used","msg":"Invalid Refresh Token: Already Used"}"#
+                        .to_string(),
+                )
+            } else if body.contains(r#""refresh_token":"rotated-by-peer""#) {
+                (200, response.clone())
+            } else {
+                (400, r#"{"error":"invalid_grant"}"#.to_string())
+            }
+        });
+        let config = TokenLifecycleConfig::new(auth_url, "publishable".to_string());
+        let state = TokenLifecycleState::new();
+
+        let minted = ensure_valid_access_token(store.as_ref(), &state, Some(&config))
+            .await
+            .expect("retry succeeds");
+
+        assert_eq!(minted, token);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            store
+                .get_secret(CLOUD_REFRESH_TOKEN_KEY)
+                .unwrap()
+                .as_deref(),
+            Some("next")
+        );
+    }
+
+    #[test]
+    fn refresh_token_reuse_is_detected() {
+        assert!(is_refresh_token_reused("refresh_token_already_used", ""));
+        assert!(is_refresh_token_reused(
+            "",
+            "Invalid Refresh Token: Already Used"
+        ));
+        assert!(!is_refresh_token_reused(
+            "refresh_token_not_found",
+            "Invalid Refresh Token: Refresh Token Not Found"
+        ));
+    }
 }
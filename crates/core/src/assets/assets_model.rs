@@ -121,6 +121,12 @@ pub struct BondSpec {
     pub face_value: Option<Decimal>,  // Par value per bond (typically 1000.0)
     pub coupon_frequency: Option<String>, // ANNUAL, SEMI_ANNUAL, QUARTERLY, MONTHLY
     pub isin: Option<String>,
+    pub is_tips: Option<bool>, // Treasury Inflation-Protected Security
+    pub index_ratio: Option<Decimal>, // CPI index ratio applied to par (required to price TIPS)
+    pub ref_cpi_on_dated_date: Option<Decimal>, // Reference CPI on the dated date (TIPS only)
+    pub call_date: Option<chrono::NaiveDate>, // First call date (callable bonds only)
+    pub call_price: Option<Decimal>, // Call price as a fraction of par
+    pub currency: Option<String>, // Currency the bond pays in; providers assume their market's when unset
 }
 
 /// Builds structured asset metadata (OptionSpec, BondSpec) for the given instrument type.
@@ -742,9 +742,11 @@ impl AssetServiceTrait for AssetService {
         // Enrich US Treasury bonds with maturity/coupon data from TreasuryDirect
         // when the bond spec is missing this data (needed for yield-curve pricing).
         if existing_asset.is_bond() {
-            let needs_bond_enrichment = existing_asset
-                .bond_spec()
-                .is_none_or(|s| s.maturity_date.is_none());
+            // TIPS enriched before the reference CPI was stored need it too.
+            let needs_bond_enrichment = existing_asset.bond_spec().is_none_or(|s| {
+                s.maturity_date.is_none()
+                    || (s.is_tips == Some(true) && s.ref_cpi_on_dated_date.is_none())
+            });
 
             if needs_bond_enrichment {
                 if let Some(isin) = existing_asset.instrument_symbol.as_deref() {
@@ -758,6 +760,12 @@ impl AssetServiceTrait for AssetService {
                                     maturity_date: Some(details.maturity_date),
                                     face_value: Some(details.face_value),
                                     coupon_frequency: Some(details.coupon_frequency),
+                                    is_tips: Some(details.is_tips),
+                                    index_ratio: existing_asset.bond_spec().and_then(|s| s.index_ratio),
+                                    ref_cpi_on_dated_date: details.ref_cpi_on_dated_date,
+                                    call_date: details.call_date,
+                                    call_price: details.call_price,
+                                    currency: None,
                                 };
                                 let meta = updated_metadata.get_or_insert_with(|| serde_json::json!({}));
                                 if let Some(obj) = meta.as_object_mut() {
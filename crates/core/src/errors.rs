@@ -69,6 +69,9 @@ pub enum Error {
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 
+    #[error("API error {status}: {message}")]
+    Api { status: u16, message: String },
+
     #[error("Fx error: {0}")]
     Fx(#[from] FxError),
 }
@@ -364,20 +364,39 @@ impl MarketDataClient {
             Some(spec) if spec.maturity_date.is_some() => Some(BondQuoteMetadata {
                 coupon_rate: spec.coupon_rate.unwrap_or(rust_decimal::Decimal::ZERO),
                 maturity_date: spec.maturity_date.unwrap(),
-                face_value: spec.face_value.unwrap_or(rust_decimal::Decimal::from(1000)),
+                face_value: spec.face_value,
                 coupon_frequency: spec
                     .coupon_frequency
                     .unwrap_or_else(|| "SEMI_ANNUAL".to_string()),
+                is_tips: spec.is_tips.unwrap_or(false),
+                index_ratio: spec.index_ratio,
+                ref_cpi_on_dated_date: spec.ref_cpi_on_dated_date,
+                call_date: spec.call_date,
+                call_price: spec.call_price,
+                currency: spec.currency.map(Cow::Owned),
             }),
             _ => None,
         };
 
+        // Venue hint for multi-venue bond providers; equities carry their MIC
+        // in the instrument itself.
+        let mic_hint: Option<Cow<'static, str>> = match &instrument {
+            wealthfolio_market_data::InstrumentId::Bond { .. } => asset
+                .instrument_exchange_mic
+                .as_ref()
+                .filter(|mic| !mic.is_empty())
+                .map(|mic| Cow::Owned(mic.clone())),
+            _ => None,
+        };
+
         Ok(QuoteContext {
             instrument,
             overrides,
             currency_hint,
             preferred_provider,
             bond_metadata,
+            mic_hint,
+            settlement_offset_days: None,
         })
     }
 
@@ -1024,10 +1043,37 @@ mod tests {
             bond_meta.maturity_date,
             NaiveDate::from_ymd_opt(2040, 11, 15).unwrap()
         );
-        assert_eq!(bond_meta.face_value, dec!(1000));
+        assert_eq!(bond_meta.face_value, Some(dec!(1000)));
+        // The quote currency is not the bond's currency.
+        assert_eq!(bond_meta.currency, None);
         assert_eq!(bond_meta.coupon_frequency, "SEMI_ANNUAL");
     }
 
+    #[test]
+    fn test_build_quote_context_bond_currency_comes_from_bond_metadata() {
+        use crate::assets::InstrumentType;
+
+        // A UST held in a EUR-quoted position still pays in USD.
+        let mut asset = create_test_asset(AssetKind::Investment, "US912810TD00", "EUR");
+        asset.instrument_type = Some(InstrumentType::Bond);
+        asset.instrument_symbol = Some("US912810TD00".to_string());
+        asset.metadata = Some(serde_json::json!({
+            "bond": {
+                "couponRate": 0.04375,
+                "maturityDate": "2040-11-15",
+                "currency": "USD"
+            }
+        }));
+        let client = create_test_client();
+
+        let context = client.build_quote_context(&asset).unwrap();
+
+        assert_eq!(context.currency_hint.as_deref(), Some("EUR"));
+        let bond_meta = context.bond_metadata.expect("bond metadata");
+        assert_eq!(bond_meta.currency.as_deref(), Some("USD"));
+        assert_eq!(bond_meta.face_value, None);
+    }
+
     #[test]
     fn test_build_quote_context_bond_metadata_none_when_missing() {
         use crate::assets::InstrumentType;
@@ -1164,7 +1210,7 @@ mod tests {
             bond_meta.maturity_date,
             NaiveDate::from_ymd_opt(2025, 12, 18).unwrap()
         );
-        assert_eq!(bond_meta.face_value, dec!(1000));
+        assert_eq!(bond_meta.face_value, Some(dec!(1000)));
         assert_eq!(bond_meta.coupon_frequency, "ZERO");
     }
 }
@@ -963,6 +963,12 @@ where
                                 maturity_date: Some(details.maturity_date),
                                 face_value: Some(details.face_value),
                                 coupon_frequency: Some(details.coupon_frequency),
+                                is_tips: Some(details.is_tips),
+                                index_ratio: None,
+                                ref_cpi_on_dated_date: details.ref_cpi_on_dated_date,
+                                call_date: details.call_date,
+                                call_price: details.call_price,
+                                currency: None,
                             };
                             (isin, serde_json::json!({ "bond": spec }))
                         })
@@ -27,6 +27,11 @@ pub const APP_SYNC_TABLES: [&str; 15] = [
     "holdings_snapshots",
 ];
 
+/// Schema version of the synced tables as written into snapshots by this build.
+/// Bump when a snapshot from this build could not be restored by older builds
+/// through the common-columns intersection.
+pub const SNAPSHOT_SCHEMA_VERSION: i32 = 1;
+
 /// Entity names used by incremental sync events.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -63,6 +68,34 @@ pub enum SyncOutboxStatus {
     Pending,
     Sent,
     Dead,
+    /// A newer pending update for the same entity replaced this one before it
+    /// was pushed.
+    Superseded,
+}
+
+/// What the replay path did with a remote event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAuditOutcome {
+    /// The event was written to local data.
+    Applied,
+    /// The local version won conflict resolution and the event was dropped.
+    SkippedLww,
+    /// The event was refused, e.g. for a malformed payload or a table outside
+    /// the replay allowlist.
+    RejectedValidation,
+}
+
+/// Audit trail entry for one remote event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEventAudit {
+    pub entity: SyncEntity,
+    pub entity_id: String,
+    pub event_id: String,
+    pub outcome: SyncAuditOutcome,
+    pub reason: Option<String>,
+    pub recorded_at: String,
 }
 
 /// Sync outbox event payload stored locally before server push.
@@ -83,6 +116,10 @@ pub struct SyncOutboxEvent {
     pub last_error: Option<String>,
     pub last_error_code: Option<String>,
     pub created_at: String,
+    /// Monotonic per-device emission sequence. `None` for rows written before
+    /// the sequence was tracked.
+    #[serde(default)]
+    pub device_seq: Option<i64>,
 }
 
 /// LWW metadata tracked per entity row.
@@ -94,6 +131,12 @@ pub struct SyncEntityMetadata {
     pub last_event_id: String,
     pub last_client_timestamp: String,
     pub last_seq: i64,
+    /// Device that emitted the last applied event, with its per-device
+    /// sequence, when the event carried one.
+    #[serde(default)]
+    pub last_device_id: Option<String>,
+    #[serde(default)]
+    pub last_device_seq: Option<i64>,
 }
 
 /// Lightweight sync engine status.
@@ -108,6 +151,12 @@ pub struct SyncEngineStatus {
     pub next_retry_at: Option<String>,
     pub last_cycle_status: Option<String>,
     pub last_cycle_duration_ms: Option<i64>,
+    /// Encrypted event payload bytes pushed by the last cycle.
+    pub last_cycle_pushed_bytes: Option<i64>,
+    /// Event payload bytes pulled by the last cycle.
+    pub last_cycle_pulled_bytes: Option<i64>,
+    /// Remote events applied locally by the last cycle.
+    pub last_cycle_events_applied: Option<i64>,
 }
 
 /// Replay result for one pulled event.
@@ -150,40 +199,121 @@ pub enum SyncApplyContext {
     RemoteReplay,
 }
 
+/// Ordering key compared by last-writer-wins.
+///
+/// `device_id` and `device_seq` are optional. When present they break
+/// timestamp ties ahead of the event id, so events from one device order by
+/// emission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LwwVersion<'a> {
+    pub client_timestamp: &'a str,
+    pub event_id: &'a str,
+    pub device_id: Option<&'a str>,
+    pub device_seq: Option<i64>,
+}
+
+impl<'a> LwwVersion<'a> {
+    pub fn new(client_timestamp: &'a str, event_id: &'a str) -> Self {
+        Self {
+            client_timestamp,
+            event_id,
+            device_id: None,
+            device_seq: None,
+        }
+    }
+
+    /// Attach the emitting device and its monotonic per-device sequence.
+    pub fn with_device_seq(mut self, device_id: &'a str, device_seq: i64) -> Self {
+        self.device_id = Some(device_id);
+        self.device_seq = Some(device_seq);
+        self
+    }
+}
+
 /// Determines whether an incoming remote mutation should overwrite local state.
 ///
 /// Rule:
 /// 1. higher client timestamp wins
 /// 2. if equal, lexicographically greater event_id wins
+///
+/// Timestamps are compared at millisecond precision, so events emitted within
+/// the same millisecond (or by callers writing second-precision timestamps)
+/// always tie and are ordered by event_id. The precision is fixed rather than
+/// configurable: replicas comparing at different precisions would order the
+/// same pair of events differently and diverge. Use
+/// [`should_apply_lww_version`] when a per-device sequence is available.
 pub fn should_apply_lww(
     local_client_timestamp: &str,
     local_event_id: &str,
     remote_client_timestamp: &str,
     remote_event_id: &str,
 ) -> bool {
-    let local_parsed = chrono::DateTime::parse_from_rfc3339(local_client_timestamp)
-        .map(|dt| dt.timestamp_millis());
-    let remote_parsed = chrono::DateTime::parse_from_rfc3339(remote_client_timestamp)
-        .map(|dt| dt.timestamp_millis());
+    should_apply_lww_version(
+        &LwwVersion::new(local_client_timestamp, local_event_id),
+        &LwwVersion::new(remote_client_timestamp, remote_event_id),
+    )
+}
 
-    if let (Ok(local_ts), Ok(remote_ts)) = (local_parsed, remote_parsed) {
-        if remote_ts > local_ts {
+/// Determines whether `remote` should overwrite `local` under last-writer-wins.
+///
+/// Versions are ordered by the tuple `(client_timestamp, device_id,
+/// device_seq, event_id)`; the greater version wins. A version without a
+/// device sequence sorts before one with it on equal timestamps. Ties on one
+/// device therefore resolve by emission order, and ties across devices by
+/// device id.
+///
+/// Because this is a single total order, the result is deterministic: every
+/// replica that sees the same events ends on the same one regardless of
+/// arrival order.
+pub fn should_apply_lww_version(local: &LwwVersion<'_>, remote: &LwwVersion<'_>) -> bool {
+    compare_client_timestamps(local.client_timestamp, remote.client_timestamp)
+        .then_with(|| {
+            (local.device_id, local.device_seq).cmp(&(remote.device_id, remote.device_seq))
+        })
+        .then_with(|| local.event_id.cmp(remote.event_id))
+        .is_lt()
+}
+
+/// How a remote mutation is reconciled with an entity that already has local
+/// sync metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Newest version wins, per [`should_apply_lww_version`].
+    #[default]
+    LastWriterWins,
+    /// Keep the local row; remote mutations are recorded but not applied.
+    LocalWins,
+    /// Always apply the remote mutation.
+    RemoteWins,
+}
+
+impl ConflictStrategy {
+    /// Whether `remote` should overwrite local state last written at `local`.
+    /// Entities without local metadata always take the remote mutation.
+    pub fn should_apply(self, local: Option<&LwwVersion<'_>>, remote: &LwwVersion<'_>) -> bool {
+        let Some(local) = local else {
             return true;
+        };
+        match self {
+            ConflictStrategy::LastWriterWins => should_apply_lww_version(local, remote),
+            ConflictStrategy::LocalWins => false,
+            ConflictStrategy::RemoteWins => true,
         }
-        if remote_ts == local_ts {
-            return remote_event_id > local_event_id;
-        }
-        return false;
     }
+}
 
-    // Fallback to lexical ordering when one/both timestamps are non-RFC3339.
-    if remote_client_timestamp > local_client_timestamp {
-        return true;
-    }
-    if remote_client_timestamp == local_client_timestamp {
-        return remote_event_id > local_event_id;
+/// Orders two client timestamps by instant (millisecond precision), falling
+/// back to lexical ordering when one/both timestamps are non-RFC3339.
+fn compare_client_timestamps(local: &str, remote: &str) -> std::cmp::Ordering {
+    let local_parsed = chrono::DateTime::parse_from_rfc3339(local).map(|dt| dt.timestamp_millis());
+    let remote_parsed =
+        chrono::DateTime::parse_from_rfc3339(remote).map(|dt| dt.timestamp_millis());
+
+    match (local_parsed, remote_parsed) {
+        (Ok(local_ts), Ok(remote_ts)) => local_ts.cmp(&remote_ts),
+        _ => local.cmp(remote),
     }
-    false
 }
 
 /// Entity adapter contract used by the sync engine.
@@ -213,7 +343,9 @@ pub trait EntitySyncAdapter: Send + Sync {
 
 #[cfg(test)]
 mod tests {
-    use super::{should_apply_lww, SyncEntity};
+    use super::{
+        should_apply_lww, should_apply_lww_version, ConflictStrategy, LwwVersion, SyncEntity,
+    };
 
     #[test]
     fn lww_newer_timestamp_wins() {
@@ -245,6 +377,64 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn lww_same_device_tie_orders_by_sequence() {
+        let earlier = LwwVersion::new("2026-01-01T00:00:00Z", "ffff").with_device_seq("dev-a", 1);
+        let later = LwwVersion::new("2026-01-01T00:00:00Z", "0000").with_device_seq("dev-a", 2);
+
+        // The later emission wins even though its event_id sorts lower.
+        assert!(should_apply_lww_version(&earlier, &later));
+        assert!(!should_apply_lww_version(&later, &earlier));
+    }
+
+    #[test]
+    fn lww_cross_device_tie_orders_by_device_id() {
+        let local = LwwVersion::new("2026-01-01T00:00:00Z", "0002").with_device_seq("dev-a", 9);
+        let remote = LwwVersion::new("2026-01-01T00:00:00Z", "0001").with_device_seq("dev-b", 1);
+
+        assert!(should_apply_lww_version(&local, &remote));
+        assert!(!should_apply_lww_version(&remote, &local));
+    }
+
+    #[test]
+    fn lww_tie_converges_in_every_arrival_order() {
+        let a = LwwVersion::new("2026-01-01T00:00:00Z", "0").with_device_seq("dev-a", 2);
+        let b = LwwVersion::new("2026-01-01T00:00:00Z", "f").with_device_seq("dev-a", 1);
+        let c = LwwVersion::new("2026-01-01T00:00:00Z", "8").with_device_seq("dev-b", 1);
+        let orders = [
+            [a, b, c],
+            [a, c, b],
+            [b, a, c],
+            [b, c, a],
+            [c, a, b],
+            [c, b, a],
+        ];
+
+        let winners: Vec<LwwVersion<'_>> = orders
+            .iter()
+            .map(|order| {
+                order.iter().skip(1).fold(order[0], |local, remote| {
+                    if should_apply_lww_version(&local, remote) {
+                        *remote
+                    } else {
+                        local
+                    }
+                })
+            })
+            .collect();
+
+        assert!(winners.iter().all(|winner| *winner == winners[0]));
+        assert_eq!(winners[0], c);
+    }
+
+    #[test]
+    fn lww_sequence_does_not_override_newer_timestamp() {
+        let local = LwwVersion::new("2026-01-01T00:00:01Z", "0001").with_device_seq("dev-a", 1);
+        let remote = LwwVersion::new("2026-01-01T00:00:00Z", "0002").with_device_seq("dev-a", 2);
+
+        assert!(!should_apply_lww_version(&local, &remote));
+    }
+
     #[test]
     fn sync_entity_serialization_matches_backend_contract() {
         let actual = [
@@ -286,4 +476,23 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn conflict_strategy_resolves_against_local_version() {
+        let local = LwwVersion::new("2026-01-01T00:00:01Z", "0002");
+        let older_remote = LwwVersion::new("2026-01-01T00:00:00Z", "0001");
+        let newer_remote = LwwVersion::new("2026-01-01T00:00:02Z", "0003");
+
+        assert!(!ConflictStrategy::LastWriterWins.should_apply(Some(&local), &older_remote));
+        assert!(ConflictStrategy::LastWriterWins.should_apply(Some(&local), &newer_remote));
+        assert!(!ConflictStrategy::LocalWins.should_apply(Some(&local), &newer_remote));
+        assert!(ConflictStrategy::RemoteWins.should_apply(Some(&local), &older_remote));
+
+        // Without local metadata every strategy takes the remote mutation.
+        assert!(ConflictStrategy::LocalWins.should_apply(None, &older_remote));
+        assert_eq!(
+            ConflictStrategy::default(),
+            ConflictStrategy::LastWriterWins
+        );
+    }
 }
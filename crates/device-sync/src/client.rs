@@ -2,31 +2,66 @@
 //!
 //! This client uses the REST API endpoints for device synchronization.
 
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
 use log::debug;
 use rand::Rng;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
-use std::collections::HashSet;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, Mutex, Notify};
 use tokio::time::sleep;
 use uuid::Uuid;
 
 use crate::error::{DeviceSyncError, Result};
+use crate::snapshot_encoding::{SnapshotEncoding, SNAPSHOT_ENCODING_HEADER};
 use crate::types::*;
 
 /// Default timeout for API requests.
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 const MAX_LOG_BODY_CHARS: usize = 512;
-const SNAPSHOT_UPLOAD_MAX_ATTEMPTS: usize = 5;
-const SNAPSHOT_UPLOAD_BASE_BACKOFF_MS: u64 = 250;
-const SNAPSHOT_UPLOAD_MAX_BACKOFF_MS: u64 = 8_000;
+/// Slice size a snapshot body is streamed in; progress is reported per slice.
+const SNAPSHOT_UPLOAD_STREAM_SLICE_BYTES: usize = 64 * 1024;
+/// Default part size for [`DeviceSyncClient::upload_snapshot_chunked`].
+pub const SNAPSHOT_UPLOAD_PART_SIZE: usize = 4 * 1024 * 1024;
+
+/// Progress and cancellation state of one in-flight snapshot upload.
+struct InFlightSnapshotUpload {
+    started_at: DateTime<Utc>,
+    bytes_sent: AtomicU64,
+    cancelled: AtomicBool,
+    cancel_notify: Notify,
+}
+
+impl InFlightSnapshotUpload {
+    fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            bytes_sent: AtomicU64::new(0),
+            cancelled: AtomicBool::new(false),
+            cancel_notify: Notify::new(),
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.cancel_notify.notify_one();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
 
-static SNAPSHOT_UPLOAD_IN_FLIGHT: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+type InFlightSnapshotUploads = HashMap<String, Arc<InFlightSnapshotUpload>>;
 
-fn snapshot_upload_in_flight() -> &'static Mutex<HashSet<String>> {
-    SNAPSHOT_UPLOAD_IN_FLIGHT.get_or_init(|| Mutex::new(HashSet::new()))
+static SNAPSHOT_UPLOAD_IN_FLIGHT: OnceLock<Mutex<InFlightSnapshotUploads>> = OnceLock::new();
+
+fn snapshot_upload_in_flight() -> &'static Mutex<InFlightSnapshotUploads> {
+    SNAPSHOT_UPLOAD_IN_FLIGHT.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
 fn compute_sha256_checksum(payload: &[u8]) -> String {
@@ -40,6 +75,40 @@ fn is_valid_sha256_checksum(checksum: &str) -> bool {
     hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit())
 }
 
+/// Compression the server applied to a snapshot download body, if any.
+///
+/// The server may use standard `Content-Encoding` or signal compression via
+/// `X-Snapshot-Compression`.
+fn snapshot_body_compression(headers: &HeaderMap) -> Option<String> {
+    ["x-snapshot-compression", "content-encoding"]
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|value| value.to_str().ok())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .find(|value| !value.is_empty() && value != "identity" && value != "none")
+}
+
+fn decompress_snapshot_body(compression: &str, body: &[u8]) -> Result<Vec<u8>> {
+    match compression {
+        "gzip" | "x-gzip" => {
+            let mut decoded = Vec::new();
+            GzDecoder::new(body)
+                .read_to_end(&mut decoded)
+                .map_err(|err| {
+                    DeviceSyncError::invalid_request(format!(
+                        "Failed to decompress gzip snapshot body: {}",
+                        err
+                    ))
+                })?;
+            Ok(decoded)
+        }
+        other => Err(DeviceSyncError::invalid_request(format!(
+            "Unsupported snapshot compression '{}'",
+            other
+        ))),
+    }
+}
+
 fn is_retryable_snapshot_status(status: u16) -> bool {
     matches!(status, 408 | 429 | 500..=599)
 }
@@ -62,12 +131,113 @@ fn is_retryable_transport_error(err: &reqwest::Error) -> bool {
     err.is_timeout() || err.is_connect() || err.is_request() || err.is_body()
 }
 
-fn snapshot_backoff_with_jitter(attempt: usize) -> Duration {
-    let exp = (attempt.saturating_sub(1) as u32).min(8);
-    let backoff = (SNAPSHOT_UPLOAD_BASE_BACKOFF_MS.saturating_mul(1_u64 << exp))
-        .min(SNAPSHOT_UPLOAD_MAX_BACKOFF_MS);
-    let jitter = rand::thread_rng().gen_range(0..=(backoff / 5).max(1));
-    Duration::from_millis(backoff.saturating_add(jitter))
+/// Retry behavior for requests to the sync API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryConfig {
+    /// Total attempts per request, including the first.
+    pub max_attempts: usize,
+    /// Backoff before the first retry; doubles on each further retry.
+    pub base_backoff: Duration,
+    /// Upper bound for the backoff before jitter is added.
+    pub max_backoff: Duration,
+    /// Random extra delay, as a fraction of the backoff.
+    pub jitter_ratio: f64,
+}
+
+impl RetryConfig {
+    /// Defaults for snapshot uploads.
+    pub const SNAPSHOT_UPLOAD: Self = Self {
+        max_attempts: 5,
+        base_backoff: Duration::from_millis(250),
+        max_backoff: Duration::from_secs(8),
+        jitter_ratio: 0.2,
+    };
+
+    /// Defaults for idempotent GET requests. The sync cycle waits on these,
+    /// so they give up sooner than uploads.
+    pub const REQUEST: Self = Self {
+        max_attempts: 3,
+        base_backoff: Duration::from_millis(250),
+        max_backoff: Duration::from_secs(2),
+        jitter_ratio: 0.2,
+    };
+
+    /// Exponential backoff with jitter after failed attempt number `attempt`.
+    fn backoff_with_jitter(&self, attempt: usize) -> Duration {
+        let exp = (attempt.saturating_sub(1) as u32).min(8);
+        let backoff = self
+            .base_backoff
+            .saturating_mul(1_u32 << exp)
+            .min(self.max_backoff);
+        let max_jitter_ms = (backoff.as_millis() as f64 * self.jitter_ratio.max(0.0)) as u64;
+        let jitter_ms = if max_jitter_ms > 0 {
+            rand::thread_rng().gen_range(0..=max_jitter_ms)
+        } else {
+            0
+        };
+        backoff.saturating_add(Duration::from_millis(jitter_ms))
+    }
+}
+
+/// Stream `payload` as a request body in fixed-size slices, sending the
+/// running byte count to `progress_tx` as each slice is handed to the
+/// transport.
+fn progress_body(payload: Arc<Vec<u8>>, progress_tx: mpsc::UnboundedSender<u64>) -> reqwest::Body {
+    let total = payload.len();
+    let slices = (0..total)
+        .step_by(SNAPSHOT_UPLOAD_STREAM_SLICE_BYTES)
+        .map(move |start| {
+            let end = (start + SNAPSHOT_UPLOAD_STREAM_SLICE_BYTES).min(total);
+            let _ = progress_tx.send(end as u64);
+            Ok::<_, std::io::Error>(payload[start..end].to_vec())
+        });
+    reqwest::Body::wrap_stream(futures::stream::iter(slices))
+}
+
+/// Check an upload's size and checksum headers against its payload bytes and
+/// assign the stable snapshot event ID reused across retries.
+fn validate_snapshot_upload(
+    upload_headers: &mut SnapshotUploadHeaders,
+    payload: &[u8],
+) -> Result<()> {
+    if payload.len() > i64::MAX as usize {
+        return Err(DeviceSyncError::invalid_request(
+            "Snapshot payload is too large for size header",
+        ));
+    }
+    let payload_size = payload.len() as i64;
+    if upload_headers.size_bytes != payload_size {
+        return Err(DeviceSyncError::invalid_request(format!(
+            "Snapshot size header mismatch: header={} payload={}",
+            upload_headers.size_bytes, payload_size
+        )));
+    }
+    if !is_valid_sha256_checksum(&upload_headers.checksum) {
+        return Err(DeviceSyncError::invalid_request(
+            "Invalid snapshot checksum format; expected sha256:<hex>",
+        ));
+    }
+    let computed_checksum = compute_sha256_checksum(payload);
+    if !upload_headers
+        .checksum
+        .eq_ignore_ascii_case(&computed_checksum)
+    {
+        return Err(DeviceSyncError::invalid_request(
+            "Snapshot checksum does not match payload bytes",
+        ));
+    }
+    upload_headers.checksum = computed_checksum.to_ascii_lowercase();
+
+    let stable_event_id = match upload_headers.event_id.take() {
+        Some(value) => {
+            Uuid::parse_str(&value)
+                .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot event ID"))?;
+            value
+        }
+        None => Uuid::new_v4().to_string(),
+    };
+    upload_headers.event_id = Some(stable_event_id);
+    Ok(())
 }
 
 /// Client for the Wealthfolio device sync cloud API.
@@ -78,6 +248,8 @@ fn snapshot_backoff_with_jitter(attempt: usize) -> Duration {
 pub struct DeviceSyncClient {
     client: reqwest::Client,
     base_url: String,
+    upload_config: RetryConfig,
+    request_retry_config: RetryConfig,
 }
 
 impl DeviceSyncClient {
@@ -221,9 +393,35 @@ impl DeviceSyncClient {
         Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
+            upload_config: RetryConfig::SNAPSHOT_UPLOAD,
+            request_retry_config: RetryConfig::REQUEST,
         }
     }
 
+    /// Replace the total request timeout (default 30s) and set a separate
+    /// timeout for establishing the connection.
+    pub fn with_timeouts(mut self, request: Duration, connect: Duration) -> Self {
+        self.client = reqwest::Client::builder()
+            .timeout(request)
+            .connect_timeout(connect)
+            .build()
+            .expect("Failed to build HTTP client");
+        self
+    }
+
+    /// Use `config` for snapshot upload retries instead of the defaults.
+    pub fn with_upload_config(mut self, config: RetryConfig) -> Self {
+        self.upload_config = config;
+        self
+    }
+
+    /// Use `config` for idempotent GET request retries instead of the
+    /// defaults.
+    pub fn with_request_retry_config(mut self, config: RetryConfig) -> Self {
+        self.request_retry_config = config;
+        self
+    }
+
     /// Create headers for an API request.
     fn headers(&self, token: &str) -> Result<HeaderMap> {
         self.headers_with_device(token, None)
@@ -384,21 +582,40 @@ impl DeviceSyncClient {
     ///
     /// GET /api/v1/sync/team/devices?scope=my|team
     pub async fn list_devices(&self, token: &str, scope: Option<&str>) -> Result<Vec<Device>> {
-        let mut url = format!("{}/api/v1/sync/team/devices", self.base_url);
-        if let Some(s) = scope {
-            url = format!("{}?scope={}", url, s);
-        }
+        let query = DeviceListQuery {
+            scope: scope.map(str::to_string),
+            ..Default::default()
+        };
+        Ok(self.list_devices_page(token, &query).await?.devices)
+    }
 
-        debug!("[DeviceSync] list_devices URL: {}", url);
+    /// List one page of devices, optionally filtered by trust state.
+    ///
+    /// GET /api/v1/sync/team/devices?scope=..&trust_state=..&limit=..&offset=..
+    pub async fn list_devices_page(
+        &self,
+        token: &str,
+        query: &DeviceListQuery,
+    ) -> Result<DeviceListPage> {
+        let url = format!("{}/api/v1/sync/team/devices", self.base_url);
+        let pairs = query.query_pairs();
 
-        let response = self
-            .client
-            .get(&url)
-            .headers(self.headers(token)?)
-            .send()
-            .await?;
+        debug!("[DeviceSync] list_devices URL: {} {:?}", url, pairs);
 
-        Self::parse_response(response).await
+        let mut request = self.client.get(&url).headers(self.headers(token)?);
+        if !pairs.is_empty() {
+            request = request.query(&pairs);
+        }
+        let devices: Vec<Device> = Self::parse_response(request.send().await?).await?;
+
+        Ok(DeviceListPage {
+            has_more: query
+                .limit
+                .is_some_and(|limit| devices.len() >= limit as usize),
+            offset: query.offset.unwrap_or(0),
+            limit: query.limit,
+            devices,
+        })
     }
 
     /// Update a device (e.g., rename).
@@ -625,14 +842,18 @@ impl DeviceSyncClient {
             query.push(("limit", value.to_string()));
         }
 
-        let mut request = self
-            .client
-            .get(&url)
-            .headers(self.headers_with_device(token, Some(device_id))?);
-        if !query.is_empty() {
-            request = request.query(&query);
-        }
-        let response = request.send().await?;
+        let response = self
+            .send_with_retry(|| {
+                let mut request = self
+                    .client
+                    .get(&url)
+                    .headers(self.headers_with_device(token, Some(device_id))?);
+                if !query.is_empty() {
+                    request = request.query(&query);
+                }
+                Ok(request)
+            })
+            .await?;
         Self::parse_response(response).await
     }
 
@@ -646,10 +867,12 @@ impl DeviceSyncClient {
     ) -> Result<ReconcileReadyStateResponse> {
         let url = format!("{}/api/v1/sync/events/reconcile-ready-state", self.base_url);
         let response = self
-            .client
-            .get(url)
-            .headers(self.headers_with_device(token, Some(device_id))?)
-            .send()
+            .send_with_retry(|| {
+                Ok(self
+                    .client
+                    .get(&url)
+                    .headers(self.headers_with_device(token, Some(device_id))?))
+            })
             .await?;
         Self::parse_response(response).await
     }
@@ -664,10 +887,12 @@ impl DeviceSyncClient {
     ) -> Result<SyncCursorResponse> {
         let url = format!("{}/api/v1/sync/events/cursor", self.base_url);
         let response = self
-            .client
-            .get(url)
-            .headers(self.headers_with_device(token, Some(device_id))?)
-            .send()
+            .send_with_retry(|| {
+                Ok(self
+                    .client
+                    .get(&url)
+                    .headers(self.headers_with_device(token, Some(device_id))?))
+            })
             .await?;
         Self::parse_response(response).await
     }
@@ -682,10 +907,12 @@ impl DeviceSyncClient {
     ) -> Result<SnapshotLatestResponse> {
         let url = format!("{}/api/v1/sync/snapshots/latest", self.base_url);
         let response = self
-            .client
-            .get(url)
-            .headers(self.headers_with_device(token, Some(device_id))?)
-            .send()
+            .send_with_retry(|| {
+                Ok(self
+                    .client
+                    .get(&url)
+                    .headers(self.headers_with_device(token, Some(device_id))?))
+            })
             .await?;
         Self::parse_response(response).await
     }
@@ -734,6 +961,9 @@ impl DeviceSyncClient {
 
     /// Download encrypted snapshot blob and metadata headers.
     ///
+    /// Bodies compressed by the server are decompressed before being returned.
+    /// The `X-Snapshot-Checksum` header is verified against the returned bytes.
+    ///
     /// GET /api/v1/sync/snapshots/{snapshotId}
     pub async fn download_snapshot(
         &self,
@@ -743,14 +973,20 @@ impl DeviceSyncClient {
     ) -> Result<(SnapshotDownloadHeaders, Vec<u8>)> {
         let url = self.snapshot_download_url(snapshot_id)?;
         let response = self
-            .client
-            .get(url)
-            .headers(self.headers_with_device(token, Some(device_id))?)
-            .send()
+            .send_with_retry(|| {
+                Ok(self
+                    .client
+                    .get(url.clone())
+                    .headers(self.headers_with_device(token, Some(device_id))?))
+            })
             .await?;
         let response = Self::parse_binary_response(response).await?;
         let headers = response.headers().clone();
-        let body = response.bytes().await?.to_vec();
+        let compression = snapshot_body_compression(&headers);
+        let body = match compression.as_deref() {
+            Some(compression) => decompress_snapshot_body(compression, &response.bytes().await?)?,
+            None => response.bytes().await?.to_vec(),
+        };
 
         let raw_tables = Self::parse_required_header_string(&headers, "x-snapshot-covers-tables")?;
         let snapshot_headers = SnapshotDownloadHeaders {
@@ -761,8 +997,28 @@ impl DeviceSyncClient {
                 .filter(|value| !value.is_empty())
                 .collect(),
             checksum: Self::parse_required_header_string(&headers, "x-snapshot-checksum")?,
+            encoding: SnapshotEncoding::from_header_value(
+                headers
+                    .get(SNAPSHOT_ENCODING_HEADER)
+                    .and_then(|value| value.to_str().ok()),
+            )?,
         };
 
+        // Verify before handing the body out so a corrupted download never
+        // reaches a restore.
+        crate::crypto::verify_sha256_checksum(&snapshot_headers.checksum, &body).map_err(
+            |err| {
+                DeviceSyncError::invalid_request(if compression.is_some() {
+                    format!(
+                        "Snapshot checksum does not match decompressed payload: {}",
+                        err
+                    )
+                } else {
+                    format!("Snapshot checksum does not match payload: {}", err)
+                })
+            },
+        )?;
+
         Ok((snapshot_headers, body))
     }
 
@@ -781,7 +1037,21 @@ impl DeviceSyncClient {
         upload_headers: SnapshotUploadHeaders,
         payload: Vec<u8>,
     ) -> Result<SnapshotUploadResponse> {
-        self.upload_snapshot_with_cancel_flag(token, device_id, upload_headers, payload, None)
+        self.upload_snapshot_with_progress(token, device_id, upload_headers, payload, None)
+            .await
+    }
+
+    /// Upload a snapshot blob, calling `progress` with (bytes sent, total) as
+    /// the body streams. Counts restart from zero if an attempt is retried.
+    pub async fn upload_snapshot_with_progress(
+        &self,
+        token: &str,
+        device_id: &str,
+        upload_headers: SnapshotUploadHeaders,
+        payload: Vec<u8>,
+        progress: Option<&(dyn Fn(u64, u64) + Send + Sync)>,
+    ) -> Result<SnapshotUploadResponse> {
+        self.upload_snapshot_tracked(token, device_id, upload_headers, payload, None, progress)
             .await
     }
 
@@ -790,47 +1060,24 @@ impl DeviceSyncClient {
         &self,
         token: &str,
         device_id: &str,
-        mut upload_headers: SnapshotUploadHeaders,
+        upload_headers: SnapshotUploadHeaders,
         payload: Vec<u8>,
         cancel_flag: Option<&AtomicBool>,
     ) -> Result<SnapshotUploadResponse> {
-        if payload.len() > i64::MAX as usize {
-            return Err(DeviceSyncError::invalid_request(
-                "Snapshot payload is too large for size header",
-            ));
-        }
-        let payload_size = payload.len() as i64;
-        if upload_headers.size_bytes != payload_size {
-            return Err(DeviceSyncError::invalid_request(format!(
-                "Snapshot size header mismatch: header={} payload={}",
-                upload_headers.size_bytes, payload_size
-            )));
-        }
-        if !is_valid_sha256_checksum(&upload_headers.checksum) {
-            return Err(DeviceSyncError::invalid_request(
-                "Invalid snapshot checksum format; expected sha256:<hex>",
-            ));
-        }
-        let computed_checksum = compute_sha256_checksum(&payload);
-        if !upload_headers
-            .checksum
-            .eq_ignore_ascii_case(&computed_checksum)
-        {
-            return Err(DeviceSyncError::invalid_request(
-                "Snapshot checksum does not match payload bytes",
-            ));
-        }
-        upload_headers.checksum = computed_checksum.to_ascii_lowercase();
+        self.upload_snapshot_tracked(token, device_id, upload_headers, payload, cancel_flag, None)
+            .await
+    }
 
-        let stable_event_id = match upload_headers.event_id.take() {
-            Some(value) => {
-                Uuid::parse_str(&value)
-                    .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot event ID"))?;
-                value
-            }
-            None => Uuid::new_v4().to_string(),
-        };
-        upload_headers.event_id = Some(stable_event_id.clone());
+    async fn upload_snapshot_tracked(
+        &self,
+        token: &str,
+        device_id: &str,
+        mut upload_headers: SnapshotUploadHeaders,
+        payload: Vec<u8>,
+        cancel_flag: Option<&AtomicBool>,
+        progress: Option<&(dyn Fn(u64, u64) + Send + Sync)>,
+    ) -> Result<SnapshotUploadResponse> {
+        validate_snapshot_upload(&mut upload_headers, &payload)?;
 
         let dedupe_key = format!(
             "{}:{}",
@@ -840,24 +1087,69 @@ impl DeviceSyncClient {
                 .as_deref()
                 .unwrap_or("missing_snapshot_event_id")
         );
+        let op = Arc::new(InFlightSnapshotUpload::new());
         {
             let mut in_flight = snapshot_upload_in_flight().lock().await;
-            if !in_flight.insert(dedupe_key.clone()) {
+            if in_flight.contains_key(&dedupe_key) {
                 return Err(DeviceSyncError::invalid_request(
                     "Snapshot upload already in progress for this snapshot event",
                 ));
             }
+            in_flight.insert(dedupe_key.clone(), Arc::clone(&op));
         }
 
         let result = self
-            .upload_snapshot_with_retry(token, device_id, &upload_headers, payload, cancel_flag)
+            .upload_snapshot_with_retry(
+                token,
+                device_id,
+                &upload_headers,
+                payload,
+                cancel_flag,
+                progress,
+                &op,
+            )
             .await;
 
+        // A cancelled entry may already have been replaced by a new upload
+        // with the same key; only remove our own.
         let mut in_flight = snapshot_upload_in_flight().lock().await;
-        in_flight.remove(&dedupe_key);
+        if in_flight
+            .get(&dedupe_key)
+            .is_some_and(|entry| Arc::ptr_eq(entry, &op))
+        {
+            in_flight.remove(&dedupe_key);
+        }
         result
     }
 
+    /// Snapshot uploads currently running in this process, oldest first.
+    pub async fn list_in_flight_snapshots() -> Vec<SnapshotOpInfo> {
+        let in_flight = snapshot_upload_in_flight().lock().await;
+        let mut ops: Vec<_> = in_flight.iter().collect();
+        ops.sort_by_key(|(_, op)| op.started_at);
+        ops.into_iter()
+            .map(|(dedupe_key, op)| SnapshotOpInfo {
+                dedupe_key: dedupe_key.clone(),
+                started_at: op.started_at.to_rfc3339(),
+                bytes_sent: op.bytes_sent.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Cancel the in-flight snapshot upload with `dedupe_key`, aborting any
+    /// request it has outstanding. Returns false if no such upload is running.
+    pub async fn cancel_in_flight_snapshot(dedupe_key: &str) -> bool {
+        let mut in_flight = snapshot_upload_in_flight().lock().await;
+        match in_flight.remove(dedupe_key) {
+            Some(op) => {
+                op.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn upload_snapshot_with_retry(
         &self,
         token: &str,
@@ -865,14 +1157,35 @@ impl DeviceSyncClient {
         upload_headers: &SnapshotUploadHeaders,
         payload: Vec<u8>,
         cancel_flag: Option<&AtomicBool>,
+        progress: Option<&(dyn Fn(u64, u64) + Send + Sync)>,
+        op: &InFlightSnapshotUpload,
     ) -> Result<SnapshotUploadResponse> {
         let url = format!("{}/api/v1/sync/snapshots/upload", self.base_url);
+        let max_attempts = self.upload_config.max_attempts.max(1);
+        let payload = Arc::new(payload);
+        let total = payload.len() as u64;
+        let report = |sent: u64| {
+            op.bytes_sent.store(sent, Ordering::Relaxed);
+            if let Some(progress) = progress {
+                progress(sent, total);
+            }
+        };
         let mut attempt = 0usize;
+        let started = std::time::Instant::now();
+        let exhausted = |attempts: usize, error: DeviceSyncError| {
+            DeviceSyncError::retries_exhausted(
+                "Snapshot upload",
+                attempts,
+                started.elapsed(),
+                error,
+            )
+        };
 
         loop {
-            if cancel_flag
-                .map(|flag| flag.load(Ordering::Relaxed))
-                .unwrap_or(false)
+            if op.is_cancelled()
+                || cancel_flag
+                    .map(|flag| flag.load(Ordering::Relaxed))
+                    .unwrap_or(false)
             {
                 return Err(DeviceSyncError::invalid_request(
                     "Snapshot upload cancelled",
@@ -880,142 +1193,331 @@ impl DeviceSyncClient {
             }
 
             attempt = attempt.saturating_add(1);
-            let mut headers = self.headers_with_device(token, Some(device_id))?;
+            let mut headers = self.snapshot_upload_headers(token, device_id, upload_headers)?;
             headers.insert(
                 CONTENT_TYPE,
                 HeaderValue::from_static("application/octet-stream"),
             );
-            if let Some(event_id) = upload_headers.event_id.as_deref() {
-                headers.insert(
-                    "x-snapshot-event-id",
-                    HeaderValue::from_str(event_id).map_err(|_| {
-                        DeviceSyncError::invalid_request("Invalid snapshot event ID")
-                    })?,
-                );
-            }
-            headers.insert(
-                "x-snapshot-schema-version",
-                HeaderValue::from_str(&upload_headers.schema_version.to_string()).map_err(
-                    |_| DeviceSyncError::invalid_request("Invalid snapshot schema version"),
-                )?,
-            );
-            headers.insert(
-                "x-snapshot-covers-tables",
-                HeaderValue::from_str(&upload_headers.covers_tables.join(",")).map_err(|_| {
-                    DeviceSyncError::invalid_request("Invalid snapshot covers tables")
-                })?,
-            );
-            headers.insert(
-                "x-snapshot-size-bytes",
-                HeaderValue::from_str(&upload_headers.size_bytes.to_string())
-                    .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot size"))?,
-            );
             headers.insert(
                 CONTENT_LENGTH,
                 HeaderValue::from_str(&upload_headers.size_bytes.to_string())
                     .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot size"))?,
             );
-            headers.insert(
-                "x-snapshot-checksum",
-                HeaderValue::from_str(&upload_headers.checksum)
-                    .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot checksum"))?,
-            );
-            headers.insert(
-                "x-snapshot-metadata-payload",
-                HeaderValue::from_str(&upload_headers.metadata_payload).map_err(|_| {
-                    DeviceSyncError::invalid_request("Invalid snapshot metadata payload")
-                })?,
-            );
-            headers.insert(
-                "x-snapshot-payload-key-version",
-                HeaderValue::from_str(&upload_headers.payload_key_version.to_string()).map_err(
-                    |_| DeviceSyncError::invalid_request("Invalid snapshot payload key version"),
-                )?,
-            );
-            if let Some(base_seq) = upload_headers.base_seq {
-                headers.insert("x-snapshot-base-seq", HeaderValue::from(base_seq));
-            }
 
-            let send_result = self
+            let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+            let send = self
                 .client
                 .post(&url)
                 .headers(headers)
-                .body(payload.clone())
-                .send()
-                .await;
+                .body(progress_body(Arc::clone(&payload), progress_tx))
+                .send();
+            tokio::pin!(send);
+            let send_result = loop {
+                tokio::select! {
+                    result = &mut send => break result,
+                    Some(sent) = progress_rx.recv() => report(sent),
+                    _ = op.cancel_notify.notified() => {
+                        return Err(DeviceSyncError::invalid_request(
+                            "Snapshot upload cancelled",
+                        ));
+                    }
+                }
+            };
+            while let Ok(sent) = progress_rx.try_recv() {
+                report(sent);
+            }
 
             match send_result {
                 Ok(response) => {
-                    let status = response.status();
-                    if status.is_success() {
+                    // A response means the server received the whole body.
+                    op.bytes_sent.store(total, Ordering::Relaxed);
+                    if response.status().is_success() {
                         return Self::parse_response(response).await;
                     }
 
-                    let body = response.text().await?;
-                    Self::log_response(status, &body);
-                    let mut parsed_error_code: Option<String> = None;
-                    let mut parsed_error_message: Option<String> = None;
-                    let error = if let Ok(api_error) =
-                        serde_json::from_str::<ApiErrorResponse>(&body)
-                    {
-                        let message = api_error.message;
-                        let code = if api_error.code.is_empty() {
-                            api_error.error
-                        } else {
-                            api_error.code
-                        };
-                        parsed_error_code = Some(code.clone());
-                        parsed_error_message = Some(message.clone());
-                        DeviceSyncError::api_structured(
-                            status.as_u16(),
-                            code,
-                            message,
-                            api_error.details,
-                        )
-                    } else {
-                        DeviceSyncError::api(status.as_u16(), format!("Request failed: {}", body))
-                    };
-
-                    if is_retryable_snapshot_error(
-                        status.as_u16(),
-                        parsed_error_code.as_deref(),
-                        parsed_error_message.as_deref(),
-                    ) && attempt < SNAPSHOT_UPLOAD_MAX_ATTEMPTS
-                    {
-                        let backoff = snapshot_backoff_with_jitter(attempt);
+                    let (error, retryable) = Self::snapshot_upload_error(response).await?;
+                    if retryable && attempt < max_attempts {
+                        let backoff = self.upload_config.backoff_with_jitter(attempt);
                         debug!(
-                            "Snapshot upload retry attempt {}/{} after HTTP {} code={:?} (event_id={})",
+                            "Snapshot upload retry attempt {}/{} after {} (event_id={})",
                             attempt + 1,
-                            SNAPSHOT_UPLOAD_MAX_ATTEMPTS,
-                            status.as_u16(),
-                            parsed_error_code.as_deref(),
+                            max_attempts,
+                            error,
                             upload_headers.event_id.as_deref().unwrap_or("none")
                         );
                         sleep(backoff).await;
                         continue;
                     }
+                    if retryable {
+                        return Err(exhausted(attempt, error));
+                    }
                     return Err(error);
                 }
                 Err(err) => {
-                    if is_retryable_transport_error(&err) && attempt < SNAPSHOT_UPLOAD_MAX_ATTEMPTS
-                    {
-                        let backoff = snapshot_backoff_with_jitter(attempt);
+                    if is_retryable_transport_error(&err) && attempt < max_attempts {
+                        let backoff = self.upload_config.backoff_with_jitter(attempt);
                         debug!(
                             "Snapshot upload retry attempt {}/{} after transport error (event_id={}): {}",
                             attempt + 1,
-                            SNAPSHOT_UPLOAD_MAX_ATTEMPTS,
+                            max_attempts,
                             upload_headers.event_id.as_deref().unwrap_or("none"),
                             err
                         );
                         sleep(backoff).await;
                         continue;
                     }
+                    if is_retryable_transport_error(&err) {
+                        return Err(exhausted(attempt, DeviceSyncError::Http(err)));
+                    }
                     return Err(DeviceSyncError::Http(err));
                 }
             }
         }
     }
 
+    /// Upload a snapshot blob in fixed-size parts.
+    ///
+    /// Each part is sent with its index and the stable `X-Snapshot-Event-Id`,
+    /// and only a failed part is retried, so a flaky connection still makes
+    /// progress on large images. Size and checksum are validated against the
+    /// full payload before the first part is sent; the server verifies them
+    /// again when the upload is completed.
+    ///
+    /// POST /api/v1/sync/snapshots/upload/part (once per part)
+    /// POST /api/v1/sync/snapshots/upload/complete
+    pub async fn upload_snapshot_chunked(
+        &self,
+        token: &str,
+        device_id: &str,
+        mut upload_headers: SnapshotUploadHeaders,
+        payload: Vec<u8>,
+        part_size: usize,
+    ) -> Result<SnapshotUploadResponse> {
+        if part_size == 0 {
+            return Err(DeviceSyncError::invalid_request(
+                "Snapshot part size must be greater than zero",
+            ));
+        }
+        validate_snapshot_upload(&mut upload_headers, &payload)?;
+        let event_id = upload_headers.event_id.clone().unwrap_or_default();
+        let event_id_header = HeaderValue::from_str(&event_id)
+            .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot event ID"))?;
+        let part_count = payload.len().div_ceil(part_size).max(1);
+
+        let part_url = format!("{}/api/v1/sync/snapshots/upload/part", self.base_url);
+        for (part_index, part) in payload.chunks(part_size).enumerate() {
+            self.send_snapshot_request_with_retry("part", &event_id, || {
+                let mut headers = self.headers_with_device(token, Some(device_id))?;
+                headers.insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/octet-stream"),
+                );
+                headers.insert(CONTENT_LENGTH, HeaderValue::from(part.len()));
+                headers.insert("x-snapshot-event-id", event_id_header.clone());
+                headers.insert("x-snapshot-part-index", HeaderValue::from(part_index));
+                headers.insert("x-snapshot-part-count", HeaderValue::from(part_count));
+                Ok(self
+                    .client
+                    .post(&part_url)
+                    .headers(headers)
+                    .body(part.to_vec()))
+            })
+            .await?;
+        }
+
+        let complete_url = format!("{}/api/v1/sync/snapshots/upload/complete", self.base_url);
+        let response = self
+            .send_snapshot_request_with_retry("complete", &event_id, || {
+                let mut headers =
+                    self.snapshot_upload_headers(token, device_id, &upload_headers)?;
+                headers.insert("x-snapshot-part-count", HeaderValue::from(part_count));
+                Ok(self.client.post(&complete_url).headers(headers))
+            })
+            .await?;
+        Self::parse_response(response).await
+    }
+
+    /// Send an idempotent request built by `build`, retrying transient
+    /// statuses and transport errors with backoff. Non-retryable responses,
+    /// including errors, are returned for the caller to parse. Only use this
+    /// for requests that are safe to repeat.
+    async fn send_with_retry<F>(&self, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn() -> Result<reqwest::RequestBuilder>,
+    {
+        let max_attempts = self.request_retry_config.max_attempts.max(1);
+        let mut attempt = 0usize;
+        loop {
+            attempt = attempt.saturating_add(1);
+            match build()?.send().await {
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    if !is_retryable_snapshot_status(status) || attempt >= max_attempts {
+                        return Ok(response);
+                    }
+                    debug!(
+                        "Retrying {} after HTTP {} (attempt {}/{})",
+                        response.url().path(),
+                        status,
+                        attempt + 1,
+                        max_attempts
+                    );
+                }
+                Err(err) => {
+                    if !is_retryable_transport_error(&err) || attempt >= max_attempts {
+                        return Err(DeviceSyncError::Http(err));
+                    }
+                    debug!(
+                        "Retrying request after transport error (attempt {}/{}): {}",
+                        attempt + 1,
+                        max_attempts,
+                        err
+                    );
+                }
+            }
+            sleep(self.request_retry_config.backoff_with_jitter(attempt)).await;
+        }
+    }
+
+    /// Send a snapshot upload request built by `build`, retrying
+    /// transient failures with backoff. Returns the successful response.
+    async fn send_snapshot_request_with_retry<F>(
+        &self,
+        label: &str,
+        event_id: &str,
+        build: F,
+    ) -> Result<reqwest::Response>
+    where
+        F: Fn() -> Result<reqwest::RequestBuilder>,
+    {
+        let max_attempts = self.upload_config.max_attempts.max(1);
+        let mut attempt = 0usize;
+        loop {
+            attempt = attempt.saturating_add(1);
+            match build()?.send().await {
+                Ok(response) => {
+                    if response.status().is_success() {
+                        return Ok(response);
+                    }
+                    let (error, retryable) = Self::snapshot_upload_error(response).await?;
+                    if !retryable || attempt >= max_attempts {
+                        return Err(error);
+                    }
+                    debug!(
+                        "Snapshot upload {} retry attempt {}/{} after {} (event_id={})",
+                        label,
+                        attempt + 1,
+                        max_attempts,
+                        error,
+                        event_id
+                    );
+                }
+                Err(err) => {
+                    if !is_retryable_transport_error(&err) || attempt >= max_attempts {
+                        return Err(DeviceSyncError::Http(err));
+                    }
+                    debug!(
+                        "Snapshot upload {} retry attempt {}/{} after transport error (event_id={}): {}",
+                        label,
+                        attempt + 1,
+                        max_attempts,
+                        event_id,
+                        err
+                    );
+                }
+            }
+            sleep(self.upload_config.backoff_with_jitter(attempt)).await;
+        }
+    }
+
+    /// Snapshot metadata headers shared by single-call and chunked uploads.
+    fn snapshot_upload_headers(
+        &self,
+        token: &str,
+        device_id: &str,
+        upload_headers: &SnapshotUploadHeaders,
+    ) -> Result<HeaderMap> {
+        let mut headers = self.headers_with_device(token, Some(device_id))?;
+        if let Some(event_id) = upload_headers.event_id.as_deref() {
+            headers.insert(
+                "x-snapshot-event-id",
+                HeaderValue::from_str(event_id)
+                    .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot event ID"))?,
+            );
+        }
+        headers.insert(
+            "x-snapshot-schema-version",
+            HeaderValue::from_str(&upload_headers.schema_version.to_string())
+                .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot schema version"))?,
+        );
+        headers.insert(
+            "x-snapshot-covers-tables",
+            HeaderValue::from_str(&upload_headers.covers_tables.join(","))
+                .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot covers tables"))?,
+        );
+        headers.insert(
+            "x-snapshot-size-bytes",
+            HeaderValue::from_str(&upload_headers.size_bytes.to_string())
+                .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot size"))?,
+        );
+        headers.insert(
+            "x-snapshot-checksum",
+            HeaderValue::from_str(&upload_headers.checksum)
+                .map_err(|_| DeviceSyncError::invalid_request("Invalid snapshot checksum"))?,
+        );
+        headers.insert(
+            "x-snapshot-metadata-payload",
+            HeaderValue::from_str(&upload_headers.metadata_payload).map_err(|_| {
+                DeviceSyncError::invalid_request("Invalid snapshot metadata payload")
+            })?,
+        );
+        headers.insert(
+            "x-snapshot-payload-key-version",
+            HeaderValue::from_str(&upload_headers.payload_key_version.to_string()).map_err(
+                |_| DeviceSyncError::invalid_request("Invalid snapshot payload key version"),
+            )?,
+        );
+        if let Some(base_seq) = upload_headers.base_seq {
+            headers.insert("x-snapshot-base-seq", HeaderValue::from(base_seq));
+        }
+        if upload_headers.encoding != SnapshotEncoding::Identity {
+            headers.insert(
+                SNAPSHOT_ENCODING_HEADER,
+                HeaderValue::from_static(upload_headers.encoding.as_header_value()),
+            );
+        }
+        Ok(headers)
+    }
+
+    /// Turn a failed snapshot upload response into an error, and say whether
+    /// the request is worth retrying.
+    async fn snapshot_upload_error(response: reqwest::Response) -> Result<(DeviceSyncError, bool)> {
+        let status = response.status();
+        let body = response.text().await?;
+        Self::log_response(status, &body);
+        let mut parsed_error_code: Option<String> = None;
+        let mut parsed_error_message: Option<String> = None;
+        let error = if let Ok(api_error) = serde_json::from_str::<ApiErrorResponse>(&body) {
+            let message = api_error.message;
+            let code = if api_error.code.is_empty() {
+                api_error.error
+            } else {
+                api_error.code
+            };
+            parsed_error_code = Some(code.clone());
+            parsed_error_message = Some(message.clone());
+            DeviceSyncError::api_structured(status.as_u16(), code, message, api_error.details)
+        } else {
+            DeviceSyncError::api(status.as_u16(), format!("Request failed: {}", body))
+        };
+        let retryable = is_retryable_snapshot_error(
+            status.as_u16(),
+            parsed_error_code.as_deref(),
+            parsed_error_message.as_deref(),
+        );
+        Ok((error, retryable))
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Pairing
     // ─────────────────────────────────────────────────────────────────────────
@@ -1264,9 +1766,12 @@ mod tests {
 
     #[derive(Debug, Clone)]
     struct CapturedUploadRequest {
+        path: String,
+        part_index: Option<String>,
         event_id: Option<String>,
         content_length: Option<String>,
         snapshot_size_bytes: Option<String>,
+        snapshot_encoding: Option<String>,
     }
 
     #[derive(Debug, Clone)]
@@ -1303,6 +1808,7 @@ mod tests {
             metadata_payload: "meta".to_string(),
             payload_key_version: 1,
             base_seq: None,
+            encoding: SnapshotEncoding::Identity,
         }
     }
 
@@ -1312,7 +1818,7 @@ mod tests {
 
     async fn read_http_request(
         stream: &mut tokio::net::TcpStream,
-    ) -> Option<(HashMap<String, String>, usize)> {
+    ) -> Option<(String, HashMap<String, String>, usize)> {
         let mut buffer = Vec::new();
         loop {
             let mut chunk = [0_u8; 2048];
@@ -1329,7 +1835,7 @@ mod tests {
         let header_end = header_end_offset(&buffer)?;
         let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
         let mut lines = head.lines();
-        let _request_line = lines.next()?.to_string();
+        let path = lines.next()?.split_whitespace().nth(1)?.to_string();
 
         let mut headers = HashMap::new();
         for line in lines {
@@ -1353,7 +1859,7 @@ mod tests {
             body_read = body_read.saturating_add(read);
         }
 
-        Some((headers, content_length))
+        Some((path, headers, content_length))
     }
 
     fn status_text(status: u16) -> &'static str {
@@ -1409,17 +1915,23 @@ mod tests {
                 let captured_inner = Arc::clone(&captured_clone);
                 let scripted_inner = Arc::clone(&scripted_clone);
                 tokio::spawn(async move {
-                    let Some((headers, _content_length)) = read_http_request(&mut stream).await
+                    let Some((path, headers, _content_length)) =
+                        read_http_request(&mut stream).await
                     else {
                         return;
                     };
+                    let part_index = headers.get("x-snapshot-part-index").cloned();
                     let event_id = headers.get("x-snapshot-event-id").cloned();
                     let content_length = headers.get("content-length").cloned();
                     let snapshot_size_bytes = headers.get("x-snapshot-size-bytes").cloned();
+                    let snapshot_encoding = headers.get(SNAPSHOT_ENCODING_HEADER).cloned();
                     captured_inner.lock().await.push(CapturedUploadRequest {
+                        path,
+                        part_index,
                         event_id,
                         content_length,
                         snapshot_size_bytes,
+                        snapshot_encoding,
                     });
 
                     let outcome = scripted_inner.lock().await.pop_front().unwrap_or(
@@ -1450,14 +1962,195 @@ mod tests {
         (format!("http://{}", addr), captured, handle)
     }
 
-    #[test]
-    fn choose_snapshot_prefers_cursor_when_latest_id_is_non_uuid_and_cursor_is_uuid() {
-        let latest = latest_snapshot("snap-legacy-id", 100);
-        let cursor = cursor_snapshot("019bb9fe-f707-71e9-a40d-733575f4f246", 90);
+    async fn start_mock_download_server(
+        extra_headers: Vec<(&'static str, String)>,
+        body: Vec<u8>,
+    ) -> (String, tokio::task::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("listener addr");
 
-        let selected =
-            DeviceSyncClient::choose_snapshot_from_latest_and_cursor(latest, Some(cursor));
-        assert_eq!(selected.snapshot_id, "019bb9fe-f707-71e9-a40d-733575f4f246");
+        let handle = tokio::spawn(async move {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            if read_http_request(&mut stream).await.is_none() {
+                return;
+            }
+            let mut head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n",
+                body.len()
+            );
+            for (name, value) in &extra_headers {
+                head.push_str(&format!("{}: {}\r\n", name, value));
+            }
+            head.push_str("\r\n");
+            let _ = stream.write_all(head.as_bytes()).await;
+            let _ = stream.write_all(&body).await;
+            let _ = stream.flush().await;
+        });
+
+        (format!("http://{}", addr), handle)
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).expect("gzip write");
+        encoder.finish().expect("gzip finish")
+    }
+
+    fn sqlite_image() -> Vec<u8> {
+        let mut image = b"SQLite format 3\0".to_vec();
+        image.resize(4096, 0);
+        image
+    }
+
+    fn snapshot_download_headers(checksum: String) -> Vec<(&'static str, String)> {
+        vec![
+            ("X-Snapshot-Schema-Version", "1".to_string()),
+            ("X-Snapshot-Covers-Tables", "accounts,assets".to_string()),
+            ("X-Snapshot-Checksum", checksum),
+        ]
+    }
+
+    #[tokio::test]
+    async fn download_snapshot_decompresses_gzip_body() {
+        let image = sqlite_image();
+        let mut headers = snapshot_download_headers(compute_sha256_checksum(&image));
+        headers.push(("Content-Encoding", "gzip".to_string()));
+        let (base_url, server) = start_mock_download_server(headers, gzip(&image)).await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let (snapshot_headers, body) = client
+            .download_snapshot("token", "device-1", "snap-1")
+            .await
+            .expect("download snapshot");
+
+        assert_eq!(body, image);
+        assert!(body.starts_with(b"SQLite format 3\0"));
+        assert_eq!(snapshot_headers.covers_tables, vec!["accounts", "assets"]);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn download_snapshot_rejects_checksum_mismatch_after_decompression() {
+        let image = sqlite_image();
+        let compressed = gzip(&image);
+        // Checksum of the compressed bytes rather than the decompressed image.
+        let mut headers = snapshot_download_headers(compute_sha256_checksum(&compressed));
+        headers.push(("X-Snapshot-Compression", "gzip".to_string()));
+        let (base_url, server) = start_mock_download_server(headers, compressed).await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let err = client
+            .download_snapshot("token", "device-1", "snap-1")
+            .await
+            .expect_err("checksum mismatch");
+
+        assert!(err.to_string().contains("decompressed payload"));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn download_snapshot_rejects_corrupted_body() {
+        let image = sqlite_image();
+        let mut corrupted = image.clone();
+        corrupted[100] ^= 0xff;
+        let headers = snapshot_download_headers(compute_sha256_checksum(&image));
+        let (base_url, server) = start_mock_download_server(headers, corrupted).await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let err = client
+            .download_snapshot("token", "device-1", "snap-1")
+            .await
+            .expect_err("checksum mismatch");
+
+        assert!(err
+            .to_string()
+            .contains("Snapshot checksum does not match payload"));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn download_snapshot_reports_payload_encoding_without_decoding() {
+        let payload = gzip(&sqlite_image());
+        let mut headers = snapshot_download_headers(compute_sha256_checksum(&payload));
+        headers.push(("X-Snapshot-Encoding", "gzip".to_string()));
+        let (base_url, server) = start_mock_download_server(headers, payload.clone()).await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let (snapshot_headers, body) = client
+            .download_snapshot("token", "device-1", "snap-1")
+            .await
+            .expect("download snapshot");
+
+        // The payload is encrypted in practice, so decoding is left to the
+        // caller after decryption.
+        assert_eq!(body, payload);
+        assert_eq!(snapshot_headers.encoding, SnapshotEncoding::Gzip);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn snapshot_upload_sends_encoding_header_only_when_encoded() {
+        let (base_url, captured, server) = start_mock_upload_server(vec![
+            MockUploadOutcome::Respond {
+                status: 201,
+                body: success_upload_body("snap-plain"),
+                delay_ms: 0,
+            },
+            MockUploadOutcome::Respond {
+                status: 201,
+                body: success_upload_body("snap-gzip"),
+                delay_ms: 0,
+            },
+        ])
+        .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let device_id = "019bb9fe-f707-71e9-a40d-733575f4f246";
+        let plain = b"snapshot-payload".to_vec();
+        client
+            .upload_snapshot(
+                "token",
+                device_id,
+                build_upload_headers(None, &plain),
+                plain,
+            )
+            .await
+            .expect("plain upload");
+
+        let encoded = SnapshotEncoding::Gzip
+            .encode(&sqlite_image())
+            .expect("encode image");
+        let mut headers = build_upload_headers(None, &encoded);
+        headers.encoding = SnapshotEncoding::Gzip;
+        client
+            .upload_snapshot("token", device_id, headers, encoded.clone())
+            .await
+            .expect("gzip upload");
+
+        let requests = captured.lock().await.clone();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].snapshot_encoding, None);
+        assert_eq!(requests[1].snapshot_encoding.as_deref(), Some("gzip"));
+        assert_eq!(
+            requests[1].snapshot_size_bytes,
+            Some(encoded.len().to_string())
+        );
+        server.abort();
+    }
+
+    #[test]
+    fn choose_snapshot_prefers_cursor_when_latest_id_is_non_uuid_and_cursor_is_uuid() {
+        let latest = latest_snapshot("snap-legacy-id", 100);
+        let cursor = cursor_snapshot("019bb9fe-f707-71e9-a40d-733575f4f246", 90);
+
+        let selected =
+            DeviceSyncClient::choose_snapshot_from_latest_and_cursor(latest, Some(cursor));
+        assert_eq!(selected.snapshot_id, "019bb9fe-f707-71e9-a40d-733575f4f246");
     }
 
     #[test]
@@ -1532,6 +2225,249 @@ mod tests {
         server.abort();
     }
 
+    fn empty_pull_body(next_cursor: i64) -> String {
+        format!(
+            r#"{{"from":0,"to":{0},"nextCursor":{0},"hasMore":false,"events":[]}}"#,
+            next_cursor
+        )
+    }
+
+    #[tokio::test]
+    async fn pull_events_recovers_after_one_unavailable_response() {
+        let (base_url, captured, server) = start_mock_upload_server(vec![
+            MockUploadOutcome::Respond {
+                status: 503,
+                body: api_error_body("UNAVAILABLE", "try again"),
+                delay_ms: 0,
+            },
+            MockUploadOutcome::Respond {
+                status: 200,
+                body: empty_pull_body(42),
+                delay_ms: 0,
+            },
+        ])
+        .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let response = client
+            .pull_events(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                Some(40),
+                Some(100),
+            )
+            .await
+            .expect("pull succeeds after retry");
+
+        assert_eq!(response.next_cursor, 42);
+        let requests = captured.lock().await.clone();
+        assert_eq!(requests.len(), 2);
+        assert!(requests
+            .iter()
+            .all(|r| r.path == "/api/v1/sync/events/pull?since=40&limit=100"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn pull_events_uses_request_retry_config() {
+        let unavailable = || MockUploadOutcome::Respond {
+            status: 503,
+            body: api_error_body("UNAVAILABLE", "try again"),
+            delay_ms: 0,
+        };
+        let (base_url, captured, server) =
+            start_mock_upload_server(vec![unavailable(), unavailable(), unavailable()]).await;
+
+        let client = DeviceSyncClient::new(&base_url)
+            .with_upload_config(RetryConfig {
+                max_attempts: 5,
+                ..RetryConfig::SNAPSHOT_UPLOAD
+            })
+            .with_request_retry_config(RetryConfig {
+                max_attempts: 2,
+                base_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(5),
+                jitter_ratio: 0.0,
+            });
+        let err = client
+            .pull_events(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                Some(40),
+                Some(100),
+            )
+            .await
+            .expect_err("pull keeps failing");
+
+        assert!(matches!(err, DeviceSyncError::Api { status: 503, .. }));
+        assert_eq!(captured.lock().await.len(), 2);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn list_devices_page_sends_filters_as_query_params() {
+        let (base_url, captured, server) = start_mock_upload_server(vec![
+            MockUploadOutcome::Respond {
+                status: 200,
+                body: "[]".to_string(),
+                delay_ms: 0,
+            },
+            MockUploadOutcome::Respond {
+                status: 200,
+                body: "[]".to_string(),
+                delay_ms: 0,
+            },
+        ])
+        .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let page = client
+            .list_devices_page(
+                "token",
+                &DeviceListQuery {
+                    scope: Some("team".to_string()),
+                    trust_state: Some(TrustState::Revoked),
+                    limit: Some(25),
+                    offset: Some(50),
+                },
+            )
+            .await
+            .expect("list page");
+        assert_eq!(page.offset, 50);
+        assert!(!page.has_more);
+        client
+            .list_devices("token", None)
+            .await
+            .expect("list all devices");
+
+        let requests = captured.lock().await.clone();
+        assert_eq!(
+            requests[0].path,
+            "/api/v1/sync/team/devices?scope=team&trust_state=revoked&limit=25&offset=50"
+        );
+        assert_eq!(requests[1].path, "/api/v1/sync/team/devices");
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn push_events_is_not_retried() {
+        let (base_url, captured, server) = start_mock_upload_server(vec![
+            MockUploadOutcome::Respond {
+                status: 503,
+                body: api_error_body("UNAVAILABLE", "try again"),
+                delay_ms: 0,
+            },
+            MockUploadOutcome::Respond {
+                status: 200,
+                body: r#"{"accepted":[],"duplicate":[]}"#.to_string(),
+                delay_ms: 0,
+            },
+        ])
+        .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let err = client
+            .push_events(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                SyncPushRequest { events: Vec::new() },
+            )
+            .await
+            .expect_err("push surfaces the 503");
+
+        assert!(
+            matches!(err, DeviceSyncError::Api { status: 503, .. }),
+            "{err:?}"
+        );
+        assert_eq!(captured.lock().await.len(), 1);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn short_request_timeout_fails_promptly() {
+        let (base_url, _captured, server) =
+            start_mock_upload_server(vec![MockUploadOutcome::Respond {
+                status: 200,
+                body: r#"{"accepted":[],"duplicate":[]}"#.to_string(),
+                delay_ms: 5_000,
+            }])
+            .await;
+
+        let client = DeviceSyncClient::new(&base_url)
+            .with_timeouts(Duration::from_millis(100), Duration::from_secs(1));
+        let started = std::time::Instant::now();
+        let err = client
+            .push_events(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                SyncPushRequest { events: Vec::new() },
+            )
+            .await
+            .expect_err("request should time out");
+
+        assert!(
+            matches!(&err, DeviceSyncError::Http(inner) if inner.is_timeout()),
+            "{err:?}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn snapshot_upload_gives_up_after_configured_attempts() {
+        let server_error = || MockUploadOutcome::Respond {
+            status: 500,
+            body: api_error_body("INTERNAL", "still failing"),
+            delay_ms: 0,
+        };
+        let (base_url, captured, server) =
+            start_mock_upload_server(vec![server_error(), server_error(), server_error()]).await;
+
+        let client = DeviceSyncClient::new(&base_url).with_upload_config(RetryConfig {
+            max_attempts: 2,
+            base_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            jitter_ratio: 0.0,
+        });
+        let payload = b"snapshot-payload-gives-up".to_vec();
+        let err = client
+            .upload_snapshot(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                build_upload_headers(None, &payload),
+                payload,
+            )
+            .await
+            .expect_err("upload keeps failing");
+
+        match &err {
+            DeviceSyncError::RetriesExhausted {
+                attempts,
+                last_status,
+                last_error,
+                ..
+            } => {
+                assert_eq!(*attempts, 2);
+                assert_eq!(*last_status, Some(500));
+                assert!(
+                    matches!(**last_error, DeviceSyncError::Api { status: 500, .. }),
+                    "{last_error:?}"
+                );
+            }
+            other => panic!("expected RetriesExhausted, got {other:?}"),
+        }
+        assert_eq!(err.status_code(), Some(500));
+        assert_eq!(err.error_code(), Some("INTERNAL"));
+        assert_eq!(captured.lock().await.len(), 2);
+
+        server.abort();
+    }
+
     #[tokio::test]
     async fn snapshot_upload_retries_unknown_outcome_with_same_event_id() {
         let stable_event_id = Uuid::new_v4().to_string();
@@ -1613,6 +2549,154 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn snapshot_upload_reports_progress_up_to_total() {
+        let (base_url, captured, server) =
+            start_mock_upload_server(vec![MockUploadOutcome::Respond {
+                status: 201,
+                body: success_upload_body("snap-progress"),
+                delay_ms: 0,
+            }])
+            .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let payload = vec![7_u8; 200_000];
+        let reported = std::sync::Mutex::new(Vec::<(u64, u64)>::new());
+        let progress = |sent: u64, total: u64| reported.lock().unwrap().push((sent, total));
+        client
+            .upload_snapshot_with_progress(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                build_upload_headers(None, &payload),
+                payload,
+                Some(&progress),
+            )
+            .await
+            .expect("upload success");
+
+        let reported = reported.into_inner().unwrap();
+        assert!(reported.len() > 1, "expected several reports: {reported:?}");
+        assert!(reported.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(reported.iter().all(|(_, total)| *total == 200_000));
+        assert_eq!(reported.last(), Some(&(200_000, 200_000)));
+        let requests = captured.lock().await.clone();
+        assert_eq!(requests[0].content_length.as_deref(), Some("200000"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn chunked_snapshot_upload_retries_only_the_failed_part() {
+        let ok = || MockUploadOutcome::Respond {
+            status: 200,
+            body: "{}".to_string(),
+            delay_ms: 0,
+        };
+        let (base_url, captured, server) = start_mock_upload_server(vec![
+            ok(),
+            MockUploadOutcome::Respond {
+                status: 500,
+                body: api_error_body("INTERNAL", "retry please"),
+                delay_ms: 0,
+            },
+            ok(),
+            ok(),
+            MockUploadOutcome::Respond {
+                status: 201,
+                body: success_upload_body("snap-chunked"),
+                delay_ms: 0,
+            },
+        ])
+        .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let payload = b"0123456789".to_vec();
+        let result = client
+            .upload_snapshot_chunked(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                build_upload_headers(None, &payload),
+                payload,
+                4,
+            )
+            .await
+            .expect("chunked upload success");
+
+        assert_eq!(result.snapshot_id, "snap-chunked");
+        let requests = captured.lock().await.clone();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/api/v1/sync/snapshots/upload/part",
+                "/api/v1/sync/snapshots/upload/part",
+                "/api/v1/sync/snapshots/upload/part",
+                "/api/v1/sync/snapshots/upload/part",
+                "/api/v1/sync/snapshots/upload/complete",
+            ]
+        );
+        let part_indexes: Vec<Option<&str>> =
+            requests.iter().map(|r| r.part_index.as_deref()).collect();
+        assert_eq!(
+            part_indexes,
+            [Some("0"), Some("1"), Some("1"), Some("2"), None]
+        );
+        let content_lengths: Vec<Option<&str>> = requests[..4]
+            .iter()
+            .map(|r| r.content_length.as_deref())
+            .collect();
+        assert_eq!(
+            content_lengths,
+            [Some("4"), Some("4"), Some("4"), Some("2")]
+        );
+        let event_id = requests[0].event_id.clone().expect("event id");
+        assert!(Uuid::parse_str(&event_id).is_ok());
+        assert!(requests
+            .iter()
+            .all(|r| r.event_id.as_deref() == Some(event_id.as_str())));
+        assert_eq!(requests[4].snapshot_size_bytes.as_deref(), Some("10"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn chunked_snapshot_upload_validates_checksum_and_stops_on_rejected_part() {
+        let (base_url, captured, server) =
+            start_mock_upload_server(vec![MockUploadOutcome::Respond {
+                status: 400,
+                body: api_error_body("INVALID_PART", "part rejected"),
+                delay_ms: 0,
+            }])
+            .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let payload = b"0123456789".to_vec();
+        let mut bad_headers = build_upload_headers(None, &payload);
+        bad_headers.checksum = compute_sha256_checksum(b"something else");
+        let err = client
+            .upload_snapshot_chunked("token", "device", bad_headers, payload.clone(), 4)
+            .await
+            .expect_err("checksum mismatch");
+        assert!(err.to_string().contains("checksum does not match"));
+        assert!(captured.lock().await.is_empty());
+
+        client
+            .upload_snapshot_chunked(
+                "token",
+                "019bb9fe-f707-71e9-a40d-733575f4f246",
+                build_upload_headers(None, &payload),
+                payload,
+                4,
+            )
+            .await
+            .expect_err("rejected part");
+        let requests = captured.lock().await.clone();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].part_index.as_deref(), Some("0"));
+
+        server.abort();
+    }
+
     #[tokio::test]
     async fn snapshot_upload_does_not_retry_snapshot_index_conflict() {
         let (base_url, captured, server) = start_mock_upload_server(vec![
@@ -1676,6 +2760,61 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn in_flight_snapshot_upload_is_listed_and_cancellable_by_key() {
+        let (base_url, _captured, server) =
+            start_mock_upload_server(vec![MockUploadOutcome::Respond {
+                status: 201,
+                body: success_upload_body("snap-never-finishes"),
+                delay_ms: 10_000,
+            }])
+            .await;
+
+        let client = DeviceSyncClient::new(&base_url);
+        let payload = b"snapshot-cancel-by-key-payload".to_vec();
+        let payload_len = payload.len() as u64;
+        let device_id = "019bb9fe-f707-71e9-a40d-733575f4f246";
+        let event_id = Uuid::new_v4().to_string();
+        let dedupe_key = format!("{}:{}", device_id, event_id);
+        let headers = build_upload_headers(Some(event_id), &payload);
+
+        let upload = tokio::spawn(async move {
+            client
+                .upload_snapshot("token", device_id, headers, payload)
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(80)).await;
+
+        let listed = DeviceSyncClient::list_in_flight_snapshots().await;
+        let op = listed
+            .iter()
+            .find(|op| op.dedupe_key == dedupe_key)
+            .expect("in-flight entry listed");
+        assert!(DateTime::parse_from_rfc3339(&op.started_at).is_ok());
+        // The server has read the body but not yet answered.
+        assert_eq!(op.bytes_sent, payload_len);
+
+        assert!(DeviceSyncClient::cancel_in_flight_snapshot(&dedupe_key).await);
+        assert!(!DeviceSyncClient::list_in_flight_snapshots()
+            .await
+            .iter()
+            .any(|op| op.dedupe_key == dedupe_key));
+        assert!(!DeviceSyncClient::cancel_in_flight_snapshot(&dedupe_key).await);
+
+        let result = tokio::time::timeout(Duration::from_secs(2), upload)
+            .await
+            .expect("cancelled upload returns promptly")
+            .expect("upload task join");
+        match result {
+            Err(DeviceSyncError::InvalidRequest(message)) => {
+                assert!(message.contains("cancelled"));
+            }
+            other => panic!("expected cancellation error, got {:?}", other),
+        }
+
+        server.abort();
+    }
+
     #[tokio::test]
     async fn snapshot_upload_blocks_duplicate_concurrent_payload_uploads() {
         let (base_url, captured, server) =
@@ -259,6 +259,29 @@ pub fn sha256_checksum(data: &[u8]) -> String {
     format!("sha256:{:x}", digest)
 }
 
+/// Check `data` against an expected SHA-256 checksum. The comparison ignores
+/// case and accepts the digest with or without the `sha256:` prefix.
+pub fn verify_sha256_checksum(expected: &str, data: &[u8]) -> Result<(), String> {
+    fn digest_hex(checksum: &str) -> &str {
+        let checksum = checksum.trim();
+        match checksum.get(..7) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("sha256:") => &checksum[7..],
+            _ => checksum,
+        }
+    }
+
+    let actual = sha256_checksum(data);
+    if digest_hex(expected).eq_ignore_ascii_case(digest_hex(&actual)) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Checksum mismatch: expected={}, got={}",
+            expected.trim(),
+            actual
+        ))
+    }
+}
+
 /// Generate a UUID v4 device ID
 pub fn generate_device_id() -> String {
     uuid::Uuid::new_v4().to_string()
@@ -268,6 +291,21 @@ pub fn generate_device_id() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_verify_sha256_checksum() {
+        let data = b"snapshot bytes";
+        let checksum = sha256_checksum(data);
+        let hex = checksum.trim_start_matches("sha256:");
+
+        assert!(verify_sha256_checksum(&checksum, data).is_ok());
+        assert!(verify_sha256_checksum(&checksum.to_ascii_uppercase(), data).is_ok());
+        assert!(verify_sha256_checksum(hex, data).is_ok());
+
+        let err = verify_sha256_checksum(&checksum, b"corrupted bytes").unwrap_err();
+        assert!(err.contains("Checksum mismatch"));
+        assert!(verify_sha256_checksum("", data).is_err());
+    }
+
     #[test]
     fn test_root_key_generation() {
         let key = generate_root_key();
@@ -4,19 +4,20 @@ use std::sync::Arc;
 use uuid::Uuid;
 use wealthfolio_core::sync::{SyncEntity, SyncOperation};
 
-use crate::{ApiRetryClass, SyncPushEventRequest, SyncPushRequest, SyncState};
+use crate::{ApiRetryClass, SyncPullResponse, SyncPushEventRequest, SyncPushRequest, SyncState};
 
 pub mod ports;
 mod runtime;
 
 pub use ports::{
-    CredentialStore, OutboxStore, ReadyReconcileStore, ReplayEvent, ReplayStore,
-    SyncBootstrapResult, SyncCycleResult, SyncIdentity, SyncReadyReconcileResult, SyncTransport,
-    TransportError,
+    CredentialStore, OutboxStore, PullWindowSummary, PulledEventSummary, ReadyReconcileStore,
+    ReplayEvent, ReplayStore, SyncBootstrapResult, SyncCycleResult, SyncIdentity,
+    SyncReadyReconcileResult, SyncThrottleConfig, SyncTransport, TransportError,
+    DEFAULT_REPLAY_CHUNK_SIZE,
 };
 pub use runtime::{
     DeviceSyncRuntimeState, OverwriteInfo, OverwriteTableInfo, PairingFlowPhase,
-    PairingFlowResponse, PairingFlowState,
+    PairingFlowResponse, PairingFlowState, SnapshotGenerationGuard,
 };
 
 /// Foreground pull cadence in seconds.
@@ -24,6 +25,16 @@ pub const DEVICE_SYNC_FOREGROUND_INTERVAL_SECS: u64 = 5 * 60;
 /// Maximum jitter (seconds) added to periodic cycle intervals.
 pub const DEVICE_SYNC_INTERVAL_JITTER_SECS: u64 = 5;
 
+/// Days an applied-event record is kept for duplicate detection before the
+/// cycle prunes it by age.
+pub const APPLIED_EVENTS_RETENTION_DAYS: i64 = 30;
+
+/// Consecutive pulled events whose payload fails to decrypt before the cycle
+/// stops skipping them and asks for re-pairing. A lone bad event is skipped;
+/// a run of them usually means this device holds stale keys. Events from a
+/// newer key version than this device's are never skipped.
+pub const DECRYPT_FAILURE_REAUTH_THRESHOLD: usize = 5;
+
 /// Exponential backoff in seconds with cap.
 pub fn backoff_seconds(consecutive_failures: i32) -> i64 {
     const MAX_EXPONENT: i32 = 8;
@@ -33,6 +44,14 @@ pub fn backoff_seconds(consecutive_failures: i32) -> i64 {
     2_i64.pow(capped as u32) * BASE_DELAY_SECONDS
 }
 
+/// When the engine should next run after `consecutive_failures` failed
+/// cycles. Uses the outbox retry backoff from [`backoff_seconds`]: five
+/// seconds after the first failure, doubling up to its cap.
+pub fn compute_next_retry(consecutive_failures: i32) -> chrono::DateTime<Utc> {
+    let delay_seconds = backoff_seconds(consecutive_failures.saturating_sub(1));
+    Utc::now() + chrono::Duration::seconds(delay_seconds)
+}
+
 fn remote_entity_id_is_valid(entity_id: &str) -> bool {
     Uuid::parse_str(entity_id).is_ok()
 }
@@ -83,6 +102,81 @@ fn parse_event_operation(event_type: &str) -> Option<SyncOperation> {
     }
 }
 
+/// Approximate size of a pull page, counting its encrypted payload bytes.
+fn pull_response_bytes(response: &SyncPullResponse) -> usize {
+    response
+        .events
+        .iter()
+        .map(|event| event.payload.len())
+        .sum()
+}
+
+/// Encrypt an event payload with the current root key at `payload_key_version`.
+pub fn encrypt_sync_payload(
+    plaintext_payload: &str,
+    identity: &SyncIdentity,
+    payload_key_version: i32,
+) -> Result<String, String> {
+    let root_key = identity
+        .root_key
+        .as_deref()
+        .ok_or_else(|| "Sync root key is not configured".to_string())?;
+    let key_version = payload_key_version.max(1) as u32;
+    let dek = crate::crypto::derive_dek(root_key, key_version)
+        .map_err(|e| format!("Failed to derive event DEK: {}", e))?;
+    crate::crypto::encrypt(&dek, plaintext_payload)
+        .map_err(|e| format!("Failed to encrypt sync payload: {}", e))
+}
+
+/// Decrypt an event payload with the root key matching its key version, so
+/// events written just before a key rotation still decrypt after it.
+pub fn decrypt_sync_payload(
+    encrypted_payload: &str,
+    identity: &SyncIdentity,
+    payload_key_version: i32,
+) -> Result<String, String> {
+    let root_key = identity
+        .root_key_for_version(payload_key_version)
+        .ok_or_else(|| "Sync root key is not configured".to_string())?;
+    let key_version = payload_key_version.max(1) as u32;
+    let dek = crate::crypto::derive_dek(root_key, key_version)
+        .map_err(|e| format!("Failed to derive event DEK: {}", e))?;
+    crate::crypto::decrypt(&dek, encrypted_payload)
+        .map_err(|e| format!("Failed to decrypt sync payload: {}", e))
+}
+
+/// Summarize a pulled page for diagnostics: each event's entity, operation,
+/// seq and whether `decrypt` could open its payload. Nothing is applied and
+/// no cursor moves.
+pub fn summarize_pull_window<F>(response: &SyncPullResponse, decrypt: F) -> PullWindowSummary
+where
+    F: Fn(&crate::SyncEvent) -> Result<String, String>,
+{
+    let events = response
+        .events
+        .iter()
+        .map(|event| {
+            let decrypt_error = decrypt(event).err();
+            PulledEventSummary {
+                event_id: event.event_id.clone(),
+                entity: event.entity,
+                op: parse_event_operation(&event.event_type),
+                seq: event.seq,
+                payload_key_version: event.payload_key_version,
+                decrypt_ok: decrypt_error.is_none(),
+                decrypt_error,
+            }
+        })
+        .collect();
+    PullWindowSummary {
+        from: response.from,
+        to: response.to,
+        next_cursor: response.next_cursor,
+        has_more: response.has_more,
+        events,
+    }
+}
+
 fn millis_until_rfc3339(target: &str) -> Option<u64> {
     let target = chrono::DateTime::parse_from_rfc3339(target).ok()?;
     let now = chrono::Utc::now();
@@ -112,6 +206,8 @@ struct CycleContext<'a, R: ReplayStore + ?Sized> {
     local_cursor: i64,
     pushed_count: usize,
     pulled_count: usize,
+    pushed_bytes: usize,
+    pulled_bytes: usize,
 }
 
 impl<'a, R: ReplayStore + ?Sized> CycleContext<'a, R> {
@@ -144,11 +240,34 @@ impl<'a, R: ReplayStore + ?Sized> CycleContext<'a, R> {
             bootstrap_snapshot_id: None,
             bootstrap_snapshot_seq: None,
             dead_letter_count: 0,
+            pushed_bytes: self.pushed_bytes,
+            pulled_bytes: self.pulled_bytes,
         })
     }
 }
 
 pub async fn run_sync_cycle<P>(ports: &P, post_bootstrap: bool) -> Result<SyncCycleResult, String>
+where
+    P: OutboxStore + ReplayStore + SyncTransport + CredentialStore + Send + Sync,
+{
+    let result = run_sync_cycle_inner(ports, post_bootstrap).await?;
+    if let Err(err) = ports
+        .mark_cycle_transfer_stats(
+            result.pushed_bytes,
+            result.pulled_bytes,
+            result.pulled_count,
+        )
+        .await
+    {
+        warn!(
+            "[DeviceSync] Failed to record cycle transfer stats: {}",
+            err
+        );
+    }
+    Ok(result)
+}
+
+async fn run_sync_cycle_inner<P>(ports: &P, post_bootstrap: bool) -> Result<SyncCycleResult, String>
 where
     P: OutboxStore + ReplayStore + SyncTransport + CredentialStore + Send + Sync,
 {
@@ -161,6 +280,8 @@ where
         local_cursor: ports.get_cursor().await.unwrap_or(0),
         pushed_count: 0,
         pulled_count: 0,
+        pushed_bytes: 0,
+        pulled_bytes: 0,
     };
 
     let identity = match ports.get_sync_identity() {
@@ -196,6 +317,8 @@ where
                 bootstrap_snapshot_id: None,
                 bootstrap_snapshot_seq: None,
                 dead_letter_count: 0,
+                pushed_bytes: 0,
+                pulled_bytes: 0,
             });
         }
     };
@@ -232,6 +355,8 @@ where
             bootstrap_snapshot_id: None,
             bootstrap_snapshot_seq: None,
             dead_letter_count: 0,
+            pushed_bytes: 0,
+            pulled_bytes: 0,
         });
     }
 
@@ -288,6 +413,8 @@ where
                     bootstrap_snapshot_id: None,
                     bootstrap_snapshot_seq: None,
                     dead_letter_count: 0,
+                    pushed_bytes: 0,
+                    pulled_bytes: 0,
                 });
             }
             debug!("[DeviceSync] Reconcile action=NOOP but has pending outbox, proceeding with push+pull");
@@ -326,6 +453,8 @@ where
                         .map(|s| s.snapshot_id.clone()),
                     bootstrap_snapshot_seq: reconcile.latest_snapshot.as_ref().map(|s| s.oplog_seq),
                     dead_letter_count: 0,
+                    pushed_bytes: 0,
+                    pulled_bytes: 0,
                 });
             }
         }
@@ -348,6 +477,8 @@ where
                 bootstrap_snapshot_id: None,
                 bootstrap_snapshot_seq: None,
                 dead_letter_count: 0,
+                pushed_bytes: 0,
+                pulled_bytes: 0,
             });
         }
         "PULL_TAIL" => {
@@ -380,6 +511,13 @@ where
         }
     };
 
+    // Superseded updates would only be overwritten by a newer one in the
+    // same push, so drop them first. A failure here just means a larger push.
+    match ports.compact_pending_outbox().await {
+        Ok(0) => {}
+        Ok(count) => debug!("[DeviceSync] Compacted {} superseded outbox events", count),
+        Err(err) => warn!("[DeviceSync] Outbox compaction failed: {}", err),
+    }
     let pending = ports
         .list_pending_outbox(500)
         .await
@@ -395,6 +533,12 @@ where
     let current_key_version = identity.key_version.unwrap_or(1).max(1);
     let mut stale_key_version_event_ids = Vec::new();
     let mut future_key_version_event_ids = Vec::new();
+    let max_bytes_per_cycle = ports.sync_throttle().max_bytes_per_cycle;
+    // Snapshots moved since the last cycle are charged to this one; when they
+    // used the whole cap the cycle transfers nothing and the next starts fresh.
+    let snapshot_bytes = ports.take_snapshot_transfer_bytes();
+    let snapshot_budget_exhausted = max_bytes_per_cycle.is_some_and(|cap| snapshot_bytes >= cap);
+    let mut pushed_bytes = 0usize;
 
     for event in pending {
         if !remote_entity_id_is_valid(&event.entity_id) {
@@ -407,19 +551,12 @@ where
             invalid_entity_id_event_ids.push(event.event_id.clone());
             continue;
         }
-        max_retry_count = max_retry_count.max(event.retry_count);
         let event_type = format!(
             "{}.{}.v1",
             sync_entity_name(&event.entity),
             sync_operation_name(&event.op)
         );
-        push_event_ids.push(event.event_id.clone());
         let payload_key_version = event.payload_key_version.max(1);
-        if payload_key_version < current_key_version {
-            stale_key_version_event_ids.push(event.event_id.clone());
-        } else if payload_key_version > current_key_version {
-            future_key_version_event_ids.push(event.event_id.clone());
-        }
         let encrypted_payload =
             match ports.encrypt_sync_payload(&event.payload, &identity, payload_key_version) {
                 Ok(payload) => payload,
@@ -433,6 +570,27 @@ where
                         .await;
                 }
             };
+        if let Some(cap) = max_bytes_per_cycle {
+            // Always push at least one event so a large payload cannot stall the outbox.
+            let cycle_bytes = snapshot_bytes
+                .saturating_add(pushed_bytes)
+                .saturating_add(encrypted_payload.len());
+            if (!push_events.is_empty() || snapshot_budget_exhausted) && cycle_bytes > cap {
+                debug!(
+                    "[DeviceSync] Push byte cap reached ({} bytes), leaving remaining outbox events for next cycle",
+                    cap
+                );
+                break;
+            }
+        }
+        pushed_bytes = pushed_bytes.saturating_add(encrypted_payload.len());
+        max_retry_count = max_retry_count.max(event.retry_count);
+        push_event_ids.push(event.event_id.clone());
+        if payload_key_version < current_key_version {
+            stale_key_version_event_ids.push(event.event_id.clone());
+        } else if payload_key_version > current_key_version {
+            future_key_version_event_ids.push(event.event_id.clone());
+        }
         push_events.push(SyncPushEventRequest {
             event_id: event.event_id,
             device_id: device_id.clone(),
@@ -442,6 +600,7 @@ where
             client_timestamp: event.client_timestamp,
             payload: encrypted_payload,
             payload_key_version,
+            device_seq: event.device_seq,
         });
     }
 
@@ -494,7 +653,11 @@ where
             Err(err) => {
                 let err_str = err.to_string();
 
-                if err_str.contains("KEY_VERSION_MISMATCH") {
+                if err
+                    .error_code
+                    .as_deref()
+                    .is_some_and(crate::error::is_key_version_mismatch_code)
+                {
                     if !stale_key_version_event_ids.is_empty()
                         && future_key_version_event_ids.is_empty()
                     {
@@ -531,6 +694,8 @@ where
                             bootstrap_snapshot_id: None,
                             bootstrap_snapshot_seq: None,
                             dead_letter_count: dropped,
+                            pushed_bytes: 0,
+                            pulled_bytes: 0,
                         });
                     }
 
@@ -609,6 +774,7 @@ where
     }
 
     ctx.pushed_count = pushed_count;
+    ctx.pushed_bytes = pushed_bytes;
 
     if !ports
         .verify_cycle_lock(lock_version)
@@ -632,11 +798,25 @@ where
             bootstrap_snapshot_id: None,
             bootstrap_snapshot_seq: None,
             dead_letter_count: 0,
+            pushed_bytes,
+            pulled_bytes: 0,
         });
     }
 
     let mut pulled_count = 0usize;
-    if server_cursor > local_cursor {
+    let mut pulled_bytes = 0usize;
+    if server_cursor > local_cursor && !snapshot_budget_exhausted {
+        let cycle_pull_budget = max_bytes_per_cycle
+            .map(|cap| cap.saturating_sub(snapshot_bytes.saturating_add(pushed_bytes)));
+        let pull_byte_budget = match (ports.pull_byte_budget(), cycle_pull_budget) {
+            (Some(pull), Some(cycle)) => Some(pull.min(cycle)),
+            (pull, cycle) => pull.or(cycle),
+        };
+        let replay_chunk_size = ports.replay_chunk_size().max(1);
+        // A run of decrypt failures can span pages; remember the seq it
+        // started at so the cursor can be moved back before it.
+        let mut consecutive_decrypt_failures = 0usize;
+        let mut decrypt_failure_run_start: Option<i64> = None;
         loop {
             ctx.local_cursor = local_cursor;
             ctx.pulled_count = pulled_count;
@@ -686,6 +866,8 @@ where
                                 bootstrap_snapshot_id: snap_id,
                                 bootstrap_snapshot_seq: snap_seq,
                                 dead_letter_count: 0,
+                                pushed_bytes: ctx.pushed_bytes,
+                                pulled_bytes: ctx.pulled_bytes,
                             });
                         }
                     }
@@ -695,6 +877,9 @@ where
                 }
             };
 
+            pulled_bytes = pulled_bytes.saturating_add(pull_response_bytes(&pull_response));
+            ctx.pulled_bytes = pulled_bytes;
+
             if let Some(gc_watermark) = pull_response.gc_watermark {
                 if local_cursor < gc_watermark {
                     return ctx
@@ -748,18 +933,79 @@ where
                     &identity,
                     remote_event.payload_key_version,
                 ) {
-                    Ok(payload) => payload,
+                    Ok(payload) => {
+                        consecutive_decrypt_failures = 0;
+                        decrypt_failure_run_start = None;
+                        payload
+                    }
                     Err(err) => {
-                        return ctx
-                            .fail(
-                                "replay_error",
-                                format!(
-                                    "Replay decrypt failed for event {}: {}",
-                                    remote_event.event_id, err
-                                ),
-                                Some(10),
+                        // A newer key version means this device missed a key
+                        // rotation; skipping would lose the event for good.
+                        if remote_event.payload_key_version > current_key_version {
+                            warn!(
+                                "[DeviceSync] Event {} uses key version {} (local {}) — re-pairing required",
+                                remote_event.event_id,
+                                remote_event.payload_key_version,
+                                current_key_version
+                            );
+                            return ctx
+                                .fail(
+                                    retry_class_code(ApiRetryClass::ReauthRequired),
+                                    format!(
+                                        "Event {} is encrypted with key version {} but this device has version {} — re-pairing required",
+                                        remote_event.event_id,
+                                        remote_event.payload_key_version,
+                                        current_key_version
+                                    ),
+                                    None,
+                                )
+                                .await;
+                        }
+                        consecutive_decrypt_failures += 1;
+                        let run_start = *decrypt_failure_run_start.get_or_insert(remote_event.seq);
+                        if consecutive_decrypt_failures >= DECRYPT_FAILURE_REAUTH_THRESHOLD {
+                            // Nothing from this page is committed. Earlier
+                            // pages may have been committed past the start of
+                            // the run, so move the cursor back before it and
+                            // every event in the run is replayed once the keys
+                            // are fixed.
+                            if run_start <= local_cursor {
+                                local_cursor = run_start - 1;
+                                ctx.local_cursor = local_cursor;
+                                ports
+                                    .set_cursor(local_cursor)
+                                    .await
+                                    .map_err(|e| e.to_string())?;
+                            }
+                            warn!(
+                                "[DeviceSync] {} consecutive decrypt failures — re-pairing required",
+                                consecutive_decrypt_failures
+                            );
+                            return ctx
+                                .fail(
+                                    retry_class_code(ApiRetryClass::ReauthRequired),
+                                    format!(
+                                        "{} consecutive events failed to decrypt (last: {}: {}) — re-pairing required",
+                                        consecutive_decrypt_failures, remote_event.event_id, err
+                                    ),
+                                    None,
+                                )
+                                .await;
+                        }
+                        let reason = format!("Payload decrypt failed: {}", err);
+                        warn!(
+                            "[DeviceSync] Skipping event {} (seq {}): {}",
+                            remote_event.event_id, remote_event.seq, reason
+                        );
+                        ports
+                            .record_skipped_event(
+                                local_entity,
+                                remote_event.entity_id,
+                                remote_event.event_id,
+                                reason,
                             )
-                            .await;
+                            .await?;
+                        continue;
                     }
                 };
                 let payload_json: serde_json::Value = match serde_json::from_str(&decrypted_payload)
@@ -787,72 +1033,57 @@ where
                     client_timestamp: remote_event.client_timestamp,
                     seq: remote_event.seq,
                     payload: payload_json,
+                    device_id: remote_event.device_id,
+                    device_seq: remote_event.device_seq,
                 });
             }
 
-            let applied_count = match ports
-                .apply_remote_events_lww_batch(decoded_events.clone())
-                .await
-            {
-                Ok(applied) => applied,
-                Err(err) => {
-                    warn!(
-                        "[DeviceSync] Batch replay apply failed ({}). Falling back to per-event apply with dead-letter skip.",
-                        err
-                    );
-
-                    let mut applied = 0usize;
-                    let mut dead_lettered = 0usize;
-
-                    for event in decoded_events {
-                        match ports.apply_remote_event_lww(event.clone()).await {
-                            Ok(applied_one) => {
-                                if applied_one {
-                                    applied += 1;
-                                }
-                            }
-                            Err(event_err) => {
-                                dead_lettered += 1;
-                                log::error!(
-                                    "[DeviceSync] Dead-lettering replay event due to apply error: entity={:?} entity_id={} op={:?} event_id={} seq={} error={}",
-                                    event.entity,
-                                    event.entity_id,
-                                    event.op,
-                                    event.event_id,
-                                    event.seq,
-                                    event_err
-                                );
-                            }
-                        }
-                    }
-
-                    if dead_lettered > 0 {
-                        warn!(
-                            "[DeviceSync] Dead-lettered {} replay events in this pull batch (cursor will advance).",
-                            dead_lettered
-                        );
-                    }
-
-                    applied
-                }
-            };
-            pulled_count += applied_count;
-
             if pull_response.next_cursor < local_cursor {
                 return Err(format!(
                     "Server returned non-monotonic cursor ({} < {})",
                     pull_response.next_cursor, local_cursor
                 ));
             }
-            local_cursor = pull_response.next_cursor;
-            ports
-                .set_cursor(local_cursor)
-                .await
-                .map_err(|e| e.to_string())?;
+
+            // Commit large pages in bounded chunks, each together with the
+            // cursor past it, so an interrupted replay resumes after the last
+            // committed chunk. The final chunk carries the page's next cursor.
+            let mut remaining = decoded_events.into_iter().peekable();
+            while remaining.peek().is_some() {
+                let chunk: Vec<ReplayEvent> = remaining.by_ref().take(replay_chunk_size).collect();
+                let chunk_cursor = if remaining.peek().is_some() {
+                    chunk
+                        .iter()
+                        .map(|event| event.seq)
+                        .max()
+                        .map_or(local_cursor, |seq| seq.max(local_cursor))
+                } else {
+                    pull_response.next_cursor
+                };
+                pulled_count += apply_replay_chunk(ports, chunk, chunk_cursor).await?;
+                local_cursor = chunk_cursor;
+            }
+
+            if local_cursor != pull_response.next_cursor {
+                local_cursor = pull_response.next_cursor;
+                ports
+                    .set_cursor(local_cursor)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
 
             if !pull_response.has_more {
                 break;
             }
+            if let Some(budget) = pull_byte_budget {
+                if pulled_bytes > budget {
+                    debug!(
+                        "[DeviceSync] Pull byte budget reached ({} > {} bytes), resuming from cursor {} next cycle",
+                        pulled_bytes, budget, local_cursor
+                    );
+                    break;
+                }
+            }
         }
         ports
             .mark_pull_completed()
@@ -864,6 +1095,10 @@ where
         let prune_seq = local_cursor - 10_000;
         let _ = ports.prune_applied_events_up_to_seq(prune_seq).await;
     }
+    let prune_cutoff = Utc::now() - chrono::Duration::days(APPLIED_EVENTS_RETENTION_DAYS);
+    let _ = ports
+        .prune_applied_events_older_than(prune_cutoff.to_rfc3339())
+        .await;
 
     ports
         .mark_cycle_outcome(
@@ -891,9 +1126,74 @@ where
         bootstrap_snapshot_id: None,
         bootstrap_snapshot_seq: None,
         dead_letter_count: 0,
+        pushed_bytes,
+        pulled_bytes,
     })
 }
 
+/// Apply one replay chunk and commit `new_cursor` with it. When the batch
+/// fails, events are applied one by one, dead-lettering those that still
+/// fail, and the cursor moves afterwards.
+async fn apply_replay_chunk<P>(
+    ports: &P,
+    events: Vec<ReplayEvent>,
+    new_cursor: i64,
+) -> Result<usize, String>
+where
+    P: ReplayStore + Send + Sync,
+{
+    match ports
+        .apply_remote_events_lww_batch_with_cursor(events.clone(), new_cursor)
+        .await
+    {
+        Ok(applied) => Ok(applied),
+        Err(err) => {
+            warn!(
+                "[DeviceSync] Batch replay apply failed ({}). Falling back to per-event apply with dead-letter skip.",
+                err
+            );
+
+            let mut applied = 0usize;
+            let mut dead_lettered = 0usize;
+
+            for event in events {
+                match ports.apply_remote_event_lww(event.clone()).await {
+                    Ok(applied_one) => {
+                        if applied_one {
+                            applied += 1;
+                        }
+                    }
+                    Err(event_err) => {
+                        dead_lettered += 1;
+                        log::error!(
+                            "[DeviceSync] Dead-lettering replay event due to apply error: entity={:?} entity_id={} op={:?} event_id={} seq={} error={}",
+                            event.entity,
+                            event.entity_id,
+                            event.op,
+                            event.event_id,
+                            event.seq,
+                            event_err
+                        );
+                    }
+                }
+            }
+
+            if dead_lettered > 0 {
+                warn!(
+                    "[DeviceSync] Dead-lettered {} replay events in this replay chunk (cursor will advance).",
+                    dead_lettered
+                );
+            }
+
+            ports
+                .set_cursor(new_cursor)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(applied)
+        }
+    }
+}
+
 fn reconcile_error(
     mut result: SyncReadyReconcileResult,
     message: String,
@@ -1064,25 +1364,33 @@ fn compute_jitter_ms() -> u64 {
     }
 }
 
-async fn compute_cycle_delay_ms<P>(ports: &P, jitter_ms: u64) -> u64
+async fn compute_cycle_delay_ms<P>(
+    ports: &P,
+    jitter_ms: u64,
+    min_cycle_interval: std::time::Duration,
+) -> u64
 where
     P: OutboxStore + ReplayStore + Send + Sync,
 {
     let mut delay_ms = DEVICE_SYNC_FOREGROUND_INTERVAL_SECS.saturating_mul(1000) + jitter_ms;
+    let mut backing_off = false;
 
     if let Ok(engine_status) = ports.get_engine_status().await {
         if let Some(next_retry_at) = engine_status.next_retry_at.as_deref() {
             if let Some(wait_ms) = millis_until_rfc3339(next_retry_at) {
                 delay_ms = wait_ms.saturating_add(jitter_ms).max(1_000);
+                backing_off = wait_ms > 0;
             }
         }
     }
 
-    if ports.has_pending_outbox().await.unwrap_or(false) {
+    // A pending outbox must not cut a failure backoff short.
+    if !backing_off && ports.has_pending_outbox().await.unwrap_or(false) {
         delay_ms = delay_ms.min(2_000 + (jitter_ms % 500));
     }
 
-    delay_ms
+    // The throttle wins over the pending-outbox fast path.
+    delay_ms.max(u64::try_from(min_cycle_interval.as_millis()).unwrap_or(u64::MAX))
 }
 
 pub async fn run_background_loop<P>(ports: Arc<P>)
@@ -1126,7 +1434,12 @@ where
         }
 
         let jitter_ms = compute_jitter_ms();
-        let delay_ms = compute_cycle_delay_ms(ports.as_ref(), jitter_ms).await;
+        let delay_ms = compute_cycle_delay_ms(
+            ports.as_ref(),
+            jitter_ms,
+            ports.sync_throttle().min_cycle_interval,
+        )
+        .await;
         tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
     }
 }
@@ -1146,12 +1459,26 @@ mod tests {
         sync_state: Result<SyncState, String>,
         fail_mark_cycle_outcome: bool,
         pending_outbox: Arc<Mutex<Vec<wealthfolio_core::sync::SyncOutboxEvent>>>,
+        superseded_outbox: Arc<Mutex<Vec<String>>>,
         dead_outbox_batches: Arc<Mutex<Vec<Vec<String>>>>,
         push_error: Option<TransportError>,
         reconcile_response: crate::ReconcileReadyStateResponse,
         persisted_trust_states: Arc<Mutex<Vec<String>>>,
         cycle_outcomes: Arc<Mutex<Vec<String>>>,
         engine_errors: Arc<Mutex<Vec<String>>>,
+        pull_pages: Arc<Mutex<std::collections::VecDeque<crate::SyncPullResponse>>>,
+        pull_calls: Arc<Mutex<Vec<Option<i64>>>>,
+        stored_cursors: Arc<Mutex<Vec<i64>>>,
+        pull_byte_budget: Option<usize>,
+        replay_chunk_size: usize,
+        applied_chunks: Arc<Mutex<Vec<Vec<i64>>>>,
+        committed_cursors: Arc<Mutex<Vec<i64>>>,
+        sync_throttle: SyncThrottleConfig,
+        snapshot_transfer_bytes: Arc<std::sync::atomic::AtomicUsize>,
+        pushed_batches: Arc<Mutex<Vec<Vec<String>>>>,
+        transfer_stats: Arc<Mutex<Vec<(usize, usize, usize)>>>,
+        next_retry_at: Option<String>,
+        skipped_events: Arc<Mutex<Vec<String>>>,
     }
 
     impl TestPorts {
@@ -1162,6 +1489,7 @@ mod tests {
                 sync_state,
                 fail_mark_cycle_outcome: false,
                 pending_outbox: Arc::new(Mutex::new(Vec::new())),
+                superseded_outbox: Arc::new(Mutex::new(Vec::new())),
                 dead_outbox_batches: Arc::new(Mutex::new(Vec::new())),
                 push_error: None,
                 reconcile_response: crate::ReconcileReadyStateResponse {
@@ -1172,6 +1500,19 @@ mod tests {
                 persisted_trust_states: Arc::new(Mutex::new(Vec::new())),
                 cycle_outcomes: Arc::new(Mutex::new(Vec::new())),
                 engine_errors: Arc::new(Mutex::new(Vec::new())),
+                pull_pages: Arc::new(Mutex::new(std::collections::VecDeque::new())),
+                pull_calls: Arc::new(Mutex::new(Vec::new())),
+                stored_cursors: Arc::new(Mutex::new(Vec::new())),
+                pull_byte_budget: None,
+                replay_chunk_size: DEFAULT_REPLAY_CHUNK_SIZE,
+                applied_chunks: Arc::new(Mutex::new(Vec::new())),
+                committed_cursors: Arc::new(Mutex::new(Vec::new())),
+                sync_throttle: SyncThrottleConfig::default(),
+                snapshot_transfer_bytes: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+                pushed_batches: Arc::new(Mutex::new(Vec::new())),
+                transfer_stats: Arc::new(Mutex::new(Vec::new())),
+                next_retry_at: None,
+                skipped_events: Arc::new(Mutex::new(Vec::new())),
             }
         }
     }
@@ -1225,6 +1566,14 @@ mod tests {
         async fn has_pending_outbox(&self) -> Result<bool, String> {
             Ok(!self.pending_outbox.lock().await.is_empty())
         }
+
+        async fn compact_pending_outbox(&self) -> Result<usize, String> {
+            let superseded = std::mem::take(&mut *self.superseded_outbox.lock().await);
+            let mut pending = self.pending_outbox.lock().await;
+            let before = pending.len();
+            pending.retain(|event| !superseded.contains(&event.event_id));
+            Ok(before - pending.len())
+        }
     }
 
     #[async_trait]
@@ -1241,21 +1590,32 @@ mod tests {
             Ok(self.cursor)
         }
 
-        async fn set_cursor(&self, _cursor: i64) -> Result<(), String> {
+        async fn set_cursor(&self, cursor: i64) -> Result<(), String> {
+            self.stored_cursors.lock().await.push(cursor);
             Ok(())
         }
 
-        async fn apply_remote_events_lww_batch(
+        async fn apply_remote_events_lww_batch_with_cursor(
             &self,
-            _events: Vec<ReplayEvent>,
+            events: Vec<ReplayEvent>,
+            new_cursor: i64,
         ) -> Result<usize, String> {
-            Ok(0)
+            self.applied_chunks
+                .lock()
+                .await
+                .push(events.iter().map(|event| event.seq).collect());
+            self.committed_cursors.lock().await.push(new_cursor);
+            Ok(events.len())
         }
 
         async fn apply_remote_event_lww(&self, _event: ReplayEvent) -> Result<bool, String> {
             Ok(false)
         }
 
+        fn replay_chunk_size(&self) -> usize {
+            self.replay_chunk_size
+        }
+
         async fn mark_pull_completed(&self) -> Result<(), String> {
             Ok(())
         }
@@ -1273,15 +1633,46 @@ mod tests {
             Ok(())
         }
 
+        async fn mark_cycle_transfer_stats(
+            &self,
+            pushed_bytes: usize,
+            pulled_bytes: usize,
+            events_applied: usize,
+        ) -> Result<(), String> {
+            self.transfer_stats
+                .lock()
+                .await
+                .push((pushed_bytes, pulled_bytes, events_applied));
+            Ok(())
+        }
+
         async fn mark_engine_error(&self, message: String) -> Result<(), String> {
             self.engine_errors.lock().await.push(message);
             Ok(())
         }
 
+        async fn record_skipped_event(
+            &self,
+            _entity: SyncEntity,
+            _entity_id: String,
+            event_id: String,
+            _reason: String,
+        ) -> Result<(), String> {
+            self.skipped_events.lock().await.push(event_id);
+            Ok(())
+        }
+
         async fn prune_applied_events_up_to_seq(&self, _seq: i64) -> Result<(), String> {
             Ok(())
         }
 
+        async fn prune_applied_events_older_than(
+            &self,
+            _cutoff_rfc3339: String,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
         async fn get_engine_status(&self) -> Result<SyncEngineStatus, String> {
             Ok(SyncEngineStatus {
                 cursor: self.cursor,
@@ -1289,9 +1680,12 @@ mod tests {
                 last_pull_at: None,
                 last_error: None,
                 consecutive_failures: 0,
-                next_retry_at: None,
+                next_retry_at: self.next_retry_at.clone(),
                 last_cycle_status: None,
                 last_cycle_duration_ms: None,
+                last_cycle_pushed_bytes: None,
+                last_cycle_pulled_bytes: None,
+                last_cycle_events_applied: None,
             })
         }
     }
@@ -1310,8 +1704,15 @@ mod tests {
             &self,
             _token: &str,
             _device_id: &str,
-            _request: SyncPushRequest,
+            request: SyncPushRequest,
         ) -> Result<crate::SyncPushResponse, TransportError> {
+            self.pushed_batches.lock().await.push(
+                request
+                    .events
+                    .iter()
+                    .map(|event| event.event_id.clone())
+                    .collect(),
+            );
             if let Some(err) = &self.push_error {
                 return Err(err.clone());
             }
@@ -1326,10 +1727,29 @@ mod tests {
             &self,
             _token: &str,
             _device_id: &str,
-            _from_cursor: Option<i64>,
+            from_cursor: Option<i64>,
             _limit: Option<i64>,
         ) -> Result<crate::SyncPullResponse, TransportError> {
-            unreachable!("not used by these tests")
+            self.pull_calls.lock().await.push(from_cursor);
+            Ok(self
+                .pull_pages
+                .lock()
+                .await
+                .pop_front()
+                .expect("unexpected pull"))
+        }
+
+        fn pull_byte_budget(&self) -> Option<usize> {
+            self.pull_byte_budget
+        }
+
+        fn sync_throttle(&self) -> SyncThrottleConfig {
+            self.sync_throttle
+        }
+
+        fn take_snapshot_transfer_bytes(&self) -> usize {
+            self.snapshot_transfer_bytes
+                .swap(0, std::sync::atomic::Ordering::SeqCst)
         }
 
         async fn get_reconcile_ready_state(
@@ -1373,11 +1793,14 @@ mod tests {
 
         fn decrypt_sync_payload(
             &self,
-            _encrypted_payload: &str,
+            encrypted_payload: &str,
             _identity: &SyncIdentity,
             _payload_key_version: i32,
         ) -> Result<String, String> {
-            unreachable!("not used by these tests")
+            if encrypted_payload.starts_with("undecryptable") {
+                return Err("authentication tag mismatch".to_string());
+            }
+            Ok(encrypted_payload.to_string())
         }
     }
 
@@ -1403,6 +1826,7 @@ mod tests {
             device_id: None,
             root_key: None,
             key_version: Some(0),
+            ..Default::default()
         };
         let ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
 
@@ -1421,6 +1845,7 @@ mod tests {
             device_id: Some("device-1".to_string()),
             root_key: Some("root-key".to_string()),
             key_version: Some(1),
+            ..Default::default()
         };
         let ports = TestPorts::new(Some(identity), Ok(SyncState::Registered));
 
@@ -1442,6 +1867,7 @@ mod tests {
             device_id: Some("device-1".to_string()),
             root_key: Some("root-key".to_string()),
             key_version: Some(1),
+            ..Default::default()
         };
         let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Registered));
         ports.fail_mark_cycle_outcome = true;
@@ -1473,6 +1899,7 @@ mod tests {
             last_error: None,
             last_error_code: None,
             created_at: "2026-03-02T00:00:00Z".to_string(),
+            device_seq: None,
         }
     }
 
@@ -1482,6 +1909,7 @@ mod tests {
             device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
             root_key: Some("root-key".to_string()),
             key_version: Some(40),
+            ..Default::default()
         };
         let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
         ports.push_error = Some(TransportError {
@@ -1523,12 +1951,172 @@ mod tests {
         assert!(ports.engine_errors.lock().await.is_empty());
     }
 
+    #[tokio::test]
+    async fn run_sync_cycle_stops_pushing_once_cycle_byte_cap_is_reached() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.sync_throttle.max_bytes_per_cycle = Some(1);
+        {
+            let mut pending = ports.pending_outbox.lock().await;
+            for event_id in ["evt-1", "evt-2", "evt-3"] {
+                pending.push(outbox_event(
+                    event_id,
+                    "019cb093-06a8-7534-8677-546317b17957",
+                    1,
+                ));
+            }
+        }
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        // The first event always goes out; the rest wait for the next cycle.
+        assert_eq!(result.status, "ok");
+        assert_eq!(
+            ports.pushed_batches.lock().await.as_slice(),
+            [vec!["evt-1".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_charges_snapshot_transfer_to_cycle_byte_cap() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.sync_throttle.max_bytes_per_cycle = Some(1_000_000);
+        ports
+            .snapshot_transfer_bytes
+            .store(1_000_000, std::sync::atomic::Ordering::SeqCst);
+        ports.pending_outbox.lock().await.push(outbox_event(
+            "evt-1",
+            "019cb093-06a8-7534-8677-546317b17957",
+            1,
+        ));
+
+        // The snapshot used the whole cap, so nothing is pushed this cycle.
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+        assert_eq!(result.status, "ok");
+        assert!(ports.pushed_batches.lock().await.is_empty());
+
+        // The snapshot is only charged once; the next cycle pushes.
+        run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+        assert_eq!(
+            ports.pushed_batches.lock().await.as_slice(),
+            [vec!["evt-1".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_compacts_outbox_before_push() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        {
+            let mut pending = ports.pending_outbox.lock().await;
+            for event_id in ["evt-1", "evt-2"] {
+                pending.push(outbox_event(
+                    event_id,
+                    "019cb093-06a8-7534-8677-546317b17957",
+                    1,
+                ));
+            }
+        }
+        ports
+            .superseded_outbox
+            .lock()
+            .await
+            .push("evt-1".to_string());
+
+        run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(
+            ports.pushed_batches.lock().await.as_slice(),
+            [vec!["evt-2".to_string()]]
+        );
+    }
+
+    #[tokio::test]
+    async fn compute_cycle_delay_ms_enforces_min_cycle_interval() {
+        let ports = TestPorts::new(None, Ok(SyncState::Ready));
+        ports.pending_outbox.lock().await.push(outbox_event(
+            "evt-1",
+            "019cb093-06a8-7534-8677-546317b17957",
+            1,
+        ));
+
+        // A pending outbox normally shortens the wait to about two seconds.
+        let unthrottled = compute_cycle_delay_ms(&ports, 0, std::time::Duration::ZERO).await;
+        assert_eq!(unthrottled, 2_000);
+
+        let throttled =
+            compute_cycle_delay_ms(&ports, 0, std::time::Duration::from_secs(300)).await;
+        assert_eq!(throttled, 300_000);
+
+        // A minimum shorter than the computed delay changes nothing.
+        ports.pending_outbox.lock().await.clear();
+        let idle = compute_cycle_delay_ms(&ports, 0, std::time::Duration::from_secs(1)).await;
+        assert_eq!(idle, DEVICE_SYNC_FOREGROUND_INTERVAL_SECS * 1000);
+    }
+
+    #[test]
+    fn compute_next_retry_grows_with_failures_and_is_capped() {
+        let delay_secs = |failures: i32| (compute_next_retry(failures) - Utc::now()).num_seconds();
+
+        assert!((3..=5).contains(&delay_secs(1)));
+        assert!(delay_secs(3) > delay_secs(2));
+        let max_delay = backoff_seconds(i32::MAX);
+        assert!(delay_secs(10) <= max_delay);
+        assert!(delay_secs(10) >= max_delay - 2);
+        assert!(delay_secs(i32::MAX) <= max_delay);
+        assert!(delay_secs(i32::MIN) <= 5);
+    }
+
+    #[tokio::test]
+    async fn compute_cycle_delay_ms_honors_failure_backoff_over_pending_outbox() {
+        let mut ports = TestPorts::new(None, Ok(SyncState::Ready));
+        ports.pending_outbox.lock().await.push(outbox_event(
+            "evt-1",
+            "019cb093-06a8-7534-8677-546317b17957",
+            1,
+        ));
+        ports.next_retry_at = Some((Utc::now() + chrono::Duration::minutes(10)).to_rfc3339());
+
+        let delay = compute_cycle_delay_ms(&ports, 0, std::time::Duration::ZERO).await;
+        assert!(delay > 9 * 60 * 1000, "delay was {delay}ms");
+
+        // Once the retry time has passed the outbox fast path applies again.
+        ports.next_retry_at = Some((Utc::now() - chrono::Duration::minutes(1)).to_rfc3339());
+        let delay = compute_cycle_delay_ms(&ports, 0, std::time::Duration::ZERO).await;
+        assert_eq!(delay, 1_000);
+    }
+
     #[tokio::test]
     async fn run_sync_cycle_key_version_mismatch_without_stale_events_fails() {
         let identity = SyncIdentity {
             device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
             root_key: Some("root-key".to_string()),
             key_version: Some(40),
+            ..Default::default()
         };
         let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
         ports.push_error = Some(TransportError {
@@ -1561,6 +2149,351 @@ mod tests {
         );
     }
 
+    fn pull_page(seqs: std::ops::RangeInclusive<i64>, has_more: bool) -> crate::SyncPullResponse {
+        let payload = format!("{{\"note\":\"{}\"}}", "x".repeat(200));
+        crate::SyncPullResponse {
+            from: *seqs.start() - 1,
+            to: *seqs.end(),
+            next_cursor: *seqs.end(),
+            has_more,
+            events: seqs
+                .map(|seq| crate::SyncEvent {
+                    event_id: format!("evt-{}", seq),
+                    device_id: "remote-device".to_string(),
+                    event_type: "ai_message.update.v1".to_string(),
+                    entity: SyncEntity::AiMessage,
+                    entity_id: "019cb093-06a8-7534-8677-546317b17957".to_string(),
+                    client_timestamp: "2026-03-02T00:00:00Z".to_string(),
+                    payload: payload.clone(),
+                    payload_key_version: 1,
+                    device_seq: Some(seq),
+                    seq,
+                    user_id: "user-1".to_string(),
+                    team_id: "team-1".to_string(),
+                    server_timestamp: "2026-03-02T00:00:00Z".to_string(),
+                })
+                .collect(),
+            gc_watermark: None,
+            latest_snapshot_seq: None,
+        }
+    }
+
+    fn encrypt_at(root_key: &str, key_version: u32, plaintext: &str) -> String {
+        let dek = crate::crypto::derive_dek(root_key, key_version).expect("derive dek");
+        crate::crypto::encrypt(&dek, plaintext).expect("encrypt")
+    }
+
+    #[test]
+    fn decrypt_sync_payload_uses_previous_key_for_pre_rotation_events() {
+        let old_root = crate::crypto::generate_root_key();
+        let new_root = crate::crypto::generate_root_key();
+        let identity = SyncIdentity {
+            device_id: Some("device-1".to_string()),
+            root_key: Some(new_root.clone()),
+            key_version: Some(2),
+            previous_root_key: Some(old_root.clone()),
+            previous_key_version: Some(1),
+        };
+
+        let old_event = encrypt_at(&old_root, 1, r#"{"v":1}"#);
+        let new_event = encrypt_at(&new_root, 2, r#"{"v":2}"#);
+        assert_eq!(
+            decrypt_sync_payload(&old_event, &identity, 1).unwrap(),
+            r#"{"v":1}"#
+        );
+        assert_eq!(
+            decrypt_sync_payload(&new_event, &identity, 2).unwrap(),
+            r#"{"v":2}"#
+        );
+
+        // Once the previous key is dropped, pre-rotation events no longer decrypt.
+        let rotated_only = SyncIdentity {
+            previous_root_key: None,
+            previous_key_version: None,
+            ..identity
+        };
+        assert!(decrypt_sync_payload(&old_event, &rotated_only, 1).is_err());
+    }
+
+    #[test]
+    fn summarize_pull_window_reports_decrypt_outcome_per_event() {
+        let mut page = pull_page(7..=9, true);
+        page.events[1].event_type = "snapshot.published.v1".to_string();
+        page.events[1].entity = SyncEntity::Snapshot;
+        page.events[2].payload_key_version = 2;
+
+        let summary = summarize_pull_window(&page, |event| {
+            if event.payload_key_version == 1 {
+                Ok(event.payload.clone())
+            } else {
+                Err(format!("unknown key version {}", event.payload_key_version))
+            }
+        });
+
+        assert_eq!(summary.next_cursor, 9);
+        assert!(summary.has_more);
+        assert_eq!(
+            summary
+                .events
+                .iter()
+                .map(|event| (event.seq, event.entity, event.op, event.decrypt_ok))
+                .collect::<Vec<_>>(),
+            vec![
+                (7, SyncEntity::AiMessage, Some(SyncOperation::Update), true),
+                (8, SyncEntity::Snapshot, None, true),
+                (9, SyncEntity::AiMessage, Some(SyncOperation::Update), false),
+            ]
+        );
+        assert_eq!(
+            summary.events[2].decrypt_error.as_deref(),
+            Some("unknown key version 2")
+        );
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_stops_pulling_once_byte_budget_is_exceeded() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(6),
+            latest_snapshot: None,
+        };
+        ports.pull_byte_budget = Some(300);
+        {
+            let mut pages = ports.pull_pages.lock().await;
+            pages.push_back(pull_page(1..=2, true));
+            pages.push_back(pull_page(3..=4, true));
+            pages.push_back(pull_page(5..=6, false));
+        }
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "ok");
+        assert_eq!(result.cursor, 2);
+        assert_eq!(ports.pull_calls.lock().await.as_slice(), [Some(0)]);
+        assert_eq!(ports.committed_cursors.lock().await.as_slice(), [2]);
+        assert_eq!(ports.pull_pages.lock().await.len(), 2);
+
+        // Without a budget the next cycle resumes from the advanced cursor.
+        ports.pull_byte_budget = None;
+        ports.cursor = result.cursor;
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+        assert_eq!(result.cursor, 6);
+        assert_eq!(
+            ports.pull_calls.lock().await.as_slice(),
+            [Some(0), Some(2), Some(4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_skips_event_that_fails_to_decrypt() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(3),
+            latest_snapshot: None,
+        };
+        let mut page = pull_page(1..=3, false);
+        page.events[1].payload = "undecryptable".to_string();
+        ports.pull_pages.lock().await.push_back(page);
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "ok");
+        assert_eq!(result.cursor, 3);
+        assert_eq!(ports.applied_chunks.lock().await.as_slice(), [vec![1, 3]]);
+        assert_eq!(ports.skipped_events.lock().await.as_slice(), ["evt-2"]);
+        assert_eq!(ports.committed_cursors.lock().await.as_slice(), [3]);
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_requires_reauth_for_undecryptable_newer_key_version() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(3),
+            latest_snapshot: None,
+        };
+        let mut page = pull_page(1..=3, false);
+        page.events[1].payload = "undecryptable".to_string();
+        page.events[1].payload_key_version = 2;
+        ports.pull_pages.lock().await.push_back(page);
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "reauth_required");
+        assert_eq!(result.cursor, 0);
+        assert!(ports.stored_cursors.lock().await.is_empty());
+        assert!(ports.applied_chunks.lock().await.is_empty());
+        assert!(ports.skipped_events.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_requires_reauth_after_consecutive_decrypt_failures() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(6),
+            latest_snapshot: None,
+        };
+        let mut page = pull_page(1..=6, false);
+        for event in &mut page.events {
+            event.payload = "undecryptable".to_string();
+        }
+        ports.pull_pages.lock().await.push_back(page);
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "reauth_required");
+        assert_eq!(result.cursor, 0);
+        assert!(ports.stored_cursors.lock().await.is_empty());
+        assert!(ports.applied_chunks.lock().await.is_empty());
+        assert_eq!(
+            ports.skipped_events.lock().await.len(),
+            DECRYPT_FAILURE_REAUTH_THRESHOLD - 1
+        );
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_rewinds_cursor_before_decrypt_failures_on_earlier_pages() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(6),
+            latest_snapshot: None,
+        };
+        {
+            let mut first = pull_page(1..=3, true);
+            first.events[1].payload = "undecryptable".to_string();
+            first.events[2].payload = "undecryptable".to_string();
+            let mut second = pull_page(4..=6, false);
+            for event in &mut second.events {
+                event.payload = "undecryptable".to_string();
+            }
+            let mut pages = ports.pull_pages.lock().await;
+            pages.push_back(first);
+            pages.push_back(second);
+        }
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "reauth_required");
+        assert_eq!(ports.applied_chunks.lock().await.as_slice(), [vec![1]]);
+        assert_eq!(ports.committed_cursors.lock().await.as_slice(), [3]);
+        // The run started at seq 2 on the committed first page.
+        assert_eq!(ports.stored_cursors.lock().await.as_slice(), [1]);
+        assert_eq!(result.cursor, 1);
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_records_transfer_stats() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(4),
+            latest_snapshot: None,
+        };
+        let page = pull_page(1..=4, false);
+        let page_bytes = pull_response_bytes(&page);
+        ports.pull_pages.lock().await.push_back(page);
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "ok");
+        assert_eq!(result.pulled_count, 4);
+        assert_eq!(result.pulled_bytes, page_bytes);
+        assert_eq!(
+            ports.transfer_stats.lock().await.as_slice(),
+            [(0, page_bytes, 4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_sync_cycle_applies_large_pages_in_chunks() {
+        let identity = SyncIdentity {
+            device_id: Some("019cb093-06a8-7534-8677-546317b17957".to_string()),
+            root_key: Some("root-key".to_string()),
+            key_version: Some(1),
+            ..Default::default()
+        };
+        let mut ports = TestPorts::new(Some(identity), Ok(SyncState::Ready));
+        ports.reconcile_response = crate::ReconcileReadyStateResponse {
+            action: "PULL_TAIL".to_string(),
+            cursor: Some(5),
+            latest_snapshot: None,
+        };
+        ports.replay_chunk_size = 2;
+        // Events within a page need not arrive in seq order.
+        let mut page = pull_page(1..=5, false);
+        page.events.swap(0, 1);
+        ports.pull_pages.lock().await.push_back(page);
+
+        let result = run_sync_cycle(&ports, false)
+            .await
+            .expect("cycle should complete");
+
+        assert_eq!(result.status, "ok");
+        assert_eq!(result.cursor, 5);
+        assert_eq!(
+            ports.applied_chunks.lock().await.as_slice(),
+            [vec![2, 1], vec![3, 4], vec![5]]
+        );
+        // Each chunk commits with the highest seq it holds as its cursor;
+        // none is set on its own.
+        assert_eq!(ports.committed_cursors.lock().await.as_slice(), [2, 4, 5]);
+        assert!(ports.stored_cursors.lock().await.is_empty());
+    }
+
     #[derive(Clone)]
     struct ReconcileTestPorts {
         sync_state: Result<SyncState, String>,
@@ -1639,6 +2572,8 @@ mod tests {
             bootstrap_snapshot_id: None,
             bootstrap_snapshot_seq: None,
             dead_letter_count: 0,
+            pushed_bytes: 0,
+            pulled_bytes: 0,
         });
 
         let result = run_ready_reconcile_state(&ports).await;
@@ -1677,6 +2612,8 @@ mod tests {
                 bootstrap_snapshot_id: None,
                 bootstrap_snapshot_seq: None,
                 dead_letter_count: 0,
+                pushed_bytes: 0,
+                pulled_bytes: 0,
             });
             cycle_results.push(SyncCycleResult {
                 status: "stale_cursor".to_string(),
@@ -1688,6 +2625,8 @@ mod tests {
                 bootstrap_snapshot_id: None,
                 bootstrap_snapshot_seq: None,
                 dead_letter_count: 0,
+                pushed_bytes: 0,
+                pulled_bytes: 0,
             });
         }
 
@@ -1724,6 +2663,8 @@ mod tests {
             bootstrap_snapshot_id: None,
             bootstrap_snapshot_seq: None,
             dead_letter_count: 0,
+            pushed_bytes: 0,
+            pulled_bytes: 0,
         });
 
         let result = run_ready_reconcile_state(&ports).await;
@@ -1763,6 +2704,8 @@ mod tests {
                 bootstrap_snapshot_id: None,
                 bootstrap_snapshot_seq: None,
                 dead_letter_count: 0,
+                pushed_bytes: 0,
+                pulled_bytes: 0,
             });
             cycle_results.push(SyncCycleResult {
                 status: "stale_cursor".to_string(),
@@ -1774,6 +2717,8 @@ mod tests {
                 bootstrap_snapshot_id: None,
                 bootstrap_snapshot_seq: None,
                 dead_letter_count: 0,
+                pushed_bytes: 0,
+                pulled_bytes: 0,
             });
         }
 
@@ -7,12 +7,48 @@ use crate::{
     SyncPushRequest, SyncPushResponse, SyncState,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Limits on how much the background engine transfers, for metered
+/// connections. The default is unthrottled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncThrottleConfig {
+    /// Approximate bytes of event payload pushed and pulled in one cycle,
+    /// including snapshots downloaded or uploaded since the previous cycle.
+    /// Checked between batches: once reached, the cycle stops with the cursor
+    /// and outbox consistent and the rest waits for the next cycle.
+    pub max_bytes_per_cycle: Option<usize>,
+    /// Minimum delay between background cycles.
+    pub min_cycle_interval: std::time::Duration,
+}
+
+/// Default for [`ReplayStore::replay_chunk_size`].
+pub const DEFAULT_REPLAY_CHUNK_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncIdentity {
     pub device_id: Option<String>,
     pub root_key: Option<String>,
     pub key_version: Option<i32>,
+    /// Root key replaced by the last key rotation, kept while events
+    /// encrypted under it may still be pulled.
+    #[serde(default)]
+    pub previous_root_key: Option<String>,
+    #[serde(default)]
+    pub previous_key_version: Option<i32>,
+}
+
+impl SyncIdentity {
+    /// Root key for payloads encrypted at `payload_key_version`: the
+    /// previous key for events written before the last rotation, otherwise
+    /// the current one.
+    pub fn root_key_for_version(&self, payload_key_version: i32) -> Option<&str> {
+        match (&self.previous_root_key, self.previous_key_version) {
+            (Some(previous), Some(version)) if version.max(1) == payload_key_version.max(1) => {
+                Some(previous)
+            }
+            _ => self.root_key.as_deref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +67,41 @@ pub struct SyncCycleResult {
     /// Number of outbox events dead-lettered during this cycle (e.g. key version mismatch).
     #[serde(default)]
     pub dead_letter_count: usize,
+    /// Encrypted event payload bytes pushed during this cycle.
+    #[serde(default)]
+    pub pushed_bytes: usize,
+    /// Event payload bytes pulled during this cycle. `pulled_count` is the
+    /// number of those events applied locally.
+    #[serde(default)]
+    pub pulled_bytes: usize,
+}
+
+/// One pulled event as seen by a diagnostic pull, which decrypts but never
+/// applies it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PulledEventSummary {
+    pub event_id: String,
+    pub entity: SyncEntity,
+    /// `None` for event types that do not map to an operation, such as
+    /// snapshot control events.
+    pub op: Option<SyncOperation>,
+    pub seq: i64,
+    pub payload_key_version: i32,
+    pub decrypt_ok: bool,
+    #[serde(default)]
+    pub decrypt_error: Option<String>,
+}
+
+/// A window of the remote event log, summarized without applying it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullWindowSummary {
+    pub from: i64,
+    pub to: i64,
+    pub next_cursor: i64,
+    pub has_more: bool,
+    pub events: Vec<PulledEventSummary>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +137,10 @@ pub struct ReplayEvent {
     pub client_timestamp: String,
     pub seq: i64,
     pub payload: serde_json::Value,
+    /// Device that emitted the event and its per-device sequence, when the
+    /// server echoed one.
+    pub device_id: String,
+    pub device_seq: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -103,6 +178,9 @@ pub trait OutboxStore: Send + Sync {
     ) -> Result<(), String>;
     async fn mark_push_completed(&self) -> Result<(), String>;
     async fn has_pending_outbox(&self) -> Result<bool, String>;
+    /// Mark pending events that a newer pending event replaces as superseded.
+    /// Returns how many were superseded.
+    async fn compact_pending_outbox(&self) -> Result<usize, String>;
 }
 
 #[async_trait]
@@ -111,9 +189,13 @@ pub trait ReplayStore: Send + Sync {
     async fn verify_cycle_lock(&self, lock_version: i64) -> Result<bool, String>;
     async fn get_cursor(&self) -> Result<i64, String>;
     async fn set_cursor(&self, cursor: i64) -> Result<(), String>;
-    async fn apply_remote_events_lww_batch(
+    /// Apply a chunk of pulled events and move the cursor to `new_cursor` in
+    /// the same transaction, so a crash cannot leave the cursor behind the
+    /// applied events.
+    async fn apply_remote_events_lww_batch_with_cursor(
         &self,
         events: Vec<ReplayEvent>,
+        new_cursor: i64,
     ) -> Result<usize, String>;
     async fn apply_remote_event_lww(&self, event: ReplayEvent) -> Result<bool, String>;
     async fn mark_pull_completed(&self) -> Result<(), String>;
@@ -123,14 +205,38 @@ pub trait ReplayStore: Send + Sync {
         duration_ms: i64,
         next_retry_at: Option<String>,
     ) -> Result<(), String>;
+    /// Persist how much data the cycle moved, for the engine status.
+    async fn mark_cycle_transfer_stats(
+        &self,
+        pushed_bytes: usize,
+        pulled_bytes: usize,
+        events_applied: usize,
+    ) -> Result<(), String>;
     async fn mark_engine_error(&self, message: String) -> Result<(), String>;
+    /// Record a pulled event the engine skipped without applying, e.g.
+    /// because its payload could not be decrypted.
+    async fn record_skipped_event(
+        &self,
+        entity: SyncEntity,
+        entity_id: String,
+        event_id: String,
+        reason: String,
+    ) -> Result<(), String>;
     async fn prune_applied_events_up_to_seq(&self, seq: i64) -> Result<(), String>;
+    /// Drop applied-event records older than the cutoff. Implementations must
+    /// keep events at or above the current cursor.
+    async fn prune_applied_events_older_than(&self, cutoff_rfc3339: String) -> Result<(), String>;
     async fn get_engine_status(&self) -> Result<SyncEngineStatus, String>;
     /// Called after a sync cycle completes with pulled changes.
     /// Implementations can use this to trigger portfolio recalculation.
     async fn on_pull_complete(&self, _pulled_count: usize) -> Result<(), String> {
         Ok(())
     }
+    /// Maximum number of pulled events applied in one transaction. Larger
+    /// pages are split, and each chunk commits together with its cursor.
+    fn replay_chunk_size(&self) -> usize {
+        DEFAULT_REPLAY_CHUNK_SIZE
+    }
 }
 
 #[async_trait]
@@ -153,6 +259,20 @@ pub trait SyncTransport: Send + Sync {
         from_cursor: Option<i64>,
         limit: Option<i64>,
     ) -> Result<SyncPullResponse, TransportError>;
+    /// Approximate bytes of event payloads one cycle may pull before it stops
+    /// and leaves the rest for the next cycle. `None` pulls until caught up.
+    fn pull_byte_budget(&self) -> Option<usize> {
+        None
+    }
+    /// Bandwidth limits applied to background cycles.
+    fn sync_throttle(&self) -> SyncThrottleConfig {
+        SyncThrottleConfig::default()
+    }
+    /// Snapshot bytes transferred since the last call, charged to the next
+    /// cycle's `max_bytes_per_cycle`.
+    fn take_snapshot_transfer_bytes(&self) -> usize {
+        0
+    }
     async fn get_reconcile_ready_state(
         &self,
         token: &str,
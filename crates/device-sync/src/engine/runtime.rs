@@ -1,12 +1,12 @@
 use std::collections::HashMap;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 
 use super::{
     run_background_loop, run_sync_cycle, CredentialStore, OutboxStore, ReplayStore,
-    SyncCycleResult, SyncTransport,
+    SyncCycleResult, SyncThrottleConfig, SyncTransport,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -56,7 +56,10 @@ pub struct DeviceSyncRuntimeState {
     cycle_mutex: Mutex<()>,
     background_task: Mutex<Option<JoinHandle<()>>>,
     pub snapshot_upload_cancelled: AtomicBool,
+    snapshot_generation_in_progress: Arc<AtomicBool>,
     pairing_flows: std::sync::Mutex<HashMap<String, PairingFlowState>>,
+    throttle: std::sync::Mutex<SyncThrottleConfig>,
+    snapshot_transfer_bytes: AtomicUsize,
 }
 
 impl DeviceSyncRuntimeState {
@@ -65,9 +68,62 @@ impl DeviceSyncRuntimeState {
             cycle_mutex: Mutex::new(()),
             background_task: Mutex::new(None),
             snapshot_upload_cancelled: AtomicBool::new(false),
+            snapshot_generation_in_progress: Arc::new(AtomicBool::new(false)),
             pairing_flows: std::sync::Mutex::new(HashMap::new()),
+            throttle: std::sync::Mutex::new(SyncThrottleConfig::default()),
+            snapshot_transfer_bytes: AtomicUsize::new(0),
         }
     }
+
+    /// Bandwidth limits for background cycles.
+    pub fn sync_throttle(&self) -> SyncThrottleConfig {
+        *self.throttle.lock().unwrap()
+    }
+
+    /// Replace the bandwidth limits. Takes effect from the next cycle.
+    pub fn set_sync_throttle(&self, config: SyncThrottleConfig) {
+        *self.throttle.lock().unwrap() = config;
+    }
+
+    /// Record a snapshot download or upload so the next cycle's byte cap
+    /// accounts for it.
+    pub fn record_snapshot_transfer(&self, bytes: usize) {
+        self.snapshot_transfer_bytes
+            .fetch_add(bytes, Ordering::AcqRel);
+    }
+
+    /// Snapshot bytes recorded since the last call.
+    pub fn take_snapshot_transfer_bytes(&self) -> usize {
+        self.snapshot_transfer_bytes.swap(0, Ordering::AcqRel)
+    }
+
+    /// Claim the snapshot generator, or `None` if another snapshot is being
+    /// generated. The claim is released when the guard is dropped.
+    pub fn try_begin_snapshot_generation(&self) -> Option<SnapshotGenerationGuard> {
+        self.snapshot_generation_in_progress
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| SnapshotGenerationGuard {
+                in_progress: Arc::clone(&self.snapshot_generation_in_progress),
+            })
+    }
+
+    pub fn is_snapshot_generation_in_progress(&self) -> bool {
+        self.snapshot_generation_in_progress.load(Ordering::Acquire)
+    }
+}
+
+/// Exclusive claim on snapshot generation, from
+/// [`DeviceSyncRuntimeState::try_begin_snapshot_generation`].
+#[derive(Debug)]
+pub struct SnapshotGenerationGuard {
+    in_progress: Arc<AtomicBool>,
+}
+
+impl Drop for SnapshotGenerationGuard {
+    fn drop(&mut self) {
+        self.in_progress.store(false, Ordering::Release);
+    }
 }
 
 impl Default for DeviceSyncRuntimeState {
@@ -150,3 +206,46 @@ impl DeviceSyncRuntimeState {
         flows.remove(flow_id);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_snapshot_generation_short_circuits_until_first_finishes() {
+        let runtime = DeviceSyncRuntimeState::new();
+
+        let first = runtime
+            .try_begin_snapshot_generation()
+            .expect("first generation starts");
+        assert!(runtime.is_snapshot_generation_in_progress());
+        assert!(runtime.try_begin_snapshot_generation().is_none());
+
+        drop(first);
+        assert!(!runtime.is_snapshot_generation_in_progress());
+        assert!(runtime.try_begin_snapshot_generation().is_some());
+    }
+
+    #[tokio::test]
+    async fn concurrent_snapshot_generations_run_once() {
+        let runtime = Arc::new(DeviceSyncRuntimeState::new());
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+
+        let first = tokio::spawn({
+            let runtime = Arc::clone(&runtime);
+            async move {
+                let _guard = runtime.try_begin_snapshot_generation()?;
+                let _ = released.await;
+                Some(())
+            }
+        });
+        while !runtime.is_snapshot_generation_in_progress() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(runtime.try_begin_snapshot_generation().is_none());
+        release.send(()).unwrap();
+        assert!(first.await.unwrap().is_some());
+        assert!(runtime.try_begin_snapshot_generation().is_some());
+    }
+}
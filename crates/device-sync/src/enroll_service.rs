@@ -21,6 +21,8 @@ use crate::{
 // ─────────────────────────────────────────────────────────────────────────────
 
 const SYNC_IDENTITY_KEY: &str = "sync_identity";
+/// Secret store key holding the id of the team whose sync identity is active.
+pub const ACTIVE_SYNC_TEAM_KEY: &str = "sync_active_team";
 const RESET_REASON_REINITIALIZE: &str = "reinitialize";
 
 static ENROLL_OPERATION_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
@@ -29,6 +31,52 @@ fn enroll_operation_lock() -> &'static Mutex<()> {
     ENROLL_OPERATION_LOCK.get_or_init(|| Mutex::new(()))
 }
 
+/// Secret store key for the sync identity of `team_id`. Without a team the
+/// unscoped key is used, so identities saved before team scoping still load.
+pub fn sync_identity_key(team_id: Option<&str>) -> String {
+    match team_id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(team_id) => format!("{}:{}", SYNC_IDENTITY_KEY, team_id),
+        None => SYNC_IDENTITY_KEY.to_string(),
+    }
+}
+
+/// Team whose sync identity is active, if one has been selected.
+pub fn active_sync_team(secret_store: &dyn SecretStore) -> Option<String> {
+    secret_store
+        .get_secret(ACTIVE_SYNC_TEAM_KEY)
+        .ok()
+        .flatten()
+        .filter(|team_id| !team_id.trim().is_empty())
+}
+
+/// Whether selecting `team_id` would change the active team.
+pub fn is_active_sync_team_change(secret_store: &dyn SecretStore, team_id: Option<&str>) -> bool {
+    let team_id = team_id.map(str::trim).filter(|id| !id.is_empty());
+    active_sync_team(secret_store).as_deref().map(str::trim) != team_id
+}
+
+/// Select the team whose sync identity is loaded. `None` goes back to the
+/// unscoped identity. Returns whether the active team changed; the local
+/// sync state (cursor, outbox, entity metadata) belongs to the previous team
+/// and must be reset before syncing again when it did.
+pub fn set_active_sync_team(
+    secret_store: &dyn SecretStore,
+    team_id: Option<&str>,
+) -> wealthfolio_core::errors::Result<bool> {
+    let changed = is_active_sync_team_change(secret_store, team_id);
+    let team_id = team_id.map(str::trim).filter(|id| !id.is_empty());
+    match team_id {
+        Some(team_id) => secret_store.set_secret(ACTIVE_SYNC_TEAM_KEY, team_id)?,
+        None => secret_store.delete_secret(ACTIVE_SYNC_TEAM_KEY)?,
+    }
+    Ok(changed)
+}
+
+/// Secret store key of the active team's sync identity.
+pub fn active_sync_identity_key(secret_store: &dyn SecretStore) -> String {
+    sync_identity_key(active_sync_team(secret_store).as_deref())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Types
 // ─────────────────────────────────────────────────────────────────────────────
@@ -44,10 +92,30 @@ pub struct SyncIdentity {
     pub device_id: Option<String>,
     pub root_key: Option<String>,
     pub key_version: Option<i32>,
+    /// Root key replaced by the last key rotation, kept while events
+    /// encrypted under it may still be pulled.
+    #[serde(default)]
+    pub previous_root_key: Option<String>,
+    #[serde(default)]
+    pub previous_key_version: Option<i32>,
     pub device_secret_key: Option<String>,
     pub device_public_key: Option<String>,
 }
 
+impl SyncIdentity {
+    /// Install a new root key, keeping the one it replaces as the previous
+    /// key when the version changes so events encrypted under it still
+    /// decrypt.
+    pub fn set_root_key(&mut self, root_key: String, key_version: i32) {
+        if self.root_key.is_some() && self.key_version != Some(key_version) {
+            self.previous_root_key = self.root_key.take();
+            self.previous_key_version = self.key_version;
+        }
+        self.root_key = Some(root_key);
+        self.key_version = Some(key_version);
+    }
+}
+
 /// Current sync state
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -338,6 +406,7 @@ impl DeviceEnrollService {
                     platform,
                     os_version: None,
                     app_version: self.app_version.clone(),
+                    metadata: None,
                 },
             )
             .await
@@ -531,8 +600,7 @@ impl DeviceEnrollService {
 
         // Save E2EE credentials to secret store
         let mut identity = self.read_identity()?;
-        identity.root_key = Some(root_key);
-        identity.key_version = Some(key_version);
+        identity.set_root_key(root_key, key_version);
         identity.device_secret_key = Some(device_keypair.secret_key);
         identity.device_public_key = Some(device_keypair.public_key);
         self.save_identity(&identity)?;
@@ -549,7 +617,8 @@ impl DeviceEnrollService {
     // ═══════════════════════════════════════════════════════════════════════════
 
     fn read_identity(&self) -> Result<SyncIdentity, EnrollServiceError> {
-        match self.secret_store.get_secret(SYNC_IDENTITY_KEY) {
+        let key = active_sync_identity_key(self.secret_store.as_ref());
+        match self.secret_store.get_secret(&key) {
             Ok(Some(json)) => {
                 debug!("[DeviceEnrollService] Read identity from secret store");
                 serde_json::from_str(&json)
@@ -566,8 +635,9 @@ impl DeviceEnrollService {
     fn save_identity(&self, identity: &SyncIdentity) -> Result<(), EnrollServiceError> {
         let json = serde_json::to_string(identity)
             .map_err(|e| format!("Failed to serialize identity: {}", e))?;
+        let key = active_sync_identity_key(self.secret_store.as_ref());
         self.secret_store
-            .set_secret(SYNC_IDENTITY_KEY, &json)
+            .set_secret(&key, &json)
             .map_err(|e| format!("Failed to save identity: {}", e).into())
     }
 
@@ -791,3 +861,125 @@ impl DeviceEnrollService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemorySecretStore {
+        secrets: std::sync::Mutex<HashMap<String, String>>,
+    }
+
+    impl SecretStore for MemorySecretStore {
+        fn set_secret(&self, service: &str, secret: &str) -> wealthfolio_core::errors::Result<()> {
+            self.secrets
+                .lock()
+                .unwrap()
+                .insert(service.to_string(), secret.to_string());
+            Ok(())
+        }
+
+        fn get_secret(&self, service: &str) -> wealthfolio_core::errors::Result<Option<String>> {
+            Ok(self.secrets.lock().unwrap().get(service).cloned())
+        }
+
+        fn delete_secret(&self, service: &str) -> wealthfolio_core::errors::Result<()> {
+            self.secrets.lock().unwrap().remove(service);
+            Ok(())
+        }
+    }
+
+    fn identity_json(device_id: &str) -> String {
+        serde_json::to_string(&SyncIdentity {
+            version: 2,
+            device_id: Some(device_id.to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn identity_keys_are_namespaced_by_team() {
+        assert_eq!(sync_identity_key(None), "sync_identity");
+        assert_eq!(sync_identity_key(Some("  ")), "sync_identity");
+        assert_eq!(sync_identity_key(Some("team-a")), "sync_identity:team-a");
+        assert_ne!(
+            sync_identity_key(Some("team-a")),
+            sync_identity_key(Some("team-b"))
+        );
+    }
+
+    #[test]
+    fn active_team_selects_which_identity_is_loaded() {
+        let store = Arc::new(MemorySecretStore::default());
+        store
+            .set_secret(&sync_identity_key(None), &identity_json("device-legacy"))
+            .unwrap();
+        store
+            .set_secret(
+                &sync_identity_key(Some("team-a")),
+                &identity_json("device-a"),
+            )
+            .unwrap();
+        store
+            .set_secret(
+                &sync_identity_key(Some("team-b")),
+                &identity_json("device-b"),
+            )
+            .unwrap();
+        let service = DeviceEnrollService::new(
+            store.clone(),
+            "http://localhost",
+            "Test device".to_string(),
+            None,
+        );
+        let device_id = || service.read_identity().unwrap().device_id;
+
+        assert_eq!(device_id().as_deref(), Some("device-legacy"));
+        set_active_sync_team(store.as_ref(), Some("team-a")).unwrap();
+        assert_eq!(device_id().as_deref(), Some("device-a"));
+        set_active_sync_team(store.as_ref(), Some("team-b")).unwrap();
+        assert_eq!(device_id().as_deref(), Some("device-b"));
+
+        // Saving only touches the active team's identity.
+        service.clear_sync_data().unwrap();
+        assert_eq!(device_id(), None);
+        set_active_sync_team(store.as_ref(), Some("team-a")).unwrap();
+        assert_eq!(device_id().as_deref(), Some("device-a"));
+
+        set_active_sync_team(store.as_ref(), None).unwrap();
+        assert_eq!(active_sync_team(store.as_ref()), None);
+        assert_eq!(device_id().as_deref(), Some("device-legacy"));
+    }
+
+    #[test]
+    fn rotating_the_root_key_keeps_the_previous_one() {
+        let mut identity = SyncIdentity::default();
+        identity.set_root_key("key-1".to_string(), 1);
+        assert_eq!(identity.previous_root_key, None);
+
+        identity.set_root_key("key-2".to_string(), 2);
+        assert_eq!(identity.root_key.as_deref(), Some("key-2"));
+        assert_eq!(identity.key_version, Some(2));
+        assert_eq!(identity.previous_root_key.as_deref(), Some("key-1"));
+        assert_eq!(identity.previous_key_version, Some(1));
+
+        // Re-saving the same version does not clobber the previous key.
+        identity.set_root_key("key-2".to_string(), 2);
+        assert_eq!(identity.previous_root_key.as_deref(), Some("key-1"));
+    }
+
+    #[test]
+    fn set_active_team_reports_whether_the_team_changed() {
+        let store = MemorySecretStore::default();
+
+        assert!(!set_active_sync_team(&store, None).unwrap());
+        assert!(set_active_sync_team(&store, Some("team-a")).unwrap());
+        assert!(!set_active_sync_team(&store, Some(" team-a ")).unwrap());
+        assert!(set_active_sync_team(&store, Some("team-b")).unwrap());
+        assert!(set_active_sync_team(&store, Some("")).unwrap());
+        assert_eq!(active_sync_team(&store), None);
+    }
+}
@@ -1,5 +1,7 @@
 //! Error types for the device sync crate.
 
+use std::time::Duration;
+
 use thiserror::Error;
 
 /// Result type alias for device sync operations.
@@ -22,6 +24,13 @@ pub const SYNC_SEGMENT_STREAM_MISMATCH: &str = "SYNC_SEGMENT_STREAM_MISMATCH";
 pub const SYNC_EVENT_INDEX_MISMATCH: &str = "SYNC_EVENT_INDEX_MISMATCH";
 pub const SYNC_SNAPSHOT_OBJECT_MISSING: &str = "SYNC_SNAPSHOT_OBJECT_MISSING";
 pub const SYNC_SNAPSHOT_CHECKSUM_MISMATCH: &str = "SYNC_SNAPSHOT_CHECKSUM_MISMATCH";
+pub const SYNC_KEY_VERSION_MISMATCH: &str = "SYNC_KEY_VERSION_MISMATCH";
+
+/// Returns true when the given code says pushed events used a stale or
+/// future payload key version.
+pub fn is_key_version_mismatch_code(code: &str) -> bool {
+    matches!(code, SYNC_KEY_VERSION_MISMATCH | "KEY_VERSION_MISMATCH")
+}
 
 /// Returns true when the given code indicates an integrity problem.
 pub fn is_integrity_code(code: &str) -> bool {
@@ -64,6 +73,18 @@ pub enum DeviceSyncError {
     /// Authentication error (missing or invalid token)
     #[error("Authentication error: {0}")]
     Auth(String),
+
+    /// A retried request gave up after using all of its attempts
+    #[error("{operation} failed after {attempts} attempts in {total_elapsed:?}: {last_error}")]
+    RetriesExhausted {
+        operation: &'static str,
+        attempts: usize,
+        total_elapsed: Duration,
+        /// HTTP status of the last attempt, if it got a response
+        last_status: Option<u16>,
+        #[source]
+        last_error: Box<DeviceSyncError>,
+    },
 }
 
 impl DeviceSyncError {
@@ -102,10 +123,27 @@ impl DeviceSyncError {
         Self::Auth(message.into())
     }
 
+    /// Wrap the last error of a request that used up its retry budget
+    pub fn retries_exhausted(
+        operation: &'static str,
+        attempts: usize,
+        total_elapsed: Duration,
+        last_error: DeviceSyncError,
+    ) -> Self {
+        Self::RetriesExhausted {
+            operation,
+            attempts,
+            total_elapsed,
+            last_status: last_error.status_code(),
+            last_error: Box::new(last_error),
+        }
+    }
+
     /// HTTP status if this is an API error.
     pub fn status_code(&self) -> Option<u16> {
         match self {
             Self::Api { status, .. } => Some(*status),
+            Self::RetriesExhausted { last_status, .. } => *last_status,
             _ => None,
         }
     }
@@ -114,6 +152,7 @@ impl DeviceSyncError {
     pub fn error_code(&self) -> Option<&str> {
         match self {
             Self::Api { code, .. } if !code.is_empty() => Some(code.as_str()),
+            Self::RetriesExhausted { last_error, .. } => last_error.error_code(),
             _ => None,
         }
     }
@@ -141,6 +180,10 @@ impl DeviceSyncError {
     }
 
     /// Classify error for retry policy.
+    ///
+    /// Auth failures need a fresh token; timeouts, throttling, 5xx, transient
+    /// conflict/lock statuses and transport errors are retried; any other
+    /// client error is permanent.
     pub fn retry_class(&self) -> ApiRetryClass {
         match self {
             Self::Api { status, .. } => match *status {
@@ -153,6 +196,7 @@ impl DeviceSyncError {
             Self::Json(_) => ApiRetryClass::Permanent,
             Self::InvalidRequest(_) => ApiRetryClass::Permanent,
             Self::Auth(_) => ApiRetryClass::ReauthRequired,
+            Self::RetriesExhausted { last_error, .. } => last_error.retry_class(),
         }
     }
 
@@ -195,6 +239,55 @@ mod tests {
         assert_eq!(err.retry_class(), ApiRetryClass::ReauthRequired);
     }
 
+    #[test]
+    fn retry_class_maps_statuses_and_error_kinds() {
+        for status in [401, 403] {
+            assert_eq!(
+                DeviceSyncError::api(status, "auth").retry_class(),
+                ApiRetryClass::ReauthRequired,
+                "status {status}"
+            );
+        }
+        for status in [408, 429, 500, 502, 503, 599] {
+            assert_eq!(
+                DeviceSyncError::api(status, "transient").retry_class(),
+                ApiRetryClass::Retryable,
+                "status {status}"
+            );
+        }
+        for status in [400, 404, 410, 413, 422] {
+            assert_eq!(
+                DeviceSyncError::api(status, "rejected").retry_class(),
+                ApiRetryClass::Permanent,
+                "status {status}"
+            );
+        }
+
+        let transport = reqwest::Client::new()
+            .get("http://[::1")
+            .build()
+            .expect_err("malformed URL");
+        assert_eq!(
+            DeviceSyncError::Http(transport).retry_class(),
+            ApiRetryClass::Retryable
+        );
+        assert_eq!(
+            DeviceSyncError::auth("missing token").retry_class(),
+            ApiRetryClass::ReauthRequired
+        );
+        assert_eq!(
+            DeviceSyncError::invalid_request("bad input").retry_class(),
+            ApiRetryClass::Permanent
+        );
+    }
+
+    #[test]
+    fn key_version_mismatch_codes_detected() {
+        assert!(is_key_version_mismatch_code(SYNC_KEY_VERSION_MISMATCH));
+        assert!(is_key_version_mismatch_code("KEY_VERSION_MISMATCH"));
+        assert!(!is_key_version_mismatch_code(SYNC_CURSOR_TOO_OLD));
+    }
+
     #[test]
     fn stale_cursor_detected() {
         let err = DeviceSyncError::api_structured(409, SYNC_CURSOR_TOO_OLD, "Cursor too old", None);
@@ -17,6 +17,7 @@
 //!         platform: "mac".to_string(),
 //!         os_version: Some("15.2".to_string()),
 //!         app_version: Some("3.0.0".to_string()),
+//!         metadata: None,
 //!     },
 //! ).await?;
 //!
@@ -39,14 +40,21 @@ pub mod crypto;
 pub mod engine;
 mod enroll_service;
 mod error;
+mod pairing;
+mod snapshot_encoding;
 mod time;
 mod types;
 
-pub use client::DeviceSyncClient;
+pub use client::{DeviceSyncClient, RetryConfig, SNAPSHOT_UPLOAD_PART_SIZE};
 pub use enroll_service::{
-    DeviceEnrollService, EnableSyncResult, EnrollServiceError, SyncIdentity, SyncState,
-    SyncStateResult,
+    active_sync_identity_key, active_sync_team, is_active_sync_team_change, set_active_sync_team,
+    sync_identity_key, DeviceEnrollService, EnableSyncResult, EnrollServiceError, SyncIdentity,
+    SyncState, SyncStateResult, ACTIVE_SYNC_TEAM_KEY,
 };
 pub use error::{ApiRetryClass, DeviceSyncError, Result};
+pub use pairing::{PairingState, KEY_BUNDLE_PAYLOAD_TYPE};
+pub use snapshot_encoding::{
+    SnapshotEncoding, SNAPSHOT_COMPRESSION_SETTING_KEY, SNAPSHOT_ENCODING_HEADER,
+};
 pub use time::{normalize_sync_datetime, parse_sync_datetime_to_utc};
 pub use types::*;
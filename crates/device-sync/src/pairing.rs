@@ -0,0 +1,192 @@
+//! Pairing-flow state derived from server pairing responses.
+//!
+//! The pairing commands are individual calls; `PairingState` collapses the
+//! server's session status (and, on the claimer side, the pending messages)
+//! into the single step the UI should render next.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::time::parse_sync_datetime_to_utc;
+use crate::types::{GetPairingResponse, PairingMessagesResponse, PairingStatus};
+
+/// Payload type of the issuer's encrypted root-key bundle message.
+pub const KEY_BUNDLE_PAYLOAD_TYPE: &str = "rk_transfer_v1";
+
+/// Current step of a pairing flow.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PairingState {
+    /// Session is open or claimed and the issuer has not approved it yet.
+    AwaitingApproval,
+    /// Issuer approved the session but has not sent the key bundle.
+    AwaitingKeyBundle,
+    /// Claimer has received the key bundle and can confirm.
+    ReadyToConfirm,
+    /// Pairing finished.
+    Completed,
+    /// Either side cancelled the session.
+    Cancelled,
+    /// Session expired before completing.
+    Expired,
+}
+
+impl PairingState {
+    /// State of a session as seen by the issuer.
+    ///
+    /// Sessions still in progress past `expires_at` are reported as expired even
+    /// if the server has not swept them yet.
+    pub fn from_session(session: &GetPairingResponse, now: DateTime<Utc>) -> Self {
+        let state = Self::from_status(&session.status);
+        if state.is_in_progress() && is_past(&session.expires_at, now) {
+            return PairingState::Expired;
+        }
+        state
+    }
+
+    /// State of a session as seen by the claimer, which learns about the key
+    /// bundle through the session messages.
+    pub fn from_messages(response: &PairingMessagesResponse) -> Self {
+        let state = Self::from_status(&response.session_status);
+        if !state.is_in_progress() && state != PairingState::Completed {
+            return state;
+        }
+        let has_key_bundle = response
+            .messages
+            .iter()
+            .any(|message| message.payload_type == KEY_BUNDLE_PAYLOAD_TYPE);
+        if has_key_bundle {
+            PairingState::ReadyToConfirm
+        } else {
+            state
+        }
+    }
+
+    /// Whether the flow can still advance.
+    pub fn is_in_progress(self) -> bool {
+        matches!(
+            self,
+            PairingState::AwaitingApproval
+                | PairingState::AwaitingKeyBundle
+                | PairingState::ReadyToConfirm
+        )
+    }
+
+    fn from_status(status: &PairingStatus) -> Self {
+        match status {
+            PairingStatus::Open | PairingStatus::Claimed => PairingState::AwaitingApproval,
+            PairingStatus::Approved => PairingState::AwaitingKeyBundle,
+            PairingStatus::Completed => PairingState::Completed,
+            PairingStatus::Cancelled => PairingState::Cancelled,
+            PairingStatus::Expired => PairingState::Expired,
+        }
+    }
+}
+
+fn is_past(value: &str, now: DateTime<Utc>) -> bool {
+    parse_sync_datetime_to_utc(value)
+        .map(|expires_at| expires_at <= now)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PairingMessage;
+
+    fn now() -> DateTime<Utc> {
+        parse_sync_datetime_to_utc("2026-03-03T12:00:00Z").unwrap()
+    }
+
+    fn session(status: &str, expires_at: &str) -> GetPairingResponse {
+        serde_json::from_value(serde_json::json!({
+            "pairing_id": "pair-1",
+            "status": status,
+            "claimer_device_id": "device-2",
+            "claimer_ephemeral_pub": "pub",
+            "expires_at": expires_at,
+        }))
+        .unwrap()
+    }
+
+    fn messages(status: &str, payload_types: &[&str]) -> PairingMessagesResponse {
+        PairingMessagesResponse {
+            session_status: serde_json::from_value(serde_json::json!(status)).unwrap(),
+            messages: payload_types
+                .iter()
+                .enumerate()
+                .map(|(index, payload_type)| PairingMessage {
+                    id: format!("msg-{index}"),
+                    payload_type: payload_type.to_string(),
+                    payload: "ciphertext".to_string(),
+                    created_at: "2026-03-03T11:59:00Z".to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn maps_issuer_session_statuses() {
+        let future = "2026-03-03 12:05:00.000000+00";
+        let cases = [
+            ("open", PairingState::AwaitingApproval),
+            ("claimed", PairingState::AwaitingApproval),
+            ("approved", PairingState::AwaitingKeyBundle),
+            ("completed", PairingState::Completed),
+            ("cancelled", PairingState::Cancelled),
+            ("expired", PairingState::Expired),
+        ];
+        for (status, expected) in cases {
+            assert_eq!(
+                PairingState::from_session(&session(status, future), now()),
+                expected,
+                "status {status}"
+            );
+        }
+    }
+
+    #[test]
+    fn in_progress_session_past_expiry_is_expired() {
+        let past = "2026-03-03T11:55:00Z";
+        assert_eq!(
+            PairingState::from_session(&session("claimed", past), now()),
+            PairingState::Expired
+        );
+        assert_eq!(
+            PairingState::from_session(&session("completed", past), now()),
+            PairingState::Completed
+        );
+    }
+
+    #[test]
+    fn maps_claimer_messages() {
+        assert_eq!(
+            PairingState::from_messages(&messages("claimed", &[])),
+            PairingState::AwaitingApproval
+        );
+        assert_eq!(
+            PairingState::from_messages(&messages("approved", &["sas_ack_v1"])),
+            PairingState::AwaitingKeyBundle
+        );
+        assert_eq!(
+            PairingState::from_messages(&messages("approved", &[KEY_BUNDLE_PAYLOAD_TYPE])),
+            PairingState::ReadyToConfirm
+        );
+        assert_eq!(
+            PairingState::from_messages(&messages("completed", &[KEY_BUNDLE_PAYLOAD_TYPE])),
+            PairingState::ReadyToConfirm
+        );
+        assert_eq!(
+            PairingState::from_messages(&messages("completed", &[])),
+            PairingState::Completed
+        );
+        assert_eq!(
+            PairingState::from_messages(&messages("cancelled", &[KEY_BUNDLE_PAYLOAD_TYPE])),
+            PairingState::Cancelled
+        );
+        assert_eq!(
+            PairingState::from_messages(&messages("expired", &[])),
+            PairingState::Expired
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Encoding of the SQLite image inside a snapshot payload.
+//!
+//! The image may be compressed before it is encrypted (ciphertext does not
+//! compress). Compression is opt-in per device. The encoding is sent in the
+//! `X-Snapshot-Encoding` header, but downloaders detect it from the decrypted
+//! bytes, so snapshots decode correctly whatever the header says.
+
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DeviceSyncError, Result};
+
+/// Header carrying the snapshot payload encoding on upload and download.
+pub const SNAPSHOT_ENCODING_HEADER: &str = "x-snapshot-encoding";
+
+/// Settings key that opts this device into gzip-compressed snapshot uploads.
+pub const SNAPSHOT_COMPRESSION_SETTING_KEY: &str = "device_sync_compress_snapshots";
+
+/// Leading bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// How the SQLite image inside a snapshot payload is encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotEncoding {
+    /// Raw SQLite file bytes.
+    #[default]
+    Identity,
+    /// Gzip-compressed SQLite file bytes.
+    Gzip,
+}
+
+impl SnapshotEncoding {
+    pub fn as_header_value(self) -> &'static str {
+        match self {
+            SnapshotEncoding::Identity => "identity",
+            SnapshotEncoding::Gzip => "gzip",
+        }
+    }
+
+    /// Parse an `X-Snapshot-Encoding` header value. A missing or empty header
+    /// means the image is not encoded.
+    pub fn from_header_value(value: Option<&str>) -> Result<Self> {
+        let value = value
+            .map(|v| v.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        match value.as_str() {
+            "" | "identity" | "none" => Ok(SnapshotEncoding::Identity),
+            "gzip" | "x-gzip" => Ok(SnapshotEncoding::Gzip),
+            other => Err(DeviceSyncError::invalid_request(format!(
+                "Unsupported snapshot encoding '{}'",
+                other
+            ))),
+        }
+    }
+
+    /// Encoding for uploads given the stored compression setting: gzip only
+    /// when the device has opted in.
+    pub fn for_upload(compression_setting: Option<&str>) -> Self {
+        match compression_setting.map(str::trim) {
+            Some("true") => SnapshotEncoding::Gzip,
+            _ => SnapshotEncoding::Identity,
+        }
+    }
+
+    /// Detect the encoding of a decrypted snapshot image from its leading
+    /// bytes. SQLite files never start with the gzip magic.
+    pub fn detect(payload: &[u8]) -> Self {
+        if payload.starts_with(&GZIP_MAGIC) {
+            SnapshotEncoding::Gzip
+        } else {
+            SnapshotEncoding::Identity
+        }
+    }
+
+    /// Encode a raw SQLite image.
+    pub fn encode(self, image: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            SnapshotEncoding::Identity => Ok(image.to_vec()),
+            SnapshotEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(image)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|err| {
+                        DeviceSyncError::invalid_request(format!(
+                            "Failed to gzip snapshot image: {}",
+                            err
+                        ))
+                    })
+            }
+        }
+    }
+
+    /// Decode an encoded image back to raw SQLite bytes.
+    pub fn decode(self, payload: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            SnapshotEncoding::Identity => Ok(payload.to_vec()),
+            SnapshotEncoding::Gzip => {
+                let mut decoded = Vec::new();
+                GzDecoder::new(payload)
+                    .read_to_end(&mut decoded)
+                    .map_err(|err| {
+                        DeviceSyncError::invalid_request(format!(
+                            "Failed to decompress gzip snapshot image: {}",
+                            err
+                        ))
+                    })?;
+                Ok(decoded)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gzip_round_trips_and_shrinks_repetitive_images() {
+        let image = b"SQLite format 3\0".repeat(512);
+        let encoded = SnapshotEncoding::Gzip.encode(&image).expect("encode");
+        assert!(encoded.len() < image.len());
+        assert_eq!(
+            SnapshotEncoding::Gzip.decode(&encoded).expect("decode"),
+            image
+        );
+        assert_eq!(
+            SnapshotEncoding::Identity.encode(&image).expect("encode"),
+            image
+        );
+    }
+
+    #[test]
+    fn detects_gzip_from_magic_bytes() {
+        let image = b"SQLite format 3\0".repeat(4);
+        let encoded = SnapshotEncoding::Gzip.encode(&image).expect("encode");
+        assert_eq!(SnapshotEncoding::detect(&encoded), SnapshotEncoding::Gzip);
+        assert_eq!(SnapshotEncoding::detect(&image), SnapshotEncoding::Identity);
+        assert_eq!(SnapshotEncoding::detect(&[]), SnapshotEncoding::Identity);
+    }
+
+    #[test]
+    fn compression_is_opt_in() {
+        assert_eq!(
+            SnapshotEncoding::for_upload(None),
+            SnapshotEncoding::Identity
+        );
+        assert_eq!(
+            SnapshotEncoding::for_upload(Some("false")),
+            SnapshotEncoding::Identity
+        );
+        assert_eq!(
+            SnapshotEncoding::for_upload(Some("true")),
+            SnapshotEncoding::Gzip
+        );
+    }
+
+    #[test]
+    fn parses_header_values() {
+        assert_eq!(
+            SnapshotEncoding::from_header_value(None).unwrap(),
+            SnapshotEncoding::Identity
+        );
+        assert_eq!(
+            SnapshotEncoding::from_header_value(Some(" GZIP ")).unwrap(),
+            SnapshotEncoding::Gzip
+        );
+        assert!(SnapshotEncoding::from_header_value(Some("br")).is_err());
+        assert!(SnapshotEncoding::Gzip.decode(b"not gzip").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! Types for device sync API requests and responses.
 
 use serde::{Deserialize, Serialize};
+
+use crate::snapshot_encoding::SnapshotEncoding;
 use std::collections::HashMap;
 
 // Re-export the canonical SyncEntity from core to avoid duplication.
@@ -102,6 +104,30 @@ pub struct RegisterDeviceRequest {
     /// App version (e.g., "3.0.0")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub app_version: Option<String>,
+    /// Extra details that help tell devices apart (e.g. `hostname`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Enrollment metadata key holding the machine's hostname.
+pub const DEVICE_METADATA_HOSTNAME: &str = "hostname";
+
+impl RegisterDeviceRequest {
+    /// Record `hostname` in the enrollment metadata, and use it as the display
+    /// name when none was given. A missing or blank hostname changes nothing.
+    pub fn with_hostname(mut self, hostname: Option<&str>) -> Self {
+        let Some(hostname) = hostname.map(str::trim).filter(|h| !h.is_empty()) else {
+            return self;
+        };
+        if self.display_name.trim().is_empty() {
+            self.display_name = hostname.to_string();
+        }
+        self.metadata.get_or_insert_with(HashMap::new).insert(
+            DEVICE_METADATA_HOSTNAME.to_string(),
+            serde_json::Value::String(hostname.to_string()),
+        );
+        self
+    }
 }
 
 /// Summary of a trusted device (used in PAIR mode response).
@@ -187,6 +213,52 @@ pub struct Device {
     pub created_at: String,
 }
 
+/// Filters and paging for listing devices. The default lists every device.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceListQuery {
+    /// "my" or "team"
+    pub scope: Option<String>,
+    pub trust_state: Option<TrustState>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+impl DeviceListQuery {
+    /// Query parameters for the devices endpoint, in a stable order.
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(scope) = &self.scope {
+            pairs.push(("scope", scope.clone()));
+        }
+        if let Some(trust_state) = &self.trust_state {
+            let value = match trust_state {
+                TrustState::Untrusted => "untrusted",
+                TrustState::Trusted => "trusted",
+                TrustState::Revoked => "revoked",
+            };
+            pairs.push(("trust_state", value.to_string()));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(("offset", offset.to_string()));
+        }
+        pairs
+    }
+}
+
+/// One page of devices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceListPage {
+    pub devices: Vec<Device>,
+    pub offset: u32,
+    pub limit: Option<u32>,
+    /// Whether another page may follow: the page came back full.
+    pub has_more: bool,
+}
+
 /// Request to update a device.
 /// Note: Uses snake_case for cloud API serialization.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -513,6 +585,10 @@ pub struct SyncPushEventRequest {
     pub client_timestamp: String,
     pub payload: String,
     pub payload_key_version: i32,
+    /// Monotonic per-device emission sequence, used to break same-timestamp
+    /// ties between events from this device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_seq: Option<i64>,
 }
 
 /// Push batch request.
@@ -555,6 +631,8 @@ pub struct SyncEvent {
     pub payload: String,
     #[serde(alias = "payloadKeyVersion")]
     pub payload_key_version: i32,
+    #[serde(default, alias = "deviceSeq")]
+    pub device_seq: Option<i64>,
     pub seq: i64,
     #[serde(alias = "userId")]
     pub user_id: String,
@@ -627,6 +705,9 @@ pub struct SnapshotDownloadHeaders {
     pub schema_version: i32,
     pub covers_tables: Vec<String>,
     pub checksum: String,
+    /// Encoding of the SQLite image inside the decrypted payload.
+    #[serde(default)]
+    pub encoding: SnapshotEncoding,
 }
 
 /// Header metadata required for snapshot upload.
@@ -648,6 +729,22 @@ pub struct SnapshotUploadHeaders {
     /// Oplog seq at which the snapshot was generated.
     #[serde(default, alias = "baseSeq")]
     pub base_seq: Option<i64>,
+    /// Encoding of the SQLite image inside the encrypted payload. Size and
+    /// checksum always describe the uploaded (encoded) bytes.
+    #[serde(default)]
+    pub encoding: SnapshotEncoding,
+}
+
+/// A snapshot upload currently running in this process.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotOpInfo {
+    /// `<device_id>:<event_id>` key that guards against duplicate uploads.
+    pub dedupe_key: String,
+    /// RFC 3339 time the upload started.
+    pub started_at: String,
+    /// Payload bytes delivered to the server so far.
+    pub bytes_sent: u64,
 }
 
 /// Response from the reconcile-ready-state endpoint.
@@ -673,3 +770,45 @@ pub struct SnapshotUploadResponse {
     #[serde(alias = "createdAt")]
     pub created_at: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(display_name: &str) -> RegisterDeviceRequest {
+        RegisterDeviceRequest {
+            device_nonce: "nonce-1".to_string(),
+            display_name: display_name.to_string(),
+            platform: "linux".to_string(),
+            os_version: None,
+            app_version: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn register_request_records_hostname_in_metadata() {
+        let named = request("Work laptop").with_hostname(Some(" build-box-02\n"));
+        assert_eq!(named.display_name, "Work laptop");
+        assert_eq!(
+            named
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get(DEVICE_METADATA_HOSTNAME))
+                .and_then(|v| v.as_str()),
+            Some("build-box-02")
+        );
+
+        let unnamed = request(" ").with_hostname(Some("build-box-02"));
+        assert_eq!(unnamed.display_name, "build-box-02");
+    }
+
+    #[test]
+    fn register_request_without_hostname_has_no_metadata() {
+        let phone = request("Phone").with_hostname(None);
+        assert!(phone.metadata.is_none());
+        assert!(phone.with_hostname(Some("  ")).metadata.is_none());
+        let json = serde_json::to_value(request("Phone")).unwrap();
+        assert!(json.get("metadata").is_none());
+    }
+}
@@ -57,6 +57,7 @@
 
 pub mod errors;
 pub mod models;
+pub(crate) mod pricing;
 pub mod provider;
 pub mod registry;
 pub mod resolver;
@@ -79,16 +80,23 @@ pub use resolver::{
 // Re-export provider types
 pub use provider::alpha_vantage::AlphaVantageProvider;
 pub use provider::boerse_frankfurt::BoerseFrankfurtProvider;
+pub use provider::ecb::EcbYieldCurveProvider;
 pub use provider::finnhub::FinnhubProvider;
 pub use provider::marketdata_app::MarketDataAppProvider;
 pub use provider::metal_price_api::MetalPriceApiProvider;
-pub use provider::openfigi::OpenFigiProvider;
-pub use provider::us_treasury_calc::{TreasuryBondDetails, UsTreasuryCalcProvider};
+pub use provider::openfigi::{IdentifierType, OpenFigiProvider};
+pub use provider::us_treasury_calc::{
+    isin_to_cusip, BillYields, InterpolationMethod, TreasuryBondDetails, UsTreasuryCalcProvider,
+};
 pub use provider::yahoo::{YahooDividend, YahooProvider};
-pub use provider::{MarketDataProvider, ProviderCapabilities, RateLimit};
+pub use provider::{
+    CachingProvider, CircuitBreakingProvider, InstrumentedProvider, MarketDataProvider,
+    MetricsSink, ProviderCapabilities, ProviderChain, ProviderOperation, QuoteCache, RateLimit,
+    RateLimitedDispatcher,
+};
 
 // Re-export registry types
 pub use registry::{
-    CircuitBreaker, CircuitState, FetchDiagnostics, ProviderAttempt, ProviderRegistry,
-    QuoteValidator, RateLimiter, SkipReason, ValidationSeverity,
+    CircuitBreaker, CircuitState, FetchDiagnostics, ProviderAttempt, ProviderDescription,
+    ProviderRegistry, QuoteValidator, RateLimitStatus, RateLimiter, SkipReason, ValidationSeverity,
 };
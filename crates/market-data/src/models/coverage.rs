@@ -22,6 +22,10 @@ pub struct Coverage {
     /// If Some, only Metal instruments quoted in these currencies are supported.
     /// Note: Only applies to Metal instruments; FX/Crypto ignore this.
     pub metal_quote_ccy_allow: Option<&'static [&'static str]>,
+
+    /// If Some, only bonds whose ISIN starts with one of these prefixes are
+    /// supported.
+    pub bond_isin_prefix_allow: Option<&'static [&'static str]>,
 }
 
 /// Linear contains check for static slices.
@@ -65,8 +69,10 @@ impl Coverage {
             // Options: No geographic filtering (OCC symbols are US-only for now)
             InstrumentId::Option { .. } => true,
 
-            // Bonds: No geographic filtering (ISIN-based routing handled by resolver)
-            InstrumentId::Bond { .. } => true,
+            // Bonds: Apply ISIN prefix filter; other routing is handled by the resolver
+            InstrumentId::Bond { isin } => self
+                .bond_isin_prefix_allow
+                .is_none_or(|a| a.iter().any(|prefix| isin.starts_with(prefix))),
         }
     }
 
@@ -77,6 +83,7 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: false,
             metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: None,
         }
     }
 
@@ -87,6 +94,7 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: true,
             metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: None,
         }
     }
 
@@ -97,6 +105,7 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: false,
             metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: None,
         }
     }
 
@@ -107,6 +116,7 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: true,
             metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: None,
         }
     }
 
@@ -117,6 +127,7 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: false,
             metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: None,
         }
     }
 
@@ -127,6 +138,7 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: true,
             metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: None,
         }
     }
 
@@ -137,6 +149,18 @@ impl Coverage {
             equity_mic_deny: None,
             allow_unknown_mic: false,
             metal_quote_ccy_allow: Some(&["USD"]),
+            bond_isin_prefix_allow: None,
+        }
+    }
+
+    /// Bonds whose ISIN starts with one of `prefixes` (e.g. country codes).
+    pub const fn bonds_with_isin_prefixes(prefixes: &'static [&'static str]) -> Self {
+        Self {
+            equity_mic_allow: None,
+            equity_mic_deny: None,
+            allow_unknown_mic: false,
+            metal_quote_ccy_allow: None,
+            bond_isin_prefix_allow: Some(prefixes),
         }
     }
 }
@@ -228,6 +252,20 @@ mod tests {
         assert!(coverage.supports(&inst));
     }
 
+    #[test]
+    fn test_bond_isin_prefix_filter() {
+        let coverage = Coverage::bonds_with_isin_prefixes(&["DE", "FR"]);
+        let bund = InstrumentId::Bond {
+            isin: Arc::from("DE0001102580"),
+        };
+        let treasury = InstrumentId::Bond {
+            isin: Arc::from("US91282CJL54"),
+        };
+        assert!(coverage.supports(&bund));
+        assert!(!coverage.supports(&treasury));
+        assert!(Coverage::global_best_effort().supports(&treasury));
+    }
+
     #[test]
     fn test_coverage_is_const() {
         const _: Coverage = Coverage::us_only_strict();
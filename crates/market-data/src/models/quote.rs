@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use super::instrument::InstrumentId;
 use super::provider_params::ProviderOverrides;
-use super::types::{Currency, ProviderId};
+use super::types::{Currency, Mic, ProviderId};
 
 /// Bond metadata needed for yield-curve-based price calculation.
 #[derive(Clone, Debug)]
@@ -13,10 +13,25 @@ pub struct BondQuoteMetadata {
     pub coupon_rate: Decimal,
     /// Maturity date of the bond
     pub maturity_date: NaiveDate,
-    /// Face/par value of the bond
-    pub face_value: Decimal,
+    /// Face/par value of the bond; providers use their market's usual face
+    /// value when unset
+    pub face_value: Option<Decimal>,
     /// Coupon payment frequency: "SEMI_ANNUAL", "ANNUAL", "QUARTERLY", "ZERO"
     pub coupon_frequency: String,
+    /// Treasury Inflation-Protected Security (priced off the real yield curve)
+    pub is_tips: bool,
+    /// CPI index ratio applied to par; required to price TIPS
+    pub index_ratio: Option<Decimal>,
+    /// Reference CPI on the TIPS dated date; providers that derive the index
+    /// ratio from CPI data divide a settlement date's reference CPI by it
+    pub ref_cpi_on_dated_date: Option<Decimal>,
+    /// First call date for callable bonds
+    pub call_date: Option<NaiveDate>,
+    /// Call price as a fraction of par (defaults to par when a call date is set)
+    pub call_price: Option<Decimal>,
+    /// Currency the bond is denominated in; providers use their market's
+    /// currency when unset
+    pub currency: Option<Currency>,
 }
 
 /// Request context for quote fetching
@@ -36,6 +51,15 @@ pub struct QuoteContext {
 
     /// Bond metadata for yield-curve-based pricing (coupon, maturity, face value)
     pub bond_metadata: Option<BondQuoteMetadata>,
+
+    /// Exchange MIC hint for providers that quote on several venues
+    /// (e.g. XETR instead of XFRA on Boerse Frankfurt)
+    pub mic_hint: Option<Mic>,
+
+    /// Business days from trade to settlement for calculated bond prices.
+    /// `None` uses the provider's market convention (e.g. T+1 for US
+    /// Treasuries, T+2 for euro-area government bonds).
+    pub settlement_offset_days: Option<i64>,
 }
 
 /// Market data quote
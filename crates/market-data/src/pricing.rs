@@ -0,0 +1,922 @@
+//! Yield-curve bond pricing shared by the calculated-price providers.
+//!
+//! A `YieldCurve` holds one day's (tenor, yield) points. Bonds are priced by
+//! interpolating the yield at their remaining maturity (or call date) with
+//! [`interpolate_yield`] and discounting coupon and principal cash flows at
+//! that yield with [`price_from_yield`].
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
+use log::warn;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::MarketDataError;
+use crate::models::{BondQuoteMetadata, Quote};
+
+// ---------------------------------------------------------------------------
+// Yield curve types
+// ---------------------------------------------------------------------------
+
+/// A single day's yield curve: sorted vec of (tenor_years, yield_pct).
+/// Yields are in percent (e.g. 4.25 means 4.25%).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct YieldCurve(pub(crate) Vec<(f64, f64)>);
+
+impl YieldCurve {
+    /// Linearly interpolate the yield for a given maturity in years.
+    pub(crate) fn interpolate(&self, years: f64) -> Option<f64> {
+        let pts = &self.0;
+        if pts.is_empty() {
+            return None;
+        }
+        // Clamp to range
+        if years <= pts[0].0 {
+            return Some(pts[0].1);
+        }
+        if years >= pts[pts.len() - 1].0 {
+            return Some(pts[pts.len() - 1].1);
+        }
+        // Find surrounding points
+        for i in 0..pts.len() - 1 {
+            if pts[i].0 <= years && years <= pts[i + 1].0 {
+                let t = (years - pts[i].0) / (pts[i + 1].0 - pts[i].0);
+                return Some(pts[i].1 + t * (pts[i + 1].1 - pts[i].1));
+            }
+        }
+        None
+    }
+
+    /// Interpolate the yield using a natural cubic spline through the tenor
+    /// points.  Clamps outside the curve range like [`Self::interpolate`] and
+    /// falls back to linear when there are fewer than three points.
+    pub(crate) fn interpolate_cubic(&self, years: f64) -> Option<f64> {
+        let pts = &self.0;
+        let n = pts.len();
+        if n < 3 {
+            return self.interpolate(years);
+        }
+        if years <= pts[0].0 {
+            return Some(pts[0].1);
+        }
+        if years >= pts[n - 1].0 {
+            return Some(pts[n - 1].1);
+        }
+
+        let m = Self::spline_second_derivatives(pts);
+
+        let i = (0..n - 1).find(|&i| pts[i].0 <= years && years <= pts[i + 1].0)?;
+        let (x0, y0) = pts[i];
+        let (x1, y1) = pts[i + 1];
+        let h = x1 - x0;
+        let a = (x1 - years) / h;
+        let b = (years - x0) / h;
+        Some(a * y0 + b * y1 + ((a.powi(3) - a) * m[i] + (b.powi(3) - b) * m[i + 1]) * h * h / 6.0)
+    }
+
+    /// Solve for the spline's second derivatives at each knot with natural
+    /// boundary conditions (zero curvature at both ends).
+    fn spline_second_derivatives(pts: &[(f64, f64)]) -> Vec<f64> {
+        let n = pts.len();
+        let mut m = vec![0.0; n];
+        // Tridiagonal system for the interior knots, solved with the Thomas algorithm.
+        let mut c_prime = vec![0.0; n];
+        let mut d_prime = vec![0.0; n];
+        for i in 1..n - 1 {
+            let h0 = pts[i].0 - pts[i - 1].0;
+            let h1 = pts[i + 1].0 - pts[i].0;
+            let rhs = 6.0 * ((pts[i + 1].1 - pts[i].1) / h1 - (pts[i].1 - pts[i - 1].1) / h0);
+            let diag = 2.0 * (h0 + h1) - h0 * c_prime[i - 1];
+            c_prime[i] = h1 / diag;
+            d_prime[i] = (rhs - h0 * d_prime[i - 1]) / diag;
+        }
+        for i in (1..n - 1).rev() {
+            m[i] = d_prime[i] - c_prime[i] * m[i + 1];
+        }
+        m
+    }
+
+    /// Interpolate the yield using the given method.
+    pub(crate) fn interpolate_with(&self, years: f64, method: InterpolationMethod) -> Option<f64> {
+        match method {
+            InterpolationMethod::Linear => self.interpolate(years),
+            InterpolationMethod::CubicSpline => self.interpolate_cubic(years),
+        }
+    }
+}
+
+/// How yields are interpolated between tenor points on the curve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InterpolationMethod {
+    /// Straight lines between adjacent tenors.
+    #[default]
+    Linear,
+    /// Natural cubic spline through all tenors (smooth par curve).
+    CubicSpline,
+}
+
+/// Interpolate the curve yield (percent) at `years` with `method`.
+pub(crate) fn interpolate_yield(
+    curve: &YieldCurve,
+    years: f64,
+    method: InterpolationMethod,
+) -> Option<f64> {
+    curve.interpolate_with(years, method)
+}
+
+/// Day-count convention used to turn a date range into a year fraction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum DayCount {
+    /// Actual days over 365.25 (average year length, leap years included).
+    #[default]
+    Actual365_25,
+    /// Actual days over a 360-day year (money-market convention).
+    Actual360,
+}
+
+impl DayCount {
+    /// Years between `start` and `end` under this convention.
+    pub(crate) fn year_fraction(self, start: NaiveDate, end: NaiveDate) -> f64 {
+        let days = (end - start).num_days() as f64;
+        match self {
+            DayCount::Actual365_25 => days / 365.25,
+            DayCount::Actual360 => days / 360.0,
+        }
+    }
+}
+
+/// Coupon payments per year for a coupon frequency, or `None` for a
+/// zero-coupon bond. Unknown frequencies are treated as semi-annual.
+pub(crate) fn coupons_per_year(coupon_frequency: &str) -> Option<f64> {
+    match coupon_frequency {
+        "ZERO" => None,
+        "ANNUAL" => Some(1.0),
+        "QUARTERLY" => Some(4.0),
+        _ => Some(2.0),
+    }
+}
+
+/// The date `periods` coupon periods of `months` each before `maturity`.
+/// Month-end maturities keep every coupon on a month end; other days are
+/// clamped to shorter months without drifting later dates.
+fn roll_back(maturity: NaiveDate, months: u32, periods: u32) -> NaiveDate {
+    let date = maturity
+        .checked_sub_months(Months::new(months * periods))
+        .unwrap_or(NaiveDate::MIN);
+    if is_month_end(maturity) {
+        last_day_of_month(date)
+    } else {
+        date
+    }
+}
+
+fn is_month_end(date: NaiveDate) -> bool {
+    date.succ_opt()
+        .is_none_or(|next| next.month() != date.month())
+}
+
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date)
+}
+
+/// Coupon dates after `start` (issue or last coupon date) up to and
+/// including `maturity`, rolled backward from maturity so the schedule
+/// lands on the bond's actual coupon days. Any short stub falls in the
+/// first period after `start`. A zero-coupon bond pays only at maturity.
+pub(crate) fn coupon_schedule(
+    start: NaiveDate,
+    maturity: NaiveDate,
+    coupon_frequency: &str,
+) -> Vec<NaiveDate> {
+    if maturity <= start {
+        return Vec::new();
+    }
+    let Some(freq) = coupons_per_year(coupon_frequency) else {
+        return vec![maturity];
+    };
+    let months = (12.0 / freq) as u32;
+
+    let mut dates: Vec<NaiveDate> = (0..)
+        .map(|periods| roll_back(maturity, months, periods))
+        .take_while(|date| *date > start)
+        .collect();
+    dates.reverse();
+    dates
+}
+
+/// The settlement date `offset_days` business days after `trade_date`.
+/// Weekends are skipped; market holidays are not modelled. A zero or
+/// negative offset settles on the trade date itself.
+pub(crate) fn settlement_date(trade_date: NaiveDate, offset_days: i64) -> NaiveDate {
+    let mut date = trade_date;
+    let mut remaining = offset_days;
+    while remaining > 0 {
+        date = date + Days::new(1);
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+// ---------------------------------------------------------------------------
+// Pricing
+// ---------------------------------------------------------------------------
+
+/// Calculate bond price as fraction of par for a given date, discounting at
+/// the curve yield interpolated at the bond's remaining maturity.
+///
+/// For callable bonds (`call` is the call date and call price as a
+/// fraction of par), the bond is priced to both maturity and call and the
+/// lower price — the one implied by the yield-to-worst — is returned.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn calculate_price(
+    provider: &str,
+    curve: &YieldCurve,
+    interpolation: InterpolationMethod,
+    settlement_date: NaiveDate,
+    maturity_date: NaiveDate,
+    coupon_rate: f64,
+    coupon_frequency: &str,
+    face_value: f64,
+    call: Option<(NaiveDate, f64)>,
+) -> Result<f64, MarketDataError> {
+    let day_count = DayCount::default();
+    let years_to_maturity = day_count.year_fraction(settlement_date, maturity_date);
+
+    if years_to_maturity <= 0.0 {
+        // Bond has matured — return par
+        return Ok(1.0);
+    }
+
+    let yield_pct =
+        interpolate_yield(curve, years_to_maturity, interpolation).ok_or_else(|| {
+            MarketDataError::ProviderError {
+                provider: provider.to_string(),
+                message: "Could not interpolate yield".to_string(),
+            }
+        })?;
+
+    let price_to_maturity = price_from_yield(
+        yield_pct,
+        settlement_date,
+        maturity_date,
+        coupon_rate,
+        coupon_frequency,
+        face_value,
+        1.0,
+        day_count,
+    );
+
+    let Some((call_date, call_price)) =
+        call.filter(|(date, _)| *date > settlement_date && *date < maturity_date)
+    else {
+        return Ok(price_to_maturity);
+    };
+
+    let years_to_call = day_count.year_fraction(settlement_date, call_date);
+    let call_yield_pct =
+        interpolate_yield(curve, years_to_call, interpolation).ok_or_else(|| {
+            MarketDataError::ProviderError {
+                provider: provider.to_string(),
+                message: "Could not interpolate yield to call".to_string(),
+            }
+        })?;
+    let price_to_call = price_from_yield(
+        call_yield_pct,
+        settlement_date,
+        call_date,
+        coupon_rate,
+        coupon_frequency,
+        face_value,
+        call_price,
+        day_count,
+    );
+
+    Ok(price_to_maturity.min(price_to_call))
+}
+
+/// Clean price of a bond yielding `yield_pct` (percent), as a fraction of
+/// par: the [`dirty_price_from_yield`] less the coupon accrued since the
+/// previous coupon date.  Quotes and yield-to-maturity work on clean prices.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn price_from_yield(
+    yield_pct: f64,
+    settlement_date: NaiveDate,
+    maturity_date: NaiveDate,
+    coupon_rate: f64,
+    coupon_frequency: &str,
+    face_value: f64,
+    redemption: f64,
+    day_count: DayCount,
+) -> f64 {
+    dirty_price_from_yield(
+        yield_pct,
+        settlement_date,
+        maturity_date,
+        coupon_rate,
+        coupon_frequency,
+        face_value,
+        redemption,
+        day_count,
+    ) - accrued_interest(
+        settlement_date,
+        maturity_date,
+        coupon_rate,
+        coupon_frequency,
+        day_count,
+    )
+}
+
+/// Discount a bond's cash flows at `yield_pct` (percent), returning the
+/// full (dirty) price as a fraction of par, accrued interest included.
+/// `redemption` is the principal repaid at `maturity_date` as a fraction of
+/// par.
+///
+/// Coupon bonds are discounted per cash flow on their [`coupon_schedule`]:
+/// the next coupon by the fraction of the current period still to run
+/// (measured with `day_count`), each later one by a further whole period.
+///
+/// Zero-coupon bonds use simple money-market discounting on Act/360.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn dirty_price_from_yield(
+    yield_pct: f64,
+    settlement_date: NaiveDate,
+    maturity_date: NaiveDate,
+    coupon_rate: f64,
+    coupon_frequency: &str,
+    face_value: f64,
+    redemption: f64,
+    day_count: DayCount,
+) -> f64 {
+    let yield_dec = yield_pct / 100.0; // e.g. 4.25% → 0.0425
+
+    let freq = match coupons_per_year(coupon_frequency) {
+        Some(freq) if coupon_rate != 0.0 => freq,
+        _ => {
+            // T-bill / zero-coupon: P = F / (1 + y * t/360)
+            let years = DayCount::Actual360.year_fraction(settlement_date, maturity_date);
+            return redemption / (1.0 + yield_dec * years);
+        }
+    };
+
+    let schedule = coupon_schedule(settlement_date, maturity_date, coupon_frequency);
+    let Some(remaining_fraction) =
+        remaining_period_fraction(&schedule, settlement_date, maturity_date, freq, day_count)
+    else {
+        return redemption;
+    };
+
+    let coupon_payment = face_value * coupon_rate / freq;
+    let period_yield = yield_dec / freq;
+    let discount = |i: usize| (1.0 + period_yield).powf(remaining_fraction + i as f64);
+
+    let mut pv = 0.0;
+    for i in 0..schedule.len() {
+        pv += coupon_payment / discount(i);
+    }
+    pv += face_value * redemption / discount(schedule.len() - 1);
+
+    // Return as fraction of par
+    pv / face_value
+}
+
+/// Coupon accrued between the previous coupon date and `settlement_date`,
+/// as a fraction of par.  Zero for zero-coupon bonds.
+pub(crate) fn accrued_interest(
+    settlement_date: NaiveDate,
+    maturity_date: NaiveDate,
+    coupon_rate: f64,
+    coupon_frequency: &str,
+    day_count: DayCount,
+) -> f64 {
+    let freq = match coupons_per_year(coupon_frequency) {
+        Some(freq) if coupon_rate != 0.0 => freq,
+        _ => return 0.0,
+    };
+    let schedule = coupon_schedule(settlement_date, maturity_date, coupon_frequency);
+    remaining_period_fraction(&schedule, settlement_date, maturity_date, freq, day_count)
+        .map_or(0.0, |remaining| coupon_rate / freq * (1.0 - remaining))
+}
+
+/// Fraction of the current coupon period still to run at `settlement_date`,
+/// or `None` when no coupons remain.
+fn remaining_period_fraction(
+    schedule: &[NaiveDate],
+    settlement_date: NaiveDate,
+    maturity_date: NaiveDate,
+    freq: f64,
+    day_count: DayCount,
+) -> Option<f64> {
+    let next_coupon = *schedule.first()?;
+    let previous_coupon = roll_back(maturity_date, (12.0 / freq) as u32, schedule.len() as u32);
+    Some(
+        day_count.year_fraction(settlement_date, next_coupon)
+            / day_count.year_fraction(previous_coupon, next_coupon),
+    )
+}
+
+/// The two yields Treasury quotes for a bill, in percent.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillYields {
+    /// Bank discount yield: the discount from par annualised over 360 days.
+    pub discount_yield: f64,
+    /// Bond-equivalent (investment) yield: the return on the price paid
+    /// annualised over 365 days, comparable with coupon-bond yields.
+    pub bond_equivalent_yield: f64,
+}
+
+/// Discount and bond-equivalent yields of a bill priced at `price_fraction`
+/// of par with `days_to_maturity` days left, using Treasury's formulas
+/// (including the semi-annual compounding adjustment past 182 days).
+/// Returns `None` for a matured bill or a non-positive price.
+pub(crate) fn bill_yields(price_fraction: f64, days_to_maturity: i64) -> Option<BillYields> {
+    if days_to_maturity <= 0 || price_fraction <= 0.0 {
+        return None;
+    }
+    let days = days_to_maturity as f64;
+    let discount = 1.0 - price_fraction;
+    let discount_yield = discount * 360.0 / days;
+
+    let bond_equivalent_yield = if days_to_maturity <= 182 {
+        discount / price_fraction * 365.0 / days
+    } else {
+        let t = days / 365.0;
+        let a = 2.0 * t - 1.0;
+        (-2.0 * t + 2.0 * (t * t - a * (1.0 - 1.0 / price_fraction)).sqrt()) / a
+    };
+
+    Some(BillYields {
+        discount_yield: discount_yield * 100.0,
+        bond_equivalent_yield: bond_equivalent_yield * 100.0,
+    })
+}
+
+/// The bond's face value from its metadata, else `default`. Prices are a
+/// fraction of par, so the face value does not change them and a missing one
+/// is not worth a warning; one that is not a positive number points at bad
+/// metadata and is logged.
+pub(crate) fn face_value(provider: &str, bond: &BondQuoteMetadata, default: f64) -> f64 {
+    let Some(value) = bond.face_value else {
+        return default;
+    };
+    match f64::try_from(value) {
+        Ok(face_value) if face_value > 0.0 => face_value,
+        _ => {
+            warn!(
+                "{}: invalid face value {}, assuming {}",
+                provider, value, default
+            );
+            default
+        }
+    }
+}
+
+/// Build a Quote from a calculated fraction-of-par price, stamped at 16:00 UTC
+/// on `date`.
+pub(crate) fn make_quote(
+    provider: &str,
+    date: NaiveDate,
+    price_fraction: f64,
+    currency: &str,
+) -> Result<Quote, MarketDataError> {
+    let close =
+        Decimal::try_from(price_fraction).map_err(|_| MarketDataError::ValidationFailed {
+            message: format!("Invalid price: {}", price_fraction),
+        })?;
+
+    let timestamp =
+        DateTime::<Utc>::from_naive_utc_and_offset(date.and_hms_opt(16, 0, 0).unwrap(), Utc);
+
+    Ok(Quote::new(
+        timestamp,
+        close,
+        currency.to_string(),
+        provider.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yield_curve_interpolation() {
+        let curve = YieldCurve(vec![
+            (1.0, 4.0),
+            (2.0, 4.2),
+            (5.0, 4.5),
+            (10.0, 4.8),
+            (30.0, 5.0),
+        ]);
+
+        // Exact match
+        assert!((curve.interpolate(1.0).unwrap() - 4.0).abs() < 1e-10);
+        assert!((curve.interpolate(10.0).unwrap() - 4.8).abs() < 1e-10);
+
+        // Interpolation: midpoint between 1.0 and 2.0
+        assert!((curve.interpolate(1.5).unwrap() - 4.1).abs() < 1e-10);
+
+        // Below range clamps to first
+        assert!((curve.interpolate(0.5).unwrap() - 4.0).abs() < 1e-10);
+
+        // Above range clamps to last
+        assert!((curve.interpolate(40.0).unwrap() - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cubic_spline_passes_through_knots() {
+        let curve = YieldCurve(vec![
+            (1.0 / 12.0, 4.34),
+            (0.5, 4.28),
+            (1.0, 4.22),
+            (2.0, 4.25),
+            (5.0, 4.40),
+            (10.0, 4.57),
+            (30.0, 4.78),
+        ]);
+
+        for (tenor, yield_pct) in &curve.0 {
+            let y = curve.interpolate_cubic(*tenor).unwrap();
+            assert!(
+                (y - yield_pct).abs() < 1e-10,
+                "spline at knot {} = {}, expected {}",
+                tenor,
+                y,
+                yield_pct
+            );
+        }
+
+        // Clamps outside the curve range
+        assert!((curve.interpolate_cubic(0.01).unwrap() - 4.34).abs() < 1e-10);
+        assert!((curve.interpolate_cubic(40.0).unwrap() - 4.78).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cubic_spline_monotonic_on_monotonic_input() {
+        let curve = YieldCurve(vec![
+            (1.0, 4.0),
+            (2.0, 4.2),
+            (3.0, 4.35),
+            (5.0, 4.5),
+            (7.0, 4.6),
+            (10.0, 4.7),
+        ]);
+
+        let mut prev = curve.interpolate_cubic(1.0).unwrap();
+        let mut years = 1.0;
+        while years <= 10.0 {
+            let y = curve.interpolate_cubic(years).unwrap();
+            assert!(
+                y >= prev - 1e-12,
+                "spline decreased at {}: {} < {}",
+                years,
+                y,
+                prev
+            );
+            prev = y;
+            years += 0.05;
+        }
+    }
+
+    #[test]
+    fn test_cubic_spline_falls_back_to_linear_for_two_points() {
+        let curve = YieldCurve(vec![(1.0, 4.0), (2.0, 4.2)]);
+        assert!((curve.interpolate_cubic(1.5).unwrap() - 4.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_yield_curve_empty() {
+        let curve = YieldCurve(vec![]);
+        assert!(curve.interpolate(5.0).is_none());
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_interpolate_yield_uses_method() {
+        let curve = YieldCurve(vec![(1.0, 4.0), (2.0, 4.6), (5.0, 4.5), (10.0, 4.8)]);
+        assert_eq!(
+            interpolate_yield(&curve, 3.0, InterpolationMethod::Linear),
+            curve.interpolate(3.0)
+        );
+        assert_eq!(
+            interpolate_yield(&curve, 3.0, InterpolationMethod::CubicSpline),
+            curve.interpolate_cubic(3.0)
+        );
+        assert_ne!(
+            interpolate_yield(&curve, 3.0, InterpolationMethod::Linear),
+            interpolate_yield(&curve, 3.0, InterpolationMethod::CubicSpline)
+        );
+        assert!(interpolate_yield(&YieldCurve(vec![]), 3.0, InterpolationMethod::Linear).is_none());
+    }
+
+    #[test]
+    fn test_day_count_year_fraction() {
+        let start = date(2024, 1, 1);
+        let end = date(2025, 1, 1); // 366 days
+        assert!((DayCount::Actual365_25.year_fraction(start, end) - 366.0 / 365.25).abs() < 1e-12);
+        assert!((DayCount::Actual360.year_fraction(start, end) - 366.0 / 360.0).abs() < 1e-12);
+        assert!(DayCount::default().year_fraction(end, start) < 0.0);
+    }
+
+    #[test]
+    fn test_coupons_per_year() {
+        assert_eq!(coupons_per_year("ANNUAL"), Some(1.0));
+        assert_eq!(coupons_per_year("SEMI_ANNUAL"), Some(2.0));
+        assert_eq!(coupons_per_year("QUARTERLY"), Some(4.0));
+        assert_eq!(coupons_per_year("MONTHLY"), Some(2.0));
+        assert_eq!(coupons_per_year("ZERO"), None);
+    }
+
+    #[test]
+    fn test_price_from_yield_at_par_when_coupon_equals_yield() {
+        let (settle, maturity) = (date(2025, 1, 1), date(2035, 1, 1));
+        for frequency in ["ANNUAL", "SEMI_ANNUAL", "QUARTERLY"] {
+            let price = price_from_yield(
+                5.0,
+                settle,
+                maturity,
+                0.05,
+                frequency,
+                1000.0,
+                1.0,
+                DayCount::Actual365_25,
+            );
+            assert!(
+                (price - 1.0).abs() < 1e-12,
+                "{} priced at {}",
+                frequency,
+                price
+            );
+        }
+    }
+
+    #[test]
+    fn test_coupon_schedule_for_2043_bond() {
+        // 2.875% Treasury bond issued 2013-05-15, maturing 2043-05-15.
+        let full = coupon_schedule(date(2013, 5, 15), date(2043, 5, 15), "SEMI_ANNUAL");
+        assert_eq!(full.len(), 60);
+        assert_eq!(full.first(), Some(&date(2013, 11, 15)));
+        assert_eq!(full.last(), Some(&date(2043, 5, 15)));
+        assert!(full
+            .iter()
+            .all(|d| d.day() == 15 && matches!(d.month(), 5 | 11)));
+
+        // Settling mid-period leaves a short stub to the next coupon.
+        let remaining = coupon_schedule(date(2025, 1, 2), date(2043, 5, 15), "SEMI_ANNUAL");
+        assert_eq!(remaining.len(), 37);
+        assert_eq!(remaining[0], date(2025, 5, 15));
+        assert_eq!(remaining[1], date(2025, 11, 15));
+    }
+
+    #[test]
+    fn test_coupon_schedule_month_end_and_zero() {
+        let eom = coupon_schedule(date(2041, 12, 31), date(2043, 2, 28), "QUARTERLY");
+        assert_eq!(
+            eom,
+            vec![
+                date(2042, 2, 28),
+                date(2042, 5, 31),
+                date(2042, 8, 31),
+                date(2042, 11, 30),
+                date(2043, 2, 28)
+            ]
+        );
+        assert_eq!(
+            coupon_schedule(date(2025, 1, 1), date(2026, 1, 1), "ZERO"),
+            vec![date(2026, 1, 1)]
+        );
+        assert!(coupon_schedule(date(2026, 1, 1), date(2026, 1, 1), "ANNUAL").is_empty());
+    }
+
+    #[test]
+    fn test_price_from_yield_discounts_stub_by_remaining_fraction() {
+        // Half of an annual period left: both cash flows move half a period
+        // closer than on the previous coupon date.
+        let price = dirty_price_from_yield(
+            5.0,
+            date(2025, 7, 2),
+            date(2027, 1, 1),
+            0.06,
+            "ANNUAL",
+            100.0,
+            1.0,
+            DayCount::default(),
+        );
+        let w = 183.0 / 365.0;
+        let expected = (6.0 / 1.05_f64.powf(w) + 106.0 / 1.05_f64.powf(1.0 + w)) / 100.0;
+        assert!((price - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_price_from_yield_is_clean_of_accrued_interest() {
+        let (settle, maturity) = (date(2025, 7, 2), date(2027, 1, 1));
+        let accrued = accrued_interest(settle, maturity, 0.06, "ANNUAL", DayCount::default());
+        assert!((accrued - 0.06 * (1.0 - 183.0 / 365.0)).abs() < 1e-12);
+
+        let clean = price_from_yield(
+            5.0,
+            settle,
+            maturity,
+            0.06,
+            "ANNUAL",
+            100.0,
+            1.0,
+            DayCount::default(),
+        );
+        let dirty = dirty_price_from_yield(
+            5.0,
+            settle,
+            maturity,
+            0.06,
+            "ANNUAL",
+            100.0,
+            1.0,
+            DayCount::default(),
+        );
+        assert!((dirty - clean - accrued).abs() < 1e-12);
+
+        // A bond yielding its coupon trades near par between coupon dates,
+        // rather than drifting up with the accrued coupon.
+        let at_coupon = price_from_yield(
+            6.0,
+            settle,
+            maturity,
+            0.06,
+            "ANNUAL",
+            100.0,
+            1.0,
+            DayCount::default(),
+        );
+        assert!((at_coupon - 1.0).abs() < 1e-3);
+
+        // Nothing accrues on zero-coupon bonds or on a coupon date.
+        assert_eq!(
+            accrued_interest(settle, maturity, 0.0, "ZERO", DayCount::default()),
+            0.0
+        );
+        assert_eq!(
+            accrued_interest(
+                date(2026, 1, 1),
+                maturity,
+                0.06,
+                "ANNUAL",
+                DayCount::default()
+            ),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_price_from_yield_premium_and_discount() {
+        let (settle, maturity) = (date(2025, 1, 1), date(2030, 1, 1));
+        let price = |yield_pct| {
+            price_from_yield(
+                yield_pct,
+                settle,
+                maturity,
+                0.04,
+                "SEMI_ANNUAL",
+                100.0,
+                1.0,
+                DayCount::default(),
+            )
+        };
+        assert!(price(3.0) > 1.0);
+        assert!(price(5.0) < 1.0);
+        assert!(price(6.0) < price(5.0));
+    }
+
+    #[test]
+    fn test_price_from_yield_annual_matches_closed_form() {
+        // Two annual 6% coupons discounted at 5%.
+        let price = price_from_yield(
+            5.0,
+            date(2025, 1, 1),
+            date(2027, 1, 1),
+            0.06,
+            "ANNUAL",
+            100.0,
+            1.0,
+            DayCount::default(),
+        );
+        let expected = (6.0 / 1.05 + 106.0 / 1.05_f64.powi(2)) / 100.0;
+        assert!((price - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_price_from_yield_zero_coupon_money_market() {
+        let (settle, maturity) = (date(2025, 1, 2), date(2025, 7, 3)); // 182 days
+        let expected = 1.0 / (1.0 + 0.04 * 182.0 / 360.0);
+        for (coupon_rate, frequency) in [(0.0, "ZERO"), (0.0, "SEMI_ANNUAL"), (0.05, "ZERO")] {
+            let price = price_from_yield(
+                4.0,
+                settle,
+                maturity,
+                coupon_rate,
+                frequency,
+                100.0,
+                1.0,
+                DayCount::Actual365_25,
+            );
+            assert!((price - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bill_yields_match_treasury_formulas() {
+        // 13-week bill at a 5.250% discount rate: price 98.672917,
+        // investment rate 5.395%.
+        let yields = bill_yields(0.98672917, 91).unwrap();
+        assert!((yields.discount_yield - 5.250).abs() < 0.01);
+        assert!((yields.bond_equivalent_yield - 5.395).abs() < 0.01);
+
+        // 52-week bill at the same discount rate: investment rate 5.545%,
+        // using the compounding-adjusted formula for bills over 182 days.
+        let yields = bill_yields(0.94691667, 364).unwrap();
+        assert!((yields.discount_yield - 5.250).abs() < 0.01);
+        assert!((yields.bond_equivalent_yield - 5.545).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bill_yields_rejects_matured_or_worthless() {
+        assert!(bill_yields(1.0, 0).is_none());
+        assert!(bill_yields(0.0, 91).is_none());
+    }
+
+    #[test]
+    fn test_settlement_date_skips_weekends() {
+        // Thursday 2025-01-02
+        assert_eq!(settlement_date(date(2025, 1, 2), 0), date(2025, 1, 2));
+        assert_eq!(settlement_date(date(2025, 1, 2), -1), date(2025, 1, 2));
+        assert_eq!(settlement_date(date(2025, 1, 2), 1), date(2025, 1, 3));
+        assert_eq!(settlement_date(date(2025, 1, 2), 2), date(2025, 1, 6));
+        // Saturday settles T+1 on Monday
+        assert_eq!(settlement_date(date(2025, 1, 4), 1), date(2025, 1, 6));
+    }
+
+    #[test]
+    fn test_price_from_yield_scales_redemption() {
+        let (settle, maturity) = (date(2025, 1, 1), date(2026, 1, 1));
+        let par = price_from_yield(
+            4.0,
+            settle,
+            maturity,
+            0.0,
+            "ZERO",
+            100.0,
+            1.0,
+            DayCount::default(),
+        );
+        let premium = price_from_yield(
+            4.0,
+            settle,
+            maturity,
+            0.0,
+            "ZERO",
+            100.0,
+            1.02,
+            DayCount::default(),
+        );
+        assert!((premium / par - 1.02).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calculate_price_matured_and_empty_curve() {
+        let curve = YieldCurve(vec![(1.0, 4.0)]);
+        let matured = calculate_price(
+            "TEST",
+            &curve,
+            InterpolationMethod::Linear,
+            date(2025, 6, 1),
+            date(2025, 1, 1),
+            0.05,
+            "SEMI_ANNUAL",
+            100.0,
+            None,
+        );
+        assert_eq!(matured.unwrap(), 1.0);
+
+        let empty = calculate_price(
+            "TEST",
+            &YieldCurve(vec![]),
+            InterpolationMethod::Linear,
+            date(2025, 1, 1),
+            date(2030, 1, 1),
+            0.05,
+            "SEMI_ANNUAL",
+            100.0,
+            None,
+        );
+        assert!(matches!(
+            empty,
+            Err(MarketDataError::ProviderError { ref provider, .. }) if provider == "TEST"
+        ));
+    }
+}
@@ -1,26 +1,30 @@
 //! Boerse Frankfurt (Deutsche Boerse) provider for bond market data.
 //!
-//! Fetches bond price history from the Deutsche Boerse live API (XFRA exchange).
-//! Prices are quoted as percentage-of-par and converted to decimal fractions
-//! (e.g., 97.025 -> 0.97025).
+//! Fetches bond price history from the Deutsche Boerse live API. Queries XFRA by
+//! default; the venue is configurable per provider (`with_mic`) and per request
+//! (`QuoteContext::mic_hint`), with optional fallback venues tried when the
+//! instrument is not found. Prices are quoted as percentage-of-par and converted
+//! to decimal fractions (e.g., 97.025 -> 0.97025).
 //!
 //! No API key required. Authentication uses a salt scraped from the frontend JS bundle
 //! to compute per-request headers (`x-security`, `x-client-traceid`).
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
-use log::warn;
+use log::{debug, warn};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 use crate::errors::MarketDataError;
 use crate::models::{
-    AssetProfile, Coverage, InstrumentKind, ProviderInstrument, Quote, QuoteContext,
+    AssetProfile, Coverage, InstrumentKind, ProviderInstrument, Quote, QuoteContext, SearchResult,
 };
+use crate::provider::utils::{read_bytes_capped, read_text_capped, DEFAULT_MAX_BODY_BYTES};
 use crate::provider::{MarketDataProvider, ProviderCapabilities, RateLimit};
 
 const PROVIDER_ID: &str = "BOERSE_FRANKFURT";
@@ -28,7 +32,13 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 const BASE_URL: &str = "https://api.live.deutsche-boerse.com/v1/data/price_history";
 const INSTRUMENT_INFO_URL: &str =
     "https://api.live.deutsche-boerse.com/v1/data/instrument_information";
+const SEARCH_URL: &str = "https://api.live.deutsche-boerse.com/v1/global_search/limitedsearch/en";
 const MAIN_JS_URL: &str = "https://live.deutsche-boerse.com";
+const DEFAULT_MIC: &str = "XFRA";
+/// Currency assumed when instrument_information does not report one.
+const DEFAULT_CURRENCY: &str = "EUR";
+/// How long a scraped salt is trusted before it is re-scraped proactively.
+const DEFAULT_SALT_TTL: Duration = Duration::from_secs(30 * 60);
 
 /// A single data point from the BF price history response.
 #[derive(Debug, Deserialize)]
@@ -64,6 +74,19 @@ struct PriceHistoryResponse {
 #[serde(rename_all = "camelCase")]
 struct InstrumentInfoResponse {
     instrument_name: Option<InstrumentName>,
+    #[serde(default)]
+    trading_currency: Option<String>,
+}
+
+impl InstrumentInfoResponse {
+    /// Traded currency as an uppercase ISO code, if the API reported one.
+    fn currency(&self) -> Option<String> {
+        self.trading_currency
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_uppercase)
+    }
 }
 
 /// Nested name object within instrument_information response.
@@ -73,10 +96,58 @@ struct InstrumentName {
     original_value: Option<String>,
 }
 
+/// Response from the instrument search endpoint.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    #[serde(default)]
+    result: Vec<SearchHit>,
+}
+
+/// A single instrument returned by the search endpoint.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchHit {
+    isin: Option<String>,
+    name: Option<InstrumentName>,
+    #[serde(default, rename = "type")]
+    instrument_type: Option<String>,
+}
+
+/// Scraped salt with the time it was fetched.
+#[derive(Debug, Clone)]
+struct CachedSalt {
+    value: String,
+    fetched_at: Instant,
+}
+
+impl CachedSalt {
+    fn new(value: String) -> Self {
+        Self {
+            value,
+            fetched_at: Instant::now(),
+        }
+    }
+
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.fetched_at.elapsed() < ttl
+    }
+}
+
 /// Boerse Frankfurt provider for bond market data.
 pub struct BoerseFrankfurtProvider {
     client: Client,
-    salt: Arc<RwLock<Option<String>>>,
+    salt: Arc<RwLock<Option<CachedSalt>>>,
+    /// Age after which the salt is re-scraped even without an auth error
+    salt_ttl: Duration,
+    /// Venue queried when the request carries no MIC hint
+    mic: String,
+    /// Venues retried, in order, when the primary returns no data
+    fallback_mics: Vec<String>,
+    /// Traded currency per ISIN, looked up when a request has no currency hint
+    currencies: Arc<RwLock<HashMap<String, String>>>,
+    /// Largest response body accepted, including the scraped JS bundle
+    max_body_bytes: usize,
 }
 
 impl Default for BoerseFrankfurtProvider {
@@ -95,26 +166,109 @@ impl BoerseFrankfurtProvider {
         Self {
             client,
             salt: Arc::new(RwLock::new(None)),
+            salt_ttl: DEFAULT_SALT_TTL,
+            mic: DEFAULT_MIC.to_string(),
+            fallback_mics: Vec::new(),
+            currencies: Arc::new(RwLock::new(HashMap::new())),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
         }
     }
 
-    /// Get the salt, scraping from the frontend JS if not cached.
+    /// Reject responses larger than `max_bytes` (default 16 MiB).
+    pub fn with_max_body_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_body_bytes = max_bytes;
+        self
+    }
+
+    /// Set how long a scraped salt is reused before it is refreshed.
+    pub fn with_salt_ttl(mut self, ttl: Duration) -> Self {
+        self.salt_ttl = ttl;
+        self
+    }
+
+    /// Set the default venue (e.g. "XETR") queried when a request has no MIC hint.
+    pub fn with_mic(mut self, mic: &str) -> Self {
+        self.mic = mic.to_uppercase();
+        self
+    }
+
+    /// Set the venues retried, in order, when the primary venue has no data
+    /// for the instrument.
+    pub fn with_fallback_mics(mut self, mics: &[&str]) -> Self {
+        self.fallback_mics = mics.iter().map(|mic| mic.to_uppercase()).collect();
+        self
+    }
+
+    /// Venues to query for a request: the hint (if any), then the default
+    /// venue, then the fallbacks, without duplicates.
+    fn candidate_mics(&self, mic_hint: Option<&str>) -> Vec<String> {
+        let mut mics: Vec<String> = Vec::new();
+        let hint = mic_hint.map(str::to_uppercase);
+        for mic in hint
+            .iter()
+            .chain(std::iter::once(&self.mic))
+            .chain(self.fallback_mics.iter())
+        {
+            if !mic.is_empty() && !mics.contains(mic) {
+                mics.push(mic.clone());
+            }
+        }
+        mics
+    }
+
+    /// Run `fetch` against each candidate venue until one returns data.
+    /// Only `SymbolNotFound` moves on to the next venue; other errors abort.
+    async fn with_mic_fallback<T, F, Fut>(
+        &self,
+        symbol: &str,
+        mic_hint: Option<&str>,
+        fetch: F,
+    ) -> Result<T, MarketDataError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = Result<T, MarketDataError>>,
+    {
+        for mic in self.candidate_mics(mic_hint) {
+            match fetch(mic.clone()).await {
+                Err(MarketDataError::SymbolNotFound(_)) => {
+                    debug!("BF: {} not found on {}", symbol, mic);
+                }
+                result => return result,
+            }
+        }
+        Err(MarketDataError::SymbolNotFound(symbol.to_string()))
+    }
+
+    /// Get the salt, scraping from the frontend JS if not cached or expired.
     async fn get_salt(&self) -> Result<String, MarketDataError> {
+        self.get_salt_or_scrape(|| self.scrape_salt()).await
+    }
+
+    /// Return the cached salt while it is within the TTL, otherwise call
+    /// `scrape` and cache the result.
+    async fn get_salt_or_scrape<F, Fut>(&self, scrape: F) -> Result<String, MarketDataError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<String, MarketDataError>>,
+    {
         // Check cache first
         {
             let cached = self.salt.read().await;
             if let Some(ref s) = *cached {
-                return Ok(s.clone());
+                if s.is_fresh(self.salt_ttl) {
+                    return Ok(s.value.clone());
+                }
+                debug!("BF: cached salt expired, re-scraping");
             }
         }
 
         // Scrape salt from main page
-        let salt = self.scrape_salt().await?;
+        let salt = scrape().await?;
 
         // Cache it
         {
             let mut w = self.salt.write().await;
-            *w = Some(salt.clone());
+            *w = Some(CachedSalt::new(salt.clone()));
         }
 
         Ok(salt)
@@ -123,21 +277,13 @@ impl BoerseFrankfurtProvider {
     /// Scrape the salt from the Deutsche Boerse frontend JS bundle.
     async fn scrape_salt(&self) -> Result<String, MarketDataError> {
         // First fetch the main page to find the JS bundle URL
-        let html = self
-            .client
-            .get(MAIN_JS_URL)
-            .send()
-            .await
-            .map_err(|e| MarketDataError::ProviderError {
+        let resp = self.client.get(MAIN_JS_URL).send().await.map_err(|e| {
+            MarketDataError::ProviderError {
                 provider: PROVIDER_ID.to_string(),
                 message: format!("Failed to fetch main page: {}", e),
-            })?
-            .text()
-            .await
-            .map_err(|e| MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("Failed to read main page: {}", e),
-            })?;
+            }
+        })?;
+        let html = read_text_capped(resp, self.max_body_bytes, PROVIDER_ID).await?;
 
         // Find main.*.js bundle URL
         let js_url = extract_main_js_url(&html).ok_or_else(|| MarketDataError::ProviderError {
@@ -152,21 +298,13 @@ impl BoerseFrankfurtProvider {
         };
 
         // Fetch the JS bundle
-        let js_body = self
-            .client
-            .get(&full_js_url)
-            .send()
-            .await
-            .map_err(|e| MarketDataError::ProviderError {
+        let resp = self.client.get(&full_js_url).send().await.map_err(|e| {
+            MarketDataError::ProviderError {
                 provider: PROVIDER_ID.to_string(),
                 message: format!("Failed to fetch JS bundle: {}", e),
-            })?
-            .text()
-            .await
-            .map_err(|e| MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("Failed to read JS bundle: {}", e),
-            })?;
+            }
+        })?;
+        let js_body = read_text_capped(resp, self.max_body_bytes, PROVIDER_ID).await?;
 
         // Extract salt from JS: salt:"<hex>"
         extract_salt_from_js(&js_body).ok_or_else(|| MarketDataError::ProviderError {
@@ -209,18 +347,17 @@ impl BoerseFrankfurtProvider {
         headers
     }
 
-    /// Fetch the instrument name for a bond ISIN.
-    async fn fetch_instrument_name(&self, isin: &str) -> Result<String, MarketDataError> {
+    /// Send an authenticated GET. Invalidates the cached salt on 401/403 and
+    /// maps non-success statuses to provider errors.
+    async fn get_authenticated(&self, url: &str) -> Result<reqwest::Response, MarketDataError> {
         let salt = self.get_salt().await?;
 
-        let url = format!("{}?isin={}&mic=XFRA", INSTRUMENT_INFO_URL, isin);
-
         let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
-        let headers = Self::build_headers(&timestamp, &url, &salt);
+        let headers = Self::build_headers(&timestamp, url, &salt);
 
         let resp = self
             .client
-            .get(&url)
+            .get(url)
             .headers(headers)
             .send()
             .await
@@ -231,6 +368,7 @@ impl BoerseFrankfurtProvider {
 
         let status = resp.status();
         if !status.is_success() {
+            // If 403/401, the salt may be stale — invalidate cache
             if status.as_u16() == 401 || status.as_u16() == 403 {
                 let mut w = self.salt.write().await;
                 *w = None;
@@ -241,79 +379,104 @@ impl BoerseFrankfurtProvider {
             });
         }
 
-        let body: InstrumentInfoResponse =
-            resp.json()
-                .await
-                .map_err(|e| MarketDataError::ProviderError {
-                    provider: PROVIDER_ID.to_string(),
-                    message: format!("JSON parse error: {}", e),
-                })?;
+        Ok(resp)
+    }
+
+    /// Read and parse a JSON response within the body size cap.
+    async fn read_json<T: serde::de::DeserializeOwned>(
+        &self,
+        resp: reqwest::Response,
+    ) -> Result<T, MarketDataError> {
+        let body = read_bytes_capped(resp, self.max_body_bytes, PROVIDER_ID).await?;
+        serde_json::from_slice(&body).map_err(|e| MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: format!("JSON parse error: {}", e),
+        })
+    }
+
+    /// Search instruments by name, ISIN or ISIN prefix, keeping bonds only.
+    async fn search_instruments(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        let url = search_url(query)?;
+        let resp = self.get_authenticated(&url).await?;
+
+        let body: SearchResponse = self.read_json(resp).await?;
+
+        Ok(search_hits_to_results(body.result, &self.mic))
+    }
+
+    /// Fetch instrument information for a bond ISIN on the given venue.
+    async fn fetch_instrument_info(
+        &self,
+        isin: &str,
+        mic: &str,
+    ) -> Result<InstrumentInfoResponse, MarketDataError> {
+        let url = instrument_info_url(isin, mic);
+        let resp = self.get_authenticated(&url).await?;
 
-        body.instrument_name
+        self.read_json(resp).await
+    }
+
+    /// Fetch the instrument name for a bond ISIN on the given venue.
+    async fn fetch_instrument_name(
+        &self,
+        isin: &str,
+        mic: &str,
+    ) -> Result<String, MarketDataError> {
+        self.fetch_instrument_info(isin, mic)
+            .await?
+            .instrument_name
             .and_then(|n| n.original_value)
             .filter(|n| !n.is_empty())
-            .ok_or_else(|| MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("No instrument name found for {}", isin),
-            })
+            .ok_or_else(|| MarketDataError::SymbolNotFound(isin.to_string()))
+    }
+
+    /// Traded currency of a bond ISIN, cached per ISIN. Falls back to EUR when
+    /// the API omits it; lookup failures fall back without being cached.
+    async fn instrument_currency(&self, isin: &str, mic: &str) -> String {
+        if let Some(currency) = self.currencies.read().await.get(isin) {
+            return currency.clone();
+        }
+
+        let currency = match self.fetch_instrument_info(isin, mic).await {
+            Ok(info) => info
+                .currency()
+                .unwrap_or_else(|| DEFAULT_CURRENCY.to_string()),
+            Err(e) => {
+                warn!("BF: currency lookup failed for {}: {}", isin, e);
+                return DEFAULT_CURRENCY.to_string();
+            }
+        };
+        self.currencies
+            .write()
+            .await
+            .insert(isin.to_string(), currency.clone());
+        currency
     }
 
-    /// Fetch price history for a bond ISIN.
+    /// Fetch price history for a bond ISIN on the given venue.
     async fn fetch_price_history(
         &self,
         isin: &str,
+        mic: &str,
         min_date: &str,
         max_date: &str,
         currency_hint: Option<&str>,
     ) -> Result<Vec<Quote>, MarketDataError> {
-        let salt = self.get_salt().await?;
-
-        let url = format!(
-            "{}?isin={}&mic=XFRA&minDate={}&maxDate={}&offset=0&limit=2000",
-            BASE_URL, isin, min_date, max_date
-        );
+        let url = price_history_url(isin, mic, min_date, max_date);
+        let resp = self.get_authenticated(&url).await?;
 
-        let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
-        let headers = Self::build_headers(&timestamp, &url, &salt);
-
-        let resp = self
-            .client
-            .get(&url)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(|e| MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("HTTP request failed: {}", e),
-            })?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            // If 403/401, the salt may be stale — invalidate cache
-            if status.as_u16() == 401 || status.as_u16() == 403 {
-                let mut w = self.salt.write().await;
-                *w = None;
-            }
-            return Err(MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("HTTP {}", status),
-            });
-        }
-
-        let body: PriceHistoryResponse =
-            resp.json()
-                .await
-                .map_err(|e| MarketDataError::ProviderError {
-                    provider: PROVIDER_ID.to_string(),
-                    message: format!("JSON parse error: {}", e),
-                })?;
+        let body: PriceHistoryResponse = self.read_json(resp).await?;
 
         if body.data.is_empty() {
             return Err(MarketDataError::SymbolNotFound(isin.to_string()));
         }
 
-        // Use the asset's quote_ccy from context, or default to EUR for XFRA
-        let currency = currency_hint.unwrap_or("EUR").to_string();
+        // Use the asset's quote_ccy from context, or the currency the
+        // instrument is traded in on the venue
+        let currency = match currency_hint {
+            Some(currency) => currency.to_string(),
+            None => self.instrument_currency(isin, mic).await,
+        };
 
         let mut quotes = Vec::with_capacity(body.data.len());
         for point in &body.data {
@@ -364,6 +527,57 @@ impl BoerseFrankfurtProvider {
     }
 }
 
+/// Price history URL for an ISIN on a Deutsche Boerse venue.
+fn price_history_url(isin: &str, mic: &str, min_date: &str, max_date: &str) -> String {
+    format!(
+        "{}?isin={}&mic={}&minDate={}&maxDate={}&offset=0&limit=2000",
+        BASE_URL, isin, mic, min_date, max_date
+    )
+}
+
+/// Instrument information URL for an ISIN on a Deutsche Boerse venue.
+fn instrument_info_url(isin: &str, mic: &str) -> String {
+    format!("{}?isin={}&mic={}", INSTRUMENT_INFO_URL, isin, mic)
+}
+
+/// Instrument search URL for a free-text query.
+fn search_url(query: &str) -> Result<String, MarketDataError> {
+    reqwest::Url::parse_with_params(SEARCH_URL, &[("searchTerms", query.trim())])
+        .map(|url| url.to_string())
+        .map_err(|e| MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: format!("Invalid search URL: {}", e),
+        })
+}
+
+/// Convert search hits to bond search results, skipping hits without an ISIN
+/// or name and non-bond instruments.
+fn search_hits_to_results(hits: Vec<SearchHit>, mic: &str) -> Vec<SearchResult> {
+    let mut seen = std::collections::HashSet::new();
+    hits.into_iter()
+        .filter(|hit| {
+            hit.instrument_type
+                .as_deref()
+                .is_none_or(|t| t.to_ascii_uppercase().contains("BOND"))
+        })
+        .filter_map(|hit| {
+            let isin = hit.isin.filter(|i| !i.is_empty())?;
+            let name = hit
+                .name
+                .and_then(|n| n.original_value)
+                .filter(|n| !n.is_empty())?;
+            if !seen.insert(isin.clone()) {
+                return None;
+            }
+            Some(
+                SearchResult::new(isin, name, mic, "BOND")
+                    .with_exchange_mic(mic)
+                    .with_data_source(PROVIDER_ID),
+            )
+        })
+        .collect()
+}
+
 /// Extract the main.*.js bundle URL from the HTML page.
 fn extract_main_js_url(html: &str) -> Option<String> {
     // Look for script src containing "main." and ".js"
@@ -412,7 +626,7 @@ impl MarketDataProvider for BoerseFrankfurtProvider {
             coverage: Coverage::global_best_effort(),
             supports_latest: true,
             supports_historical: true,
-            supports_search: false,
+            supports_search: true,
             supports_profile: true,
         }
     }
@@ -449,7 +663,13 @@ impl MarketDataProvider for BoerseFrankfurtProvider {
             .to_string();
 
         let quotes = self
-            .fetch_price_history(&isin, &start, &end, currency_hint)
+            .with_mic_fallback(&isin, context.mic_hint.as_deref(), |mic| {
+                let (isin, start, end) = (&isin, &start, &end);
+                async move {
+                    self.fetch_price_history(isin, &mic, start, end, currency_hint)
+                        .await
+                }
+            })
             .await?;
 
         quotes.into_iter().last().ok_or_else(|| {
@@ -479,12 +699,29 @@ impl MarketDataProvider for BoerseFrankfurtProvider {
         let min_date = start.format("%Y-%m-%d").to_string();
         let max_date = end.format("%Y-%m-%d").to_string();
 
-        self.fetch_price_history(&isin, &min_date, &max_date, currency_hint)
-            .await
+        self.with_mic_fallback(&isin, context.mic_hint.as_deref(), |mic| {
+            let (isin, min_date, max_date) = (&isin, &min_date, &max_date);
+            async move {
+                self.fetch_price_history(isin, &mic, min_date, max_date, currency_hint)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        self.search_instruments(query).await
     }
 
     async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
-        let name = self.fetch_instrument_name(symbol).await?;
+        let name = self
+            .with_mic_fallback(symbol, None, |mic| async move {
+                self.fetch_instrument_name(symbol, &mic).await
+            })
+            .await?;
         Ok(AssetProfile::with_name(name))
     }
 }
@@ -512,7 +749,7 @@ mod tests {
         assert_eq!(caps.instrument_kinds, &[InstrumentKind::Bond]);
         assert!(caps.supports_latest);
         assert!(caps.supports_historical);
-        assert!(!caps.supports_search);
+        assert!(caps.supports_search);
         assert!(caps.supports_profile);
     }
 
@@ -553,6 +790,151 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_price_history_url_default_mic() {
+        let url = price_history_url("XS2530331413", DEFAULT_MIC, "2026-02-06", "2026-02-16");
+        assert_eq!(
+            url,
+            "https://api.live.deutsche-boerse.com/v1/data/price_history?isin=XS2530331413&mic=XFRA&minDate=2026-02-06&maxDate=2026-02-16&offset=0&limit=2000"
+        );
+    }
+
+    #[test]
+    fn test_urls_with_non_default_mic() {
+        let provider = BoerseFrankfurtProvider::new().with_mic("xetr");
+        let mic = &provider.candidate_mics(None)[0];
+        assert_eq!(mic, "XETR");
+
+        let url = price_history_url("DE0001102580", mic, "2026-02-06", "2026-02-16");
+        assert!(url.contains("isin=DE0001102580&mic=XETR&minDate=2026-02-06"));
+        assert!(!url.contains("XFRA"));
+
+        assert_eq!(
+            instrument_info_url("DE0001102580", mic),
+            "https://api.live.deutsche-boerse.com/v1/data/instrument_information?isin=DE0001102580&mic=XETR"
+        );
+    }
+
+    #[test]
+    fn test_candidate_mics_order() {
+        let provider = BoerseFrankfurtProvider::new();
+        assert_eq!(provider.candidate_mics(None), vec!["XFRA"]);
+
+        let provider = BoerseFrankfurtProvider::new()
+            .with_mic("XETR")
+            .with_fallback_mics(&["XFRA", "xetr", "XSTU"]);
+        assert_eq!(provider.candidate_mics(None), vec!["XETR", "XFRA", "XSTU"]);
+        // Request hint overrides the default, which stays as the first fallback
+        assert_eq!(
+            provider.candidate_mics(Some("xstu")),
+            vec!["XSTU", "XETR", "XFRA"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mic_fallback_retries_only_on_symbol_not_found() {
+        let provider = BoerseFrankfurtProvider::new().with_fallback_mics(&["XETR", "XSTU"]);
+        let attempts = std::sync::Mutex::new(Vec::new());
+
+        let result = provider
+            .with_mic_fallback("DE0001102580", None, |mic| {
+                attempts.lock().unwrap().push(mic.clone());
+                async move {
+                    if mic == "XETR" {
+                        Ok(mic)
+                    } else {
+                        Err(MarketDataError::SymbolNotFound("DE0001102580".to_string()))
+                    }
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "XETR");
+        assert_eq!(*attempts.lock().unwrap(), vec!["XFRA", "XETR"]);
+
+        attempts.lock().unwrap().clear();
+        let result: Result<String, _> = provider
+            .with_mic_fallback("DE0001102580", None, |mic| {
+                attempts.lock().unwrap().push(mic);
+                async {
+                    Err(MarketDataError::ProviderError {
+                        provider: PROVIDER_ID.to_string(),
+                        message: "HTTP 500".to_string(),
+                    })
+                }
+            })
+            .await;
+        assert!(matches!(result, Err(MarketDataError::ProviderError { .. })));
+        assert_eq!(*attempts.lock().unwrap(), vec!["XFRA"]);
+    }
+
+    #[tokio::test]
+    async fn test_salt_rescraped_only_after_ttl() {
+        let scrapes = std::sync::atomic::AtomicUsize::new(0);
+        let scrape = || async {
+            let n = scrapes.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(format!("salt{}", n))
+        };
+
+        // Fresh salt is reused
+        let provider = BoerseFrankfurtProvider::new();
+        assert_eq!(provider.get_salt_or_scrape(scrape).await.unwrap(), "salt0");
+        assert_eq!(provider.get_salt_or_scrape(scrape).await.unwrap(), "salt0");
+        assert_eq!(scrapes.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Expired salt triggers a re-scrape
+        let provider = BoerseFrankfurtProvider::new().with_salt_ttl(Duration::ZERO);
+        *provider.salt.write().await = Some(CachedSalt::new("stale".to_string()));
+        assert_eq!(provider.get_salt_or_scrape(scrape).await.unwrap(), "salt1");
+        assert_eq!(scrapes.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_search_url_encodes_query() {
+        assert_eq!(
+            search_url(" Bund 2030 ").unwrap(),
+            "https://api.live.deutsche-boerse.com/v1/global_search/limitedsearch/en?searchTerms=Bund+2030"
+        );
+    }
+
+    #[test]
+    fn test_parse_search_response() {
+        let json = r#"{
+            "result": [
+                {"isin": "DE0001102580", "name": {"originalValue": "Bundesrep.Deutschland Anl.v.2022(2032)"}, "type": "BOND"},
+                {"isin": "DE0007164600", "name": {"originalValue": "SAP SE"}, "type": "EQUITY"},
+                {"isin": "DE0001102580", "name": {"originalValue": "Duplicate"}, "type": "BOND"},
+                {"isin": "XS2530331413", "name": {"originalValue": "Some Corp Bond"}},
+                {"name": {"originalValue": "No ISIN"}, "type": "BOND"}
+            ]
+        }"#;
+
+        let resp: SearchResponse = serde_json::from_str(json).unwrap();
+        let results = search_hits_to_results(resp.result, "XETR");
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].symbol, "DE0001102580");
+        assert_eq!(results[0].name, "Bundesrep.Deutschland Anl.v.2022(2032)");
+        assert_eq!(results[0].asset_type, "BOND");
+        assert_eq!(results[0].exchange_mic.as_deref(), Some("XETR"));
+        assert_eq!(results[0].data_source.as_deref(), Some(PROVIDER_ID));
+        assert_eq!(results[1].symbol, "XS2530331413");
+    }
+
+    #[test]
+    fn test_parse_empty_search_response() {
+        let resp: SearchResponse = serde_json::from_str(r#"{"result": []}"#).unwrap();
+        assert!(search_hits_to_results(resp.result, DEFAULT_MIC).is_empty());
+
+        let resp: SearchResponse = serde_json::from_str(r#"{}"#).unwrap();
+        assert!(search_hits_to_results(resp.result, DEFAULT_MIC).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blank_search_returns_empty() {
+        let provider = BoerseFrankfurtProvider::new();
+        assert!(provider.search("   ").await.unwrap().is_empty());
+    }
+
     #[test]
     fn test_extract_main_js_url() {
         let html = r#"<script src="main.abc123.js"></script>"#;
@@ -601,6 +983,46 @@ mod tests {
         assert_eq!(resp.data[0].turnover_pieces, Some(10000.0));
     }
 
+    #[test]
+    fn test_parse_instrument_info_currency() {
+        let json = r#"{
+            "isin": "US912810TV08",
+            "instrumentName": {"originalValue": "United States of America DL-Bonds 2023(53)"},
+            "instrumentTypeKey": "bond",
+            "tradingCurrency": "usd"
+        }"#;
+
+        let resp: InstrumentInfoResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.currency().as_deref(), Some("USD"));
+        assert_eq!(
+            resp.instrument_name
+                .and_then(|n| n.original_value)
+                .as_deref(),
+            Some("United States of America DL-Bonds 2023(53)")
+        );
+
+        let json = r#"{"instrumentName": {"originalValue": "Bund 2034"}}"#;
+        let resp: InstrumentInfoResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.currency(), None);
+    }
+
+    #[tokio::test]
+    async fn test_instrument_currency_served_from_cache() {
+        let provider = BoerseFrankfurtProvider::new();
+        provider
+            .currencies
+            .write()
+            .await
+            .insert("XS2530331413".to_string(), "GBP".to_string());
+
+        assert_eq!(
+            provider
+                .instrument_currency("XS2530331413", DEFAULT_MIC)
+                .await,
+            "GBP"
+        );
+    }
+
     #[test]
     fn test_percent_of_par_conversion() {
         // 97.025% of par → 0.97025
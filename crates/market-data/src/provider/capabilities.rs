@@ -45,6 +45,26 @@ impl ProviderCapabilities {
         // 2. Check coverage restrictions
         self.coverage.supports(inst)
     }
+
+    /// Check if this provider supports the given operation.
+    pub fn supports_operation(&self, operation: ProviderOperation) -> bool {
+        match operation {
+            ProviderOperation::LatestQuote => self.supports_latest,
+            ProviderOperation::HistoricalQuotes => self.supports_historical,
+            ProviderOperation::Search => self.supports_search,
+            ProviderOperation::Profile => self.supports_profile,
+        }
+    }
+}
+
+/// An operation a provider may support, as declared in its
+/// [`ProviderCapabilities`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProviderOperation {
+    LatestQuote,
+    HistoricalQuotes,
+    Search,
+    Profile,
 }
 
 /// Rate limiting configuration for a provider.
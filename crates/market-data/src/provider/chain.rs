@@ -0,0 +1,482 @@
+//! Provider that falls back across an ordered list of providers.
+//!
+//! `ProviderChain` lets a fixed combination of providers stand in for a single
+//! one, e.g. OpenFIGI then Boerse Frankfurt for bond names, or Boerse
+//! Frankfurt then the Treasury calculator for bond prices. Each call goes to
+//! the providers in priority order, skipping those whose capabilities rule
+//! the operation or instrument out, and returns the first success.
+//!
+//! Unlike `ProviderRegistry`, the chain has no rate limiting or circuit
+//! breaking of its own and passes the same `ProviderInstrument` to every
+//! provider, so it should only combine providers that share an instrument
+//! format.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::errors::MarketDataError;
+use crate::models::{
+    AssetProfile, Coverage, InstrumentKind, ProviderInstrument, Quote, QuoteContext, SearchResult,
+    SplitEvent,
+};
+
+use super::{MarketDataProvider, ProviderCapabilities, RateLimit};
+
+/// Instrument kinds reported by a chain. Kind and coverage checks happen per
+/// provider when a call is delegated.
+const ALL_INSTRUMENT_KINDS: &[InstrumentKind] = &[
+    InstrumentKind::Equity,
+    InstrumentKind::Crypto,
+    InstrumentKind::Fx,
+    InstrumentKind::Metal,
+    InstrumentKind::Option,
+    InstrumentKind::Bond,
+];
+
+/// A `MarketDataProvider` that delegates to other providers in order.
+pub struct ProviderChain {
+    id: &'static str,
+    providers: Vec<Arc<dyn MarketDataProvider>>,
+}
+
+impl ProviderChain {
+    /// Create a chain identified by `id`. Providers are ordered by priority;
+    /// providers with equal priority keep the order given.
+    pub fn new(id: &'static str, mut providers: Vec<Arc<dyn MarketDataProvider>>) -> Self {
+        providers.sort_by_key(|provider| provider.priority());
+        Self { id, providers }
+    }
+
+    /// Provider ids in the order they are tried.
+    pub fn provider_ids(&self) -> Vec<&'static str> {
+        self.providers
+            .iter()
+            .map(|provider| provider.id())
+            .collect()
+    }
+
+    /// Call `call` on each eligible provider until one succeeds.
+    async fn first_success<T, E, F, Fut>(&self, eligible: E, call: F) -> Result<T, MarketDataError>
+    where
+        E: Fn(&ProviderCapabilities) -> bool + Send,
+        F: Fn(Arc<dyn MarketDataProvider>) -> Fut + Send,
+        Fut: Future<Output = Result<T, MarketDataError>> + Send,
+    {
+        let mut errors = Vec::new();
+        for provider in &self.providers {
+            if !eligible(&provider.capabilities()) {
+                continue;
+            }
+            let provider_id = provider.id();
+            match call(Arc::clone(provider)).await {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    log::debug!("{}: {} failed: {}", self.id, provider_id, error);
+                    errors.push((provider_id, error));
+                }
+            }
+        }
+        Err(self.chain_error(errors))
+    }
+
+    /// Error returned when no provider succeeded. A single attempt keeps its
+    /// own error; several are folded into one message.
+    fn chain_error(&self, mut errors: Vec<(&'static str, MarketDataError)>) -> MarketDataError {
+        if errors.len() <= 1 {
+            return errors
+                .pop()
+                .map(|(_, error)| error)
+                .unwrap_or(MarketDataError::NoProvidersAvailable);
+        }
+        let message = errors
+            .iter()
+            .map(|(provider_id, error)| format!("{}: {}", provider_id, error))
+            .collect::<Vec<_>>()
+            .join("; ");
+        MarketDataError::ProviderError {
+            provider: self.id.to_string(),
+            message,
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for ProviderChain {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn priority(&self) -> u8 {
+        self.providers
+            .iter()
+            .map(|provider| provider.priority())
+            .min()
+            .unwrap_or(10)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        let any = |supports: fn(&ProviderCapabilities) -> bool| {
+            self.providers
+                .iter()
+                .any(|provider| supports(&provider.capabilities()))
+        };
+        ProviderCapabilities {
+            instrument_kinds: ALL_INSTRUMENT_KINDS,
+            coverage: Coverage::default(),
+            supports_latest: any(|caps| caps.supports_latest),
+            supports_historical: any(|caps| caps.supports_historical),
+            supports_search: any(|caps| caps.supports_search),
+            supports_profile: any(|caps| caps.supports_profile),
+        }
+    }
+
+    /// The most restrictive limits of the chained providers.
+    fn rate_limit(&self) -> RateLimit {
+        let limits: Vec<RateLimit> = self
+            .providers
+            .iter()
+            .map(|provider| provider.rate_limit())
+            .collect();
+        if limits.is_empty() {
+            return RateLimit::default();
+        }
+        RateLimit {
+            requests_per_minute: limits
+                .iter()
+                .map(|limit| limit.requests_per_minute)
+                .min()
+                .unwrap_or_default(),
+            max_concurrency: limits
+                .iter()
+                .map(|limit| limit.max_concurrency)
+                .min()
+                .unwrap_or_default(),
+            min_delay: limits
+                .iter()
+                .map(|limit| limit.min_delay)
+                .max()
+                .unwrap_or(Duration::ZERO),
+        }
+    }
+
+    async fn get_latest_quote(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+    ) -> Result<Quote, MarketDataError> {
+        self.first_success(
+            |caps| caps.supports_latest && caps.supports_instrument(&context.instrument),
+            |provider| {
+                let instrument = instrument.clone();
+                async move { provider.get_latest_quote(context, instrument).await }
+            },
+        )
+        .await
+    }
+
+    /// Sends the whole batch to the first eligible provider, then only the
+    /// instruments that failed to each provider after it.
+    async fn get_latest_quotes(
+        &self,
+        context: &QuoteContext,
+        instruments: &[ProviderInstrument],
+    ) -> Vec<Result<Quote, MarketDataError>> {
+        let mut quotes: Vec<Option<Quote>> = vec![None; instruments.len()];
+        let mut errors: Vec<Vec<(&'static str, MarketDataError)>> =
+            instruments.iter().map(|_| Vec::new()).collect();
+        let mut pending: Vec<usize> = (0..instruments.len()).collect();
+
+        for provider in &self.providers {
+            if pending.is_empty() {
+                break;
+            }
+            let caps = provider.capabilities();
+            if !(caps.supports_latest && caps.supports_instrument(&context.instrument)) {
+                continue;
+            }
+            let provider_id = provider.id();
+            let batch: Vec<ProviderInstrument> = pending
+                .iter()
+                .map(|&index| instruments[index].clone())
+                .collect();
+            let results = provider.get_latest_quotes(context, &batch).await;
+            let mut still_pending = Vec::new();
+            for (index, result) in pending.into_iter().zip(results) {
+                match result {
+                    Ok(quote) => quotes[index] = Some(quote),
+                    Err(error) => {
+                        log::debug!("{}: {} failed: {}", self.id, provider_id, error);
+                        errors[index].push((provider_id, error));
+                        still_pending.push(index);
+                    }
+                }
+            }
+            pending = still_pending;
+        }
+
+        quotes
+            .into_iter()
+            .zip(errors)
+            .map(|(quote, errors)| quote.ok_or_else(|| self.chain_error(errors)))
+            .collect()
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        self.first_success(
+            |caps| caps.supports_historical && caps.supports_instrument(&context.instrument),
+            |provider| {
+                let instrument = instrument.clone();
+                async move {
+                    provider
+                        .get_historical_quotes(context, instrument, start, end)
+                        .await
+                }
+            },
+        )
+        .await
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        self.first_success(
+            |caps| caps.supports_search,
+            |provider| async move { provider.search(query).await },
+        )
+        .await
+    }
+
+    async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+        self.first_success(
+            |caps| caps.supports_profile,
+            |provider| async move { provider.get_profile(symbol).await },
+        )
+        .await
+    }
+
+    async fn get_splits(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SplitEvent>, MarketDataError> {
+        self.first_success(
+            |caps| caps.supports_instrument(&context.instrument),
+            |provider| {
+                let instrument = instrument.clone();
+                async move { provider.get_splits(context, instrument, start, end).await }
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InstrumentId;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Bond provider that either succeeds or reports the bond as unknown.
+    struct MockBondProvider {
+        id: &'static str,
+        priority: u8,
+        found: bool,
+        supports_profile: bool,
+        calls: AtomicUsize,
+        batches: AtomicUsize,
+    }
+
+    impl MockBondProvider {
+        fn new(id: &'static str, priority: u8, found: bool) -> Self {
+            Self {
+                id,
+                priority,
+                found,
+                supports_profile: true,
+                calls: AtomicUsize::new(0),
+                batches: AtomicUsize::new(0),
+            }
+        }
+
+        fn respond<T>(&self, value: T) -> Result<T, MarketDataError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.found {
+                Ok(value)
+            } else {
+                Err(MarketDataError::SymbolNotFound(format!("{} miss", self.id)))
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for MockBondProvider {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+        fn priority(&self) -> u8 {
+            self.priority
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Bond],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: true,
+                supports_historical: true,
+                supports_search: false,
+                supports_profile: self.supports_profile,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit::default()
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            self.respond(Quote::new(
+                Utc::now(),
+                dec!(98.5),
+                "EUR".to_string(),
+                self.id.to_string(),
+            ))
+        }
+        async fn get_latest_quotes(
+            &self,
+            context: &QuoteContext,
+            instruments: &[ProviderInstrument],
+        ) -> Vec<Result<Quote, MarketDataError>> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            let mut quotes = Vec::with_capacity(instruments.len());
+            for instrument in instruments {
+                quotes.push(self.get_latest_quote(context, instrument.clone()).await);
+            }
+            quotes
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            self.respond(Vec::new())
+        }
+        async fn get_profile(&self, _: &str) -> Result<AssetProfile, MarketDataError> {
+            self.respond(AssetProfile::with_name(format!("{} name", self.id)))
+        }
+    }
+
+    fn bond_context() -> QuoteContext {
+        QuoteContext {
+            instrument: InstrumentId::Bond {
+                isin: Arc::from("DE0001102580"),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        }
+    }
+
+    fn bond_instrument() -> ProviderInstrument {
+        ProviderInstrument::BondIsin {
+            isin: Arc::from("DE0001102580"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_after_symbol_not_found() {
+        let first = Arc::new(MockBondProvider::new("FIRST", 1, false));
+        let second = Arc::new(MockBondProvider::new("SECOND", 2, true));
+        let chain = ProviderChain::new("BOND_CHAIN", vec![second.clone(), first.clone()]);
+        assert_eq!(chain.provider_ids(), vec!["FIRST", "SECOND"]);
+
+        let profile = chain.get_profile("DE0001102580").await.unwrap();
+        assert_eq!(profile.name.as_deref(), Some("SECOND name"));
+
+        let quote = chain
+            .get_latest_quote(&bond_context(), bond_instrument())
+            .await
+            .unwrap();
+        assert_eq!(quote.source, "SECOND");
+        assert_eq!(first.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(second.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_batch_falls_back_for_failed_instruments() {
+        let first = Arc::new(MockBondProvider::new("FIRST", 1, false));
+        let second = Arc::new(MockBondProvider::new("SECOND", 2, true));
+        let chain = ProviderChain::new("BOND_CHAIN", vec![first.clone(), second.clone()]);
+
+        let quotes = chain
+            .get_latest_quotes(&bond_context(), &[bond_instrument(), bond_instrument()])
+            .await;
+
+        assert_eq!(quotes.len(), 2);
+        assert!(quotes
+            .iter()
+            .all(|quote| quote.as_ref().unwrap().source == "SECOND"));
+        // Each provider sees the pending instruments as one batch.
+        assert_eq!(first.batches.load(Ordering::SeqCst), 1);
+        assert_eq!(second.batches.load(Ordering::SeqCst), 1);
+        assert_eq!(second.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_skips_providers_without_capability() {
+        let mut first = MockBondProvider::new("FIRST", 1, true);
+        first.supports_profile = false;
+        let first = Arc::new(first);
+        let second = Arc::new(MockBondProvider::new("SECOND", 2, true));
+        let chain = ProviderChain::new("BOND_CHAIN", vec![first.clone(), second.clone()]);
+
+        let profile = chain.get_profile("DE0001102580").await.unwrap();
+        assert_eq!(profile.name.as_deref(), Some("SECOND name"));
+        assert_eq!(first.calls.load(Ordering::SeqCst), 0);
+
+        let equity = QuoteContext {
+            instrument: InstrumentId::Equity {
+                ticker: Arc::from("AAPL"),
+                mic: None,
+            },
+            ..bond_context()
+        };
+        let result = chain.get_latest_quote(&equity, bond_instrument()).await;
+        assert!(matches!(result, Err(MarketDataError::NoProvidersAvailable)));
+    }
+
+    #[tokio::test]
+    async fn test_collects_errors_when_all_providers_fail() {
+        let chain = ProviderChain::new(
+            "BOND_CHAIN",
+            vec![
+                Arc::new(MockBondProvider::new("FIRST", 1, false)),
+                Arc::new(MockBondProvider::new("SECOND", 2, false)),
+            ],
+        );
+
+        match chain.get_profile("DE0001102580").await {
+            Err(MarketDataError::ProviderError { provider, message }) => {
+                assert_eq!(provider, "BOND_CHAIN");
+                assert!(message.contains("FIRST: Symbol not found: FIRST miss"));
+                assert!(message.contains("SECOND: Symbol not found: SECOND miss"));
+            }
+            other => panic!("expected aggregated provider error, got {:?}", other.err()),
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Circuit-breaking wrapper around a single provider.
+//!
+//! `ProviderRegistry` consults its circuit breaker before every call, but a
+//! provider used on its own (e.g. for batch name enrichment) keeps paying the
+//! full timeout while it is down. `CircuitBreakingProvider` wraps any provider
+//! with the same `CircuitBreaker`: after enough consecutive failures calls are
+//! rejected immediately until the recovery timeout passes, then one probe at a
+//! time is let through until the circuit closes again.
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::errors::MarketDataError;
+use crate::models::{
+    AssetProfile, ProviderInstrument, Quote, QuoteContext, SearchResult, SplitEvent,
+};
+use crate::registry::{CircuitBreaker, CircuitState};
+
+use super::{MarketDataProvider, ProviderCapabilities, RateLimit};
+
+/// A `MarketDataProvider` that stops calling its inner provider while the
+/// provider's circuit is open.
+pub struct CircuitBreakingProvider {
+    inner: Arc<dyn MarketDataProvider>,
+    breaker: Arc<CircuitBreaker>,
+    probe_in_flight: AtomicBool,
+}
+
+/// Clears the probe flag when a half-open probe finishes or is dropped.
+struct ProbeGuard<'a>(Option<&'a AtomicBool>);
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(flag) = self.0 {
+            flag.store(false, Ordering::Release);
+        }
+    }
+}
+
+impl CircuitBreakingProvider {
+    /// Wrap `inner` with its own circuit breaker using default settings.
+    pub fn new(inner: Arc<dyn MarketDataProvider>) -> Self {
+        Self {
+            inner,
+            breaker: Arc::new(CircuitBreaker::new()),
+            probe_in_flight: AtomicBool::new(false),
+        }
+    }
+
+    /// Use `breaker` instead, e.g. to share circuit state with a registry or
+    /// to configure thresholds.
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Current circuit state of the wrapped provider.
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker.state(&Cow::Borrowed(self.inner.id()))
+    }
+
+    fn circuit_open_error(&self) -> MarketDataError {
+        MarketDataError::ProviderError {
+            provider: self.inner.id().to_string(),
+            message: "Circuit open, skipping call".to_string(),
+        }
+    }
+
+    /// Run `call` unless the circuit is open, and record its outcome.
+    async fn guarded<T, Fut>(&self, call: Fut) -> Result<T, MarketDataError>
+    where
+        Fut: Future<Output = Result<T, MarketDataError>> + Send,
+    {
+        self.guarded_by(
+            call,
+            |result| matches!(result, Err(error) if is_provider_failure(error)),
+        )
+        .await?
+    }
+
+    /// Run `call` unless the circuit is open, recording a failure when
+    /// `failed` says its output shows the provider is unhealthy. Errs only
+    /// when the circuit rejects the call.
+    async fn guarded_by<T, Fut>(
+        &self,
+        call: Fut,
+        failed: impl Fn(&T) -> bool + Send,
+    ) -> Result<T, MarketDataError>
+    where
+        Fut: Future<Output = T> + Send,
+    {
+        let provider_id = Cow::Borrowed(self.inner.id());
+        if !self.breaker.is_allowed(&provider_id) {
+            return Err(self.circuit_open_error());
+        }
+
+        let _probe = if self.breaker.state(&provider_id) == CircuitState::HalfOpen {
+            if self
+                .probe_in_flight
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+            {
+                return Err(self.circuit_open_error());
+            }
+            ProbeGuard(Some(&self.probe_in_flight))
+        } else {
+            ProbeGuard(None)
+        };
+
+        let output = call.await;
+        if failed(&output) {
+            self.breaker.record_failure(&provider_id);
+        } else {
+            self.breaker.record_success(&provider_id);
+        }
+        Ok(output)
+    }
+}
+
+/// Errors that say the provider itself is unhealthy, as opposed to answers
+/// about the requested symbol or external throttling.
+fn is_provider_failure(error: &MarketDataError) -> bool {
+    matches!(
+        error,
+        MarketDataError::ProviderError { .. }
+            | MarketDataError::Timeout { .. }
+            | MarketDataError::Network(_)
+    )
+}
+
+#[async_trait]
+impl MarketDataProvider for CircuitBreakingProvider {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn rate_limit(&self) -> RateLimit {
+        self.inner.rate_limit()
+    }
+
+    async fn get_latest_quote(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+    ) -> Result<Quote, MarketDataError> {
+        self.guarded(self.inner.get_latest_quote(context, instrument))
+            .await
+    }
+
+    /// Forwards the batch as one guarded call. It counts as a failure only
+    /// when every instrument failed with a provider-health error.
+    async fn get_latest_quotes(
+        &self,
+        context: &QuoteContext,
+        instruments: &[ProviderInstrument],
+    ) -> Vec<Result<Quote, MarketDataError>> {
+        let batch = self
+            .guarded_by(
+                self.inner.get_latest_quotes(context, instruments),
+                |quotes: &Vec<Result<Quote, MarketDataError>>| {
+                    !quotes.is_empty()
+                        && quotes
+                            .iter()
+                            .all(|quote| matches!(quote, Err(error) if is_provider_failure(error)))
+                },
+            )
+            .await;
+        match batch {
+            Ok(quotes) => quotes,
+            Err(_) => instruments
+                .iter()
+                .map(|_| Err(self.circuit_open_error()))
+                .collect(),
+        }
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        self.guarded(
+            self.inner
+                .get_historical_quotes(context, instrument, start, end),
+        )
+        .await
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        self.guarded(self.inner.search(query)).await
+    }
+
+    async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+        self.guarded(self.inner.get_profile(symbol)).await
+    }
+
+    async fn get_splits(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SplitEvent>, MarketDataError> {
+        self.guarded(self.inner.get_splits(context, instrument, start, end))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coverage, InstrumentId, InstrumentKind};
+    use crate::registry::CircuitBreakerConfig;
+    use rust_decimal::Decimal;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    /// Profile provider whose failures can be switched on and off.
+    struct FlakyProvider {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyProvider {
+        fn new() -> Self {
+            Self {
+                failing: AtomicBool::new(true),
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn respond<T>(&self, value: T) -> Result<T, MarketDataError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                Err(MarketDataError::ProviderError {
+                    provider: "FLAKY".to_string(),
+                    message: "HTTP 403 Forbidden".to_string(),
+                })
+            } else {
+                Ok(value)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for FlakyProvider {
+        fn id(&self) -> &'static str {
+            "FLAKY"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Bond],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: false,
+                supports_historical: false,
+                supports_search: false,
+                supports_profile: true,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit::default()
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            Err(MarketDataError::NoDataForRange)
+        }
+        async fn get_latest_quotes(
+            &self,
+            _: &QuoteContext,
+            instruments: &[ProviderInstrument],
+        ) -> Vec<Result<Quote, MarketDataError>> {
+            let failed = self.respond(()).is_err();
+            instruments
+                .iter()
+                .map(|_| {
+                    if failed {
+                        Err(MarketDataError::ProviderError {
+                            provider: "FLAKY".to_string(),
+                            message: "HTTP 403 Forbidden".to_string(),
+                        })
+                    } else {
+                        Ok(Quote::new(
+                            Utc::now(),
+                            Decimal::ONE_HUNDRED,
+                            "EUR".to_string(),
+                            "FLAKY".to_string(),
+                        ))
+                    }
+                })
+                .collect()
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            Err(MarketDataError::NoDataForRange)
+        }
+        async fn get_profile(&self, _: &str) -> Result<AssetProfile, MarketDataError> {
+            self.respond(AssetProfile::with_name("Recovered"))
+        }
+    }
+
+    fn wrap(inner: Arc<FlakyProvider>) -> CircuitBreakingProvider {
+        CircuitBreakingProvider::new(inner).with_breaker(Arc::new(CircuitBreaker::with_config(
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                recovery_timeout: Duration::from_millis(50),
+                half_open_success_threshold: 1,
+            },
+        )))
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_threshold_and_short_circuits() {
+        let inner = Arc::new(FlakyProvider::new());
+        let provider = wrap(inner.clone());
+
+        assert!(provider.get_profile("X").await.is_err());
+        assert_eq!(provider.circuit_state(), CircuitState::Closed);
+        assert!(provider.get_profile("X").await.is_err());
+        assert_eq!(provider.circuit_state(), CircuitState::Open);
+
+        let result = provider.get_profile("X").await;
+        assert!(matches!(
+            result,
+            Err(MarketDataError::ProviderError { ref message, .. }) if message.contains("Circuit open")
+        ));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_probe_after_cooldown_closes_circuit() {
+        let inner = Arc::new(FlakyProvider::new());
+        let provider = wrap(inner.clone());
+        let _ = provider.get_profile("X").await;
+        let _ = provider.get_profile("X").await;
+        assert_eq!(provider.circuit_state(), CircuitState::Open);
+
+        // A failed probe reopens the circuit.
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(provider.get_profile("X").await.is_err());
+        assert_eq!(provider.circuit_state(), CircuitState::Open);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+
+        inner.failing.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let profile = provider.get_profile("X").await.unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Recovered"));
+        assert_eq!(provider.circuit_state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_only_one_probe_while_half_open() {
+        let inner = Arc::new(FlakyProvider::new());
+        let provider = wrap(inner.clone());
+        let _ = provider.get_profile("X").await;
+        let _ = provider.get_profile("X").await;
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        provider.probe_in_flight.store(true, Ordering::SeqCst);
+        assert!(provider.get_profile("X").await.is_err());
+        assert_eq!(provider.circuit_state(), CircuitState::HalfOpen);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failing_batches_open_the_circuit() {
+        let inner = Arc::new(FlakyProvider::new());
+        let provider = wrap(inner.clone());
+        let context = QuoteContext {
+            instrument: InstrumentId::Bond {
+                isin: Arc::from("DE0001102580"),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        };
+        let instruments = vec![
+            ProviderInstrument::BondIsin {
+                isin: Arc::from("DE0001102580"),
+            };
+            2
+        ];
+
+        // Each batch is one call to the inner provider and one failure.
+        let _ = provider.get_latest_quotes(&context, &instruments).await;
+        assert_eq!(provider.circuit_state(), CircuitState::Closed);
+        let _ = provider.get_latest_quotes(&context, &instruments).await;
+        assert_eq!(provider.circuit_state(), CircuitState::Open);
+
+        let results = provider.get_latest_quotes(&context, &instruments).await;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| matches!(
+            result,
+            Err(MarketDataError::ProviderError { message, .. }) if message.contains("Circuit open")
+        )));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_only_provider_health_errors_count_as_failures() {
+        assert!(is_provider_failure(&MarketDataError::Timeout {
+            provider: "FLAKY".to_string()
+        }));
+        assert!(!is_provider_failure(&MarketDataError::SymbolNotFound(
+            "X".to_string()
+        )));
+        assert!(!is_provider_failure(&MarketDataError::RateLimited {
+            provider: "FLAKY".to_string()
+        }));
+    }
+}
@@ -0,0 +1,640 @@
+//! ECB yield-curve calculated-price provider.
+//!
+//! Prices euro-area government bonds from the European Central Bank's daily
+//! AAA-rated euro-area government par yield curve, published through the ECB
+//! Data Portal (SDW) `YC` dataset. One CSV request per calendar year returns
+//! every business day's curve; years are cached in memory.
+//!
+//! **Data flow:**
+//! 1. Fetch the par yield series (`PY_1Y` … `PY_30Y`) for the relevant year(s).
+//! 2. Interpolate the yield at the bond's remaining maturity.
+//! 3. Discount coupon + principal cash flows to get PV as fraction-of-par,
+//!    using the same pricing helpers as the US Treasury provider.
+//!
+//! The AAA curve is a benchmark: lower-rated sovereigns trade at a spread to
+//! it, so prices for those bonds are approximations.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use log::{debug, warn};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::errors::MarketDataError;
+use crate::models::{
+    BondQuoteMetadata, Coverage, InstrumentKind, ProviderInstrument, Quote, QuoteContext,
+};
+use crate::pricing::{
+    calculate_price, face_value, make_quote, settlement_date, InterpolationMethod, YieldCurve,
+};
+use crate::provider::utils::{read_text_capped, DEFAULT_MAX_BODY_BYTES};
+use crate::provider::{MarketDataProvider, ProviderCapabilities, RateLimit};
+
+const PROVIDER_ID: &str = "ECB_YIELD_CURVE";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// ECB Data Portal series for the AAA euro-area government par yield curve.
+/// The tenor list is appended as a `+`-separated key dimension.
+const ECB_SERIES_URL: &str =
+    "https://data-api.ecb.europa.eu/service/data/YC/B.U2.EUR.4F.G_N_A.SV_C_YM";
+
+/// Par yield tenors requested from the ECB.
+const PAR_YIELD_TENORS: &[&str] = &[
+    "PY_1Y", "PY_2Y", "PY_3Y", "PY_5Y", "PY_7Y", "PY_10Y", "PY_15Y", "PY_20Y", "PY_30Y",
+];
+
+/// Default face value when the bond metadata has none.
+const DEFAULT_FACE_VALUE: f64 = 100.0;
+
+/// Price currency when the bond metadata names none; the caller's currency
+/// hint is never used.
+const EUR_CURRENCY: &str = "EUR";
+
+/// Euro-area government bonds settle T+2 unless the quote context says
+/// otherwise.
+const DEFAULT_SETTLEMENT_OFFSET_DAYS: i64 = 2;
+
+/// ISIN prefixes of euro-area sovereign issuers, plus EU-issued bonds.
+const EURO_AREA_ISIN_PREFIXES: &[&str] = &[
+    "AT", "BE", "BG", "CY", "DE", "EE", "ES", "EU", "FI", "FR", "GR", "HR", "IE", "IT", "LT", "LU",
+    "LV", "MT", "NL", "PT", "SI", "SK",
+];
+
+/// Default age after which the current year's curves are re-fetched.
+const DEFAULT_CURRENT_YEAR_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Dated curves for one calendar year, sorted by date.
+type YearCurves = Vec<(NaiveDate, YieldCurve)>;
+
+struct CachedYear {
+    curves: YearCurves,
+    fetched_at: Instant,
+}
+
+// ---------------------------------------------------------------------------
+// Provider
+// ---------------------------------------------------------------------------
+
+pub struct EcbYieldCurveProvider {
+    client: reqwest::Client,
+    /// Cached yield curves keyed by calendar year.
+    curve_cache: Arc<RwLock<HashMap<i32, CachedYear>>>,
+    /// Yield interpolation method used when pricing.
+    interpolation: InterpolationMethod,
+    /// Age after which the current year's curves are re-fetched.
+    current_year_ttl: Duration,
+    /// Largest response body accepted from the ECB.
+    max_body_bytes: usize,
+}
+
+impl Default for EcbYieldCurveProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EcbYieldCurveProvider {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            curve_cache: Arc::new(RwLock::new(HashMap::new())),
+            interpolation: InterpolationMethod::default(),
+            current_year_ttl: DEFAULT_CURRENT_YEAR_TTL,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+        }
+    }
+
+    /// Interpolate yields with the given method (default linear).
+    pub fn with_interpolation(mut self, interpolation: InterpolationMethod) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Re-fetch the current year's curves once they are older than `ttl`
+    /// (default 6 hours). Past years stay cached indefinitely.
+    pub fn with_current_year_ttl(mut self, ttl: Duration) -> Self {
+        self.current_year_ttl = ttl;
+        self
+    }
+
+    /// Reject curve responses larger than `max_bytes` (default 16 MiB).
+    pub fn with_max_body_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_body_bytes = max_bytes;
+        self
+    }
+
+    fn is_cached_year_fresh(&self, year: i32, cached: &CachedYear) -> bool {
+        year < Utc::now().year() || cached.fetched_at.elapsed() < self.current_year_ttl
+    }
+
+    /// Make sure the curves for `year` are cached and fresh.
+    async fn ensure_curves(&self, year: i32) -> Result<(), MarketDataError> {
+        if let Some(cached) = self.curve_cache.read().await.get(&year) {
+            if self.is_cached_year_fresh(year, cached) {
+                return Ok(());
+            }
+        }
+
+        let curves = self.fetch_year_curves(year).await?;
+        self.curve_cache.write().await.insert(
+            year,
+            CachedYear {
+                curves,
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn fetch_year_curves(&self, year: i32) -> Result<YearCurves, MarketDataError> {
+        let url = format!(
+            "{}.{}?startPeriod={}-01-01&endPeriod={}-12-31&format=csvdata&detail=dataonly",
+            ECB_SERIES_URL,
+            PAR_YIELD_TENORS.join("+"),
+            year,
+            year
+        );
+
+        debug!("Fetching ECB yield curve for year {}", year);
+
+        let resp =
+            self.client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| MarketDataError::ProviderError {
+                    provider: PROVIDER_ID.to_string(),
+                    message: format!("HTTP request failed: {}", e),
+                })?;
+
+        // The ECB answers 404 when a period has no observations yet.
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !resp.status().is_success() {
+            return Err(MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("HTTP {}", resp.status()),
+            });
+        }
+
+        let body = read_text_capped(resp, self.max_body_bytes, PROVIDER_ID).await?;
+
+        parse_ecb_yield_curve_csv(&body)
+    }
+
+    /// Look up the curve for `date`, falling back to the latest earlier
+    /// business day.
+    async fn get_curve_for_date(&self, date: NaiveDate) -> Result<YieldCurve, MarketDataError> {
+        self.ensure_curves(date.year()).await?;
+
+        let cache = self.curve_cache.read().await;
+        let latest = cache
+            .get(&date.year())
+            .and_then(|cached| cached.curves.iter().rev().find(|(d, _)| *d <= date))
+            .map(|(_, curve)| curve.clone());
+        if let Some(curve) = latest {
+            return Ok(curve);
+        }
+        drop(cache);
+
+        // Early January: the year's first curve may not be published yet.
+        let previous_year = date.year() - 1;
+        self.ensure_curves(previous_year).await?;
+        self.curve_cache
+            .read()
+            .await
+            .get(&previous_year)
+            .and_then(|cached| cached.curves.last())
+            .map(|(_, curve)| curve.clone())
+            .ok_or(MarketDataError::NoDataForRange)
+    }
+
+    /// Price `bond` off `curve` for settlement on `settlement`.
+    fn price(
+        &self,
+        curve: &YieldCurve,
+        settlement: NaiveDate,
+        bond: &BondQuoteMetadata,
+    ) -> Result<f64, MarketDataError> {
+        let coupon_rate: f64 = bond.coupon_rate.try_into().unwrap_or(0.0);
+        let face_value = face_value(PROVIDER_ID, bond, DEFAULT_FACE_VALUE);
+        let call = bond.call_date.map(|call_date| {
+            let call_price = bond
+                .call_price
+                .and_then(|p| p.try_into().ok())
+                .unwrap_or(1.0);
+            (call_date, call_price)
+        });
+
+        calculate_price(
+            PROVIDER_ID,
+            curve,
+            self.interpolation,
+            settlement,
+            bond.maturity_date,
+            coupon_rate,
+            &bond.coupon_frequency,
+            face_value,
+            call,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MarketDataProvider impl
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl MarketDataProvider for EcbYieldCurveProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn priority(&self) -> u8 {
+        10
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            instrument_kinds: &[InstrumentKind::Bond],
+            coverage: Coverage::bonds_with_isin_prefixes(EURO_AREA_ISIN_PREFIXES),
+            supports_latest: true,
+            supports_historical: true,
+            supports_search: false,
+            supports_profile: false,
+        }
+    }
+
+    fn rate_limit(&self) -> RateLimit {
+        RateLimit {
+            requests_per_minute: 30,
+            max_concurrency: 2,
+            min_delay: Duration::from_millis(500),
+        }
+    }
+
+    async fn get_latest_quote(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+    ) -> Result<Quote, MarketDataError> {
+        let isin = extract_isin(&instrument)?;
+        guard_euro_area(&isin)?;
+        let bond = require_bond_metadata(context)?;
+
+        let today = Utc::now().date_naive();
+        let curve = self.get_curve_for_date(today).await.inspect_err(|e| {
+            warn!(
+                "{}: yield curve fetch failed for {}: {}",
+                PROVIDER_ID, isin, e
+            );
+        })?;
+        let settlement = settlement_date(today, settlement_offset(context));
+        let price = self.price(&curve, settlement, bond).inspect_err(|e| {
+            warn!(
+                "{}: price calculation failed for {}: {}",
+                PROVIDER_ID, isin, e
+            );
+        })?;
+        debug!(
+            "{}: {} price={:.6} (coupon={}, maturity={}, freq={})",
+            PROVIDER_ID, isin, price, bond.coupon_rate, bond.maturity_date, bond.coupon_frequency
+        );
+
+        make_quote(PROVIDER_ID, today, price, bond_currency(bond))
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        let isin = extract_isin(&instrument)?;
+        guard_euro_area(&isin)?;
+        let bond = require_bond_metadata(context)?;
+
+        let offset = settlement_offset(context);
+
+        let start_date = start.date_naive();
+        let end_date = end.date_naive();
+        let years = start_date.year()..=end_date.year();
+
+        let mut failures = Vec::new();
+        for year in years.clone() {
+            if let Err(e) = self.ensure_curves(year).await {
+                warn!(
+                    "{}: yield curve fetch failed for {} ({}): {}",
+                    PROVIDER_ID, isin, year, e
+                );
+                failures.push(e);
+            }
+        }
+        if !failures.is_empty() && failures.len() == years.clone().count() {
+            return Err(failures.remove(0));
+        }
+
+        let cache = self.curve_cache.read().await;
+        let mut quotes = Vec::new();
+        for year in years {
+            let Some(cached) = cache.get(&year) else {
+                continue;
+            };
+            for (date, curve) in &cached.curves {
+                if *date < start_date || *date > end_date {
+                    continue;
+                }
+                match self
+                    .price(curve, settlement_date(*date, offset), bond)
+                    .and_then(|price| make_quote(PROVIDER_ID, *date, price, bond_currency(bond)))
+                {
+                    Ok(quote) => quotes.push(quote),
+                    Err(e) => debug!("Skipping date {}: {}", date, e),
+                }
+            }
+        }
+
+        quotes.sort_by_key(|q| q.timestamp);
+        Ok(quotes)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn extract_isin(instrument: &ProviderInstrument) -> Result<String, MarketDataError> {
+    match instrument {
+        ProviderInstrument::BondIsin { isin } => Ok(isin.to_string()),
+        _ => Err(MarketDataError::UnsupportedAssetType(format!(
+            "{:?}",
+            instrument
+        ))),
+    }
+}
+
+/// The bond's currency from its metadata, else EUR.
+fn bond_currency(bond: &BondQuoteMetadata) -> &str {
+    bond.currency.as_deref().unwrap_or(EUR_CURRENCY)
+}
+
+/// Business days to settlement: the context's offset, else T+2.
+fn settlement_offset(context: &QuoteContext) -> i64 {
+    context
+        .settlement_offset_days
+        .unwrap_or(DEFAULT_SETTLEMENT_OFFSET_DAYS)
+}
+
+/// Only accept ISINs of euro-area issuers.
+fn guard_euro_area(isin: &str) -> Result<(), MarketDataError> {
+    if !EURO_AREA_ISIN_PREFIXES
+        .iter()
+        .any(|prefix| isin.starts_with(prefix))
+    {
+        return Err(MarketDataError::SymbolNotFound(format!(
+            "{} is not a euro-area ISIN",
+            isin
+        )));
+    }
+    Ok(())
+}
+
+fn require_bond_metadata(context: &QuoteContext) -> Result<&BondQuoteMetadata, MarketDataError> {
+    context
+        .bond_metadata
+        .as_ref()
+        .ok_or_else(|| MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: "Bond metadata (coupon, maturity) required for calculated pricing".to_string(),
+        })
+}
+
+/// Tenor in years for an ECB `DATA_TYPE_FM` code such as `PY_10Y`, `PY_6M`
+/// or `PY_1Y6M`.
+fn parse_tenor(code: &str) -> Option<f64> {
+    let tenor = code.strip_prefix("PY_")?;
+    let (years, months) = match tenor.split_once('Y') {
+        Some((years, rest)) => (years.parse::<f64>().ok()?, rest),
+        None => (0.0, tenor),
+    };
+    let months = match months.strip_suffix('M') {
+        Some(months) => months.parse::<f64>().ok()?,
+        None if months.is_empty() => 0.0,
+        None => return None,
+    };
+    let total = years + months / 12.0;
+    (total > 0.0).then_some(total)
+}
+
+/// Parse an ECB Data Portal `csvdata` response into dated yield curves.
+///
+/// Rows are one observation each; the header names the columns. Rows with an
+/// unknown tenor or missing value are skipped.
+fn parse_ecb_yield_curve_csv(csv: &str) -> Result<YearCurves, MarketDataError> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<&str> = lines.next().map(split_csv_line).unwrap_or_default();
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| *h == name)
+            .ok_or_else(|| MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("ECB response is missing the {} column", name),
+            })
+    };
+    let tenor_col = column("DATA_TYPE_FM")?;
+    let date_col = column("TIME_PERIOD")?;
+    let value_col = column("OBS_VALUE")?;
+
+    let mut by_date: BTreeMap<NaiveDate, Vec<(f64, f64)>> = BTreeMap::new();
+    for line in lines {
+        let fields = split_csv_line(line);
+        let field = |index: usize| fields.get(index).copied().unwrap_or_default();
+        let Some(tenor) = parse_tenor(field(tenor_col)) else {
+            continue;
+        };
+        let Ok(date) = NaiveDate::parse_from_str(field(date_col), "%Y-%m-%d") else {
+            continue;
+        };
+        let Ok(yield_pct) = field(value_col).parse::<f64>() else {
+            continue;
+        };
+        by_date.entry(date).or_default().push((tenor, yield_pct));
+    }
+
+    Ok(by_date
+        .into_iter()
+        .map(|(date, mut points)| {
+            points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            (date, YieldCurve(points))
+        })
+        .collect())
+}
+
+/// Split a CSV line on commas, trimming surrounding quotes. The `dataonly`
+/// format has no quoted commas.
+fn split_csv_line(line: &str) -> Vec<&str> {
+    line.split(',')
+        .map(|field| field.trim().trim_matches('"'))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const SAMPLE_CSV: &str = "\
+KEY,FREQ,REF_AREA,CURRENCY,PROVIDER_FM,INSTRUMENT_FM,PROVIDER_FM_ID,DATA_TYPE_FM,TIME_PERIOD,OBS_VALUE
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.PY_10Y,B,U2,EUR,4F,G_N_A,SV_C_YM,PY_10Y,2025-06-02,2.5012
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.PY_10Y,B,U2,EUR,4F,G_N_A,SV_C_YM,PY_10Y,2025-06-03,2.5331
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.PY_1Y,B,U2,EUR,4F,G_N_A,SV_C_YM,PY_1Y,2025-06-02,1.8420
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.PY_1Y,B,U2,EUR,4F,G_N_A,SV_C_YM,PY_1Y,2025-06-03,1.8511
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.PY_5Y,B,U2,EUR,4F,G_N_A,SV_C_YM,PY_5Y,2025-06-02,2.0975
+YC.B.U2.EUR.4F.G_N_A.SV_C_YM.PY_5Y,B,U2,EUR,4F,G_N_A,SV_C_YM,PY_5Y,2025-06-03,
+";
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tenor() {
+        assert_eq!(parse_tenor("PY_10Y"), Some(10.0));
+        assert_eq!(parse_tenor("PY_6M"), Some(0.5));
+        assert_eq!(parse_tenor("PY_1Y6M"), Some(1.5));
+        assert_eq!(parse_tenor("SR_10Y"), None);
+        assert_eq!(parse_tenor("PY_XY"), None);
+    }
+
+    #[test]
+    fn test_parse_ecb_yield_curve_csv() {
+        let curves = parse_ecb_yield_curve_csv(SAMPLE_CSV).unwrap();
+        assert_eq!(curves.len(), 2);
+
+        let (first_date, first) = &curves[0];
+        assert_eq!(*first_date, date(2025, 6, 2));
+        assert_eq!(first.0, vec![(1.0, 1.8420), (5.0, 2.0975), (10.0, 2.5012)]);
+
+        // The empty 5Y observation is skipped; the curve interpolates across it.
+        let (second_date, second) = &curves[1];
+        assert_eq!(*second_date, date(2025, 6, 3));
+        assert_eq!(second.0, vec![(1.0, 1.8511), (10.0, 2.5331)]);
+        assert!((second.interpolate(5.5).unwrap() - 2.1921).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_parse_ecb_yield_curve_csv_handles_quotes_and_blank_lines() {
+        let csv = "\"KEY\",\"DATA_TYPE_FM\",\"TIME_PERIOD\",\"OBS_VALUE\"\n\n\
+                   \"YC.X\",\"PY_2Y\",\"2025-01-02\",\"2.1\"\n";
+        let curves = parse_ecb_yield_curve_csv(csv).unwrap();
+        assert_eq!(curves.len(), 1);
+        assert_eq!(curves[0].0, date(2025, 1, 2));
+        assert_eq!(curves[0].1 .0, vec![(2.0, 2.1)]);
+    }
+
+    #[test]
+    fn test_parse_ecb_yield_curve_csv_missing_column() {
+        let result = parse_ecb_yield_curve_csv("KEY,TIME_PERIOD,OBS_VALUE\n");
+        assert!(matches!(result, Err(MarketDataError::ProviderError { .. })));
+        assert!(parse_ecb_yield_curve_csv("").is_err());
+    }
+
+    #[test]
+    fn test_guard_euro_area() {
+        assert!(guard_euro_area("DE0001102580").is_ok());
+        assert!(guard_euro_area("FR0013508470").is_ok());
+        assert!(guard_euro_area("US91282CJL54").is_err());
+    }
+
+    #[test]
+    fn test_capabilities_cover_euro_area_bonds_only() {
+        let provider = EcbYieldCurveProvider::new();
+        let caps = provider.capabilities();
+        assert_eq!(caps.instrument_kinds, &[InstrumentKind::Bond]);
+        assert!(
+            caps.supports_instrument(&crate::models::InstrumentId::Bond {
+                isin: Arc::from("DE0001102580"),
+            })
+        );
+        assert!(
+            !caps.supports_instrument(&crate::models::InstrumentId::Bond {
+                isin: Arc::from("US91282CJL54"),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prices_bund_from_cached_curve() {
+        let provider = EcbYieldCurveProvider::new();
+        let curves = parse_ecb_yield_curve_csv(SAMPLE_CSV).unwrap();
+        provider.curve_cache.write().await.insert(
+            2025,
+            CachedYear {
+                curves,
+                fetched_at: Instant::now(),
+            },
+        );
+
+        let context = QuoteContext {
+            instrument: crate::models::InstrumentId::Bond {
+                isin: "DE0001102580".into(),
+            },
+            overrides: None,
+            currency_hint: Some("USD".into()),
+            preferred_provider: None,
+            bond_metadata: Some(BondQuoteMetadata {
+                coupon_rate: dec!(0.025),
+                maturity_date: date(2035, 6, 3),
+                face_value: Some(dec!(100)),
+                coupon_frequency: "ANNUAL".to_string(),
+                is_tips: false,
+                index_ratio: None,
+                ref_cpi_on_dated_date: None,
+                call_date: None,
+                call_price: None,
+                currency: None,
+            }),
+            mic_hint: None,
+            settlement_offset_days: None,
+        };
+        let instrument = ProviderInstrument::BondIsin {
+            isin: Arc::from("DE0001102580"),
+        };
+
+        let quotes = provider
+            .get_historical_quotes(
+                &context,
+                instrument,
+                DateTime::<Utc>::from_naive_utc_and_offset(
+                    date(2025, 6, 3).and_hms_opt(0, 0, 0).unwrap(),
+                    Utc,
+                ),
+                DateTime::<Utc>::from_naive_utc_and_offset(
+                    date(2025, 6, 3).and_hms_opt(23, 0, 0).unwrap(),
+                    Utc,
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].currency, "EUR");
+        assert_eq!(quotes[0].source, PROVIDER_ID);
+        // 2.5% annual coupon at a ~2.53% par yield prices just under par.
+        assert!(quotes[0].close > dec!(0.99) && quotes[0].close < dec!(1));
+    }
+}
@@ -668,6 +668,8 @@ mod tests {
             currency_hint: currency_hint.map(Cow::Borrowed),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
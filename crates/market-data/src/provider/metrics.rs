@@ -0,0 +1,400 @@
+//! Call metrics for market data providers.
+//!
+//! `InstrumentedProvider` wraps any provider and reports one
+//! `ProviderCallMetric` per call (operation, instrument kind, duration and
+//! outcome) to a pluggable `MetricsSink`. The wrapper is otherwise
+//! transparent: id, priority, capabilities and rate limits are the wrapped
+//! provider's.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::errors::MarketDataError;
+use crate::models::{
+    AssetProfile, InstrumentKind, ProviderInstrument, Quote, QuoteContext, SearchResult, SplitEvent,
+};
+
+use super::{MarketDataProvider, ProviderCapabilities, RateLimit};
+
+/// Outcome of a provider call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallOutcome {
+    Success,
+    /// Failed with the given error class (see [`error_class`]).
+    Error(&'static str),
+}
+
+impl CallOutcome {
+    fn of<T>(result: &Result<T, MarketDataError>) -> Self {
+        match result {
+            Ok(_) => CallOutcome::Success,
+            Err(error) => CallOutcome::Error(error_class(error)),
+        }
+    }
+}
+
+/// A single provider call.
+#[derive(Clone, Debug)]
+pub struct ProviderCallMetric {
+    pub provider: &'static str,
+    pub operation: &'static str,
+    /// Instrument kind for quote and split calls; `None` for search and profile.
+    pub instrument_kind: Option<InstrumentKind>,
+    pub duration: Duration,
+    pub outcome: CallOutcome,
+}
+
+/// Destination for provider call metrics.
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, metric: &ProviderCallMetric);
+}
+
+/// Sink that discards every metric.
+#[derive(Debug, Default)]
+pub struct NoopMetricsSink;
+
+impl MetricsSink for NoopMetricsSink {
+    fn record(&self, _metric: &ProviderCallMetric) {}
+}
+
+/// Sink that counts calls per operation and outcome.
+#[derive(Debug, Default)]
+pub struct InMemoryMetricsSink {
+    counts: Mutex<HashMap<(&'static str, CallOutcome), u64>>,
+}
+
+impl InMemoryMetricsSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of `operation` calls that ended with `outcome`.
+    pub fn count(&self, operation: &'static str, outcome: CallOutcome) -> u64 {
+        self.counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&(operation, outcome))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl MetricsSink for InMemoryMetricsSink {
+    fn record(&self, metric: &ProviderCallMetric) {
+        *self
+            .counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry((metric.operation, metric.outcome))
+            .or_insert(0) += 1;
+    }
+}
+
+/// Stable label for the kind of error a provider returned.
+pub fn error_class(error: &MarketDataError) -> &'static str {
+    match error {
+        MarketDataError::SymbolNotFound(_) => "symbol_not_found",
+        MarketDataError::UnsupportedAssetType(_) => "unsupported_asset_type",
+        MarketDataError::NoDataForRange => "no_data_for_range",
+        MarketDataError::RateLimited { .. } => "rate_limited",
+        MarketDataError::Timeout { .. } => "timeout",
+        MarketDataError::ProviderError { .. } => "provider_error",
+        MarketDataError::ResolutionFailed { .. } => "resolution_failed",
+        MarketDataError::CircuitOpen { .. } => "circuit_open",
+        MarketDataError::ValidationFailed { .. } => "validation_failed",
+        MarketDataError::NoProvidersAvailable => "no_providers_available",
+        MarketDataError::AllProvidersFailed => "all_providers_failed",
+        MarketDataError::NotSupported { .. } => "not_supported",
+        MarketDataError::Network(_) => "network",
+    }
+}
+
+/// A `MarketDataProvider` that reports every call to a `MetricsSink`.
+pub struct InstrumentedProvider {
+    inner: Arc<dyn MarketDataProvider>,
+    sink: Arc<dyn MetricsSink>,
+}
+
+impl InstrumentedProvider {
+    /// Wrap `inner` with a sink that discards metrics.
+    pub fn new(inner: Arc<dyn MarketDataProvider>) -> Self {
+        Self {
+            inner,
+            sink: Arc::new(NoopMetricsSink),
+        }
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Await `call`, timing it and reporting the outcome.
+    async fn measured<T, Fut>(
+        &self,
+        operation: &'static str,
+        instrument_kind: Option<InstrumentKind>,
+        call: Fut,
+    ) -> Result<T, MarketDataError>
+    where
+        Fut: Future<Output = Result<T, MarketDataError>> + Send,
+    {
+        let started = Instant::now();
+        let result = call.await;
+        self.sink.record(&ProviderCallMetric {
+            provider: self.inner.id(),
+            operation,
+            instrument_kind,
+            duration: started.elapsed(),
+            outcome: CallOutcome::of(&result),
+        });
+        result
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for InstrumentedProvider {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn rate_limit(&self) -> RateLimit {
+        self.inner.rate_limit()
+    }
+
+    async fn get_latest_quote(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+    ) -> Result<Quote, MarketDataError> {
+        self.measured(
+            "latest_quote",
+            Some(context.instrument.instrument_kind()),
+            self.inner.get_latest_quote(context, instrument),
+        )
+        .await
+    }
+
+    /// Forwards the batch as one call and reports one `latest_quotes` metric
+    /// per instrument, each carrying the duration of the whole batch.
+    async fn get_latest_quotes(
+        &self,
+        context: &QuoteContext,
+        instruments: &[ProviderInstrument],
+    ) -> Vec<Result<Quote, MarketDataError>> {
+        let started = Instant::now();
+        let quotes = self.inner.get_latest_quotes(context, instruments).await;
+        let duration = started.elapsed();
+        for quote in &quotes {
+            self.sink.record(&ProviderCallMetric {
+                provider: self.inner.id(),
+                operation: "latest_quotes",
+                instrument_kind: Some(context.instrument.instrument_kind()),
+                duration,
+                outcome: CallOutcome::of(quote),
+            });
+        }
+        quotes
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        self.measured(
+            "historical_quotes",
+            Some(context.instrument.instrument_kind()),
+            self.inner
+                .get_historical_quotes(context, instrument, start, end),
+        )
+        .await
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        self.measured("search", None, self.inner.search(query))
+            .await
+    }
+
+    async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+        self.measured("profile", None, self.inner.get_profile(symbol))
+            .await
+    }
+
+    async fn get_splits(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SplitEvent>, MarketDataError> {
+        self.measured(
+            "splits",
+            Some(context.instrument.instrument_kind()),
+            self.inner.get_splits(context, instrument, start, end),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coverage, InstrumentId};
+    use rust_decimal_macros::dec;
+
+    /// Provider that prices every instrument except "MISSING".
+    struct StubProvider;
+
+    #[async_trait]
+    impl MarketDataProvider for StubProvider {
+        fn id(&self) -> &'static str {
+            "STUB"
+        }
+        fn priority(&self) -> u8 {
+            3
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Equity],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: true,
+                supports_historical: false,
+                supports_search: false,
+                supports_profile: false,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit {
+                requests_per_minute: 7,
+                max_concurrency: 1,
+                min_delay: Duration::from_secs(2),
+            }
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            instrument: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            let symbol = instrument.to_symbol_string();
+            if symbol == "MISSING" {
+                return Err(MarketDataError::SymbolNotFound(symbol));
+            }
+            Ok(Quote::new(
+                Utc::now(),
+                dec!(10),
+                "USD".to_string(),
+                "STUB".to_string(),
+            ))
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            Err(MarketDataError::NoDataForRange)
+        }
+    }
+
+    fn context() -> QuoteContext {
+        QuoteContext {
+            instrument: InstrumentId::Equity {
+                ticker: Arc::from("AAPL"),
+                mic: None,
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        }
+    }
+
+    fn equity(symbol: &str) -> ProviderInstrument {
+        ProviderInstrument::EquitySymbol {
+            symbol: Arc::from(symbol),
+        }
+    }
+
+    #[test]
+    fn test_preserves_wrapped_provider_metadata() {
+        let provider = InstrumentedProvider::new(Arc::new(StubProvider));
+        assert_eq!(provider.id(), "STUB");
+        assert_eq!(provider.priority(), 3);
+        assert!(provider.capabilities().supports_latest);
+        assert!(!provider.capabilities().supports_historical);
+        assert_eq!(provider.rate_limit().requests_per_minute, 7);
+        assert_eq!(provider.rate_limit().min_delay, Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_counts_successes_and_errors() {
+        let sink = Arc::new(InMemoryMetricsSink::new());
+        let provider = InstrumentedProvider::new(Arc::new(StubProvider)).with_sink(sink.clone());
+
+        provider
+            .get_latest_quote(&context(), equity("AAPL"))
+            .await
+            .unwrap();
+        provider
+            .get_latest_quote(&context(), equity("AAPL"))
+            .await
+            .unwrap();
+        assert!(provider
+            .get_latest_quote(&context(), equity("MISSING"))
+            .await
+            .is_err());
+        assert!(provider.get_profile("AAPL").await.is_err());
+
+        assert_eq!(sink.count("latest_quote", CallOutcome::Success), 2);
+        assert_eq!(
+            sink.count("latest_quote", CallOutcome::Error("symbol_not_found")),
+            1
+        );
+        assert_eq!(
+            sink.count("profile", CallOutcome::Error("not_supported")),
+            1
+        );
+        assert_eq!(sink.count("search", CallOutcome::Success), 0);
+    }
+
+    #[tokio::test]
+    async fn test_records_each_instrument_of_a_batch() {
+        let sink = Arc::new(InMemoryMetricsSink::new());
+        let provider = InstrumentedProvider::new(Arc::new(StubProvider)).with_sink(sink.clone());
+
+        let results = provider
+            .get_latest_quotes(
+                &context(),
+                &[equity("AAPL"), equity("MISSING"), equity("MSFT")],
+            )
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(sink.count("latest_quotes", CallOutcome::Success), 2);
+        assert_eq!(
+            sink.count("latest_quotes", CallOutcome::Error("symbol_not_found")),
+            1
+        );
+        // The batch goes to the inner provider, not through the single-quote path.
+        assert_eq!(sink.count("latest_quote", CallOutcome::Success), 0);
+    }
+}
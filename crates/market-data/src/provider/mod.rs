@@ -19,11 +19,18 @@
 //! resolver module, not in the providers themselves.
 
 mod capabilities;
+mod chain;
+mod circuit;
+mod metrics;
+mod quote_cache;
+mod rate_limited;
 mod traits;
+pub(crate) mod utils;
 
 // Provider implementations
 pub mod alpha_vantage;
 pub mod boerse_frankfurt;
+pub mod ecb;
 pub mod finnhub;
 pub mod marketdata_app;
 pub mod metal_price_api;
@@ -32,5 +39,13 @@ pub mod us_treasury_calc;
 pub mod yahoo;
 
 // Re-exports
-pub use capabilities::{ProviderCapabilities, RateLimit};
+pub use capabilities::{ProviderCapabilities, ProviderOperation, RateLimit};
+pub use chain::ProviderChain;
+pub use circuit::CircuitBreakingProvider;
+pub use metrics::{
+    error_class, CallOutcome, InMemoryMetricsSink, InstrumentedProvider, MetricsSink,
+    NoopMetricsSink, ProviderCallMetric,
+};
+pub use quote_cache::{CachingProvider, QuoteCache};
+pub use rate_limited::RateLimitedDispatcher;
 pub use traits::MarketDataProvider;
@@ -1,17 +1,21 @@
 //! OpenFIGI provider for bond search and name enrichment.
 //!
-//! Uses the free OpenFIGI API:
+//! Uses the OpenFIGI API (anonymous, or keyed via `with_api_key` /
+//! `OPENFIGI_API_KEY` for a higher rate limit):
 //! - `/v3/search` — free-text search (e.g. "JPMORGAN", "US Treasury")
-//! - `/v3/mapping` — exact identifier lookup (ISIN, FIGI)
+//! - `/v3/mapping` — exact identifier lookup (ISIN, CUSIP, SEDOL, FIGI)
 //! - Profile lookup via mapping for bond name enrichment
 //!
 //! No pricing support.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
-use serde::Deserialize;
-use std::time::Duration;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 
 use crate::errors::MarketDataError;
 use crate::models::{
@@ -23,10 +27,90 @@ const PROVIDER_ID: &str = "OPENFIGI";
 const MAPPING_URL: &str = "https://api.openfigi.com/v3/mapping";
 const SEARCH_URL: &str = "https://api.openfigi.com/v3/search";
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const API_KEY_HEADER: &str = "X-OPENFIGI-APIKEY";
+/// Environment variable read when no API key is set explicitly.
+const API_KEY_ENV: &str = "OPENFIGI_API_KEY";
+/// Maximum jobs per `/v3/mapping` request with and without an API key.
+const MAX_MAPPING_JOBS_WITH_KEY: usize = 100;
+const MAX_MAPPING_JOBS_ANONYMOUS: usize = 10;
+/// Default number of identifiers whose names are kept in memory.
+const DEFAULT_NAME_CACHE_SIZE: usize = 1000;
+/// How long an ISIN OpenFIGI did not know is answered from the cache.
+const DEFAULT_NEGATIVE_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
 
 /// Bond-related market sectors in OpenFIGI responses.
 const BOND_MARKET_SECTORS: &[&str] = &["Corp", "Govt", "Mtge", "Muni", "Pfd"];
 
+// ---------------------------------------------------------------------------
+// Request models
+// ---------------------------------------------------------------------------
+
+/// Identifier kinds accepted for name lookups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierType {
+    Isin,
+    Cusip,
+    Sedol,
+}
+
+impl IdentifierType {
+    /// OpenFIGI `idType` for this identifier.
+    fn id_type(self) -> &'static str {
+        match self {
+            IdentifierType::Isin => "ID_ISIN",
+            IdentifierType::Cusip => "ID_CUSIP",
+            IdentifierType::Sedol => "ID_SEDOL",
+        }
+    }
+
+    /// Guess the identifier type from its shape, defaulting to ISIN.
+    fn detect(id_value: &str) -> Self {
+        let value = id_value.trim();
+        if !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return IdentifierType::Isin;
+        }
+        match value.len() {
+            9 => IdentifierType::Cusip,
+            7 => IdentifierType::Sedol,
+            _ => IdentifierType::Isin,
+        }
+    }
+}
+
+/// A single `/v3/mapping` job.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MappingJob<'a> {
+    id_type: &'a str,
+    id_value: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exch_code: Option<&'a str>,
+}
+
+impl<'a> MappingJob<'a> {
+    fn new(id_type: &'a str, id_value: &'a str) -> Self {
+        Self {
+            id_type,
+            id_value,
+            exch_code: None,
+        }
+    }
+
+    /// Job for a name lookup. The exchange code is only sent for CUSIPs,
+    /// which otherwise match the same security on every exchange.
+    fn for_identifier(
+        identifier: IdentifierType,
+        id_value: &'a str,
+        exch_code: Option<&'a str>,
+    ) -> Self {
+        Self {
+            exch_code: exch_code
+                .filter(|code| identifier == IdentifierType::Cusip && !code.is_empty()),
+            ..Self::new(identifier.id_type(), id_value)
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Response models
 // ---------------------------------------------------------------------------
@@ -59,8 +143,23 @@ struct SearchResponse {
 // Provider
 // ---------------------------------------------------------------------------
 
+/// Cached name lookup for an identifier; `None` records that OpenFIGI had no match.
+#[derive(Debug, Clone)]
+struct CachedName {
+    name: Option<String>,
+    cached_at: Instant,
+}
+
 pub struct OpenFigiProvider {
     client: Client,
+    /// API key sent with every request; anonymous when `None`
+    api_key: Option<String>,
+    /// Bond names by ISIN. Names never change, so hits never expire.
+    names: Arc<RwLock<HashMap<String, CachedName>>>,
+    /// Entries kept before the oldest is evicted
+    name_cache_size: usize,
+    /// Age after which a not-found entry is looked up again
+    negative_cache_ttl: Duration,
 }
 
 impl Default for OpenFigiProvider {
@@ -75,23 +174,71 @@ impl OpenFigiProvider {
             .timeout(REQUEST_TIMEOUT)
             .build()
             .unwrap_or_else(|_| Client::new());
-        Self { client }
+        Self {
+            client,
+            api_key: std::env::var(API_KEY_ENV)
+                .ok()
+                .and_then(|key| normalize_api_key(&key)),
+            names: Arc::new(RwLock::new(HashMap::new())),
+            name_cache_size: DEFAULT_NAME_CACHE_SIZE,
+            negative_cache_ttl: DEFAULT_NEGATIVE_CACHE_TTL,
+        }
+    }
+
+    /// Set how many ISIN names are cached before the oldest is evicted.
+    pub fn with_name_cache_size(mut self, size: usize) -> Self {
+        self.name_cache_size = size;
+        self
+    }
+
+    /// Set how long an ISIN without a match is answered from the cache.
+    pub fn with_negative_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_cache_ttl = ttl;
+        self
+    }
+
+    /// Authenticate requests with an OpenFIGI API key. A blank key keeps the
+    /// key from `OPENFIGI_API_KEY`, if any.
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        if let Some(key) = normalize_api_key(api_key) {
+            self.api_key = Some(key);
+        }
+        self
+    }
+
+    /// POST request to an OpenFIGI endpoint, with the API key header when set.
+    fn post(&self, url: &str) -> RequestBuilder {
+        let request = self.client.post(url);
+        match &self.api_key {
+            Some(key) => request.header(API_KEY_HEADER, key),
+            None => request,
+        }
     }
 
     // -- /v3/mapping helpers ------------------------------------------------
 
-    /// Call the v3/mapping API with a given idType/idValue pair.
-    async fn fetch_mapping(
-        &self,
-        id_type: &str,
-        id_value: &str,
-    ) -> Result<Vec<FigiRecord>, MarketDataError> {
-        let body = serde_json::json!([{"idType": id_type, "idValue": id_value}]);
+    /// Call the v3/mapping API with a single job.
+    async fn fetch_mapping(&self, job: MappingJob<'_>) -> Result<Vec<FigiRecord>, MarketDataError> {
+        let results = self.fetch_mapping_jobs(&[job]).await?;
 
+        let data = results
+            .into_iter()
+            .next()
+            .and_then(|r| r.data)
+            .unwrap_or_default();
+
+        Ok(data)
+    }
+
+    /// Call the v3/mapping API with several jobs in one request. Results are
+    /// returned in job order.
+    async fn fetch_mapping_jobs(
+        &self,
+        jobs: &[MappingJob<'_>],
+    ) -> Result<Vec<MappingResult>, MarketDataError> {
         let resp = self
-            .client
             .post(MAPPING_URL)
-            .json(&body)
+            .json(jobs)
             .send()
             .await
             .map_err(|e| MarketDataError::ProviderError {
@@ -107,43 +254,155 @@ impl OpenFigiProvider {
             });
         }
 
-        let results: Vec<MappingResult> =
-            resp.json()
-                .await
-                .map_err(|e| MarketDataError::ProviderError {
-                    provider: PROVIDER_ID.to_string(),
-                    message: format!("JSON parse error: {}", e),
-                })?;
+        resp.json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("JSON parse error: {}", e),
+            })
+    }
 
-        let data = results
-            .into_iter()
-            .next()
-            .and_then(|r| r.data)
-            .unwrap_or_default();
+    /// Look up display names for many ISINs, batching them into as few
+    /// mapping requests as the rate tier allows. ISINs OpenFIGI does not know
+    /// are absent from the returned map.
+    pub async fn fetch_names_batch(
+        &self,
+        isins: &[&str],
+    ) -> Result<HashMap<String, String>, MarketDataError> {
+        let chunk_size = if self.api_key.is_some() {
+            MAX_MAPPING_JOBS_WITH_KEY
+        } else {
+            MAX_MAPPING_JOBS_ANONYMOUS
+        };
 
-        Ok(data)
+        let mut names = HashMap::new();
+        for chunk in isins.chunks(chunk_size) {
+            let jobs: Vec<_> = chunk
+                .iter()
+                .map(|isin| MappingJob::for_identifier(IdentifierType::Isin, isin, None))
+                .collect();
+            let results = self.fetch_mapping_jobs(&jobs).await?;
+            names.extend(Self::names_from_mapping_results(chunk, results));
+        }
+        Ok(names)
+    }
+
+    /// Pair mapping results with the ISINs they were requested for. The API
+    /// answers jobs positionally; jobs without data are skipped.
+    fn names_from_mapping_results(
+        isins: &[&str],
+        results: Vec<MappingResult>,
+    ) -> HashMap<String, String> {
+        isins
+            .iter()
+            .zip(results)
+            .filter_map(|(isin, result)| {
+                let record = result.data?.into_iter().next()?;
+                Some((isin.to_string(), Self::display_name(record)?))
+            })
+            .collect()
     }
 
-    async fn fetch_name(&self, isin: &str) -> Result<String, MarketDataError> {
-        let data_vec = self.fetch_mapping("ID_ISIN", isin).await?;
+    /// "NAME - TICKER" for a record, or just the name when it has no ticker.
+    fn display_name(record: FigiRecord) -> Option<String> {
+        let name = record.name.filter(|n| !n.is_empty())?;
+        Some(match record.ticker.filter(|t| !t.is_empty()) {
+            Some(ticker) => format!("{} - {}", name, ticker),
+            None => name,
+        })
+    }
+
+    /// Name for an ISIN, CUSIP or SEDOL, served from the cache when possible.
+    /// `exch_code` narrows CUSIP lookups and is ignored for other types.
+    pub async fn fetch_name_for(
+        &self,
+        identifier: IdentifierType,
+        id_value: &str,
+        exch_code: Option<&str>,
+    ) -> Result<String, MarketDataError> {
+        let job = MappingJob::for_identifier(identifier, id_value, exch_code);
+        let key = Self::name_cache_key(&job, identifier);
+        self.fetch_name_or_lookup(&key, || self.lookup_name(job))
+            .await
+    }
+
+    /// Cache key for a name lookup. ISINs are keyed by value alone; other
+    /// identifiers are prefixed with their type and exchange.
+    fn name_cache_key(job: &MappingJob<'_>, identifier: IdentifierType) -> String {
+        match (identifier, job.exch_code) {
+            (IdentifierType::Isin, _) => job.id_value.to_string(),
+            (_, Some(exch_code)) => format!("{}:{}@{}", job.id_type, job.id_value, exch_code),
+            (_, None) => format!("{}:{}", job.id_type, job.id_value),
+        }
+    }
+
+    /// Return the cached name (or cached not-found) for `key`, otherwise call
+    /// `lookup` and cache a name or a not-found result.
+    async fn fetch_name_or_lookup<F, Fut>(
+        &self,
+        key: &str,
+        lookup: F,
+    ) -> Result<String, MarketDataError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<String, MarketDataError>>,
+    {
+        if let Some(cached) = self.names.read().await.get(key) {
+            match &cached.name {
+                Some(name) => return Ok(name.clone()),
+                None if cached.cached_at.elapsed() < self.negative_cache_ttl => {
+                    return Err(MarketDataError::SymbolNotFound(key.to_string()));
+                }
+                None => {}
+            }
+        }
+
+        let result = lookup().await;
+        let name = match &result {
+            Ok(name) => Some(name.clone()),
+            Err(MarketDataError::SymbolNotFound(_)) => None,
+            Err(_) => return result,
+        };
+        self.cache_name(key, name).await;
+        result
+    }
+
+    /// Insert a lookup result, evicting the oldest entry when full.
+    async fn cache_name(&self, key: &str, name: Option<String>) {
+        if self.name_cache_size == 0 {
+            return;
+        }
+        let mut names = self.names.write().await;
+        if !names.contains_key(key) && names.len() >= self.name_cache_size {
+            let oldest = names
+                .iter()
+                .min_by_key(|(_, cached)| cached.cached_at)
+                .map(|(cached_key, _)| cached_key.clone());
+            if let Some(oldest) = oldest {
+                names.remove(&oldest);
+            }
+        }
+        names.insert(
+            key.to_string(),
+            CachedName {
+                name,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+
+    async fn lookup_name(&self, job: MappingJob<'_>) -> Result<String, MarketDataError> {
+        let data_vec = self.fetch_mapping(job).await?;
 
         let data = data_vec
             .into_iter()
             .next()
-            .ok_or_else(|| MarketDataError::SymbolNotFound(isin.to_string()))?;
+            .ok_or_else(|| MarketDataError::SymbolNotFound(job.id_value.to_string()))?;
 
-        let name =
-            data.name
-                .filter(|n| !n.is_empty())
-                .ok_or_else(|| MarketDataError::ProviderError {
-                    provider: PROVIDER_ID.to_string(),
-                    message: format!("No name found for {}", isin),
-                })?;
-
-        match data.ticker.filter(|t| !t.is_empty()) {
-            Some(ticker) => Ok(format!("{} - {}", name, ticker)),
-            None => Ok(name),
-        }
+        Self::display_name(data).ok_or_else(|| MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: format!("No name found for {}", job.id_value),
+        })
     }
 
     // -- /v3/search helpers -------------------------------------------------
@@ -153,7 +412,6 @@ impl OpenFigiProvider {
         let body = serde_json::json!({"query": query});
 
         let resp = self
-            .client
             .post(SEARCH_URL)
             .json(&body)
             .send()
@@ -263,10 +521,18 @@ impl MarketDataProvider for OpenFigiProvider {
     }
 
     fn rate_limit(&self) -> RateLimit {
-        RateLimit {
-            requests_per_minute: 25,
-            max_concurrency: 1,
-            min_delay: Duration::from_secs(3),
+        if self.api_key.is_some() {
+            RateLimit {
+                requests_per_minute: 250,
+                max_concurrency: 4,
+                min_delay: Duration::from_millis(250),
+            }
+        } else {
+            RateLimit {
+                requests_per_minute: 25,
+                max_concurrency: 1,
+                min_delay: Duration::from_secs(3),
+            }
         }
     }
 
@@ -301,7 +567,7 @@ impl MarketDataProvider for OpenFigiProvider {
         // Exact identifier lookup (ISIN / FIGI) → mapping API
         if let Some(id_types) = Self::detect_id_types(trimmed) {
             for id_type in &id_types {
-                if let Ok(records) = self.fetch_mapping(id_type, trimmed).await {
+                if let Ok(records) = self.fetch_mapping(MappingJob::new(id_type, trimmed)).await {
                     if !records.is_empty() {
                         return Ok(Self::records_to_search_results(records, &symbol));
                     }
@@ -320,11 +586,18 @@ impl MarketDataProvider for OpenFigiProvider {
     }
 
     async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
-        let name = self.fetch_name(symbol).await?;
+        let name = self
+            .fetch_name_for(IdentifierType::detect(symbol), symbol, None)
+            .await?;
         Ok(AssetProfile::with_name(name))
     }
 }
 
+fn normalize_api_key(key: &str) -> Option<String> {
+    let key = key.trim();
+    (!key.is_empty()).then(|| key.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,13 +625,145 @@ mod tests {
         assert!(caps.supports_profile);
     }
 
+    fn anonymous_provider() -> OpenFigiProvider {
+        OpenFigiProvider {
+            api_key: None,
+            ..OpenFigiProvider::new()
+        }
+    }
+
     #[test]
     fn test_rate_limit() {
-        let provider = OpenFigiProvider::new();
+        let provider = anonymous_provider();
         let rl = provider.rate_limit();
         assert_eq!(rl.requests_per_minute, 25);
         assert_eq!(rl.max_concurrency, 1);
         assert_eq!(rl.min_delay, Duration::from_secs(3));
+
+        let rl = provider.with_api_key("secret").rate_limit();
+        assert_eq!(rl.requests_per_minute, 250);
+        assert_eq!(rl.max_concurrency, 4);
+    }
+
+    #[test]
+    fn test_api_key_header_only_when_configured() {
+        let provider = anonymous_provider();
+        let request = provider.post(MAPPING_URL).build().unwrap();
+        assert!(request.headers().get(API_KEY_HEADER).is_none());
+
+        let provider = provider.with_api_key("  ");
+        let request = provider.post(MAPPING_URL).build().unwrap();
+        assert!(request.headers().get(API_KEY_HEADER).is_none());
+
+        let provider = provider.with_api_key("secret");
+        let request = provider.post(SEARCH_URL).build().unwrap();
+        assert_eq!(request.headers()[API_KEY_HEADER], "secret");
+    }
+
+    fn lookup_counter() -> std::sync::atomic::AtomicUsize {
+        std::sync::atomic::AtomicUsize::new(0)
+    }
+
+    async fn counted_lookup(
+        provider: &OpenFigiProvider,
+        isin: &str,
+        calls: &std::sync::atomic::AtomicUsize,
+        result: Result<&str, ()>,
+    ) -> Result<String, MarketDataError> {
+        provider
+            .fetch_name_or_lookup(isin, || async {
+                calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                result
+                    .map(str::to_string)
+                    .map_err(|_| MarketDataError::SymbolNotFound(isin.to_string()))
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_name_cache_hit_skips_lookup() {
+        let provider = anonymous_provider();
+        let calls = lookup_counter();
+
+        let name = counted_lookup(&provider, "US91282CJL54", &calls, Ok("US TREASURY N/B")).await;
+        assert_eq!(name.unwrap(), "US TREASURY N/B");
+        let name = counted_lookup(&provider, "US91282CJL54", &calls, Ok("ignored")).await;
+        assert_eq!(name.unwrap(), "US TREASURY N/B");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_name_cache_miss_and_eviction() {
+        let provider = anonymous_provider().with_name_cache_size(2);
+        let calls = lookup_counter();
+
+        for isin in ["ISIN00000001", "ISIN00000002", "ISIN00000003"] {
+            counted_lookup(&provider, isin, &calls, Ok(isin))
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert_eq!(provider.names.read().await.len(), 2);
+
+        // The oldest entry was evicted and is looked up again.
+        counted_lookup(&provider, "ISIN00000001", &calls, Ok("ISIN00000001"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 4);
+        counted_lookup(&provider, "ISIN00000003", &calls, Ok("ISIN00000003"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_negative_name_cache_expires() {
+        let provider = anonymous_provider().with_negative_cache_ttl(Duration::from_millis(50));
+        let calls = lookup_counter();
+
+        for _ in 0..2 {
+            let result = counted_lookup(&provider, "XS0000000000", &calls, Err(())).await;
+            assert!(matches!(result, Err(MarketDataError::SymbolNotFound(_))));
+        }
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let name = counted_lookup(&provider, "XS0000000000", &calls, Ok("NEW ISSUE")).await;
+        assert_eq!(name.unwrap(), "NEW ISSUE");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_mapping_job_body_per_identifier_type() {
+        let body = |identifier, exch_code| {
+            serde_json::to_value(MappingJob::for_identifier(identifier, "X123", exch_code)).unwrap()
+        };
+
+        assert_eq!(
+            body(IdentifierType::Isin, Some("US")),
+            serde_json::json!({"idType": "ID_ISIN", "idValue": "X123"})
+        );
+        assert_eq!(
+            body(IdentifierType::Cusip, Some("US")),
+            serde_json::json!({"idType": "ID_CUSIP", "idValue": "X123", "exchCode": "US"})
+        );
+        assert_eq!(
+            body(IdentifierType::Cusip, None),
+            serde_json::json!({"idType": "ID_CUSIP", "idValue": "X123"})
+        );
+        assert_eq!(
+            body(IdentifierType::Sedol, Some("LN")),
+            serde_json::json!({"idType": "ID_SEDOL", "idValue": "X123"})
+        );
+    }
+
+    #[test]
+    fn test_detect_identifier_type() {
+        assert_eq!(IdentifierType::detect("US912828ZT58"), IdentifierType::Isin);
+        assert_eq!(IdentifierType::detect("912828ZT5"), IdentifierType::Cusip);
+        assert_eq!(IdentifierType::detect("B0YBKJ7"), IdentifierType::Sedol);
+        assert_eq!(IdentifierType::detect("not-an-id"), IdentifierType::Isin);
     }
 
     #[test]
@@ -464,6 +869,42 @@ mod tests {
         assert!(results[0].data.is_none());
     }
 
+    #[test]
+    fn test_names_from_multi_element_mapping_response() {
+        let json = r#"[
+            {"data":[{"figi":"BBG00GBVBK04","name":"JPMORGAN CHASE & CO","ticker":"JPM V2.069 06/01/29","exchCode":"US","securityType":"Corp","marketSector":"Corp"}]},
+            {"warning":"No identifier found."},
+            {"data":[{"figi":"BBG01K2Z4KL9","name":"US TREASURY N/B","ticker":"","marketSector":"Govt"}]},
+            {"data":[]}
+        ]"#;
+        let results: Vec<MappingResult> = serde_json::from_str(json).unwrap();
+        let isins = [
+            "US46647PCH71",
+            "XS0000000000",
+            "US91282CJL54",
+            "US0000000000",
+        ];
+
+        let names = OpenFigiProvider::names_from_mapping_results(&isins, results);
+        assert_eq!(names.len(), 2);
+        assert_eq!(
+            names.get("US46647PCH71").map(String::as_str),
+            Some("JPMORGAN CHASE & CO - JPM V2.069 06/01/29")
+        );
+        assert_eq!(
+            names.get("US91282CJL54").map(String::as_str),
+            Some("US TREASURY N/B")
+        );
+        assert!(!names.contains_key("XS0000000000"));
+        assert!(!names.contains_key("US0000000000"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_names_batch_empty_input() {
+        let names = anonymous_provider().fetch_names_batch(&[]).await.unwrap();
+        assert!(names.is_empty());
+    }
+
     #[test]
     fn test_parse_search_response() {
         let json = r#"{"data":[{"figi":"BBG000B9XRY4","name":"APPLE INC","ticker":"AAPL","exchCode":"US","securityType":"Common Stock","marketSector":"Equity"}]}"#;
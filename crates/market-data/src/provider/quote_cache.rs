@@ -0,0 +1,552 @@
+//! In-memory cache of provider quotes.
+//!
+//! Portfolio recalculations ask for the same instrument on the same date
+//! over and over, and calculated providers re-run curve interpolation every
+//! time. `QuoteCache` stores quotes keyed by instrument, date and provider,
+//! and `CachingProvider` wraps any provider so quote calls check that cache
+//! first. One `QuoteCache` can be shared by several wrapped providers.
+//!
+//! Historical ranges that end before today never change and are kept until
+//! evicted; anything covering today (including latest quotes) expires after
+//! a short TTL. Errors are not cached.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::errors::MarketDataError;
+use crate::models::{
+    AssetProfile, ProviderInstrument, Quote, QuoteContext, SearchResult, SplitEvent,
+};
+
+use super::{MarketDataProvider, ProviderCapabilities, RateLimit};
+
+/// Default number of cached entries.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Default lifetime of quotes that cover today.
+const DEFAULT_LATEST_TTL: Duration = Duration::from_secs(60);
+
+/// Dates a cache entry covers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum QuoteSpan {
+    /// The latest quote, as of this date.
+    Latest(NaiveDate),
+    /// Historical quotes from the first date to the second, inclusive.
+    Historical(NaiveDate, NaiveDate),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct QuoteCacheKey {
+    instrument: String,
+    span: QuoteSpan,
+    provider: &'static str,
+}
+
+impl QuoteCacheKey {
+    fn new(provider: &'static str, instrument: &ProviderInstrument, span: QuoteSpan) -> Self {
+        Self {
+            // The instrument's debug form covers every identifying field.
+            instrument: format!("{:?}", instrument),
+            span,
+            provider,
+        }
+    }
+}
+
+struct CacheEntry {
+    quotes: Vec<Quote>,
+    /// Insertion order, for evicting the oldest entry.
+    seq: u64,
+    /// `None` for entries that never expire.
+    expires: Option<Instant>,
+}
+
+impl CacheEntry {
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires.is_none_or(|expires| now < expires)
+    }
+}
+
+/// Bounded, TTL-aware store of quotes keyed by instrument, date and provider.
+///
+/// The key does not include the rest of the `QuoteContext` (e.g. bond
+/// metadata); call [`QuoteCache::clear`] after changing it.
+pub struct QuoteCache {
+    entries: Mutex<HashMap<QuoteCacheKey, CacheEntry>>,
+    next_seq: AtomicU64,
+    capacity: usize,
+    latest_ttl: Duration,
+}
+
+impl Default for QuoteCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuoteCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            next_seq: AtomicU64::new(0),
+            capacity: DEFAULT_CAPACITY,
+            latest_ttl: DEFAULT_LATEST_TTL,
+        }
+    }
+
+    /// Keep at most `capacity` entries (default 10,000), evicting the
+    /// oldest first.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Expire quotes that cover today after `ttl` (default 60 seconds).
+    pub fn with_latest_ttl(mut self, ttl: Duration) -> Self {
+        self.latest_ttl = ttl;
+        self
+    }
+
+    /// Number of live entries.
+    pub fn len(&self) -> usize {
+        let now = Instant::now();
+        self.lock()
+            .values()
+            .filter(|entry| entry.is_live(now))
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every cached quote.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<QuoteCacheKey, CacheEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get(&self, key: &QuoteCacheKey) -> Option<Vec<Quote>> {
+        let mut entries = self.lock();
+        match entries.get(key) {
+            Some(entry) if entry.is_live(Instant::now()) => Some(entry.quotes.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: QuoteCacheKey, quotes: Vec<Quote>, permanent: bool) {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.is_live(now));
+            while entries.len() >= self.capacity {
+                let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.seq)
+                    .map(|(key, _)| key.clone())
+                else {
+                    break;
+                };
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                quotes,
+                seq: self.next_seq.fetch_add(1, Ordering::Relaxed),
+                expires: (!permanent).then(|| now + self.latest_ttl),
+            },
+        );
+    }
+}
+
+/// A `MarketDataProvider` that serves quotes from a [`QuoteCache`] before
+/// calling its inner provider. Search, profile and split calls pass through.
+pub struct CachingProvider {
+    inner: Arc<dyn MarketDataProvider>,
+    cache: Arc<QuoteCache>,
+}
+
+impl CachingProvider {
+    pub fn new(inner: Arc<dyn MarketDataProvider>, cache: Arc<QuoteCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Return the cached quotes for `span`, or run `call` and cache its result.
+    async fn cached<Fut>(
+        &self,
+        instrument: &ProviderInstrument,
+        span: QuoteSpan,
+        call: Fut,
+    ) -> Result<Vec<Quote>, MarketDataError>
+    where
+        Fut: Future<Output = Result<Vec<Quote>, MarketDataError>> + Send,
+    {
+        let key = QuoteCacheKey::new(self.inner.id(), instrument, span);
+        if let Some(quotes) = self.cache.get(&key) {
+            return Ok(quotes);
+        }
+        let quotes = call.await?;
+        let today = Utc::now().date_naive();
+        let permanent = matches!(span, QuoteSpan::Historical(_, end) if end < today);
+        self.cache.insert(key, quotes.clone(), permanent);
+        Ok(quotes)
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for CachingProvider {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn rate_limit(&self) -> RateLimit {
+        self.inner.rate_limit()
+    }
+
+    async fn get_latest_quote(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+    ) -> Result<Quote, MarketDataError> {
+        let span = QuoteSpan::Latest(Utc::now().date_naive());
+        let call = async {
+            self.inner
+                .get_latest_quote(context, instrument.clone())
+                .await
+                .map(|quote| vec![quote])
+        };
+        self.cached(&instrument, span, call)
+            .await?
+            .pop()
+            .ok_or(MarketDataError::NoDataForRange)
+    }
+
+    /// Serves cached instruments and forwards the rest to the inner provider
+    /// in one batch.
+    async fn get_latest_quotes(
+        &self,
+        context: &QuoteContext,
+        instruments: &[ProviderInstrument],
+    ) -> Vec<Result<Quote, MarketDataError>> {
+        let span = QuoteSpan::Latest(Utc::now().date_naive());
+        let keys: Vec<QuoteCacheKey> = instruments
+            .iter()
+            .map(|instrument| QuoteCacheKey::new(self.inner.id(), instrument, span))
+            .collect();
+        let mut results: Vec<Option<Result<Quote, MarketDataError>>> = keys
+            .iter()
+            .map(|key| {
+                self.cache
+                    .get(key)
+                    .and_then(|mut quotes| quotes.pop())
+                    .map(Ok)
+            })
+            .collect();
+
+        let missing: Vec<usize> = (0..instruments.len())
+            .filter(|&index| results[index].is_none())
+            .collect();
+        if !missing.is_empty() {
+            let batch: Vec<ProviderInstrument> = missing
+                .iter()
+                .map(|&index| instruments[index].clone())
+                .collect();
+            let fetched = self.inner.get_latest_quotes(context, &batch).await;
+            for (index, quote) in missing.into_iter().zip(fetched) {
+                if let Ok(quote) = &quote {
+                    self.cache
+                        .insert(keys[index].clone(), vec![quote.clone()], false);
+                }
+                results[index] = Some(quote);
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|quote| quote.unwrap_or(Err(MarketDataError::NoDataForRange)))
+            .collect()
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        let span = QuoteSpan::Historical(start.date_naive(), end.date_naive());
+        let call = self
+            .inner
+            .get_historical_quotes(context, instrument.clone(), start, end);
+        self.cached(&instrument, span, call).await
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        self.inner.search(query).await
+    }
+
+    async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+        self.inner.get_profile(symbol).await
+    }
+
+    async fn get_splits(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SplitEvent>, MarketDataError> {
+        self.inner.get_splits(context, instrument, start, end).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coverage, InstrumentId, InstrumentKind};
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Bond provider that counts quote calls and latest-quote batches.
+    struct CountingProvider {
+        id: &'static str,
+        calls: AtomicUsize,
+        batches: AtomicUsize,
+    }
+
+    impl CountingProvider {
+        fn new(id: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                id,
+                calls: AtomicUsize::new(0),
+                batches: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+
+        fn quote(&self, date: DateTime<Utc>) -> Quote {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Quote::new(date, dec!(0.98), "USD".to_string(), self.id.to_string())
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for CountingProvider {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Bond],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: true,
+                supports_historical: true,
+                supports_search: false,
+                supports_profile: false,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit::default()
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            Ok(self.quote(Utc::now()))
+        }
+        async fn get_latest_quotes(
+            &self,
+            _: &QuoteContext,
+            instruments: &[ProviderInstrument],
+        ) -> Vec<Result<Quote, MarketDataError>> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            instruments
+                .iter()
+                .map(|_| Ok(self.quote(Utc::now())))
+                .collect()
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            start: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            Ok(vec![self.quote(start)])
+        }
+    }
+
+    fn context() -> QuoteContext {
+        QuoteContext {
+            instrument: InstrumentId::Bond {
+                isin: "US91282CJL63".into(),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        }
+    }
+
+    fn bond(isin: &'static str) -> ProviderInstrument {
+        ProviderInstrument::BondIsin { isin: isin.into() }
+    }
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(16, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[tokio::test]
+    async fn test_past_historical_quotes_served_from_cache() {
+        let inner = CountingProvider::new("COUNTING");
+        let cache = Arc::new(QuoteCache::new().with_latest_ttl(Duration::ZERO));
+        let provider = CachingProvider::new(inner.clone(), cache.clone());
+        let (start, end) = (utc(2024, 3, 1), utc(2024, 3, 1));
+
+        let first = provider
+            .get_historical_quotes(&context(), bond("US91282CJL63"), start, end)
+            .await
+            .unwrap();
+        let second = provider
+            .get_historical_quotes(&context(), bond("US91282CJL63"), start, end)
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 1, "past range is cached despite zero TTL");
+        assert_eq!(first[0].close, second[0].close);
+
+        // A different instrument or range misses.
+        provider
+            .get_historical_quotes(&context(), bond("US912810TH14"), start, end)
+            .await
+            .unwrap();
+        provider
+            .get_historical_quotes(&context(), bond("US91282CJL63"), start, utc(2024, 3, 2))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 3);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_latest_quote_hit_miss_and_expiry() {
+        let inner = CountingProvider::new("COUNTING");
+        let provider = CachingProvider::new(inner.clone(), Arc::new(QuoteCache::new()));
+        provider
+            .get_latest_quote(&context(), bond("US91282CJL63"))
+            .await
+            .unwrap();
+        provider
+            .get_latest_quote(&context(), bond("US91282CJL63"))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 1);
+
+        let expiring = CachingProvider::new(
+            inner.clone(),
+            Arc::new(QuoteCache::new().with_latest_ttl(Duration::ZERO)),
+        );
+        expiring
+            .get_latest_quote(&context(), bond("US91282CJL63"))
+            .await
+            .unwrap();
+        expiring
+            .get_latest_quote(&context(), bond("US91282CJL63"))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_shared_cache_keys_by_provider_and_evicts_oldest() {
+        let cache = Arc::new(QuoteCache::new().with_capacity(2));
+        let a = CountingProvider::new("A");
+        let b = CountingProvider::new("B");
+        let cached_a = CachingProvider::new(a.clone(), cache.clone());
+        let cached_b = CachingProvider::new(b.clone(), cache.clone());
+        let day = utc(2024, 3, 1);
+
+        cached_a
+            .get_historical_quotes(&context(), bond("US91282CJL63"), day, day)
+            .await
+            .unwrap();
+        cached_b
+            .get_historical_quotes(&context(), bond("US91282CJL63"), day, day)
+            .await
+            .unwrap();
+        assert_eq!((a.calls(), b.calls()), (1, 1));
+
+        // A third entry evicts provider A's, the oldest.
+        cached_b
+            .get_historical_quotes(&context(), bond("US912810TH14"), day, day)
+            .await
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+        cached_a
+            .get_historical_quotes(&context(), bond("US91282CJL63"), day, day)
+            .await
+            .unwrap();
+        assert_eq!(a.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_latest_batch_forwards_only_misses() {
+        let inner = CountingProvider::new("COUNTING");
+        let provider = CachingProvider::new(inner.clone(), Arc::new(QuoteCache::new()));
+
+        let first = provider
+            .get_latest_quotes(&context(), &[bond("A"), bond("B")])
+            .await;
+        assert!(first.iter().all(Result::is_ok));
+        assert_eq!(inner.calls(), 2);
+
+        let second = provider
+            .get_latest_quotes(&context(), &[bond("A"), bond("C"), bond("B")])
+            .await;
+        assert_eq!(second.len(), 3);
+        assert!(second.iter().all(Result::is_ok));
+        assert_eq!(inner.calls(), 3, "only C is fetched");
+        assert_eq!(inner.batches.load(Ordering::SeqCst), 2);
+
+        // Single-quote lookups share the batch's cache entries.
+        provider
+            .get_latest_quote(&context(), bond("C"))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls(), 3);
+    }
+}
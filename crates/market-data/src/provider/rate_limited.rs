@@ -0,0 +1,329 @@
+//! Enforcement of a provider's declared rate limit.
+//!
+//! Providers declare a `RateLimit`, but a provider used outside
+//! `ProviderRegistry` is called as fast as its caller likes. `RateLimitedDispatcher`
+//! wraps any provider and holds each call back until it fits the declared
+//! limits: a token bucket for `requests_per_minute`, a semaphore for
+//! `max_concurrency`, and the time of the last request for `min_delay`. Calls
+//! wait for their turn rather than failing.
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::debug;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::Instant;
+
+use crate::errors::MarketDataError;
+use crate::models::{
+    AssetProfile, ProviderId, ProviderInstrument, Quote, QuoteContext, SearchResult, SplitEvent,
+};
+use crate::registry::{RateLimitConfig, RateLimiter};
+
+use super::{MarketDataProvider, ProviderCapabilities, RateLimit};
+
+/// A `MarketDataProvider` that paces calls to its inner provider according
+/// to the inner provider's `RateLimit`.
+pub struct RateLimitedDispatcher {
+    inner: Arc<dyn MarketDataProvider>,
+    limit: RateLimit,
+    bucket: RateLimiter,
+    concurrency: Semaphore,
+    /// Start time of the most recent call, for `min_delay`.
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl RateLimitedDispatcher {
+    /// Wrap `inner`, enforcing the limits it declares.
+    pub fn new(inner: Arc<dyn MarketDataProvider>) -> Self {
+        let limit = inner.rate_limit();
+        let bucket = RateLimiter::new();
+        bucket.configure(
+            &Cow::Borrowed(inner.id()),
+            RateLimitConfig {
+                requests_per_minute: limit.requests_per_minute,
+                burst_capacity: limit.max_concurrency.max(1) as f64,
+            },
+        );
+        Self {
+            inner,
+            limit,
+            bucket,
+            concurrency: Semaphore::new(limit.max_concurrency.max(1)),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Wait until the declared limits allow another call, then run `call`.
+    async fn dispatch<T, Fut>(&self, call: Fut) -> Result<T, MarketDataError>
+    where
+        Fut: Future<Output = Result<T, MarketDataError>> + Send,
+    {
+        self.dispatch_weighted(1, call).await
+    }
+
+    /// Like [`dispatch`](Self::dispatch), but charge `requests` tokens against
+    /// `requests_per_minute`, e.g. one per instrument of a batch.
+    async fn dispatch_weighted<T, Fut>(&self, requests: usize, call: Fut) -> T
+    where
+        Fut: Future<Output = T> + Send,
+    {
+        // The semaphore is never closed, so acquiring cannot fail.
+        let _slot = self.concurrency.acquire().await.ok();
+        let provider_id: ProviderId = Cow::Borrowed(self.inner.id());
+        let mut permits = Vec::with_capacity(requests.max(1));
+        for _ in 0..requests.max(1) {
+            permits.push(self.bucket.acquire(&provider_id).await);
+        }
+        {
+            // Held while sleeping so waiting calls are spaced one by one.
+            let mut last_request = self.last_request.lock().await;
+            if let Some(last) = *last_request {
+                let ready_at = last + self.limit.min_delay;
+                if ready_at > Instant::now() {
+                    debug!(
+                        "Rate limiter: delaying '{}' call by {:?}",
+                        provider_id,
+                        ready_at - Instant::now()
+                    );
+                    tokio::time::sleep_until(ready_at).await;
+                }
+            }
+            *last_request = Some(Instant::now());
+        }
+        call.await
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for RateLimitedDispatcher {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn rate_limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    async fn get_latest_quote(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+    ) -> Result<Quote, MarketDataError> {
+        self.dispatch(self.inner.get_latest_quote(context, instrument))
+            .await
+    }
+
+    /// Forwards the batch as one paced call. Each instrument is charged
+    /// against `requests_per_minute`, since the inner provider may fall back
+    /// to one request per instrument.
+    async fn get_latest_quotes(
+        &self,
+        context: &QuoteContext,
+        instruments: &[ProviderInstrument],
+    ) -> Vec<Result<Quote, MarketDataError>> {
+        self.dispatch_weighted(
+            instruments.len(),
+            self.inner.get_latest_quotes(context, instruments),
+        )
+        .await
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        self.dispatch(
+            self.inner
+                .get_historical_quotes(context, instrument, start, end),
+        )
+        .await
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, MarketDataError> {
+        self.dispatch(self.inner.search(query)).await
+    }
+
+    async fn get_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+        self.dispatch(self.inner.get_profile(symbol)).await
+    }
+
+    async fn get_splits(
+        &self,
+        context: &QuoteContext,
+        instrument: ProviderInstrument,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SplitEvent>, MarketDataError> {
+        self.dispatch(self.inner.get_splits(context, instrument, start, end))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coverage, InstrumentId, InstrumentKind};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex as StdMutex;
+    use std::time::Duration;
+
+    /// Profile provider that records when each call starts and how many run
+    /// at once.
+    struct PacedProvider {
+        limit: RateLimit,
+        started: StdMutex<Vec<std::time::Instant>>,
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+    }
+
+    impl PacedProvider {
+        fn new(limit: RateLimit) -> Self {
+            Self {
+                limit,
+                started: StdMutex::new(Vec::new()),
+                active: AtomicUsize::new(0),
+                max_active: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for PacedProvider {
+        fn id(&self) -> &'static str {
+            "PACED"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Bond],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: false,
+                supports_historical: false,
+                supports_search: false,
+                supports_profile: true,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            self.limit
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            Err(MarketDataError::NoDataForRange)
+        }
+        async fn get_latest_quotes(
+            &self,
+            _: &QuoteContext,
+            instruments: &[ProviderInstrument],
+        ) -> Vec<Result<Quote, MarketDataError>> {
+            self.started.lock().unwrap().push(std::time::Instant::now());
+            instruments
+                .iter()
+                .map(|_| Err(MarketDataError::NoDataForRange))
+                .collect()
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            Err(MarketDataError::NoDataForRange)
+        }
+        async fn get_profile(&self, _: &str) -> Result<AssetProfile, MarketDataError> {
+            self.started.lock().unwrap().push(std::time::Instant::now());
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(AssetProfile::with_name("Paced"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spaces_calls_by_min_delay() {
+        let inner = Arc::new(PacedProvider::new(RateLimit {
+            requests_per_minute: 600,
+            max_concurrency: 2,
+            min_delay: Duration::from_secs(1),
+        }));
+        let provider = RateLimitedDispatcher::new(inner.clone());
+
+        provider.get_profile("A").await.unwrap();
+        provider.get_profile("B").await.unwrap();
+
+        let started = inner.started.lock().unwrap();
+        assert_eq!(started.len(), 2);
+        assert!(started[1] - started[0] >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_limits_concurrent_calls() {
+        let inner = Arc::new(PacedProvider::new(RateLimit {
+            requests_per_minute: 6000,
+            max_concurrency: 1,
+            min_delay: Duration::ZERO,
+        }));
+        let provider = RateLimitedDispatcher::new(inner.clone());
+
+        let (a, b, c) = tokio::join!(
+            provider.get_profile("A"),
+            provider.get_profile("B"),
+            provider.get_profile("C")
+        );
+        assert!(a.is_ok() && b.is_ok() && c.is_ok());
+        assert_eq!(inner.max_active.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_charges_each_instrument_of_a_batch() {
+        let inner = Arc::new(PacedProvider::new(RateLimit {
+            requests_per_minute: 600,
+            max_concurrency: 1,
+            min_delay: Duration::ZERO,
+        }));
+        let provider = RateLimitedDispatcher::new(inner.clone());
+        let context = QuoteContext {
+            instrument: InstrumentId::Bond {
+                isin: Arc::from("DE0001102580"),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        };
+        let instruments: Vec<_> = ["A", "B", "C"]
+            .into_iter()
+            .map(|isin| ProviderInstrument::BondIsin {
+                isin: Arc::from(isin),
+            })
+            .collect();
+
+        let begin = std::time::Instant::now();
+        let results = provider.get_latest_quotes(&context, &instruments).await;
+
+        // One batch call, held back until the bucket paid for three requests.
+        assert_eq!(results.len(), 3);
+        assert_eq!(inner.started.lock().unwrap().len(), 1);
+        assert!(begin.elapsed() >= Duration::from_millis(150));
+    }
+}
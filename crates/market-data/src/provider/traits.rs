@@ -95,6 +95,24 @@ pub trait MarketDataProvider: Send + Sync {
         instrument: ProviderInstrument,
     ) -> Result<Quote, MarketDataError>;
 
+    /// Fetch the latest quotes for several instruments at once.
+    ///
+    /// Returns one result per instrument, in input order, so one failing
+    /// instrument does not fail the rest. The default implementation calls
+    /// [`get_latest_quote`](Self::get_latest_quote) for each instrument in
+    /// turn; providers with a batch endpoint should override it.
+    async fn get_latest_quotes(
+        &self,
+        context: &QuoteContext,
+        instruments: &[ProviderInstrument],
+    ) -> Vec<Result<Quote, MarketDataError>> {
+        let mut quotes = Vec::with_capacity(instruments.len());
+        for instrument in instruments {
+            quotes.push(self.get_latest_quote(context, instrument.clone()).await);
+        }
+        quotes
+    }
+
     /// Fetch historical quotes for an instrument.
     ///
     /// # Arguments
@@ -172,3 +190,95 @@ pub trait MarketDataProvider: Send + Sync {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coverage, InstrumentId, InstrumentKind};
+    use rust_decimal_macros::dec;
+
+    /// Quotes any ISIN except ones starting with "XX".
+    struct IsinProvider;
+
+    #[async_trait]
+    impl MarketDataProvider for IsinProvider {
+        fn id(&self) -> &'static str {
+            "ISIN"
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Bond],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: true,
+                supports_historical: false,
+                supports_search: false,
+                supports_profile: false,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit::default()
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            instrument: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            let ProviderInstrument::BondIsin { isin } = instrument else {
+                return Err(MarketDataError::UnsupportedAssetType("not a bond".into()));
+            };
+            if isin.starts_with("XX") {
+                return Err(MarketDataError::SymbolNotFound(isin.to_string()));
+            }
+            Ok(Quote::new(
+                Utc::now(),
+                dec!(1),
+                "USD".into(),
+                isin.to_string(),
+            ))
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            Err(MarketDataError::NoDataForRange)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_batch_keeps_order_and_per_item_errors() {
+        let context = QuoteContext {
+            instrument: InstrumentId::Bond {
+                isin: "US912810TH14".into(),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        };
+        let bond = |isin: &'static str| ProviderInstrument::BondIsin { isin: isin.into() };
+        let instruments = [
+            bond("US912810TH14"),
+            bond("XX0000000000"),
+            bond("DE0001102580"),
+        ];
+
+        let results = IsinProvider.get_latest_quotes(&context, &instruments).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().source, "US912810TH14");
+        assert!(matches!(
+            results[1],
+            Err(MarketDataError::SymbolNotFound(ref isin)) if isin == "XX0000000000"
+        ));
+        assert_eq!(results[2].as_ref().unwrap().source, "DE0001102580");
+        assert!(IsinProvider
+            .get_latest_quotes(&context, &[])
+            .await
+            .is_empty());
+    }
+}
@@ -2,72 +2,149 @@
 //!
 //! Computes bond prices from the daily Treasury yield curve published by
 //! Treasury.gov.  The provider fetches one XML feed per calendar year
-//! (containing every trading day's curve) and caches it in memory.
+//! (containing every trading day's curve) and caches it in memory, and
+//! optionally on disk so the cache survives restarts.
 //!
 //! **Data flow:**
 //! 1. Extract CUSIP from ISIN (US ISINs only: prefix "US912").
-//! 2. Fetch the yield curve for the relevant year(s).
+//! 2. Fetch the yield curve for the relevant year(s) — the real yield curve
+//!    for TIPS, the nominal curve otherwise.
 //! 3. Interpolate the yield at the bond's remaining maturity.
-//! 4. Discount coupon + principal cash flows to get PV as fraction-of-par.
+//! 4. Discount coupon + principal cash flows to get PV as fraction-of-par
+//!    (scaled by the CPI index ratio from the bond metadata for TIPS, or,
+//!    when enabled, derived per settlement date from monthly CPI-U published
+//!    by the Bureau of Labor Statistics).
 //!
 //! Also exposes a TreasuryDirect auction-data lookup so the core crate can
 //! enrich bonds that are missing coupon/maturity metadata.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use futures::stream::{FuturesUnordered, StreamExt};
 use log::{debug, warn};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 
 use crate::errors::MarketDataError;
-use crate::models::{Coverage, InstrumentKind, ProviderInstrument, Quote, QuoteContext};
+use crate::models::{
+    BondQuoteMetadata, Coverage, InstrumentKind, ProviderInstrument, Quote, QuoteContext,
+};
+use crate::pricing::{
+    bill_yields, calculate_price, face_value, make_quote, price_from_yield, settlement_date,
+    DayCount, YieldCurve,
+};
+use crate::provider::utils::{read_bytes_capped, read_text_capped, DEFAULT_MAX_BODY_BYTES};
 use crate::provider::{MarketDataProvider, ProviderCapabilities, RateLimit};
 
+pub use crate::pricing::{BillYields, InterpolationMethod};
+
 const PROVIDER_ID: &str = "US_TREASURY_CALC";
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Standard US Treasury face value.
 const US_TREASURY_FACE_VALUE: f64 = 1000.0;
 
-// ---------------------------------------------------------------------------
-// Yield curve types
-// ---------------------------------------------------------------------------
+/// Treasuries are USD-denominated, so calculated prices are in USD unless the
+/// bond metadata names a currency, whatever currency the caller hints at.
+const US_TREASURY_CURRENCY: &str = "USD";
 
-/// A single day's yield curve: sorted vec of (tenor_years, yield_pct).
-/// Yields are in percent (e.g. 4.25 means 4.25%).
-#[derive(Clone, Debug)]
-struct YieldCurve(Vec<(f64, f64)>);
+/// Treasury securities settle T+1 unless the quote context says otherwise.
+const DEFAULT_SETTLEMENT_OFFSET_DAYS: i64 = 1;
 
-impl YieldCurve {
-    /// Linearly interpolate the yield for a given maturity in years.
-    fn interpolate(&self, years: f64) -> Option<f64> {
-        let pts = &self.0;
-        if pts.is_empty() {
-            return None;
+/// Yield-to-maturity solver settings.  Yields are in percent.
+const YTM_MAX_NEWTON_ITERATIONS: usize = 50;
+const YTM_MAX_BISECTION_ITERATIONS: usize = 200;
+const YTM_PRICE_TOLERANCE: f64 = 1e-12;
+const YTM_MIN_YIELD_PCT: f64 = -10.0;
+const YTM_MAX_YIELD_PCT: f64 = 100.0;
+
+/// File name prefix for on-disk yield curve cache entries.
+const DISK_CACHE_PREFIX: &str = "us_treasury_curves_";
+
+/// Default age after which the current year's curves are re-fetched.
+const DEFAULT_CURRENT_YEAR_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// BLS series for CPI-U, all items, not seasonally adjusted — the index TIPS
+/// principal is linked to.
+const CPI_SERIES_ID: &str = "CUUR0000SA0";
+
+/// Most years the public BLS API returns per request.
+const CPI_MAX_YEARS_PER_REQUEST: i32 = 10;
+
+/// Map from date → YieldCurve for one calendar year.
+type YearCurves = Vec<(NaiveDate, YieldCurve)>;
+
+/// Treasury.gov daily curve feeds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum CurveFeed {
+    /// Nominal par yield curve, used for bills, notes and bonds.
+    Nominal,
+    /// Real par yield curve, used for TIPS.
+    Real,
+}
+
+impl CurveFeed {
+    /// Value of the feed's `data` query parameter.
+    fn data_param(self) -> &'static str {
+        match self {
+            CurveFeed::Nominal => "daily_treasury_yield_curve",
+            CurveFeed::Real => "daily_treasury_real_yield_curve",
         }
-        // Clamp to range
-        if years <= pts[0].0 {
-            return Some(pts[0].1);
+    }
+
+    fn tenor_map(self) -> &'static [(&'static str, f64)] {
+        match self {
+            CurveFeed::Nominal => TENOR_MAP,
+            CurveFeed::Real => REAL_TENOR_MAP,
         }
-        if years >= pts[pts.len() - 1].0 {
-            return Some(pts[pts.len() - 1].1);
+    }
+
+    /// Disk cache file name infix, empty for the nominal feed so existing
+    /// cache files keep their names.
+    fn cache_infix(self) -> &'static str {
+        match self {
+            CurveFeed::Nominal => "",
+            CurveFeed::Real => "real_",
         }
-        // Find surrounding points
-        for i in 0..pts.len() - 1 {
-            if pts[i].0 <= years && years <= pts[i + 1].0 {
-                let t = (years - pts[i].0) / (pts[i + 1].0 - pts[i].0);
-                return Some(pts[i].1 + t * (pts[i + 1].1 - pts[i].1));
-            }
+    }
+
+    fn for_bond(bond: &BondQuoteMetadata) -> Self {
+        if bond.is_tips {
+            CurveFeed::Real
+        } else {
+            CurveFeed::Nominal
         }
-        None
     }
 }
 
-/// Map from date → YieldCurve for one calendar year.
-type YearCurves = Vec<(NaiveDate, YieldCurve)>;
+/// One year of curves held in memory, with the time they were fetched.
+#[derive(Debug, Clone)]
+struct CachedYear {
+    fetched_at: DateTime<Utc>,
+    curves: YearCurves,
+}
+
+/// One year of monthly CPI-U values held in memory, keyed by month (1-12).
+#[derive(Debug, Clone)]
+struct CachedCpiYear {
+    fetched_at: DateTime<Utc>,
+    months: HashMap<u32, f64>,
+}
+
+/// One year of curves as persisted in the on-disk cache.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskCacheEntry {
+    year: i32,
+    fetched_at: DateTime<Utc>,
+    curves: YearCurves,
+}
 
 // ---------------------------------------------------------------------------
 // TreasuryDirect bond details (for enrichment)
@@ -80,6 +157,15 @@ pub struct TreasuryBondDetails {
     pub maturity_date: NaiveDate,
     pub face_value: Decimal,
     pub coupon_frequency: String,
+    /// Treasury Inflation-Protected Security.
+    pub is_tips: bool,
+    /// Reference CPI on the dated date (TIPS only), the base of the index
+    /// ratio on any later settlement date.
+    pub ref_cpi_on_dated_date: Option<Decimal>,
+    /// First call date for callable bonds.
+    pub call_date: Option<NaiveDate>,
+    /// Call price as a fraction of par (callable Treasuries are called at par).
+    pub call_price: Option<Decimal>,
 }
 
 /// Response item from TreasuryDirect securities search.
@@ -92,6 +178,16 @@ struct TdSecurityItem {
     maturity_date: Option<String>,
     #[serde(default)]
     interest_payment_frequency: Option<String>,
+    /// "Yes" for TIPS.
+    #[serde(default)]
+    tips: Option<String>,
+    #[serde(default)]
+    ref_cpi_on_dated_date: Option<String>,
+    /// "Yes" for callable bonds.
+    #[serde(default)]
+    callable: Option<String>,
+    #[serde(default)]
+    call_date: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -100,8 +196,28 @@ struct TdSecurityItem {
 
 pub struct UsTreasuryCalcProvider {
     client: reqwest::Client,
-    /// Cached yield curves keyed by calendar year.
-    curve_cache: Arc<RwLock<HashMap<i32, YearCurves>>>,
+    /// Cached yield curves keyed by feed and calendar year.
+    curve_cache: Arc<RwLock<HashMap<(CurveFeed, i32), CachedYear>>>,
+    /// Cached monthly CPI-U values keyed by calendar year, for TIPS.
+    cpi_cache: Arc<RwLock<HashMap<i32, CachedCpiYear>>>,
+    /// Yield interpolation method used when pricing.
+    interpolation: InterpolationMethod,
+    /// Directory for the on-disk curve cache (disabled when `None`).
+    cache_dir: Option<PathBuf>,
+    /// Age after which the current year's curves are re-fetched, since new
+    /// trading days are appended to the feed daily.
+    current_year_ttl: Duration,
+    /// Largest response body accepted from Treasury.gov.
+    max_body_bytes: usize,
+    /// Whether a date before the year's first curve uses the next curve
+    /// instead of failing.
+    fall_forward: bool,
+    /// Whether a date before the year's first curve uses the previous year's
+    /// last curve. Checked before `fall_forward`.
+    previous_year_fallback: bool,
+    /// Whether TIPS index ratios are derived from BLS CPI-U data instead of
+    /// taken from the bond metadata.
+    cpi_index_ratios: bool,
 }
 
 impl Default for UsTreasuryCalcProvider {
@@ -120,7 +236,144 @@ impl UsTreasuryCalcProvider {
         Self {
             client,
             curve_cache: Arc::new(RwLock::new(HashMap::new())),
+            cpi_cache: Arc::new(RwLock::new(HashMap::new())),
+            interpolation: InterpolationMethod::default(),
+            cache_dir: None,
+            current_year_ttl: DEFAULT_CURRENT_YEAR_TTL,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            fall_forward: false,
+            previous_year_fallback: false,
+            cpi_index_ratios: false,
+        }
+    }
+
+    /// Interpolate yields with the given method (default linear).
+    pub fn with_interpolation(mut self, interpolation: InterpolationMethod) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Persist fetched yield curves as JSON under `cache_dir`, so they are
+    /// reused across restarts.
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    /// Re-fetch the current year's curves once they are older than `ttl`
+    /// (default 6 hours).  Past years stay cached indefinitely.
+    pub fn with_current_year_ttl(mut self, ttl: Duration) -> Self {
+        self.current_year_ttl = ttl;
+        self
+    }
+
+    /// Reject yield curve responses larger than `max_bytes` (default 16 MiB).
+    pub fn with_max_body_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_body_bytes = max_bytes;
+        self
+    }
+
+    /// When no curve is published on or before a requested date within its
+    /// year (e.g. the first days of January), use the year's first curve
+    /// after the date.  Historical quotes then include such dates instead of
+    /// starting at the first curve.  Off by default, in which case such dates
+    /// fail with `NoDataForRange` unless the previous-year fallback applies.
+    pub fn with_fall_forward(mut self, enabled: bool) -> Self {
+        self.fall_forward = enabled;
+        self
+    }
+
+    /// When no curve is published on or before a requested date within its
+    /// year, use the previous year's last curve, fetching that year if it is
+    /// not cached.  Avoids look-ahead, and wins over fall-forward when both
+    /// are enabled; a previous year that fails to load leaves the date to
+    /// fall-forward.  Off by default.
+    pub fn with_previous_year_fallback(mut self, enabled: bool) -> Self {
+        self.previous_year_fallback = enabled;
+        self
+    }
+
+    /// Derive a TIPS index ratio per settlement date from monthly CPI-U
+    /// fetched from the BLS, for bonds with a reference CPI on their dated
+    /// date.  A failed fetch falls back to the ratio in the bond metadata.
+    /// Off by default, in which case pricing uses the metadata ratio and
+    /// makes no BLS requests.
+    pub fn with_cpi_index_ratios(mut self, enabled: bool) -> Self {
+        self.cpi_index_ratios = enabled;
+        self
+    }
+
+    /// Drop all cached yield curves, in memory and on disk, and cached CPI.
+    pub async fn clear_cache(&self) -> Result<(), MarketDataError> {
+        self.curve_cache.write().await.clear();
+        self.cpi_cache.write().await.clear();
+
+        let Some(dir) = &self.cache_dir else {
+            return Ok(());
+        };
+        let mut entries = match tokio::fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(MarketDataError::ProviderError {
+                    provider: PROVIDER_ID.to_string(),
+                    message: format!("Failed to read cache dir: {}", e),
+                })
+            }
+        };
+        loop {
+            let entry = match entries.next_entry().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => {
+                    return Err(MarketDataError::ProviderError {
+                        provider: PROVIDER_ID.to_string(),
+                        message: format!("Failed to read cache dir entry: {}", e),
+                    })
+                }
+            };
+            let is_cache_file = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(DISK_CACHE_PREFIX));
+            if is_cache_file {
+                tokio::fs::remove_file(entry.path()).await.map_err(|e| {
+                    MarketDataError::ProviderError {
+                        provider: PROVIDER_ID.to_string(),
+                        message: format!("Failed to remove cache file: {}", e),
+                    }
+                })?;
+            }
         }
+        Ok(())
+    }
+
+    /// Discount and bond-equivalent yields of a bill maturing on
+    /// `maturity_date`, priced from the nominal curve for `date` the same
+    /// way quotes are.
+    pub async fn bill_yields(
+        &self,
+        maturity_date: NaiveDate,
+        date: NaiveDate,
+    ) -> Result<BillYields, MarketDataError> {
+        let curve = self.get_curve_for_date(CurveFeed::Nominal, date).await?;
+        let price = calculate_price(
+            PROVIDER_ID,
+            &curve,
+            self.interpolation,
+            date,
+            maturity_date,
+            0.0,
+            "ZERO",
+            US_TREASURY_FACE_VALUE,
+            None,
+        )?;
+        bill_yields(price, (maturity_date - date).num_days()).ok_or_else(|| {
+            MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("Bill maturing {} has no yield on {}", maturity_date, date),
+            }
+        })
     }
 
     /// Fetch bond details from TreasuryDirect for enrichment.
@@ -129,10 +382,13 @@ impl UsTreasuryCalcProvider {
         client: &reqwest::Client,
         isin: &str,
     ) -> Option<TreasuryBondDetails> {
-        if !is_us_treasury_isin(isin) || isin.len() < 11 {
+        if !is_us_treasury_isin(isin) {
             return None;
         }
-        let cusip = &isin[2..11];
+        let Some(cusip) = isin_to_cusip(isin) else {
+            warn!("Skipping TreasuryDirect lookup for malformed ISIN {}", isin);
+            return None;
+        };
         let url = format!(
             "https://www.treasurydirect.gov/TA_WS/securities/search?cusip={}&format=json",
             cusip
@@ -148,7 +404,10 @@ impl UsTreasuryCalcProvider {
             return None;
         }
 
-        let items: Vec<TdSecurityItem> = resp.json().await.ok()?;
+        let body = read_bytes_capped(resp, DEFAULT_MAX_BODY_BYTES, PROVIDER_ID)
+            .await
+            .ok()?;
+        let items: Vec<TdSecurityItem> = serde_json::from_slice(&body).ok()?;
         let item = items.into_iter().next()?;
 
         let coupon_rate = item
@@ -178,11 +437,40 @@ impl UsTreasuryCalcProvider {
                 }
             });
 
+        let is_tips = item
+            .tips
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("yes"));
+        let is_callable = item
+            .callable
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case("yes"));
+        let call_date = if is_callable {
+            item.call_date
+                .as_deref()
+                .filter(|d| d.len() >= 10)
+                .and_then(|d| NaiveDate::parse_from_str(&d[..10], "%Y-%m-%d").ok())
+        } else {
+            None
+        };
+        let ref_cpi_on_dated_date = if is_tips {
+            item.ref_cpi_on_dated_date
+                .as_deref()
+                .and_then(|r| r.parse::<Decimal>().ok())
+                .filter(|r| *r > Decimal::ZERO)
+        } else {
+            None
+        };
+
         Some(TreasuryBondDetails {
             coupon_rate,
             maturity_date,
             face_value: Decimal::from(US_TREASURY_FACE_VALUE as i64),
             coupon_frequency,
+            is_tips,
+            ref_cpi_on_dated_date,
+            call_date,
+            call_price: call_date.map(|_| Decimal::ONE),
         })
     }
 
@@ -190,31 +478,198 @@ impl UsTreasuryCalcProvider {
     // Yield curve fetching
     // -----------------------------------------------------------------------
 
-    /// Ensure the curve cache has data for the given year.
-    async fn ensure_curves(&self, year: i32) -> Result<(), MarketDataError> {
-        {
-            let cache = self.curve_cache.read().await;
-            if cache.contains_key(&year) {
-                return Ok(());
-            }
+    /// Whether curves for `year` fetched at `fetched_at` can still be used.
+    /// Only the current year expires; past years are complete.
+    fn is_cached_year_fresh(
+        &self,
+        year: i32,
+        fetched_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if year < now.year() {
+            return true;
+        }
+        now.signed_duration_since(fetched_at)
+            .to_std()
+            .map_or(true, |age| age < self.current_year_ttl)
+    }
+
+    /// Whether the curve cache holds usable data for the given feed and year.
+    async fn has_fresh_curves(&self, feed: CurveFeed, year: i32) -> bool {
+        let cache = self.curve_cache.read().await;
+        cache
+            .get(&(feed, year))
+            .is_some_and(|cached| self.is_cached_year_fresh(year, cached.fetched_at, Utc::now()))
+    }
+
+    /// Ensure the curve cache has fresh data for the given feed and year.
+    async fn ensure_curves(&self, feed: CurveFeed, year: i32) -> Result<(), MarketDataError> {
+        if self.has_fresh_curves(feed, year).await {
+            return Ok(());
         }
 
-        let curves = self.fetch_year_curves(year).await?;
+        let now = Utc::now();
+        let cached = match self
+            .load_disk_cache(feed, year, now)
+            .await
+            .filter(|entry| self.is_cached_year_fresh(year, entry.fetched_at, now))
+        {
+            Some(entry) => CachedYear {
+                fetched_at: entry.fetched_at,
+                curves: entry.curves,
+            },
+            None => match self.fetch_year_curves(feed, year).await {
+                Ok(curves) => {
+                    self.store_disk_cache(feed, year, &curves, now).await;
+                    CachedYear {
+                        fetched_at: now,
+                        curves,
+                    }
+                }
+                // Serve stale curves rather than nothing if a refresh fails.
+                Err(e) if self.curve_cache.read().await.contains_key(&(feed, year)) => {
+                    warn!("Keeping stale Treasury curves for {}: {}", year, e);
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            },
+        };
         {
             let mut cache = self.curve_cache.write().await;
-            cache.insert(year, curves);
+            cache.insert((feed, year), cached);
         }
         Ok(())
     }
 
+    /// Ensure the curve cache has data for every year in `years`, loading the
+    /// missing years concurrently (bounded by the provider's `max_concurrency`).
+    ///
+    /// Returns the years that were loaded and the per-year failures; a failing
+    /// year does not abort the others.
+    async fn ensure_curves_for_years(
+        &self,
+        feed: CurveFeed,
+        years: RangeInclusive<i32>,
+    ) -> (Vec<i32>, Vec<(i32, MarketDataError)>) {
+        let mut missing = Vec::new();
+        for year in years {
+            if !self.has_fresh_curves(feed, year).await {
+                missing.push(year);
+            }
+        }
+
+        let load = |year: i32| async move { (year, self.ensure_curves(feed, year).await) };
+        let max_concurrency = self.rate_limit().max_concurrency.max(1);
+        let mut pending = missing.into_iter();
+        let mut in_flight: FuturesUnordered<_> =
+            pending.by_ref().take(max_concurrency).map(load).collect();
+
+        let mut loaded = Vec::new();
+        let mut failed = Vec::new();
+        while let Some((year, result)) = in_flight.next().await {
+            match result {
+                Ok(()) => loaded.push(year),
+                Err(e) => failed.push((year, e)),
+            }
+            if let Some(year) = pending.next() {
+                in_flight.push(load(year));
+            }
+        }
+
+        loaded.sort_unstable();
+        failed.sort_by_key(|(year, _)| *year);
+        (loaded, failed)
+    }
+
+    // -----------------------------------------------------------------------
+    // On-disk cache
+    // -----------------------------------------------------------------------
+
+    fn disk_cache_path(dir: &Path, feed: CurveFeed, year: i32) -> PathBuf {
+        dir.join(format!(
+            "{}{}{}.json",
+            DISK_CACHE_PREFIX,
+            feed.cache_infix(),
+            year
+        ))
+    }
+
+    /// Load a year's curves from the disk cache if present and still fresh.
+    async fn load_disk_cache(
+        &self,
+        feed: CurveFeed,
+        year: i32,
+        now: DateTime<Utc>,
+    ) -> Option<DiskCacheEntry> {
+        let path = Self::disk_cache_path(self.cache_dir.as_ref()?, feed, year);
+        let bytes = tokio::fs::read(&path).await.ok()?;
+        let entry: DiskCacheEntry = match serde_json::from_slice(&bytes) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!(
+                    "Ignoring unreadable Treasury curve cache {}: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+
+        if entry.year != year || !is_disk_cache_fresh(year, entry.fetched_at, now) {
+            debug!("Treasury curve cache for {} is stale", year);
+            return None;
+        }
+
+        debug!("Loaded Treasury yield curve for {} from disk cache", year);
+        Some(entry)
+    }
+
+    /// Persist a year's curves to the disk cache.  Failures are logged and
+    /// ignored since the cache is only an optimisation.
+    async fn store_disk_cache(
+        &self,
+        feed: CurveFeed,
+        year: i32,
+        curves: &YearCurves,
+        now: DateTime<Utc>,
+    ) {
+        let Some(dir) = &self.cache_dir else {
+            return;
+        };
+        let entry = DiskCacheEntry {
+            year,
+            fetched_at: now,
+            curves: curves.clone(),
+        };
+        let bytes = match serde_json::to_vec(&entry) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to encode Treasury curve cache for {}: {}", year, e);
+                return;
+            }
+        };
+        let result = match tokio::fs::create_dir_all(dir).await {
+            Ok(()) => tokio::fs::write(Self::disk_cache_path(dir, feed, year), bytes).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!("Failed to write Treasury curve cache for {}: {}", year, e);
+        }
+    }
+
     /// Fetch and parse one year of yield curve data from Treasury.gov XML.
-    async fn fetch_year_curves(&self, year: i32) -> Result<YearCurves, MarketDataError> {
+    async fn fetch_year_curves(
+        &self,
+        feed: CurveFeed,
+        year: i32,
+    ) -> Result<YearCurves, MarketDataError> {
         let url = format!(
-            "https://home.treasury.gov/resource-center/data-chart-center/interest-rates/pages/xml?data=daily_treasury_yield_curve&field_tdr_date_value={}",
+            "https://home.treasury.gov/resource-center/data-chart-center/interest-rates/pages/xml?data={}&field_tdr_date_value={}",
+            feed.data_param(),
             year
         );
 
-        debug!("Fetching Treasury yield curve for year {}", year);
+        debug!("Fetching Treasury {:?} yield curve for year {}", feed, year);
 
         let resp =
             self.client
@@ -233,128 +688,505 @@ impl UsTreasuryCalcProvider {
             });
         }
 
-        let body = resp
-            .text()
-            .await
-            .map_err(|e| MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("Failed to read response: {}", e),
-            })?;
+        let body = read_text_capped(resp, self.max_body_bytes, PROVIDER_ID).await?;
 
-        parse_yield_curve_xml(&body)
+        parse_yield_curve_xml(&body, feed.tenor_map())
     }
 
     /// Look up the yield curve for a specific date, falling back to previous
     /// trading days if the exact date is not available.
-    async fn get_curve_for_date(&self, date: NaiveDate) -> Result<YieldCurve, MarketDataError> {
-        self.ensure_curves(date.year()).await?;
+    async fn get_curve_for_date(
+        &self,
+        feed: CurveFeed,
+        date: NaiveDate,
+    ) -> Result<YieldCurve, MarketDataError> {
+        self.ensure_curves(feed, date.year()).await?;
 
-        let cache = self.curve_cache.read().await;
-        let curves = cache
-            .get(&date.year())
-            .ok_or_else(|| MarketDataError::ProviderError {
-                provider: PROVIDER_ID.to_string(),
-                message: format!("No curve data for year {}", date.year()),
-            })?;
+        let next = {
+            let cache = self.curve_cache.read().await;
+            let curves = cache
+                .get(&(feed, date.year()))
+                .map(|cached| &cached.curves)
+                .ok_or_else(|| MarketDataError::ProviderError {
+                    provider: PROVIDER_ID.to_string(),
+                    message: format!("No curve data for year {}", date.year()),
+                })?;
 
-        // Find closest date <= target date
-        let mut best: Option<&(NaiveDate, YieldCurve)> = None;
-        for entry in curves {
-            if entry.0 <= date {
-                match best {
-                    Some(b) if entry.0 > b.0 => best = Some(entry),
-                    None => best = Some(entry),
-                    _ => {}
+            // Find closest date <= target date
+            let mut best: Option<&(NaiveDate, YieldCurve)> = None;
+            for entry in curves {
+                if entry.0 <= date {
+                    match best {
+                        Some(b) if entry.0 > b.0 => best = Some(entry),
+                        None => best = Some(entry),
+                        _ => {}
+                    }
                 }
             }
+
+            if let Some((_, curve)) = best {
+                return Ok(curve.clone());
+            }
+            curves
+                .iter()
+                .filter(|(d, _)| *d > date)
+                .min_by_key(|(d, _)| *d)
+                .cloned()
+        };
+
+        // Nothing on or before the date this year. The previous year is
+        // loaded rather than used only when cached, so the result does not
+        // depend on what was fetched before.
+        if self.previous_year_fallback {
+            if let Some(curve) = self.previous_year_curve(feed, date).await {
+                return Ok(curve);
+            }
+        }
+        if !self.fall_forward {
+            return Err(MarketDataError::NoDataForRange);
+        }
+        next.map(|(d, curve)| {
+            debug!(
+                "US_TREASURY_CALC: no curve on or before {}, using {}",
+                date, d
+            );
+            curve
+        })
+        .ok_or(MarketDataError::NoDataForRange)
+    }
+
+    /// The previous year's last curve, for a date before its own year's first
+    /// curve. A previous year that fails to load is a miss, so fall-forward
+    /// still gets its turn.
+    async fn previous_year_curve(&self, feed: CurveFeed, date: NaiveDate) -> Option<YieldCurve> {
+        let previous_year = date.year() - 1;
+        if let Err(e) = self.ensure_curves(feed, previous_year).await {
+            warn!(
+                "US_TREASURY_CALC: previous-year curves for {} unavailable ({}): {}",
+                date, previous_year, e
+            );
+            return None;
         }
+        let cache = self.curve_cache.read().await;
+        let (d, curve) = cache
+            .get(&(feed, previous_year))?
+            .curves
+            .iter()
+            .max_by_key(|(d, _)| *d)?;
+        debug!(
+            "US_TREASURY_CALC: no curve on or before {}, using {}",
+            date, d
+        );
+        Some(curve.clone())
+    }
 
-        best.map(|(_, c)| c.clone())
-            .ok_or(MarketDataError::NoDataForRange)
+    /// Weekdays in `start..=end` that come before the first curve of their
+    /// year, each with the curve the fallback options pick for it. Empty
+    /// unless a fallback is enabled; years that failed to load are skipped.
+    async fn leading_fallback_curves(
+        &self,
+        feed: CurveFeed,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Vec<(NaiveDate, YieldCurve)> {
+        if !self.fall_forward && !self.previous_year_fallback {
+            return Vec::new();
+        }
+        let mut leading = Vec::new();
+        for year in start.year()..=end.year() {
+            let first_curve = {
+                let cache = self.curve_cache.read().await;
+                cache
+                    .get(&(feed, year))
+                    .and_then(|cached| cached.curves.iter().map(|(d, _)| *d).min())
+            };
+            let (Some(first_curve), Some(year_start)) =
+                (first_curve, NaiveDate::from_ymd_opt(year, 1, 1))
+            else {
+                continue;
+            };
+            let mut day = start.max(year_start);
+            while day < first_curve && day <= end {
+                if !matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                    match self.get_curve_for_date(feed, day).await {
+                        Ok(curve) => leading.push((day, curve)),
+                        Err(e) => debug!("Skipping date {}: {}", day, e),
+                    }
+                }
+                let Some(next) = day.succ_opt() else { break };
+                day = next;
+            }
+        }
+        leading
     }
 
     // -----------------------------------------------------------------------
     // Bond pricing
     // -----------------------------------------------------------------------
 
-    /// Calculate bond price as fraction of par for a given date.
-    fn calculate_price(
-        curve: &YieldCurve,
+    /// Call date and call price (fraction of par, defaulting to par) for a
+    /// callable bond.
+    fn call_provision(bond: &BondQuoteMetadata) -> Option<(NaiveDate, f64)> {
+        let call_date = bond.call_date?;
+        let call_price = bond
+            .call_price
+            .and_then(|p| p.try_into().ok())
+            .unwrap_or(1.0);
+        Some((call_date, call_price))
+    }
+
+    /// Solve for the yield to maturity (in percent, matching the curve
+    /// convention) implied by a clean fraction-of-par price.
+    ///
+    /// Uses Newton-Raphson, falling back to bisection if it fails to converge
+    /// within `YTM_MAX_NEWTON_ITERATIONS`.
+    pub fn yield_to_maturity(
+        price_fraction: f64,
         settlement_date: NaiveDate,
         maturity_date: NaiveDate,
         coupon_rate: f64,
         coupon_frequency: &str,
-        face_value: f64,
     ) -> Result<f64, MarketDataError> {
-        let years_to_maturity = (maturity_date - settlement_date).num_days() as f64 / 365.25;
-
-        if years_to_maturity <= 0.0 {
-            // Bond has matured — return par
-            return Ok(1.0);
+        if maturity_date <= settlement_date {
+            return Err(MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: "Cannot compute yield for a matured bond".to_string(),
+            });
+        }
+        if !price_fraction.is_finite() || price_fraction <= 0.0 {
+            return Err(MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("Invalid price for yield calculation: {}", price_fraction),
+            });
         }
 
-        let yield_pct =
-            curve
-                .interpolate(years_to_maturity)
-                .ok_or_else(|| MarketDataError::ProviderError {
-                    provider: PROVIDER_ID.to_string(),
-                    message: "Could not interpolate yield".to_string(),
-                })?;
+        let frequency = normalize_frequency(coupon_frequency);
+        let price_error = |yield_pct: f64| {
+            price_from_yield(
+                yield_pct,
+                settlement_date,
+                maturity_date,
+                coupon_rate,
+                &frequency,
+                US_TREASURY_FACE_VALUE,
+                1.0,
+                DayCount::default(),
+            ) - price_fraction
+        };
 
-        let yield_dec = yield_pct / 100.0; // e.g. 4.25% → 0.0425
+        // Newton-Raphson with a central-difference derivative.
+        let mut yield_pct = (coupon_rate * 100.0).max(1.0);
+        for _ in 0..YTM_MAX_NEWTON_ITERATIONS {
+            let error = price_error(yield_pct);
+            if error.abs() < YTM_PRICE_TOLERANCE {
+                return Ok(yield_pct);
+            }
+            let h = 1e-5;
+            let slope = (price_error(yield_pct + h) - price_error(yield_pct - h)) / (2.0 * h);
+            if slope.abs() < f64::EPSILON || !slope.is_finite() {
+                break;
+            }
+            let next = yield_pct - error / slope;
+            if !next.is_finite() || next <= YTM_MIN_YIELD_PCT || next >= YTM_MAX_YIELD_PCT {
+                break;
+            }
+            yield_pct = next;
+        }
 
-        let price = if coupon_frequency == "ZERO" || coupon_rate == 0.0 {
-            // T-bill / zero-coupon: simple discount
-            // P = F / (1 + y * t/360)  (money-market convention)
-            let days = (maturity_date - settlement_date).num_days() as f64;
-            face_value / (1.0 + yield_dec * days / 360.0)
-        } else {
-            // Coupon bond PV: semi-annual assumed unless ANNUAL/QUARTERLY
-            let freq = match coupon_frequency {
-                "ANNUAL" => 1.0,
-                "QUARTERLY" => 4.0,
-                _ => 2.0, // SEMI_ANNUAL default
-            };
+        // Bisection fallback: price is strictly decreasing in yield.
+        let (mut lo, mut hi) = (YTM_MIN_YIELD_PCT, YTM_MAX_YIELD_PCT);
+        if price_error(lo) < 0.0 || price_error(hi) > 0.0 {
+            return Err(MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!(
+                    "No yield between {}% and {}% matches price {}",
+                    lo, hi, price_fraction
+                ),
+            });
+        }
+        for _ in 0..YTM_MAX_BISECTION_ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+            let error = price_error(mid);
+            if error.abs() < YTM_PRICE_TOLERANCE {
+                return Ok(mid);
+            }
+            if error > 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok((lo + hi) / 2.0)
+    }
 
-            let coupon_payment = face_value * coupon_rate / freq;
-            let periods = (years_to_maturity * freq).ceil() as u32;
-            let period_yield = yield_dec / freq;
+    // -----------------------------------------------------------------------
+    // CPI index ratio (TIPS)
+    // -----------------------------------------------------------------------
 
-            let mut pv = 0.0;
-            for i in 1..=periods {
-                pv += coupon_payment / (1.0 + period_yield).powi(i as i32);
+    /// Index ratios for settlement dates in `settlements`.
+    ///
+    /// Nominal bonds use 1.0 and a TIPS uses the ratio in its bond metadata.
+    /// With [`Self::with_cpi_index_ratios`], a TIPS with a reference CPI on
+    /// its dated date gets a per-date ratio from monthly CPI-U instead, when
+    /// the CPI can be fetched.  Pricing a TIPS with no ratio would silently
+    /// use unadjusted principal, so that is an error.
+    async fn index_ratios(
+        &self,
+        bond: &BondQuoteMetadata,
+        settlements: RangeInclusive<NaiveDate>,
+    ) -> Result<IndexRatios, MarketDataError> {
+        if !bond.is_tips {
+            return Ok(IndexRatios::Fixed(1.0));
+        }
+        let fixed = bond
+            .index_ratio
+            .and_then(|r| r.try_into().ok())
+            .filter(|r: &f64| r.is_finite() && *r > 0.0);
+        let base = bond
+            .ref_cpi_on_dated_date
+            .and_then(|r| r.try_into().ok())
+            .filter(|r: &f64| r.is_finite() && *r > 0.0);
+
+        let missing_ratio = || MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: "Index ratio required in bond metadata for TIPS pricing".to_string(),
+        };
+        let Some(base) = base.filter(|_| self.cpi_index_ratios) else {
+            return fixed.map(IndexRatios::Fixed).ok_or_else(missing_ratio);
+        };
+
+        // The reference CPI for a date interpolates the CPI-U of the third and
+        // second months before it.
+        let first_year = cpi_lag_month(*settlements.start(), 3).0;
+        let last_year = settlements.end().year();
+        match self.ensure_cpi(first_year..=last_year).await {
+            Ok(()) => {
+                let cache = self.cpi_cache.read().await;
+                let monthly = cache
+                    .iter()
+                    .filter(|(year, _)| (first_year..=last_year).contains(*year))
+                    .flat_map(|(year, cached)| {
+                        cached
+                            .months
+                            .iter()
+                            .map(move |(month, value)| ((*year, *month), *value))
+                    })
+                    .collect();
+                Ok(IndexRatios::Cpi {
+                    ref_cpi_on_dated_date: base,
+                    monthly,
+                })
             }
-            pv += face_value / (1.0 + period_yield).powi(periods as i32);
-            pv
+            Err(e) => match fixed {
+                Some(ratio) => {
+                    warn!(
+                        "US_TREASURY_CALC: CPI fetch failed, using fixed index ratio: {}",
+                        e
+                    );
+                    Ok(IndexRatios::Fixed(ratio))
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Ensure the CPI cache has fresh data for every year in `years`.  A
+    /// failed refresh keeps stale years rather than failing.
+    async fn ensure_cpi(&self, years: RangeInclusive<i32>) -> Result<(), MarketDataError> {
+        let now = Utc::now();
+        let missing: Vec<i32> = {
+            let cache = self.cpi_cache.read().await;
+            years
+                .filter(|year| {
+                    !cache.get(year).is_some_and(|cached| {
+                        self.is_cached_year_fresh(*year, cached.fetched_at, now)
+                    })
+                })
+                .collect()
+        };
+        let (Some(&first), Some(&last)) = (missing.first(), missing.last()) else {
+            return Ok(());
         };
 
-        // Return as fraction of par
-        Ok(price / face_value)
+        let mut start = first;
+        while start <= last {
+            let end = (start + CPI_MAX_YEARS_PER_REQUEST - 1).min(last);
+            match self.fetch_cpi_years(start, end).await {
+                Ok(fetched) => {
+                    let mut cache = self.cpi_cache.write().await;
+                    for year in start..=end {
+                        let months = fetched.get(&year).cloned().unwrap_or_default();
+                        cache.insert(
+                            year,
+                            CachedCpiYear {
+                                fetched_at: now,
+                                months,
+                            },
+                        );
+                    }
+                }
+                Err(e) => {
+                    let cache = self.cpi_cache.read().await;
+                    if !(start..=end).all(|year| cache.contains_key(&year)) {
+                        return Err(e);
+                    }
+                    warn!("Keeping stale CPI data for {}-{}: {}", start, end, e);
+                }
+            }
+            start = end + 1;
+        }
+        Ok(())
     }
 
-    /// Build a Quote from a calculated price.
-    fn make_quote(
-        date: NaiveDate,
-        price_fraction: f64,
-        currency: &str,
-    ) -> Result<Quote, MarketDataError> {
-        let close =
-            Decimal::try_from(price_fraction).map_err(|_| MarketDataError::ValidationFailed {
-                message: format!("Invalid price: {}", price_fraction),
+    /// Fetch monthly CPI-U values for `start_year..=end_year` from the BLS
+    /// public API.
+    async fn fetch_cpi_years(
+        &self,
+        start_year: i32,
+        end_year: i32,
+    ) -> Result<HashMap<i32, HashMap<u32, f64>>, MarketDataError> {
+        debug!("Fetching CPI-U for {}-{}", start_year, end_year);
+
+        let resp = self
+            .client
+            .post("https://api.bls.gov/publicAPI/v2/timeseries/data/")
+            .json(&serde_json::json!({
+                "seriesid": [CPI_SERIES_ID],
+                "startyear": start_year.to_string(),
+                "endyear": end_year.to_string(),
+            }))
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("CPI request failed: {}", e),
             })?;
 
-        let timestamp =
-            DateTime::<Utc>::from_naive_utc_and_offset(date.and_hms_opt(16, 0, 0).unwrap(), Utc);
+        if !resp.status().is_success() {
+            return Err(MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("CPI HTTP {}", resp.status()),
+            });
+        }
 
-        Ok(Quote::new(
-            timestamp,
-            close,
-            currency.to_string(),
-            PROVIDER_ID.to_string(),
-        ))
+        let body = read_bytes_capped(resp, self.max_body_bytes, PROVIDER_ID).await?;
+        parse_bls_cpi_json(&body)
     }
 }
 
+/// CPI index ratios for the settlement dates of one pricing request.
+#[derive(Debug)]
+enum IndexRatios {
+    /// The same ratio on every date (1.0 for nominal bonds).
+    Fixed(f64),
+    /// Reference CPI on each date over the reference CPI on the dated date.
+    Cpi {
+        ref_cpi_on_dated_date: f64,
+        monthly: HashMap<(i32, u32), f64>,
+    },
+}
+
+impl IndexRatios {
+    /// Index ratio on `settlement`, or `None` when the CPI it needs has not
+    /// been published.
+    fn on(&self, settlement: NaiveDate) -> Option<f64> {
+        match self {
+            IndexRatios::Fixed(ratio) => Some(*ratio),
+            IndexRatios::Cpi {
+                ref_cpi_on_dated_date,
+                monthly,
+            } => reference_cpi(settlement, |year, month| {
+                monthly.get(&(year, month)).copied()
+            })
+            .map(|cpi| cpi / ref_cpi_on_dated_date),
+        }
+    }
+}
+
+/// The (year, month) `lag` months before `date`'s month.
+fn cpi_lag_month(date: NaiveDate, lag: u32) -> (i32, u32) {
+    let months = date.year() * 12 + date.month0() as i32 - lag as i32;
+    (months.div_euclid(12), months.rem_euclid(12) as u32 + 1)
+}
+
+/// Treasury reference CPI for `date`: the CPI-U of the third preceding month,
+/// plus the change to the second preceding month prorated by day of month.
+fn reference_cpi(date: NaiveDate, monthly: impl Fn(i32, u32) -> Option<f64>) -> Option<f64> {
+    let (y3, m3) = cpi_lag_month(date, 3);
+    let (y2, m2) = cpi_lag_month(date, 2);
+    let cpi3 = monthly(y3, m3)?;
+    let cpi2 = monthly(y2, m2)?;
+    let days_in_month = NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?
+        .checked_add_months(chrono::Months::new(1))?
+        .pred_opt()?
+        .day();
+    Some(cpi3 + f64::from(date.day() - 1) / f64::from(days_in_month) * (cpi2 - cpi3))
+}
+
+/// BLS timeseries API response (only the fields we read).
+#[derive(Debug, Deserialize)]
+struct BlsResponse {
+    status: String,
+    #[serde(rename = "Results", default)]
+    results: Option<BlsResults>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlsResults {
+    #[serde(default)]
+    series: Vec<BlsSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlsSeries {
+    #[serde(default)]
+    data: Vec<BlsObservation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlsObservation {
+    year: String,
+    period: String,
+    value: String,
+}
+
+/// Parse a BLS timeseries response into monthly values keyed by year, then
+/// month.  Annual averages (`M13`) and unparseable rows are skipped.
+fn parse_bls_cpi_json(body: &[u8]) -> Result<HashMap<i32, HashMap<u32, f64>>, MarketDataError> {
+    let response: BlsResponse =
+        serde_json::from_slice(body).map_err(|e| MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: format!("Invalid CPI response: {}", e),
+        })?;
+    if response.status != "REQUEST_SUCCEEDED" {
+        return Err(MarketDataError::ProviderError {
+            provider: PROVIDER_ID.to_string(),
+            message: format!("CPI request failed: {}", response.status),
+        });
+    }
+
+    let mut years: HashMap<i32, HashMap<u32, f64>> = HashMap::new();
+    let observations = response
+        .results
+        .into_iter()
+        .flat_map(|results| results.series)
+        .flat_map(|series| series.data);
+    for obs in observations {
+        let month = obs
+            .period
+            .strip_prefix('M')
+            .and_then(|m| m.parse::<u32>().ok())
+            .filter(|m| (1..=12).contains(m));
+        let (Some(month), Ok(year), Ok(value)) =
+            (month, obs.year.parse::<i32>(), obs.value.parse::<f64>())
+        else {
+            continue;
+        };
+        years.entry(year).or_default().insert(month, value);
+    }
+    Ok(years)
+}
+
 // ---------------------------------------------------------------------------
 // MarketDataProvider impl
 // ---------------------------------------------------------------------------
@@ -407,7 +1239,10 @@ impl MarketDataProvider for UsTreasuryCalcProvider {
                 })?;
 
         let today = Utc::now().date_naive();
-        let curve = match self.get_curve_for_date(today).await {
+        let curve = match self
+            .get_curve_for_date(CurveFeed::for_bond(bond), today)
+            .await
+        {
             Ok(c) => c,
             Err(e) => {
                 warn!(
@@ -419,20 +1254,37 @@ impl MarketDataProvider for UsTreasuryCalcProvider {
         };
 
         let coupon_rate: f64 = bond.coupon_rate.try_into().unwrap_or(0.0);
-        let face_value: f64 = bond.face_value.try_into().unwrap_or(US_TREASURY_FACE_VALUE);
-
-        let price = match Self::calculate_price(
+        let face_value = face_value(PROVIDER_ID, bond, US_TREASURY_FACE_VALUE);
+        let currency = bond.currency.as_deref().unwrap_or(US_TREASURY_CURRENCY);
+
+        let settlement_offset = context
+            .settlement_offset_days
+            .unwrap_or(DEFAULT_SETTLEMENT_OFFSET_DAYS);
+        let settlement = settlement_date(today, settlement_offset);
+        let index_ratio = self
+            .index_ratios(bond, settlement..=settlement)
+            .await?
+            .on(settlement)
+            .ok_or_else(|| MarketDataError::ProviderError {
+                provider: PROVIDER_ID.to_string(),
+                message: format!("No CPI data for settlement on {}", settlement),
+            })?;
+        let price = match calculate_price(
+            PROVIDER_ID,
             &curve,
-            today,
+            self.interpolation,
+            settlement,
             bond.maturity_date,
             coupon_rate,
             &bond.coupon_frequency,
             face_value,
+            Self::call_provision(bond),
         ) {
             Ok(p) => {
+                let p = p * index_ratio;
                 debug!(
-                    "US_TREASURY_CALC: {} price={:.6} (coupon={}, maturity={}, freq={})",
-                    isin, p, coupon_rate, bond.maturity_date, bond.coupon_frequency
+                    "US_TREASURY_CALC: {} price={:.6} (coupon={}, maturity={}, freq={}, tips={})",
+                    isin, p, coupon_rate, bond.maturity_date, bond.coupon_frequency, bond.is_tips
                 );
                 p
             }
@@ -445,8 +1297,7 @@ impl MarketDataProvider for UsTreasuryCalcProvider {
             }
         };
 
-        let currency = context.currency_hint.as_deref().unwrap_or("USD");
-        Self::make_quote(today, price, currency)
+        make_quote(PROVIDER_ID, today, price, currency)
     }
 
     async fn get_historical_quotes(
@@ -471,42 +1322,86 @@ impl MarketDataProvider for UsTreasuryCalcProvider {
 
         let start_date = start.date_naive();
         let end_date = end.date_naive();
-        let currency = context.currency_hint.as_deref().unwrap_or("USD");
 
         let coupon_rate: f64 = bond.coupon_rate.try_into().unwrap_or(0.0);
-        let face_value: f64 = bond.face_value.try_into().unwrap_or(US_TREASURY_FACE_VALUE);
-
-        // Ensure we have curves for all years in range
-        for year in start_date.year()..=end_date.year() {
-            self.ensure_curves(year).await?;
+        let face_value = face_value(PROVIDER_ID, bond, US_TREASURY_FACE_VALUE);
+        let currency = bond.currency.as_deref().unwrap_or(US_TREASURY_CURRENCY);
+        let settlement_offset = context
+            .settlement_offset_days
+            .unwrap_or(DEFAULT_SETTLEMENT_OFFSET_DAYS);
+
+        let feed = CurveFeed::for_bond(bond);
+        let index_ratios = self
+            .index_ratios(
+                bond,
+                settlement_date(start_date, settlement_offset)
+                    ..=settlement_date(end_date, settlement_offset),
+            )
+            .await?;
+
+        // Ensure we have curves for all years in range; a year that fails to
+        // load only leaves a gap unless nothing could be loaded at all.
+        let years = start_date.year()..=end_date.year();
+        let (_, mut failed) = self.ensure_curves_for_years(feed, years.clone()).await;
+        let failed_count = failed.len();
+        for (year, e) in &failed {
+            warn!(
+                "US_TREASURY_CALC: yield curve fetch failed for {} ({}): {}",
+                isin, year, e
+            );
+        }
+        if failed_count > 0 && failed_count == years.clone().count() {
+            return Err(failed.remove(0).1);
         }
 
-        let cache = self.curve_cache.read().await;
+        // Dates before the first curve of their year have no curve of their
+        // own; with a fallback enabled they are priced off the one it picks
+        // instead of being dropped from the start of the range.
+        let leading = self
+            .leading_fallback_curves(feed, start_date, end_date)
+            .await;
+
         let mut quotes = Vec::new();
+        let mut price_on = |date: NaiveDate, curve: &YieldCurve| {
+            let settlement = settlement_date(date, settlement_offset);
+            let Some(index_ratio) = index_ratios.on(settlement) else {
+                debug!("Skipping date {}: no CPI data", date);
+                return;
+            };
+            match calculate_price(
+                PROVIDER_ID,
+                curve,
+                self.interpolation,
+                settlement,
+                bond.maturity_date,
+                coupon_rate,
+                &bond.coupon_frequency,
+                face_value,
+                Self::call_provision(bond),
+            ) {
+                Ok(price) => match make_quote(PROVIDER_ID, date, price * index_ratio, currency) {
+                    Ok(q) => quotes.push(q),
+                    Err(e) => {
+                        debug!("Skipping date {}: {}", date, e);
+                    }
+                },
+                Err(e) => {
+                    debug!("Skipping date {}: {}", date, e);
+                }
+            }
+        };
+
+        for (date, curve) in &leading {
+            price_on(*date, curve);
+        }
 
         // Collect all curve dates in range
+        let cache = self.curve_cache.read().await;
         for year in start_date.year()..=end_date.year() {
-            if let Some(year_curves) = cache.get(&year) {
-                for (date, curve) in year_curves {
+            if let Some(cached) = cache.get(&(feed, year)) {
+                for (date, curve) in &cached.curves {
                     if *date >= start_date && *date <= end_date {
-                        match Self::calculate_price(
-                            curve,
-                            *date,
-                            bond.maturity_date,
-                            coupon_rate,
-                            &bond.coupon_frequency,
-                            face_value,
-                        ) {
-                            Ok(price) => match Self::make_quote(*date, price, currency) {
-                                Ok(q) => quotes.push(q),
-                                Err(e) => {
-                                    debug!("Skipping date {}: {}", date, e);
-                                }
-                            },
-                            Err(e) => {
-                                debug!("Skipping date {}: {}", date, e);
-                            }
-                        }
+                        price_on(*date, curve);
                     }
                 }
             }
@@ -531,19 +1426,101 @@ fn extract_isin(instrument: &ProviderInstrument) -> Result<String, MarketDataErr
     }
 }
 
-/// Only accept US Treasury ISINs (prefix "US912").
-fn guard_us_treasury(isin: &str) -> Result<(), MarketDataError> {
-    if !is_us_treasury_isin(isin) {
-        return Err(MarketDataError::SymbolNotFound(format!(
-            "{} is not a US Treasury ISIN",
-            isin
-        )));
+/// Only accept well-formed US Treasury ISINs (prefix "US912", valid check
+/// digits).
+fn guard_us_treasury(isin: &str) -> Result<(), MarketDataError> {
+    if !is_us_treasury_isin(isin) || isin_to_cusip(isin).is_none() {
+        return Err(MarketDataError::SymbolNotFound(format!(
+            "{} is not a US Treasury ISIN",
+            isin
+        )));
+    }
+    Ok(())
+}
+
+fn is_us_treasury_isin(isin: &str) -> bool {
+    isin.starts_with("US912")
+}
+
+/// Extract the CUSIP from a US or Canadian ISIN.
+///
+/// Returns `None` unless the ISIN is 12 uppercase alphanumerics and both its
+/// own check digit and that of the embedded CUSIP are valid.
+pub fn isin_to_cusip(isin: &str) -> Option<String> {
+    if isin.len() != 12
+        || !isin
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        || !matches!(&isin[..2], "US" | "CA")
+    {
+        return None;
+    }
+    if isin_check_digit(&isin[..11])? != isin.as_bytes()[11] - b'0' {
+        return None;
+    }
+    let cusip = &isin[2..11];
+    if cusip_check_digit(&cusip[..8])? != cusip.as_bytes()[8] - b'0' {
+        return None;
+    }
+    Some(cusip.to_string())
+}
+
+/// Value of an ISIN/CUSIP character: digits as-is, letters from A = 10.
+fn char_value(c: char) -> Option<u32> {
+    c.to_digit(36)
+}
+
+/// ISIN check digit: letters expand to two digits, then Luhn from the right.
+fn isin_check_digit(body: &str) -> Option<u8> {
+    let mut digits = Vec::with_capacity(body.len() * 2);
+    for c in body.chars() {
+        let value = char_value(c)?;
+        if value >= 10 {
+            digits.push(value / 10);
+        }
+        digits.push(value % 10);
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = if i % 2 == 0 { d * 2 } else { d };
+            d / 10 + d % 10
+        })
+        .sum();
+    Some(((10 - sum % 10) % 10) as u8)
+}
+
+/// CUSIP check digit: every second character's value is doubled and the
+/// digits of all values summed.
+fn cusip_check_digit(body: &str) -> Option<u8> {
+    let mut sum = 0;
+    for (i, c) in body.chars().enumerate() {
+        let value = char_value(c)?;
+        let value = if i % 2 == 1 { value * 2 } else { value };
+        sum += value / 10 + value % 10;
+    }
+    Some(((10 - sum % 10) % 10) as u8)
+}
+
+/// Past years are immutable; the current year's cache is fresh only if it
+/// was fetched no earlier than the previous business day.
+fn is_disk_cache_fresh(year: i32, fetched_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    let today = now.date_naive();
+    if year < today.year() {
+        // Must have been fetched after the year closed to hold every trading day.
+        return fetched_at.date_naive().year() > year;
     }
-    Ok(())
+    fetched_at.date_naive() >= previous_business_day(today)
 }
 
-fn is_us_treasury_isin(isin: &str) -> bool {
-    isin.starts_with("US912")
+fn previous_business_day(date: NaiveDate) -> NaiveDate {
+    let mut day = date.pred_opt().unwrap_or(date);
+    while matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+        day = day.pred_opt().unwrap_or(day);
+    }
+    day
 }
 
 fn normalize_frequency(freq: &str) -> String {
@@ -577,11 +1554,34 @@ const TENOR_MAP: &[(&str, f64)] = &[
     ("BC_30YEAR", 30.0),
 ];
 
+/// Tenor labels in the real (TIPS) yield curve XML.  The feed only publishes
+/// 5–30 year points; shorter maturities clamp to the 5-year yield.
+const REAL_TENOR_MAP: &[(&str, f64)] = &[
+    ("TC_5YEAR", 5.0),
+    ("TC_7YEAR", 7.0),
+    ("TC_10YEAR", 10.0),
+    ("TC_20YEAR", 20.0),
+    ("TC_30YEAR", 30.0),
+];
+
+/// Date element names, most recent schema first. Older feeds published the
+/// curve date under `QUOTE_DATE` or `DATE` instead of `NEW_DATE`.
+const DATE_TAGS: &[&str] = &["NEW_DATE", "QUOTE_DATE", "DATE"];
+
+/// Tenor element prefixes. Feeds have used both `BC_` and the legacy `TC_`
+/// for the same tenors.
+const TENOR_PREFIXES: &[&str] = &["BC_", "TC_"];
+
 /// Parse the Treasury.gov XML feed into a vec of (date, YieldCurve).
 ///
 /// The XML uses Atom + custom namespace.  We do simple text scanning rather
-/// than a full XML parse to avoid heavy dependencies.
-fn parse_yield_curve_xml(xml: &str) -> Result<YearCurves, MarketDataError> {
+/// than a full XML parse to avoid heavy dependencies.  Entries without a
+/// recognisable date are skipped, as are tenors that are missing or not
+/// numeric.
+fn parse_yield_curve_xml(
+    xml: &str,
+    tenor_map: &[(&str, f64)],
+) -> Result<YearCurves, MarketDataError> {
     let mut results: YearCurves = Vec::new();
 
     // Each entry is between <entry> ... </entry>
@@ -595,23 +1595,14 @@ fn parse_yield_curve_xml(xml: &str) -> Result<YearCurves, MarketDataError> {
             None => continue,
         };
 
-        // Extract date from NEW_DATE
-        let date = match extract_xml_value(content, "NEW_DATE") {
-            Some(d) => {
-                // Format: "2025-01-02T00:00:00" or similar
-                let date_str = if d.len() >= 10 { &d[..10] } else { &d };
-                match NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                    Ok(nd) => nd,
-                    Err(_) => continue,
-                }
-            }
-            None => continue,
+        let Some(date) = extract_curve_date(content) else {
+            continue;
         };
 
         // Extract yield values for each tenor
         let mut points: Vec<(f64, f64)> = Vec::new();
-        for (label, tenor_years) in TENOR_MAP {
-            if let Some(val_str) = extract_xml_value(content, label) {
+        for (label, tenor_years) in tenor_map {
+            if let Some(val_str) = extract_tenor_value(content, label) {
                 if let Ok(yield_val) = val_str.parse::<f64>() {
                     points.push((*tenor_years, yield_val));
                 }
@@ -634,23 +1625,61 @@ fn parse_yield_curve_xml(xml: &str) -> Result<YearCurves, MarketDataError> {
     Ok(results)
 }
 
+/// The curve date of an entry, from the first of [`DATE_TAGS`] present.
+/// Accepts `2025-01-02T00:00:00`-style timestamps and `01/02/2025` dates.
+fn extract_curve_date(content: &str) -> Option<NaiveDate> {
+    let value = DATE_TAGS
+        .iter()
+        .find_map(|tag| extract_xml_value(content, tag))?;
+    let iso = value.get(..10).unwrap_or(&value);
+    NaiveDate::parse_from_str(iso, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&value, "%m/%d/%Y"))
+        .ok()
+}
+
+/// The value of a tenor element, trying `label` as given and then with each
+/// other prefix in [`TENOR_PREFIXES`].
+fn extract_tenor_value(content: &str, label: &str) -> Option<String> {
+    extract_xml_value(content, label).or_else(|| {
+        let suffix = TENOR_PREFIXES
+            .iter()
+            .find_map(|prefix| label.strip_prefix(prefix))?;
+        TENOR_PREFIXES
+            .iter()
+            .map(|prefix| format!("{}{}", prefix, suffix))
+            .filter(|alternate| alternate != label)
+            .find_map(|alternate| extract_xml_value(content, &alternate))
+    })
+}
+
 /// Extract the text content of a simple XML element like `<d:TAG>value</d:TAG>`.
-/// Handles both `d:TAG` and `TAG` namespace prefixes.
+/// Handles both `d:TAG` and `TAG` namespace prefixes.  Elements whose name
+/// merely starts with `TAG` (e.g. `BC_30YEARDISPLAY` for `BC_30YEAR`) and
+/// self-closing null elements are not matched.
 fn extract_xml_value(xml: &str, tag: &str) -> Option<String> {
     // Try d:TAG first (common namespace prefix)
     let patterns = [format!("d:{}", tag), tag.to_string()];
     for pat in &patterns {
         // Match opening tag with optional attributes: <d:TAG> or <d:TAG m:type="...">
         let open_prefix = format!("<{}", pat);
-        if let Some(tag_start) = xml.find(&open_prefix) {
-            let after_tag = &xml[tag_start + open_prefix.len()..];
+        let close = format!("</{}>", pat);
+        let mut search_from = 0;
+        while let Some(found) = xml[search_from..].find(&open_prefix) {
+            let after_tag = &xml[search_from + found + open_prefix.len()..];
+            search_from += found + open_prefix.len();
+            if !after_tag.starts_with(|c: char| c == '>' || c.is_whitespace()) {
+                continue;
+            }
             // Find the end of the opening tag (either > or whitespace+attributes+>)
             let content_start = after_tag.find('>')?;
+            if after_tag[..content_start].ends_with('/') {
+                break;
+            }
             let after_open = &after_tag[content_start + 1..];
-            let close = format!("</{}>", pat);
             if let Some(end) = after_open.find(&close) {
                 return Some(after_open[..end].trim().to_string());
             }
+            break;
         }
     }
     None
@@ -675,8 +1704,26 @@ mod tests {
 
     #[test]
     fn test_guard_us_treasury() {
-        assert!(guard_us_treasury("US912810TH12").is_ok());
+        assert!(guard_us_treasury("US912810TH14").is_ok());
         assert!(guard_us_treasury("DE0001102481").is_err());
+        // Right prefix, wrong check digit
+        assert!(guard_us_treasury("US912810TH12").is_err());
+    }
+
+    #[test]
+    fn test_isin_to_cusip_validates_check_digits() {
+        assert_eq!(isin_to_cusip("US912810TH14").as_deref(), Some("912810TH1"));
+        assert_eq!(isin_to_cusip("US0378331005").as_deref(), Some("037833100"));
+
+        // One digit off: the ISIN check digit no longer matches
+        assert_eq!(isin_to_cusip("US912810TH15"), None);
+        assert_eq!(isin_to_cusip("US912811TH14"), None);
+        // ISIN check digit recomputed, but the CUSIP check digit is wrong
+        assert_eq!(isin_to_cusip("US912810TH22"), None);
+
+        assert_eq!(isin_to_cusip("DE0001102481"), None);
+        assert_eq!(isin_to_cusip("us912810th14"), None);
+        assert_eq!(isin_to_cusip("US912810TH1"), None);
     }
 
     #[test]
@@ -690,33 +1737,17 @@ mod tests {
     }
 
     #[test]
-    fn test_yield_curve_interpolation() {
-        let curve = YieldCurve(vec![
-            (1.0, 4.0),
-            (2.0, 4.2),
-            (5.0, 4.5),
-            (10.0, 4.8),
-            (30.0, 5.0),
-        ]);
-
-        // Exact match
-        assert!((curve.interpolate(1.0).unwrap() - 4.0).abs() < 1e-10);
-        assert!((curve.interpolate(10.0).unwrap() - 4.8).abs() < 1e-10);
-
-        // Interpolation: midpoint between 1.0 and 2.0
-        assert!((curve.interpolate(1.5).unwrap() - 4.1).abs() < 1e-10);
-
-        // Below range clamps to first
-        assert!((curve.interpolate(0.5).unwrap() - 4.0).abs() < 1e-10);
-
-        // Above range clamps to last
-        assert!((curve.interpolate(40.0).unwrap() - 5.0).abs() < 1e-10);
-    }
-
-    #[test]
-    fn test_yield_curve_empty() {
-        let curve = YieldCurve(vec![]);
-        assert!(curve.interpolate(5.0).is_none());
+    fn test_provider_default_interpolation_is_linear() {
+        assert_eq!(
+            UsTreasuryCalcProvider::new().interpolation,
+            InterpolationMethod::Linear
+        );
+        assert_eq!(
+            UsTreasuryCalcProvider::new()
+                .with_interpolation(InterpolationMethod::CubicSpline)
+                .interpolation,
+            InterpolationMethod::CubicSpline
+        );
     }
 
     #[test]
@@ -725,13 +1756,16 @@ mod tests {
         let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
         let maturity = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(); // already matured
 
-        let price = UsTreasuryCalcProvider::calculate_price(
+        let price = calculate_price(
+            PROVIDER_ID,
             &curve,
+            InterpolationMethod::Linear,
             today,
             maturity,
             0.05,
             "SEMI_ANNUAL",
             1000.0,
+            None,
         )
         .unwrap();
         assert!((price - 1.0).abs() < 1e-10); // par
@@ -743,9 +1777,18 @@ mod tests {
         let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
         let maturity = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(); // ~6 months
 
-        let price =
-            UsTreasuryCalcProvider::calculate_price(&curve, today, maturity, 0.0, "ZERO", 1000.0)
-                .unwrap();
+        let price = calculate_price(
+            PROVIDER_ID,
+            &curve,
+            InterpolationMethod::Linear,
+            today,
+            maturity,
+            0.0,
+            "ZERO",
+            1000.0,
+            None,
+        )
+        .unwrap();
 
         // Should be slightly less than 1.0 (discounted)
         assert!(price < 1.0);
@@ -759,13 +1802,16 @@ mod tests {
         let maturity = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap(); // 5 years
 
         // 5% coupon, semi-annual, at ~4.5% yield → price should be > par
-        let price = UsTreasuryCalcProvider::calculate_price(
+        let price = calculate_price(
+            PROVIDER_ID,
             &curve,
+            InterpolationMethod::Linear,
             today,
             maturity,
             0.05,
             "SEMI_ANNUAL",
             1000.0,
+            None,
         )
         .unwrap();
 
@@ -773,6 +1819,123 @@ mod tests {
         assert!(price < 1.05, "Should be close to par: {}", price);
     }
 
+    #[test]
+    fn test_calculate_price_callable_bond_priced_to_worst() {
+        let curve = YieldCurve(vec![(1.0, 4.0), (2.0, 4.2), (5.0, 4.5), (10.0, 4.8)]);
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let maturity = NaiveDate::from_ymd_opt(2035, 1, 1).unwrap();
+        let call_date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+
+        // 8% coupon well above the curve: trades above par, so the call at
+        // par caps the price.
+        let price = |call| {
+            calculate_price(
+                PROVIDER_ID,
+                &curve,
+                InterpolationMethod::Linear,
+                today,
+                maturity,
+                0.08,
+                "SEMI_ANNUAL",
+                1000.0,
+                call,
+            )
+            .unwrap()
+        };
+        let to_maturity = price(None);
+        let to_worst = price(Some((call_date, 1.0)));
+
+        let call_yield = curve.interpolate((call_date - today).num_days() as f64 / 365.25);
+        let to_call = price_from_yield(
+            call_yield.unwrap(),
+            today,
+            call_date,
+            0.08,
+            "SEMI_ANNUAL",
+            1000.0,
+            1.0,
+            DayCount::default(),
+        );
+
+        assert!(to_maturity > 1.0);
+        assert!(to_worst < to_maturity);
+        assert!((to_worst - to_call).abs() < 1e-12);
+
+        // A call date past maturity is ignored.
+        let after_maturity = NaiveDate::from_ymd_opt(2036, 1, 1).unwrap();
+        assert_eq!(price(Some((after_maturity, 1.0))), to_maturity);
+    }
+
+    #[test]
+    fn test_yield_to_maturity_round_trips_curve_yield() {
+        let curve = YieldCurve(vec![(1.0, 4.0), (2.0, 4.2), (5.0, 4.5), (10.0, 4.8)]);
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        for (maturity, coupon_rate, frequency) in [
+            (
+                NaiveDate::from_ymd_opt(2030, 1, 1).unwrap(),
+                0.05,
+                "SEMI_ANNUAL",
+            ),
+            (NaiveDate::from_ymd_opt(2033, 7, 1).unwrap(), 0.02, "ANNUAL"),
+            (NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), 0.0, "ZERO"),
+        ] {
+            let years = (maturity - today).num_days() as f64 / 365.25;
+            let expected = curve.interpolate(years).unwrap();
+            let price = calculate_price(
+                PROVIDER_ID,
+                &curve,
+                InterpolationMethod::Linear,
+                today,
+                maturity,
+                coupon_rate,
+                frequency,
+                1000.0,
+                None,
+            )
+            .unwrap();
+
+            let ytm = UsTreasuryCalcProvider::yield_to_maturity(
+                price,
+                today,
+                maturity,
+                coupon_rate,
+                frequency,
+            )
+            .unwrap();
+            assert!(
+                (ytm - expected).abs() < 1e-4,
+                "{}: expected {}, got {}",
+                frequency,
+                expected,
+                ytm
+            );
+        }
+    }
+
+    #[test]
+    fn test_yield_to_maturity_rejects_invalid_inputs() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let maturity = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+
+        assert!(UsTreasuryCalcProvider::yield_to_maturity(
+            1.0,
+            maturity,
+            today,
+            0.05,
+            "SEMI_ANNUAL"
+        )
+        .is_err());
+        assert!(UsTreasuryCalcProvider::yield_to_maturity(
+            0.0,
+            today,
+            maturity,
+            0.05,
+            "SEMI_ANNUAL"
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_calculate_price_discount_bond() {
         let curve = YieldCurve(vec![(1.0, 5.0), (2.0, 5.2), (5.0, 5.5), (10.0, 5.8)]);
@@ -780,13 +1943,16 @@ mod tests {
         let maturity = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
 
         // 3% coupon at ~5.5% yield → discount
-        let price = UsTreasuryCalcProvider::calculate_price(
+        let price = calculate_price(
+            PROVIDER_ID,
             &curve,
+            InterpolationMethod::Linear,
             today,
             maturity,
             0.03,
             "SEMI_ANNUAL",
             1000.0,
+            None,
         )
         .unwrap();
 
@@ -826,7 +1992,7 @@ mod tests {
   </entry>
 </feed>"#;
 
-        let curves = parse_yield_curve_xml(xml).unwrap();
+        let curves = parse_yield_curve_xml(xml, TENOR_MAP).unwrap();
         assert_eq!(curves.len(), 2);
 
         // First entry
@@ -843,10 +2009,52 @@ mod tests {
         assert_eq!(curves[1].1 .0.len(), 4); // only 4 tenors in this entry
     }
 
+    #[test]
+    fn test_parse_yield_curve_xml_legacy_format() {
+        let xml = r#"<?xml version="1.0"?>
+<feed>
+  <entry>
+    <content type="application/xml">
+      <m:properties>
+        <d:QUOTE_DATE m:type="Edm.DateTime">2015-01-02T00:00:00</d:QUOTE_DATE>
+        <d:TC_1MONTH m:type="Edm.Double">0.02</d:TC_1MONTH>
+        <d:TC_2MONTH m:null="true" />
+        <d:TC_1YEAR m:type="Edm.Double">n/a</d:TC_1YEAR>
+        <d:TC_10YEAR m:type="Edm.Double">2.12</d:TC_10YEAR>
+        <d:TC_30YEARDISPLAY m:type="Edm.Double">2.50</d:TC_30YEARDISPLAY>
+        <d:TC_30YEAR m:type="Edm.Double">2.69</d:TC_30YEAR>
+        <d:TC_45YEAR m:type="Edm.Double">2.90</d:TC_45YEAR>
+      </m:properties>
+    </content>
+  </entry>
+  <entry>
+    <content type="application/xml">
+      <m:properties>
+        <d:DATE>01/05/2015</d:DATE>
+        <d:BC_10YEAR>2.04</d:BC_10YEAR>
+      </m:properties>
+    </content>
+  </entry>
+</feed>"#;
+
+        let curves = parse_yield_curve_xml(xml, TENOR_MAP).unwrap();
+        assert_eq!(curves.len(), 2);
+
+        assert_eq!(curves[0].0, NaiveDate::from_ymd_opt(2015, 1, 2).unwrap());
+        // 1M, 10Y and 30Y; the null, non-numeric and unknown tenors are skipped.
+        assert_eq!(
+            curves[0].1 .0,
+            vec![(1.0 / 12.0, 0.02), (10.0, 2.12), (30.0, 2.69)]
+        );
+
+        assert_eq!(curves[1].0, NaiveDate::from_ymd_opt(2015, 1, 5).unwrap());
+        assert_eq!(curves[1].1 .0, vec![(10.0, 2.04)]);
+    }
+
     #[test]
     fn test_parse_yield_curve_xml_empty() {
         let xml = "<feed></feed>";
-        assert!(parse_yield_curve_xml(xml).is_err());
+        assert!(parse_yield_curve_xml(xml, TENOR_MAP).is_err());
     }
 
     #[test]
@@ -865,6 +2073,520 @@ mod tests {
         assert_eq!(extract_xml_value(xml, "BC_5YEAR"), None);
     }
 
+    fn sample_curves() -> YearCurves {
+        vec![(
+            NaiveDate::from_ymd_opt(2023, 12, 29).unwrap(),
+            YieldCurve(vec![(1.0, 4.79), (10.0, 3.88)]),
+        )]
+    }
+
+    fn cached_now(curves: YearCurves) -> CachedYear {
+        CachedYear {
+            fetched_at: Utc::now(),
+            curves,
+        }
+    }
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+            Utc,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_curve_for_date_falls_forward_before_first_curve() {
+        let curve = |y: i32, m: u32, d: u32, ten_year: f64| {
+            (
+                NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+                YieldCurve(vec![(1.0, 4.0), (10.0, ten_year)]),
+            )
+        };
+        let jan_2 = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider.curve_cache.write().await.insert(
+            (CurveFeed::Nominal, 2023),
+            cached_now(vec![curve(2023, 1, 3, 3.79), curve(2023, 1, 4, 3.69)]),
+        );
+
+        assert!(matches!(
+            provider.get_curve_for_date(CurveFeed::Nominal, jan_2).await,
+            Err(MarketDataError::NoDataForRange)
+        ));
+
+        let provider = provider.with_fall_forward(true);
+        let next = provider
+            .get_curve_for_date(CurveFeed::Nominal, jan_2)
+            .await
+            .unwrap();
+        assert_eq!(next.0[1].1, 3.79);
+
+        // A cached previous year does not change the fall-forward result.
+        provider.curve_cache.write().await.insert(
+            (CurveFeed::Nominal, 2022),
+            cached_now(vec![curve(2022, 12, 30, 3.88)]),
+        );
+        let next = provider
+            .get_curve_for_date(CurveFeed::Nominal, jan_2)
+            .await
+            .unwrap();
+        assert_eq!(next.0[1].1, 3.79);
+
+        // The previous-year fallback wins over fall-forward, avoiding look-ahead.
+        let provider = provider.with_previous_year_fallback(true);
+        let previous = provider
+            .get_curve_for_date(CurveFeed::Nominal, jan_2)
+            .await
+            .unwrap();
+        assert_eq!(previous.0[1].1, 3.88);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_previous_year_still_falls_forward() {
+        let jan_2 = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        // A one-byte body limit makes any fetch of 2022 fail.
+        let provider = UsTreasuryCalcProvider::new()
+            .with_cache_dir(dir.path().to_path_buf())
+            .with_max_body_bytes(1)
+            .with_previous_year_fallback(true)
+            .with_fall_forward(true);
+        provider.curve_cache.write().await.insert(
+            (CurveFeed::Nominal, 2023),
+            cached_now(vec![(
+                NaiveDate::from_ymd_opt(2023, 1, 3).unwrap(),
+                YieldCurve(vec![(1.0, 4.0), (10.0, 3.79)]),
+            )]),
+        );
+
+        let next = provider
+            .get_curve_for_date(CurveFeed::Nominal, jan_2)
+            .await
+            .unwrap();
+        assert_eq!(next.0[1].1, 3.79);
+    }
+
+    #[tokio::test]
+    async fn test_historical_backfill_keeps_leading_days_with_fall_forward() {
+        async fn backfill(fall_forward: bool) -> Vec<Quote> {
+            let dir = tempfile::tempdir().unwrap();
+            let provider = UsTreasuryCalcProvider::new()
+                .with_cache_dir(dir.path().to_path_buf())
+                .with_fall_forward(fall_forward);
+            provider.curve_cache.write().await.insert(
+                (CurveFeed::Nominal, 2023),
+                cached_now(vec![
+                    (
+                        NaiveDate::from_ymd_opt(2023, 1, 3).unwrap(),
+                        YieldCurve(vec![(1.0, 4.0), (10.0, 3.79)]),
+                    ),
+                    (
+                        NaiveDate::from_ymd_opt(2023, 1, 4).unwrap(),
+                        YieldCurve(vec![(1.0, 4.0), (10.0, 3.69)]),
+                    ),
+                ]),
+            );
+            let isin = "US91282CJL63";
+            let context = QuoteContext {
+                instrument: crate::models::InstrumentId::Bond { isin: isin.into() },
+                overrides: None,
+                currency_hint: None,
+                preferred_provider: None,
+                bond_metadata: Some(BondQuoteMetadata {
+                    coupon_rate: dec!(0.045),
+                    maturity_date: NaiveDate::from_ymd_opt(2033, 11, 15).unwrap(),
+                    face_value: Some(dec!(1000)),
+                    coupon_frequency: "SEMI_ANNUAL".to_string(),
+                    is_tips: false,
+                    index_ratio: None,
+                    ref_cpi_on_dated_date: None,
+                    call_date: None,
+                    call_price: None,
+                    currency: None,
+                }),
+                mic_hint: None,
+                settlement_offset_days: None,
+            };
+            provider
+                .get_historical_quotes(
+                    &context,
+                    ProviderInstrument::BondIsin { isin: isin.into() },
+                    utc(2023, 1, 1),
+                    utc(2023, 1, 4),
+                )
+                .await
+                .unwrap()
+        }
+        let dates =
+            |quotes: Vec<Quote>| -> Vec<u32> { quotes.iter().map(|q| q.timestamp.day()).collect() };
+
+        // Jan 2 comes before the year's first curve and is dropped by default.
+        assert_eq!(dates(backfill(false).await), vec![3, 4]);
+        // With fall-forward it is priced off the Jan 3 curve. Jan 1 is a Sunday.
+        assert_eq!(dates(backfill(true).await), vec![2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+
+        provider
+            .store_disk_cache(CurveFeed::Nominal, 2023, &sample_curves(), utc(2024, 1, 5))
+            .await;
+
+        let loaded = provider
+            .load_disk_cache(CurveFeed::Nominal, 2023, utc(2025, 6, 1))
+            .await
+            .unwrap()
+            .curves;
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, NaiveDate::from_ymd_opt(2023, 12, 29).unwrap());
+        assert!((loaded[0].1 .0[1].1 - 3.88).abs() < 1e-10);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_curves_uses_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        UsTreasuryCalcProvider::new()
+            .with_cache_dir(dir.path().to_path_buf())
+            .store_disk_cache(CurveFeed::Nominal, 2023, &sample_curves(), utc(2024, 1, 5))
+            .await;
+
+        // A fresh provider (simulating a restart) is served from disk.
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider
+            .ensure_curves(CurveFeed::Nominal, 2023)
+            .await
+            .unwrap();
+        assert!(provider
+            .curve_cache
+            .read()
+            .await
+            .contains_key(&(CurveFeed::Nominal, 2023)));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_curves_for_years_skips_cached_years() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        // 2022 and 2024 are only on disk, so loading them needs no network.
+        for year in [2022, 2024] {
+            provider
+                .store_disk_cache(CurveFeed::Nominal, year, &sample_curves(), utc(2025, 1, 5))
+                .await;
+        }
+        {
+            let mut cache = provider.curve_cache.write().await;
+            cache.insert((CurveFeed::Nominal, 2021), cached_now(sample_curves()));
+            cache.insert((CurveFeed::Nominal, 2023), cached_now(sample_curves()));
+        }
+
+        let (loaded, failed) = provider
+            .ensure_curves_for_years(CurveFeed::Nominal, 2021..=2024)
+            .await;
+        assert_eq!(loaded, vec![2022, 2024]);
+        assert!(failed.is_empty());
+
+        let cache = provider.curve_cache.read().await;
+        assert!((2021..=2024).all(|year| cache.contains_key(&(CurveFeed::Nominal, year))));
+    }
+
+    #[tokio::test]
+    async fn test_current_year_curves_expire_after_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new()
+            .with_cache_dir(dir.path().to_path_buf())
+            .with_current_year_ttl(Duration::from_secs(60));
+        let now = Utc::now();
+        let this_year = now.year();
+        let an_hour_ago = now - chrono::Duration::hours(1);
+
+        // A fresh disk entry for the current year carries an extra trading day.
+        let mut refreshed = sample_curves();
+        refreshed.push((now.date_naive(), YieldCurve(vec![(1.0, 4.0), (10.0, 4.1)])));
+        provider
+            .store_disk_cache(CurveFeed::Nominal, this_year, &refreshed, now)
+            .await;
+        {
+            let mut cache = provider.curve_cache.write().await;
+            for year in [this_year - 1, this_year] {
+                cache.insert(
+                    (CurveFeed::Nominal, year),
+                    CachedYear {
+                        fetched_at: an_hour_ago,
+                        curves: sample_curves(),
+                    },
+                );
+            }
+        }
+
+        assert!(provider.is_cached_year_fresh(this_year - 1, an_hour_ago, now));
+        assert!(!provider.is_cached_year_fresh(this_year, an_hour_ago, now));
+
+        let (loaded, failed) = provider
+            .ensure_curves_for_years(CurveFeed::Nominal, this_year - 1..=this_year)
+            .await;
+        assert_eq!(loaded, vec![this_year]);
+        assert!(failed.is_empty());
+
+        let cache = provider.curve_cache.read().await;
+        assert_eq!(cache[&(CurveFeed::Nominal, this_year)].curves.len(), 2);
+        assert_eq!(cache[&(CurveFeed::Nominal, this_year - 1)].curves.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bill_yields_from_curve() {
+        let provider = UsTreasuryCalcProvider::new();
+        provider.curve_cache.write().await.insert(
+            (CurveFeed::Nominal, 2023),
+            cached_now(vec![(
+                NaiveDate::from_ymd_opt(2023, 12, 29).unwrap(),
+                YieldCurve(vec![(0.25, 5.40), (1.0, 4.79)]),
+            )]),
+        );
+
+        let settle = NaiveDate::from_ymd_opt(2023, 12, 29).unwrap();
+        let maturity = settle + chrono::Duration::days(91);
+        let yields = provider.bill_yields(maturity, settle).await.unwrap();
+
+        // Priced on the 5.40% money-market yield, so the discount yield sits
+        // just below it and the bond-equivalent yield above.
+        assert!(yields.discount_yield < 5.40);
+        assert!(yields.bond_equivalent_yield > 5.40);
+        let price = 1.0 - yields.discount_yield / 100.0 * 91.0 / 360.0;
+        assert!((price - 1.0 / (1.0 + 0.054 * 91.0 / 360.0)).abs() < 1e-9);
+
+        assert!(provider.bill_yields(settle, settle).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_historical_quotes_ignore_currency_hint() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider
+            .curve_cache
+            .write()
+            .await
+            .insert((CurveFeed::Nominal, 2023), cached_now(sample_curves()));
+
+        let isin = "US91282CJL63";
+        let context = QuoteContext {
+            instrument: crate::models::InstrumentId::Bond { isin: isin.into() },
+            overrides: None,
+            currency_hint: Some("EUR".into()),
+            preferred_provider: None,
+            bond_metadata: Some(BondQuoteMetadata {
+                coupon_rate: dec!(0.045),
+                maturity_date: NaiveDate::from_ymd_opt(2033, 11, 15).unwrap(),
+                face_value: Some(dec!(1000)),
+                coupon_frequency: "SEMI_ANNUAL".to_string(),
+                is_tips: false,
+                index_ratio: None,
+                ref_cpi_on_dated_date: None,
+                call_date: None,
+                call_price: None,
+                currency: None,
+            }),
+            mic_hint: None,
+            settlement_offset_days: None,
+        };
+
+        let quotes = provider
+            .get_historical_quotes(
+                &context,
+                ProviderInstrument::BondIsin { isin: isin.into() },
+                utc(2023, 12, 1),
+                utc(2023, 12, 31),
+            )
+            .await
+            .unwrap();
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].currency, "USD");
+    }
+
+    #[tokio::test]
+    async fn test_price_ignores_face_value_and_takes_currency_from_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider
+            .curve_cache
+            .write()
+            .await
+            .insert((CurveFeed::Nominal, 2023), cached_now(sample_curves()));
+
+        let isin = "US91282CJL63";
+        let quote_for = |face_value: Option<Decimal>, currency: Option<&str>| {
+            let context = QuoteContext {
+                instrument: crate::models::InstrumentId::Bond { isin: isin.into() },
+                overrides: None,
+                currency_hint: Some("EUR".into()),
+                preferred_provider: None,
+                bond_metadata: Some(BondQuoteMetadata {
+                    coupon_rate: dec!(0.045),
+                    maturity_date: NaiveDate::from_ymd_opt(2033, 11, 15).unwrap(),
+                    face_value,
+                    coupon_frequency: "SEMI_ANNUAL".to_string(),
+                    is_tips: false,
+                    index_ratio: None,
+                    ref_cpi_on_dated_date: None,
+                    call_date: None,
+                    call_price: None,
+                    currency: currency.map(|c| c.to_string().into()),
+                }),
+                mic_hint: None,
+                settlement_offset_days: None,
+            };
+            let provider = &provider;
+            async move {
+                provider
+                    .get_historical_quotes(
+                        &context,
+                        ProviderInstrument::BondIsin { isin: isin.into() },
+                        utc(2023, 12, 1),
+                        utc(2023, 12, 31),
+                    )
+                    .await
+                    .unwrap()
+                    .remove(0)
+            }
+        };
+
+        let thousand = quote_for(Some(dec!(1000)), None).await;
+        let hundred = quote_for(Some(dec!(100)), Some("CAD")).await;
+        let invalid = quote_for(Some(dec!(0)), None).await;
+        let missing = quote_for(None, None).await;
+
+        // Prices are a fraction of par, so the face value does not change them.
+        let (a, b): (f64, f64) = (
+            thousand.close.try_into().unwrap(),
+            hundred.close.try_into().unwrap(),
+        );
+        assert!((a - b).abs() < 1e-9, "{} vs {}", a, b);
+        assert!(a > 0.5 && a < 1.5);
+        assert_eq!(invalid.close, thousand.close);
+        assert_eq!(missing.close, thousand.close);
+
+        assert_eq!(thousand.currency, "USD");
+        assert_eq!(hundred.currency, "CAD");
+    }
+
+    #[tokio::test]
+    async fn test_settlement_offset_changes_price() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider
+            .curve_cache
+            .write()
+            .await
+            .insert((CurveFeed::Nominal, 2023), cached_now(sample_curves()));
+
+        let isin = "US91282CJL63";
+        let quote_with_offset = |offset: Option<i64>| {
+            let context = QuoteContext {
+                instrument: crate::models::InstrumentId::Bond { isin: isin.into() },
+                overrides: None,
+                currency_hint: None,
+                preferred_provider: None,
+                bond_metadata: Some(BondQuoteMetadata {
+                    coupon_rate: dec!(0.045),
+                    maturity_date: NaiveDate::from_ymd_opt(2033, 11, 15).unwrap(),
+                    face_value: Some(dec!(1000)),
+                    coupon_frequency: "SEMI_ANNUAL".to_string(),
+                    is_tips: false,
+                    index_ratio: None,
+                    ref_cpi_on_dated_date: None,
+                    call_date: None,
+                    call_price: None,
+                    currency: None,
+                }),
+                mic_hint: None,
+                settlement_offset_days: offset,
+            };
+            let provider = &provider;
+            async move {
+                provider
+                    .get_historical_quotes(
+                        &context,
+                        ProviderInstrument::BondIsin { isin: isin.into() },
+                        utc(2023, 12, 1),
+                        utc(2023, 12, 31),
+                    )
+                    .await
+                    .unwrap()
+                    .remove(0)
+            }
+        };
+
+        let same_day = quote_with_offset(Some(0)).await;
+        let t_plus_one = quote_with_offset(Some(1)).await;
+        let default = quote_with_offset(None).await;
+
+        // Settling later discounts over a shorter period, but the quote keeps
+        // its trade date.
+        assert_ne!(t_plus_one.close, same_day.close);
+        assert_eq!(t_plus_one.timestamp, same_day.timestamp);
+        assert_eq!(default.close, t_plus_one.close);
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_rejects_mismatched_year() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider
+            .store_disk_cache(CurveFeed::Nominal, 2023, &sample_curves(), utc(2024, 1, 5))
+            .await;
+        std::fs::rename(
+            UsTreasuryCalcProvider::disk_cache_path(dir.path(), CurveFeed::Nominal, 2023),
+            UsTreasuryCalcProvider::disk_cache_path(dir.path(), CurveFeed::Nominal, 2022),
+        )
+        .unwrap();
+
+        assert!(provider
+            .load_disk_cache(CurveFeed::Nominal, 2022, utc(2025, 6, 1))
+            .await
+            .is_none());
+    }
+
+    #[test]
+    fn test_disk_cache_freshness() {
+        // Past year fetched after it closed is immutable.
+        assert!(is_disk_cache_fresh(2023, utc(2024, 1, 5), utc(2025, 6, 1)));
+        // Past year fetched while it was still in progress is incomplete.
+        assert!(!is_disk_cache_fresh(2024, utc(2024, 6, 1), utc(2025, 6, 1)));
+        // Current year: Monday accepts Friday's fetch, but not Thursday's.
+        assert!(is_disk_cache_fresh(2025, utc(2025, 6, 6), utc(2025, 6, 9)));
+        assert!(!is_disk_cache_fresh(2025, utc(2025, 6, 5), utc(2025, 6, 9)));
+        assert!(is_disk_cache_fresh(2025, utc(2025, 6, 9), utc(2025, 6, 9)));
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache_removes_disk_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = UsTreasuryCalcProvider::new().with_cache_dir(dir.path().to_path_buf());
+        provider
+            .store_disk_cache(CurveFeed::Nominal, 2023, &sample_curves(), utc(2024, 1, 5))
+            .await;
+        provider
+            .curve_cache
+            .write()
+            .await
+            .insert((CurveFeed::Nominal, 2023), cached_now(sample_curves()));
+        std::fs::write(dir.path().join("unrelated.txt"), "keep").unwrap();
+
+        provider.clear_cache().await.unwrap();
+
+        assert!(provider.curve_cache.read().await.is_empty());
+        assert!(
+            !UsTreasuryCalcProvider::disk_cache_path(dir.path(), CurveFeed::Nominal, 2023).exists()
+        );
+        assert!(dir.path().join("unrelated.txt").exists());
+    }
+
     #[test]
     fn test_provider_id() {
         let provider = UsTreasuryCalcProvider::new();
@@ -885,7 +2607,7 @@ mod tests {
     #[test]
     fn test_make_quote() {
         let date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
-        let quote = UsTreasuryCalcProvider::make_quote(date, 0.97025, "USD").unwrap();
+        let quote = make_quote(PROVIDER_ID, date, 0.97025, "USD").unwrap();
         assert_eq!(quote.currency, "USD");
         assert_eq!(quote.source, "US_TREASURY_CALC");
         assert!(quote.close > dec!(0));
@@ -918,6 +2640,173 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_treasury_direct_tips_response() {
+        let json = r#"[{
+            "cusip": "912828Z37",
+            "type": "Note",
+            "interestRate": "0.125",
+            "maturityDate": "2030-01-15T00:00:00",
+            "interestPaymentFrequency": "Semi-Annual",
+            "tips": "Yes",
+            "refCpiOnDatedDate": "256.97400",
+            "callable": "No",
+            "callDate": ""
+        }]"#;
+
+        let items: Vec<TdSecurityItem> = serde_json::from_str(json).unwrap();
+        assert_eq!(items[0].tips.as_deref(), Some("Yes"));
+        assert_eq!(items[0].ref_cpi_on_dated_date.as_deref(), Some("256.97400"));
+        assert_eq!(items[0].callable.as_deref(), Some("No"));
+    }
+
+    #[test]
+    fn test_parse_real_yield_curve_xml_missing_tenor() {
+        let xml = r#"<feed>
+  <entry>
+    <content type="application/xml">
+      <m:properties>
+        <d:NEW_DATE>2025-01-02T00:00:00</d:NEW_DATE>
+        <d:TC_5YEAR>1.90</d:TC_5YEAR>
+        <d:TC_10YEAR>2.10</d:TC_10YEAR>
+        <d:TC_30YEAR>2.40</d:TC_30YEAR>
+      </m:properties>
+    </content>
+  </entry>
+</feed>"#;
+
+        let curves = parse_yield_curve_xml(xml, REAL_TENOR_MAP).unwrap();
+        assert_eq!(curves.len(), 1);
+        let curve = &curves[0].1;
+        assert_eq!(curve.0.len(), 3); // 7Y and 20Y missing
+
+        // Missing tenor interpolates; short maturities clamp to the 5-year point.
+        assert!((curve.interpolate(7.5).unwrap() - 2.0).abs() < 1e-10);
+        assert!((curve.interpolate(2.0).unwrap() - 1.90).abs() < 1e-10);
+    }
+
+    fn tips_bond() -> BondQuoteMetadata {
+        BondQuoteMetadata {
+            coupon_rate: dec!(0.00125),
+            maturity_date: NaiveDate::from_ymd_opt(2030, 1, 15).unwrap(),
+            face_value: Some(dec!(1000)),
+            coupon_frequency: "SEMI_ANNUAL".to_string(),
+            is_tips: true,
+            index_ratio: Some(dec!(1.25)),
+            ref_cpi_on_dated_date: None,
+            call_date: None,
+            call_price: None,
+            currency: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_ratio() {
+        let provider = UsTreasuryCalcProvider::new();
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let mut bond = tips_bond();
+        let ratio = provider.index_ratios(&bond, day..=day).await.unwrap();
+        assert!((0.96 * ratio.on(day).unwrap() - 1.2).abs() < 1e-10);
+
+        // The reference CPI is ignored unless CPI ratios are enabled, so a
+        // TIPS without a metadata ratio cannot be priced.
+        bond.ref_cpi_on_dated_date = Some(dec!(250));
+        let ratio = provider.index_ratios(&bond, day..=day).await.unwrap();
+        assert!((ratio.on(day).unwrap() - 1.25).abs() < 1e-10);
+        bond.index_ratio = None;
+        assert!(provider.index_ratios(&bond, day..=day).await.is_err());
+
+        // Nominal bonds ignore any ratio.
+        bond.is_tips = false;
+        bond.index_ratio = Some(dec!(1.25));
+        let ratio = provider.index_ratios(&bond, day..=day).await.unwrap();
+        assert_eq!(ratio.on(day), Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_index_ratio_from_cpi_per_settlement_date() {
+        let provider = UsTreasuryCalcProvider::new().with_cpi_index_ratios(true);
+        {
+            let mut cache = provider.cpi_cache.write().await;
+            let months_2023 = [(10, 307.671), (11, 307.051), (12, 306.746)];
+            for (year, months) in [(2023, months_2023.into()), (2024, HashMap::new())] {
+                cache.insert(
+                    year,
+                    CachedCpiYear {
+                        fetched_at: Utc::now(),
+                        months,
+                    },
+                );
+            }
+        }
+        let mut bond = tips_bond();
+        bond.ref_cpi_on_dated_date = Some(dec!(250));
+
+        let first = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let mid = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        let feb = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let april = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let ratios = provider.index_ratios(&bond, first..=april).await.unwrap();
+
+        // The CPI ratio wins over the fixed one and moves with the date.
+        assert!((ratios.on(first).unwrap() - 307.671 / 250.0).abs() < 1e-10);
+        let mid_cpi = 307.671 + 15.0 / 31.0 * (307.051 - 307.671);
+        assert!((ratios.on(mid).unwrap() - mid_cpi / 250.0).abs() < 1e-10);
+        assert!((ratios.on(feb).unwrap() - 307.051 / 250.0).abs() < 1e-10);
+        // January CPI has not been published here.
+        assert_eq!(ratios.on(april), None);
+    }
+
+    #[test]
+    fn test_cpi_lag_month_crosses_year() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 10).unwrap();
+        assert_eq!(cpi_lag_month(date, 3), (2023, 11));
+        assert_eq!(cpi_lag_month(date, 2), (2023, 12));
+    }
+
+    #[test]
+    fn test_parse_bls_cpi_json() {
+        let json = br#"{
+            "status": "REQUEST_SUCCEEDED",
+            "Results": {"series": [{"seriesID": "CUUR0000SA0", "data": [
+                {"year": "2024", "period": "M02", "value": "310.326"},
+                {"year": "2024", "period": "M01", "value": "308.417"},
+                {"year": "2023", "period": "M13", "value": "304.702"},
+                {"year": "2023", "period": "M12", "value": "-"}
+            ]}]}
+        }"#;
+        let years = parse_bls_cpi_json(json).unwrap();
+        assert_eq!(years[&2024][&1], 308.417);
+        assert_eq!(years[&2024][&2], 310.326);
+        assert!(!years.contains_key(&2023));
+
+        let failed = br#"{"status": "REQUEST_NOT_PROCESSED", "Results": {}}"#;
+        assert!(parse_bls_cpi_json(failed).is_err());
+    }
+
+    #[test]
+    fn test_curve_feed_selection() {
+        let mut bond = BondQuoteMetadata {
+            coupon_rate: dec!(0.04),
+            maturity_date: NaiveDate::from_ymd_opt(2030, 1, 15).unwrap(),
+            face_value: Some(dec!(1000)),
+            coupon_frequency: "SEMI_ANNUAL".to_string(),
+            is_tips: false,
+            index_ratio: None,
+            ref_cpi_on_dated_date: None,
+            call_date: None,
+            call_price: None,
+            currency: None,
+        };
+        assert_eq!(CurveFeed::for_bond(&bond), CurveFeed::Nominal);
+        bond.is_tips = true;
+        assert_eq!(CurveFeed::for_bond(&bond), CurveFeed::Real);
+        assert_eq!(
+            CurveFeed::Real.data_param(),
+            "daily_treasury_real_yield_curve"
+        );
+    }
+
     #[test]
     fn test_calculate_price_tbill_182_day() {
         // Concrete T-bill example: 182-day T-bill at 4.5% yield
@@ -929,9 +2818,16 @@ mod tests {
         let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
         let maturity = NaiveDate::from_ymd_opt(2025, 7, 2).unwrap(); // 182 days
 
-        let price = UsTreasuryCalcProvider::calculate_price(
-            &curve, today, maturity, 0.0, // zero coupon
-            "ZERO", 1000.0,
+        let price = calculate_price(
+            PROVIDER_ID,
+            &curve,
+            InterpolationMethod::Linear,
+            today,
+            maturity,
+            0.0, // zero coupon
+            "ZERO",
+            1000.0,
+            None,
         )
         .unwrap();
 
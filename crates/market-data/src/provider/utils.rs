@@ -0,0 +1,122 @@
+//! HTTP helpers shared by provider implementations.
+
+use crate::errors::MarketDataError;
+
+/// Default cap on a provider response body (16 MiB). Large enough for a year
+/// of yield curves or a minified JS bundle.
+pub const DEFAULT_MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+fn too_large(provider: &str, max_bytes: usize) -> MarketDataError {
+    MarketDataError::ProviderError {
+        provider: provider.to_string(),
+        message: format!("Response body exceeds {} bytes", max_bytes),
+    }
+}
+
+/// Read a response body, failing once it grows past `max_bytes`.
+///
+/// The body is streamed chunk by chunk, so an oversized response is rejected
+/// without buffering all of it. A declared `Content-Length` over the cap is
+/// rejected before reading.
+pub async fn read_bytes_capped(
+    mut resp: reqwest::Response,
+    max_bytes: usize,
+    provider: &str,
+) -> Result<Vec<u8>, MarketDataError> {
+    if resp
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(too_large(provider, max_bytes));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| MarketDataError::ProviderError {
+            provider: provider.to_string(),
+            message: format!("Failed to read response: {}", e),
+        })?
+    {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(too_large(provider, max_bytes));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Read a response body as text, failing once it grows past `max_bytes`.
+/// Invalid UTF-8 is replaced rather than rejected.
+pub async fn read_text_capped(
+    resp: reqwest::Response,
+    max_bytes: usize,
+    provider: &str,
+) -> Result<String, MarketDataError> {
+    let body = read_bytes_capped(resp, max_bytes, provider).await?;
+    Ok(String::from_utf8(body)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    /// Serve `body` once, optionally without a `Content-Length` header so the
+    /// cap has to be enforced while streaming.
+    fn serve_once(body: Vec<u8>, declare_length: bool) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            let header = if declare_length {
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                )
+            } else {
+                "HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n".to_string()
+            };
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(&body);
+        });
+        url
+    }
+
+    async fn fetch(url: &str) -> reqwest::Response {
+        reqwest::get(url).await.expect("request")
+    }
+
+    #[tokio::test]
+    async fn test_rejects_oversized_body() {
+        for declare_length in [true, false] {
+            let url = serve_once(vec![b'x'; 64 * 1024], declare_length);
+            let result = read_text_capped(fetch(&url).await, 1024, "TEST").await;
+            assert!(
+                matches!(
+                    result,
+                    Err(MarketDataError::ProviderError { ref provider, ref message })
+                        if provider == "TEST" && message.contains("exceeds 1024 bytes")
+                ),
+                "declare_length={}: {:?}",
+                declare_length,
+                result
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reads_body_within_cap() {
+        let url = serve_once(b"2024-01-02,3.1".to_vec(), false);
+        let text = read_text_capped(fetch(&url).await, 1024, "TEST")
+            .await
+            .unwrap();
+        assert_eq!(text, "2024-01-02,3.1");
+    }
+}
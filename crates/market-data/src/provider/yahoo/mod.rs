@@ -1225,6 +1225,8 @@ mod tests {
             currency_hint: Some(Cow::Borrowed("USD")),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
@@ -1243,6 +1245,8 @@ mod tests {
             currency_hint: currency_hint.map(Cow::Borrowed),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
@@ -1338,6 +1342,8 @@ mod tests {
             overrides: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
         assert_eq!(provider.get_currency(&context), "GBp");
     }
@@ -12,8 +12,8 @@ mod rate_limiter;
 mod skip_reason;
 mod validator;
 
-pub use circuit_breaker::{CircuitBreaker, CircuitState};
-pub use provider_registry::ProviderRegistry;
-pub use rate_limiter::{RateLimitConfig, RateLimiter};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+pub use provider_registry::{ProviderDescription, ProviderRegistry};
+pub use rate_limiter::{RateLimitConfig, RateLimitPermit, RateLimitStatus, RateLimiter};
 pub use skip_reason::{FetchDiagnostics, ProviderAttempt, SkipReason};
 pub use validator::{QuoteValidator, ValidationSeverity};
@@ -15,15 +15,28 @@ use chrono::{DateTime, Utc};
 use log::{debug, warn};
 
 use super::{
-    CircuitBreaker, FetchDiagnostics, QuoteValidator, RateLimitConfig, RateLimiter, SkipReason,
+    CircuitBreaker, FetchDiagnostics, QuoteValidator, RateLimitConfig, RateLimitStatus,
+    RateLimiter, SkipReason,
 };
 use crate::errors::{MarketDataError, RetryClass};
 use crate::models::{
-    AssetProfile, InstrumentId, ProviderId, Quote, QuoteContext, SearchResult, SplitEvent,
+    AssetProfile, InstrumentId, InstrumentKind, ProviderId, Quote, QuoteContext, SearchResult,
+    SplitEvent,
 };
-use crate::provider::MarketDataProvider;
+use crate::provider::{MarketDataProvider, ProviderCapabilities, ProviderOperation, RateLimit};
 use crate::resolver::SymbolResolver;
 
+/// What a registered provider is and what it supports.
+#[derive(Clone, Debug)]
+pub struct ProviderDescription {
+    pub id: &'static str,
+    /// Effective priority: the user-configured priority if set, otherwise the
+    /// provider's default. Lower values = higher priority.
+    pub priority: i32,
+    pub capabilities: ProviderCapabilities,
+    pub rate_limit: RateLimit,
+}
+
 /// Provider registry for orchestrating market data fetching.
 pub struct ProviderRegistry {
     providers: Vec<Arc<dyn MarketDataProvider>>,
@@ -168,7 +181,7 @@ impl ProviderRegistry {
             );
 
             // Rate limit
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             // Fetch quotes
             match provider
@@ -213,7 +226,7 @@ impl ProviderRegistry {
                     return Ok(valid_quotes);
                 }
                 Err(e) => {
-                    let retry_class = e.retry_class();
+                    let retry_class = retry_class_for(context, &e);
 
                     match retry_class {
                         RetryClass::Never => {
@@ -273,7 +286,7 @@ impl ProviderRegistry {
                 Err(_) => continue,
             };
 
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             match provider
                 .get_latest_quote(context, resolved.instrument)
@@ -294,7 +307,7 @@ impl ProviderRegistry {
                     return Ok(quote);
                 }
                 Err(e) => {
-                    let retry_class = e.retry_class();
+                    let retry_class = retry_class_for(context, &e);
 
                     if retry_class == RetryClass::Never {
                         return Err(e);
@@ -334,7 +347,7 @@ impl ProviderRegistry {
                 Err(_) => continue,
             };
 
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             match provider
                 .get_splits(context, resolved.instrument, start, end)
@@ -477,19 +490,59 @@ impl ProviderRegistry {
                 }
             }
 
-            // Use custom priority if configured, otherwise use provider's default
-            self.custom_priorities
-                .get(p.id())
-                .copied()
-                .unwrap_or_else(|| p.priority() as i32)
+            self.effective_priority(p)
         });
     }
 
+    /// Custom priority if configured, otherwise the provider's default.
+    fn effective_priority(&self, provider: &Arc<dyn MarketDataProvider>) -> i32 {
+        self.custom_priorities
+            .get(provider.id())
+            .copied()
+            .unwrap_or_else(|| provider.priority() as i32)
+    }
+
     /// Get the list of registered providers.
     pub fn providers(&self) -> &[Arc<dyn MarketDataProvider>] {
         &self.providers
     }
 
+    /// Describe every registered provider, in priority order.
+    pub fn describe(&self) -> Vec<ProviderDescription> {
+        let mut descriptions: Vec<_> = self
+            .providers
+            .iter()
+            .map(|p| ProviderDescription {
+                id: p.id(),
+                priority: self.effective_priority(p),
+                capabilities: p.capabilities(),
+                rate_limit: p.rate_limit(),
+            })
+            .collect();
+        descriptions.sort_by_key(|d| d.priority);
+        descriptions
+    }
+
+    /// Providers that support `operation` for `instrument_kind`, in priority
+    /// order. Coverage restrictions (e.g. MICs) are not considered.
+    pub fn providers_for(
+        &self,
+        instrument_kind: InstrumentKind,
+        operation: ProviderOperation,
+    ) -> Vec<&Arc<dyn MarketDataProvider>> {
+        let mut providers: Vec<_> = self
+            .providers
+            .iter()
+            .filter(|p| {
+                let caps = p.capabilities();
+                caps.instrument_kinds.contains(&instrument_kind)
+                    && caps.supports_operation(operation)
+            })
+            .collect();
+        providers.sort_by_key(|p| self.effective_priority(p));
+        providers
+    }
+
     /// Check if a provider's circuit is open.
     pub fn is_circuit_open(&self, provider_id: &ProviderId) -> bool {
         !self.circuit_breaker.is_allowed(provider_id)
@@ -500,6 +553,12 @@ impl ProviderRegistry {
         self.circuit_breaker.reset(provider_id);
     }
 
+    /// Report a provider's current rate-limit headroom (available tokens,
+    /// time until the next token, and in-flight requests).
+    pub fn rate_limit_status(&self, provider_id: &ProviderId) -> RateLimitStatus {
+        self.rate_limiter.status(provider_id)
+    }
+
     /// Search for symbols matching the query.
     ///
     /// Tries providers that support search until one succeeds.
@@ -528,7 +587,7 @@ impl ProviderRegistry {
                 continue;
             }
 
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             match provider.search(query).await {
                 Ok(results) if !results.is_empty() => {
@@ -615,7 +674,7 @@ impl ProviderRegistry {
 
             let symbol = resolved.instrument.to_symbol_string();
 
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             match provider.get_profile(&symbol).await {
                 Ok(profile) => {
@@ -693,7 +752,7 @@ impl ProviderRegistry {
                 provider_id, resolved.instrument, resolved.source
             );
 
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             match provider
                 .get_historical_quotes(context, resolved.instrument, start, end)
@@ -731,7 +790,7 @@ impl ProviderRegistry {
                     return (Ok(valid_quotes), diagnostics);
                 }
                 Err(e) => {
-                    let retry_class = e.retry_class();
+                    let retry_class = retry_class_for(context, &e);
                     diagnostics.record_error(provider_id.clone(), format!("{:?}", e));
 
                     match retry_class {
@@ -789,7 +848,7 @@ impl ProviderRegistry {
                 }
             };
 
-            self.rate_limiter.acquire(&provider_id).await;
+            let _permit = self.rate_limiter.acquire(&provider_id).await;
 
             match provider
                 .get_latest_quote(context, resolved.instrument)
@@ -811,7 +870,7 @@ impl ProviderRegistry {
                     return (Ok(quote), diagnostics);
                 }
                 Err(e) => {
-                    let retry_class = e.retry_class();
+                    let retry_class = retry_class_for(context, &e);
                     diagnostics.record_error(provider_id.clone(), format!("{:?}", e));
 
                     if retry_class == RetryClass::Never {
@@ -837,11 +896,22 @@ impl ProviderRegistry {
     }
 }
 
+/// Retry class of a provider error for the instrument being fetched.
+///
+/// Bond prices come from independent sources (calculated from a yield curve or
+/// quoted on a venue), so a bond unknown to one source is still worth asking
+/// the next one about.
+fn retry_class_for(context: &QuoteContext, error: &MarketDataError) -> RetryClass {
+    match (&context.instrument, error) {
+        (InstrumentId::Bond { .. }, MarketDataError::SymbolNotFound(_)) => RetryClass::NextProvider,
+        _ => error.retry_class(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Coverage, Currency, InstrumentKind, ProviderInstrument};
-    use crate::provider::{ProviderCapabilities, RateLimit};
+    use crate::models::{Coverage, Currency, ProviderInstrument};
     use crate::resolver::{ResolutionSource, ResolvedInstrument};
     use rust_decimal_macros::dec;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -995,6 +1065,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let ordered = registry.ordered_providers(&context, true);
@@ -1024,6 +1096,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: Some(Cow::Borrowed("PROVIDER_C")),
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let ordered = registry.ordered_providers(&context, true);
@@ -1096,6 +1170,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let equity_providers = registry.ordered_providers(&equity_context, true);
@@ -1112,6 +1188,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let crypto_providers = registry.ordered_providers(&crypto_context, true);
@@ -1174,6 +1252,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
         assert_eq!(registry.ordered_providers(&us_context, true).len(), 1);
 
@@ -1187,6 +1267,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
         assert_eq!(registry.ordered_providers(&ca_context, true).len(), 0);
 
@@ -1200,10 +1282,139 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
         assert_eq!(registry.ordered_providers(&unknown_context, true).len(), 0);
     }
 
+    /// Bond source that either prices the bond or fails with `error`.
+    struct BondSource {
+        id: &'static str,
+        priority: u8,
+        error: Option<fn() -> MarketDataError>,
+    }
+
+    impl BondSource {
+        fn quote(&self) -> Result<Quote, MarketDataError> {
+            match self.error {
+                Some(error) => Err(error()),
+                None => Ok(Quote::new(
+                    Utc::now(),
+                    dec!(0.97),
+                    "USD".to_string(),
+                    self.id.to_string(),
+                )),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl MarketDataProvider for BondSource {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+        fn priority(&self) -> u8 {
+            self.priority
+        }
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                instrument_kinds: &[InstrumentKind::Bond],
+                coverage: Coverage::global_best_effort(),
+                supports_latest: true,
+                supports_historical: true,
+                supports_search: false,
+                supports_profile: false,
+            }
+        }
+        fn rate_limit(&self) -> RateLimit {
+            RateLimit::default()
+        }
+        async fn get_latest_quote(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+        ) -> Result<Quote, MarketDataError> {
+            self.quote()
+        }
+        async fn get_historical_quotes(
+            &self,
+            _: &QuoteContext,
+            _: ProviderInstrument,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> Result<Vec<Quote>, MarketDataError> {
+            self.quote().map(|quote| vec![quote])
+        }
+    }
+
+    fn bond_context() -> QuoteContext {
+        QuoteContext {
+            instrument: InstrumentId::Bond {
+                isin: Arc::from("US91282CJL54"),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+            bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bond_curve_failure_falls_back_to_market_price() {
+        let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
+            Arc::new(BondSource {
+                id: "US_TREASURY_CALC",
+                priority: 10,
+                error: Some(|| MarketDataError::ProviderError {
+                    provider: "US_TREASURY_CALC".to_string(),
+                    message: "yield curve unavailable".to_string(),
+                }),
+            }),
+            Arc::new(BondSource {
+                id: "BOERSE_FRANKFURT",
+                priority: 20,
+                error: None,
+            }),
+        ];
+        let registry = ProviderRegistry::new(providers, Arc::new(MockResolver));
+        let context = bond_context();
+
+        let quotes = registry
+            .fetch_quotes(&context, Utc::now() - chrono::Duration::days(7), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(quotes[0].source, "BOERSE_FRANKFURT");
+
+        let quote = registry.fetch_latest_quote(&context).await.unwrap();
+        assert_eq!(quote.source, "BOERSE_FRANKFURT");
+    }
+
+    #[tokio::test]
+    async fn test_bond_unknown_to_market_falls_back_to_calculated_price() {
+        let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
+            Arc::new(BondSource {
+                id: "BOERSE_FRANKFURT",
+                priority: 10,
+                error: Some(|| MarketDataError::SymbolNotFound("US91282CJL54".to_string())),
+            }),
+            Arc::new(BondSource {
+                id: "US_TREASURY_CALC",
+                priority: 20,
+                error: None,
+            }),
+        ];
+        let registry = ProviderRegistry::new(providers, Arc::new(MockResolver));
+
+        let (result, diagnostics) = registry
+            .fetch_latest_quote_with_diagnostics(&bond_context())
+            .await;
+        assert_eq!(result.unwrap().source, "US_TREASURY_CALC");
+        assert!(diagnostics.summary().contains("BOERSE_FRANKFURT"));
+    }
+
     #[test]
     fn test_custom_priorities_override_defaults() {
         // Providers with hardcoded priorities: 5, 10, 20
@@ -1230,6 +1441,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let ordered = registry.ordered_providers(&context, true);
@@ -1240,6 +1453,80 @@ mod tests {
         assert_eq!(ordered[2].id(), "PROVIDER_A");
     }
 
+    fn bond_registry(custom_priorities: HashMap<String, i32>) -> ProviderRegistry {
+        use crate::provider::{
+            boerse_frankfurt::BoerseFrankfurtProvider, openfigi::OpenFigiProvider,
+            us_treasury_calc::UsTreasuryCalcProvider,
+        };
+
+        let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
+            Arc::new(BoerseFrankfurtProvider::new()),
+            Arc::new(OpenFigiProvider::new()),
+            Arc::new(UsTreasuryCalcProvider::new()),
+        ];
+        ProviderRegistry::with_priorities(providers, Arc::new(MockResolver), custom_priorities)
+    }
+
+    fn ids(providers: Vec<&Arc<dyn MarketDataProvider>>) -> Vec<&'static str> {
+        providers.iter().map(|p| p.id()).collect()
+    }
+
+    #[test]
+    fn test_describe_bond_providers_in_priority_order() {
+        let registry = bond_registry(HashMap::new());
+
+        let described = registry.describe();
+        let summary: Vec<_> = described.iter().map(|d| (d.id, d.priority)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("OPENFIGI", 5),
+                ("US_TREASURY_CALC", 10),
+                ("BOERSE_FRANKFURT", 15)
+            ]
+        );
+        assert!(!described[0].capabilities.supports_latest);
+        assert!(described[0].capabilities.supports_profile);
+    }
+
+    #[test]
+    fn test_providers_for_bond_operations() {
+        let registry = bond_registry(HashMap::new());
+
+        assert_eq!(
+            ids(registry.providers_for(InstrumentKind::Bond, ProviderOperation::LatestQuote)),
+            vec!["US_TREASURY_CALC", "BOERSE_FRANKFURT"]
+        );
+        assert_eq!(
+            ids(registry.providers_for(InstrumentKind::Bond, ProviderOperation::HistoricalQuotes)),
+            vec!["US_TREASURY_CALC", "BOERSE_FRANKFURT"]
+        );
+        assert_eq!(
+            ids(registry.providers_for(InstrumentKind::Bond, ProviderOperation::Profile)),
+            vec!["OPENFIGI", "BOERSE_FRANKFURT"]
+        );
+        assert_eq!(
+            ids(registry.providers_for(InstrumentKind::Bond, ProviderOperation::Search)),
+            vec!["OPENFIGI", "BOERSE_FRANKFURT"]
+        );
+        assert!(registry
+            .providers_for(InstrumentKind::Equity, ProviderOperation::LatestQuote)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_providers_for_honours_custom_priorities() {
+        let mut custom_priorities = HashMap::new();
+        custom_priorities.insert("BOERSE_FRANKFURT".to_string(), 1);
+        let registry = bond_registry(custom_priorities);
+
+        assert_eq!(
+            ids(registry.providers_for(InstrumentKind::Bond, ProviderOperation::LatestQuote)),
+            vec!["BOERSE_FRANKFURT", "US_TREASURY_CALC"]
+        );
+        assert_eq!(registry.describe()[0].id, "BOERSE_FRANKFURT");
+    }
+
     #[test]
     fn test_preferred_provider_overrides_custom_priorities() {
         let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
@@ -1267,6 +1554,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: Some(Cow::Borrowed("PROVIDER_C")),
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let ordered = registry.ordered_providers(&context, true);
@@ -90,6 +90,39 @@ impl TokenBucket {
     }
 }
 
+/// Snapshot of a provider's rate-limit headroom, for diagnostics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimitStatus {
+    /// Tokens currently available in the provider's bucket.
+    pub available_tokens: f64,
+    /// Time until the next token becomes available (zero if one is available now).
+    pub reset_in: Duration,
+    /// Requests that acquired a token and have not yet completed.
+    pub in_flight: usize,
+}
+
+/// Marks a request as in flight until dropped.
+///
+/// Returned by [`RateLimiter::acquire`]; hold it for the duration of the
+/// provider call so [`RateLimiter::status`] can report in-flight requests.
+#[must_use = "dropping the permit immediately stops tracking the request as in flight"]
+pub struct RateLimitPermit<'a> {
+    limiter: &'a RateLimiter,
+    provider: String,
+}
+
+impl Drop for RateLimitPermit<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.limiter.lock_in_flight();
+        if let Some(count) = in_flight.get_mut(&self.provider) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                in_flight.remove(&self.provider);
+            }
+        }
+    }
+}
+
 /// Rate limiter configuration for a provider.
 #[derive(Clone, Debug)]
 pub struct RateLimitConfig {
@@ -118,6 +151,8 @@ pub struct RateLimiter {
     buckets: Mutex<HashMap<String, TokenBucket>>,
     /// Per-provider configuration overrides.
     configs: Mutex<HashMap<String, RateLimitConfig>>,
+    /// Per-provider count of requests holding a permit.
+    in_flight: Mutex<HashMap<String, usize>>,
 }
 
 impl RateLimiter {
@@ -126,6 +161,7 @@ impl RateLimiter {
         Self {
             buckets: Mutex::new(HashMap::new()),
             configs: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
         }
     }
 
@@ -149,6 +185,14 @@ impl RateLimiter {
         })
     }
 
+    /// Lock the in-flight mutex, recovering from poison if necessary.
+    fn lock_in_flight(&self) -> MutexGuard<'_, HashMap<String, usize>> {
+        self.in_flight.lock().unwrap_or_else(|poisoned| {
+            warn!("Rate limiter in-flight mutex was poisoned, recovering");
+            poisoned.into_inner()
+        })
+    }
+
     /// Configure rate limits for a specific provider.
     pub fn configure(&self, provider: &ProviderId, config: RateLimitConfig) {
         let mut configs = self.lock_configs();
@@ -164,8 +208,9 @@ impl RateLimiter {
     ///
     /// This method will wait (asynchronously) until a token is available.
     /// If the provider doesn't have a bucket yet, one is created with
-    /// default settings.
-    pub async fn acquire(&self, provider: &ProviderId) {
+    /// default settings. The request counts as in flight until the returned
+    /// permit is dropped.
+    pub async fn acquire(&self, provider: &ProviderId) -> RateLimitPermit<'_> {
         loop {
             let wait_time = {
                 let mut buckets = self.lock_buckets();
@@ -176,7 +221,15 @@ impl RateLimiter {
 
                 if bucket.try_acquire() {
                     debug!("Rate limiter: acquired token for '{}'", provider);
-                    return;
+                    drop(buckets);
+                    *self
+                        .lock_in_flight()
+                        .entry(provider.to_string())
+                        .or_insert(0) += 1;
+                    return RateLimitPermit {
+                        limiter: self,
+                        provider: provider.to_string(),
+                    };
                 }
 
                 bucket.time_until_available()
@@ -217,6 +270,31 @@ impl RateLimiter {
         }
     }
 
+    /// Report the current rate-limit headroom for a provider.
+    pub fn status(&self, provider: &ProviderId) -> RateLimitStatus {
+        let (available_tokens, reset_in) = {
+            let mut buckets = self.lock_buckets();
+            match buckets.get_mut(provider.as_ref()) {
+                Some(bucket) => {
+                    let reset_in = bucket.time_until_available();
+                    (bucket.tokens, reset_in)
+                }
+                None => (DEFAULT_BUCKET_CAPACITY, Duration::ZERO),
+            }
+        };
+        let in_flight = self
+            .lock_in_flight()
+            .get(provider.as_ref())
+            .copied()
+            .unwrap_or(0);
+
+        RateLimitStatus {
+            available_tokens,
+            reset_in,
+            in_flight,
+        }
+    }
+
     /// Reset the rate limiter for a provider.
     pub fn reset(&self, provider: &ProviderId) {
         let mut buckets = self.lock_buckets();
@@ -371,16 +449,41 @@ mod tests {
         );
 
         // First two should be immediate
-        limiter.acquire(&provider).await;
-        limiter.acquire(&provider).await;
+        let _first = limiter.acquire(&provider).await;
+        let _second = limiter.acquire(&provider).await;
 
         // Third should require waiting (but should complete)
         let start = Instant::now();
-        limiter.acquire(&provider).await;
+        let _third = limiter.acquire(&provider).await;
         let elapsed = start.elapsed();
 
         // Should have waited some time (at least a few ms)
         // Note: in practice, with 100 req/sec, wait is ~10ms
         assert!(elapsed.as_millis() >= 5);
     }
+
+    #[tokio::test]
+    async fn test_status_after_draining_bucket() {
+        let limiter = RateLimiter::new();
+        let provider: ProviderId = Cow::Borrowed("STATUS_PROVIDER");
+
+        limiter.configure(
+            &provider,
+            RateLimitConfig {
+                requests_per_minute: 60,
+                burst_capacity: 2.0,
+            },
+        );
+
+        let first = limiter.acquire(&provider).await;
+        let _second = limiter.acquire(&provider).await;
+
+        let status = limiter.status(&provider);
+        assert!(status.available_tokens < 1.0);
+        assert!(status.reset_in > Duration::ZERO);
+        assert_eq!(status.in_flight, 2);
+
+        drop(first);
+        assert_eq!(limiter.status(&provider).in_flight, 1);
+    }
 }
@@ -83,6 +83,8 @@ mod tests {
             currency_hint: Some("CAD".into()),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let result = resolver.resolve(&"YAHOO".into(), &context);
@@ -113,6 +115,8 @@ mod tests {
             currency_hint: Some("USD".into()),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let result = resolver.resolve(&"YAHOO".into(), &context);
@@ -143,6 +147,8 @@ mod tests {
             currency_hint: Some("CAD".into()),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         // Try to resolve for ALPHA_VANTAGE (no override)
@@ -32,6 +32,7 @@ use super::traits::{ResolvedInstrument, Resolver, SymbolResolver};
 ///     currency_hint: Some("CAD".into()),
 ///     preferred_provider: None,
 ///     bond_metadata: None,
+///     mic_hint: None,
 /// };
 ///
 /// let resolved = chain.resolve(&"YAHOO".into(), &context)?;
@@ -145,6 +146,8 @@ mod tests {
             currency_hint: Some("CAD".into()),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let resolved = chain.resolve(&"YAHOO".into(), &context).unwrap();
@@ -173,6 +176,8 @@ mod tests {
             currency_hint: Some("CAD".into()),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let resolved = chain.resolve(&"YAHOO".into(), &context).unwrap();
@@ -209,6 +214,8 @@ mod tests {
             currency_hint: Some("CAD".into()),
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         // Resolve for ALPHA_VANTAGE (no override) - should use rules
@@ -236,6 +243,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let resolved = chain.resolve(&"YAHOO".into(), &context).unwrap();
@@ -261,6 +270,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let result = chain.resolve(&"UNKNOWN_PROVIDER".into(), &context);
@@ -287,6 +298,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let currency = chain.get_currency(&"YAHOO".into(), &context);
@@ -306,6 +319,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         };
 
         let currency = chain.get_currency(&"YAHOO".into(), &context);
@@ -55,6 +55,7 @@
 //!     currency_hint: Some("CAD".into()),
 //!     preferred_provider: None,
 //!     bond_metadata: None,
+//!     mic_hint: None,
 //! };
 //!
 //! let resolved = chain.resolve(&"YAHOO".into(), &context)?;
@@ -71,6 +72,7 @@
 //!     currency_hint: None,
 //!     preferred_provider: None,
 //!     bond_metadata: None,
+//!     mic_hint: None,
 //! };
 //!
 //! let resolved = chain.resolve(&"YAHOO".into(), &fx_context)?;
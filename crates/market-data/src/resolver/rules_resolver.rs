@@ -237,6 +237,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
@@ -250,6 +252,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
@@ -263,6 +267,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
@@ -276,6 +282,8 @@ mod tests {
             currency_hint: None,
             preferred_provider: None,
             bond_metadata: None,
+            mic_hint: None,
+            settlement_offset_days: None,
         }
     }
 
@@ -0,0 +1,8 @@
+-- Per-table content checksums recorded after a snapshot restore, used to detect
+-- local changes that no sync event accounts for.
+CREATE TABLE sync_table_baseline (
+    table_name TEXT PRIMARY KEY NOT NULL,
+    checksum TEXT NOT NULL,
+    row_count BIGINT NOT NULL,
+    computed_at TEXT NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Latest remote payload received for an entity that still had an unsent local
+-- edit, kept so the two versions can be compared field by field.
+CREATE TABLE sync_conflicts (
+    entity TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    remote_event_id TEXT NOT NULL,
+    remote_client_timestamp TEXT NOT NULL,
+    remote_payload TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    PRIMARY KEY (entity, entity_id)
+);
@@ -0,0 +1,13 @@
+-- Outcome of every remote event the replay path looked at, so an edit that
+-- lost to a newer version or failed validation leaves a trail.
+CREATE TABLE sync_event_audit (
+    id TEXT PRIMARY KEY NOT NULL,
+    entity TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    reason TEXT,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_sync_event_audit_recorded_at ON sync_event_audit (recorded_at);
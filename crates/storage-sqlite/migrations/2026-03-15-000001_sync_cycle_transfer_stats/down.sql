@@ -0,0 +1,3 @@
+ALTER TABLE sync_engine_state DROP COLUMN last_cycle_events_applied;
+ALTER TABLE sync_engine_state DROP COLUMN last_cycle_pulled_bytes;
+ALTER TABLE sync_engine_state DROP COLUMN last_cycle_pushed_bytes;
@@ -0,0 +1,5 @@
+-- How much data the last sync cycle moved, so a "successful" cycle that did
+-- nothing can be told apart from one that transferred changes.
+ALTER TABLE sync_engine_state ADD COLUMN last_cycle_pushed_bytes BIGINT;
+ALTER TABLE sync_engine_state ADD COLUMN last_cycle_pulled_bytes BIGINT;
+ALTER TABLE sync_engine_state ADD COLUMN last_cycle_events_applied BIGINT;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS sync_filtered_events;
+ALTER TABLE sync_device_config DROP COLUMN synced_account_ids;
@@ -0,0 +1,13 @@
+-- Per-device account allow-list as a JSON array of account ids. NULL or an
+-- empty array means the device syncs every account.
+ALTER TABLE sync_device_config ADD COLUMN synced_account_ids TEXT;
+
+-- Remote events skipped because their account was filtered out. Widening the
+-- filter rewinds the cursor to the oldest of these so they are pulled again.
+CREATE TABLE sync_filtered_events (
+    event_id TEXT PRIMARY KEY NOT NULL,
+    account_id TEXT NOT NULL,
+    seq BIGINT NOT NULL
+);
+
+CREATE INDEX idx_sync_filtered_events_account_id ON sync_filtered_events (account_id);
@@ -0,0 +1,4 @@
+ALTER TABLE sync_entity_metadata DROP COLUMN last_device_seq;
+ALTER TABLE sync_entity_metadata DROP COLUMN last_device_id;
+ALTER TABLE sync_outbox DROP COLUMN device_seq;
+DROP TABLE IF EXISTS sync_device_seq;
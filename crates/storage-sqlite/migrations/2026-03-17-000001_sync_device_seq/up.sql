@@ -0,0 +1,14 @@
+-- Per-device emission order. Same-timestamp events from one device resolve by
+-- this sequence under last-writer-wins instead of by event id.
+CREATE TABLE sync_device_seq (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    last_seq BIGINT NOT NULL DEFAULT 0
+);
+
+INSERT INTO sync_device_seq (id, last_seq)
+VALUES (1, 0)
+ON CONFLICT(id) DO NOTHING;
+
+ALTER TABLE sync_outbox ADD COLUMN device_seq BIGINT;
+ALTER TABLE sync_entity_metadata ADD COLUMN last_device_id TEXT;
+ALTER TABLE sync_entity_metadata ADD COLUMN last_device_seq BIGINT;
@@ -2,6 +2,7 @@ use super::DbPool;
 use crate::errors::StorageError;
 use crate::sync::app_sync::ProjectedChange;
 use crate::sync::{flush_projected_outbox, OutboxWriteRequest, SyncOutboxModel};
+use diesel::connection::{AnsiTransactionManager, TransactionManager};
 use diesel::SqliteConnection;
 use std::any::Any;
 use std::time::{Duration, Instant};
@@ -191,6 +192,24 @@ impl WriteProjection {
     }
 }
 
+/// Roll back a transaction that a failed job left open. SQLite keeps the
+/// transaction alive when `COMMIT` fails (e.g. on a deferred foreign-key
+/// check), and the next job would otherwise run inside it as a savepoint.
+fn rollback_open_transaction(conn: &mut SqliteConnection) {
+    let in_transaction = matches!(
+        AnsiTransactionManager::transaction_manager_status_mut(conn).transaction_depth(),
+        Ok(Some(_))
+    );
+    if in_transaction {
+        if let Err(err) = AnsiTransactionManager::rollback_transaction(conn) {
+            log::error!(
+                "Writer actor failed to roll back a failed transaction: {}",
+                err
+            );
+        }
+    }
+}
+
 /// Spawns a background Tokio task that acts as a single writer to the database.
 /// This actor owns one database connection from the pool and processes write jobs serially.
 ///
@@ -253,6 +272,9 @@ pub fn spawn_writer(pool: DbPool) -> Result<WriteHandle> {
                     job(c).map_err(StorageError::from)
                 })
                 .map_err(|e: StorageError| e.into());
+            if result.is_err() {
+                rollback_open_transaction(&mut conn);
+            }
 
             // Send the result back to the requester.
             // Ignore error if the receiver has dropped (e.g., request timed out or was cancelled).
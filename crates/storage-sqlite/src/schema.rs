@@ -299,6 +299,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sync_conflicts (entity, entity_id) {
+        entity -> Text,
+        entity_id -> Text,
+        remote_event_id -> Text,
+        remote_client_timestamp -> Text,
+        remote_payload -> Text,
+        detected_at -> Text,
+    }
+}
+
 diesel::table! {
     sync_cursor (id) {
         id -> Integer,
@@ -314,6 +325,14 @@ diesel::table! {
         trust_state -> Text,
         last_bootstrap_at -> Nullable<Text>,
         min_snapshot_created_at -> Nullable<Text>,
+        synced_account_ids -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    sync_device_seq (id) {
+        id -> Integer,
+        last_seq -> BigInt,
     }
 }
 
@@ -328,6 +347,9 @@ diesel::table! {
         next_retry_at -> Nullable<Text>,
         last_cycle_status -> Nullable<Text>,
         last_cycle_duration_ms -> Nullable<BigInt>,
+        last_cycle_pushed_bytes -> Nullable<BigInt>,
+        last_cycle_pulled_bytes -> Nullable<BigInt>,
+        last_cycle_events_applied -> Nullable<BigInt>,
     }
 }
 
@@ -338,6 +360,28 @@ diesel::table! {
         last_event_id -> Text,
         last_client_timestamp -> Text,
         last_seq -> BigInt,
+        last_device_id -> Nullable<Text>,
+        last_device_seq -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    sync_event_audit (id) {
+        id -> Text,
+        entity -> Text,
+        entity_id -> Text,
+        event_id -> Text,
+        outcome -> Text,
+        reason -> Nullable<Text>,
+        recorded_at -> Text,
+    }
+}
+
+diesel::table! {
+    sync_filtered_events (event_id) {
+        event_id -> Text,
+        account_id -> Text,
+        seq -> BigInt,
     }
 }
 
@@ -358,6 +402,16 @@ diesel::table! {
         last_error_code -> Nullable<Text>,
         device_id -> Nullable<Text>,
         created_at -> Text,
+        device_seq -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    sync_table_baseline (table_name) {
+        table_name -> Text,
+        checksum -> Text,
+        row_count -> BigInt,
+        computed_at -> Text,
     }
 }
 
@@ -456,11 +510,16 @@ diesel::allow_tables_to_appear_in_same_query!(
     quote_sync_state,
     quotes,
     sync_applied_events,
+    sync_conflicts,
     sync_cursor,
     sync_device_config,
+    sync_device_seq,
     sync_engine_state,
     sync_entity_metadata,
+    sync_event_audit,
+    sync_filtered_events,
     sync_outbox,
+    sync_table_baseline,
     sync_table_state,
     taxonomies,
     taxonomy_categories,
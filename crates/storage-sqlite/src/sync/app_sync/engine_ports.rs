@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use wealthfolio_core::sync::{SyncAuditOutcome, SyncEntity};
 use wealthfolio_device_sync::engine::{OutboxStore, ReplayEvent, ReplayStore};
 
 use super::repository::AppSyncRepository;
@@ -72,6 +73,13 @@ impl OutboxStore for SqliteSyncEngineDbPorts {
             .map(|rows| !rows.is_empty())
             .map_err(|e| e.to_string())
     }
+
+    async fn compact_pending_outbox(&self) -> Result<usize, String> {
+        self.repository
+            .compact_pending_outbox()
+            .await
+            .map_err(|e| e.to_string())
+    }
 }
 
 #[async_trait]
@@ -100,15 +108,17 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
             .map_err(|e| e.to_string())
     }
 
-    async fn apply_remote_events_lww_batch(
+    async fn apply_remote_events_lww_batch_with_cursor(
         &self,
         events: Vec<ReplayEvent>,
+        new_cursor: i64,
     ) -> Result<usize, String> {
         self.repository
-            .apply_remote_events_lww_batch(
+            .apply_remote_events_lww_batch_with_cursor(
                 events
                     .into_iter()
                     .map(|event| {
+                        let origin = event.device_seq.map(|seq| (event.device_id, seq));
                         (
                             event.entity,
                             event.entity_id,
@@ -117,15 +127,18 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
                             event.client_timestamp,
                             event.seq,
                             event.payload,
+                            origin,
                         )
                     })
                     .collect(),
+                new_cursor,
             )
             .await
             .map_err(|e| e.to_string())
     }
 
     async fn apply_remote_event_lww(&self, event: ReplayEvent) -> Result<bool, String> {
+        let origin = event.device_seq.map(|seq| (event.device_id, seq));
         self.repository
             .apply_remote_event_lww(
                 event.entity,
@@ -135,6 +148,7 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
                 event.client_timestamp,
                 event.seq,
                 event.payload,
+                origin,
             )
             .await
             .map_err(|e| e.to_string())
@@ -159,6 +173,22 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
             .map_err(|e| e.to_string())
     }
 
+    async fn mark_cycle_transfer_stats(
+        &self,
+        pushed_bytes: usize,
+        pulled_bytes: usize,
+        events_applied: usize,
+    ) -> Result<(), String> {
+        self.repository
+            .mark_cycle_transfer_stats(
+                i64::try_from(pushed_bytes).unwrap_or(i64::MAX),
+                i64::try_from(pulled_bytes).unwrap_or(i64::MAX),
+                i64::try_from(events_applied).unwrap_or(i64::MAX),
+            )
+            .await
+            .map_err(|e| e.to_string())
+    }
+
     async fn mark_engine_error(&self, message: String) -> Result<(), String> {
         self.repository
             .mark_engine_error(message)
@@ -166,6 +196,25 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
             .map_err(|e| e.to_string())
     }
 
+    async fn record_skipped_event(
+        &self,
+        entity: SyncEntity,
+        entity_id: String,
+        event_id: String,
+        reason: String,
+    ) -> Result<(), String> {
+        self.repository
+            .record_audit(
+                entity,
+                entity_id,
+                event_id,
+                SyncAuditOutcome::RejectedValidation,
+                Some(reason),
+            )
+            .await
+            .map_err(|e| e.to_string())
+    }
+
     async fn prune_applied_events_up_to_seq(&self, seq: i64) -> Result<(), String> {
         self.repository
             .prune_applied_events_up_to_seq(seq)
@@ -174,6 +223,14 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
             .map_err(|e| e.to_string())
     }
 
+    async fn prune_applied_events_older_than(&self, cutoff_rfc3339: String) -> Result<(), String> {
+        self.repository
+            .prune_applied_events_older_than(cutoff_rfc3339)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
     async fn get_engine_status(&self) -> Result<wealthfolio_core::sync::SyncEngineStatus, String> {
         self.repository
             .get_engine_status()
@@ -9,11 +9,14 @@ mod repository;
 
 pub use engine_ports::SqliteSyncEngineDbPorts;
 pub use model::{
-    SyncAppliedEventDB, SyncCursorDB, SyncDeviceConfigDB, SyncEngineStateDB, SyncEntityMetadataDB,
-    SyncOutboxEventDB, SyncTableStateDB,
+    SyncAppliedEventDB, SyncConflictDB, SyncCursorDB, SyncDeviceConfigDB, SyncEngineStateDB,
+    SyncEntityMetadataDB, SyncEventAuditDB, SyncOutboxEventDB, SyncTableBaselineDB,
+    SyncTableStateDB,
 };
 pub(crate) use outbox_projector::{flush_projected_outbox, ProjectedChange};
 pub use repository::{
-    insert_outbox_event, AppSyncRepository, OutboxWriteRequest, SyncLocalDataSummary,
-    SyncTableRowCount,
+    cleanup_orphaned_snapshot_temp_files, insert_outbox_event, AppSyncRepository, DeltaSnapshot,
+    DeltaSnapshotEntities, DeltaSnapshotManifest, FieldDiff, OutboxWriteRequest, ReplayEvent,
+    SnapshotExportOptions, SyncDryRunSummary, SyncEntityLag, SyncLocalDataSummary,
+    SyncOutboxDiagnostic, SyncTableDrift, SyncTableRowCount,
 };
@@ -64,6 +64,8 @@ pub struct SyncDeviceConfigDB {
     pub trust_state: String,
     pub last_bootstrap_at: Option<String>,
     pub min_snapshot_created_at: Option<String>,
+    /// JSON array of account ids this device syncs; `None` syncs all.
+    pub synced_account_ids: Option<String>,
 }
 
 #[derive(
@@ -89,6 +91,9 @@ pub struct SyncEngineStateDB {
     pub next_retry_at: Option<String>,
     pub last_cycle_status: Option<String>,
     pub last_cycle_duration_ms: Option<i64>,
+    pub last_cycle_pushed_bytes: Option<i64>,
+    pub last_cycle_pulled_bytes: Option<i64>,
+    pub last_cycle_events_applied: Option<i64>,
 }
 
 #[derive(
@@ -111,6 +116,8 @@ pub struct SyncEntityMetadataDB {
     pub last_event_id: String,
     pub last_client_timestamp: String,
     pub last_seq: i64,
+    pub last_device_id: Option<String>,
+    pub last_device_seq: Option<i64>,
 }
 
 #[derive(
@@ -143,6 +150,7 @@ pub struct SyncOutboxEventDB {
     pub last_error_code: Option<String>,
     pub device_id: Option<String>,
     pub created_at: String,
+    pub device_seq: Option<i64>,
 }
 
 #[derive(
@@ -165,3 +173,71 @@ pub struct SyncTableStateDB {
     pub last_snapshot_restore_at: Option<String>,
     pub last_incremental_apply_at: Option<String>,
 }
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Selectable,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(table_name))]
+#[diesel(table_name = crate::schema::sync_table_baseline)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SyncTableBaselineDB {
+    pub table_name: String,
+    pub checksum: String,
+    pub row_count: i64,
+    pub computed_at: String,
+}
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Selectable,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(entity, entity_id))]
+#[diesel(table_name = crate::schema::sync_conflicts)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SyncConflictDB {
+    pub entity: String,
+    pub entity_id: String,
+    pub remote_event_id: String,
+    pub remote_client_timestamp: String,
+    pub remote_payload: String,
+    pub detected_at: String,
+}
+
+#[derive(
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Selectable,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = crate::schema::sync_event_audit)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SyncEventAuditDB {
+    pub id: String,
+    pub entity: String,
+    pub entity_id: String,
+    pub event_id: String,
+    pub outcome: String,
+    pub reason: Option<String>,
+    pub recorded_at: String,
+}
@@ -1,29 +1,37 @@
 //! Repository for app-side device sync tables.
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::{Duration, Utc};
+use diesel::connection::SimpleConnection;
 use diesel::prelude::*;
 use diesel::r2d2::{self, Pool};
 use diesel::sqlite::SqliteConnection;
-use std::collections::{HashMap, HashSet};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::{Arc, Mutex, OnceLock};
 use uuid::Uuid;
 
 use wealthfolio_core::errors::{DatabaseError, Error, Result};
 use wealthfolio_core::sync::{
-    should_apply_lww, SyncEngineStatus, SyncEntity, SyncEntityMetadata, SyncOperation,
-    SyncOutboxEvent, SyncOutboxStatus, APP_SYNC_TABLES,
+    ConflictStrategy, LwwVersion, SyncAuditOutcome, SyncEngineStatus, SyncEntity,
+    SyncEntityMetadata, SyncEventAudit, SyncOperation, SyncOutboxEvent, SyncOutboxStatus,
+    APP_SYNC_TABLES, SNAPSHOT_SCHEMA_VERSION,
 };
+use wealthfolio_device_sync::engine::compute_next_retry;
+use wealthfolio_device_sync::SnapshotEncoding;
 
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::StorageError;
 use crate::schema::{
-    sync_applied_events, sync_cursor, sync_device_config, sync_engine_state, sync_entity_metadata,
-    sync_outbox, sync_table_state,
+    sync_applied_events, sync_conflicts, sync_cursor, sync_device_config, sync_device_seq,
+    sync_engine_state, sync_entity_metadata, sync_event_audit, sync_filtered_events, sync_outbox,
+    sync_table_baseline, sync_table_state,
 };
 
 use super::model::{
-    SyncAppliedEventDB, SyncCursorDB, SyncDeviceConfigDB, SyncEngineStateDB, SyncEntityMetadataDB,
-    SyncOutboxEventDB, SyncTableStateDB,
+    SyncAppliedEventDB, SyncConflictDB, SyncCursorDB, SyncDeviceConfigDB, SyncEngineStateDB,
+    SyncEntityMetadataDB, SyncEventAuditDB, SyncOutboxEventDB, SyncTableBaselineDB,
+    SyncTableStateDB,
 };
 
 fn enum_to_db<T: serde::Serialize>(value: &T) -> Result<String> {
@@ -44,10 +52,70 @@ fn validate_sync_table(table: &str) -> Result<()> {
     ))))
 }
 
+/// Whether `table_name` participates in sync. Tables without a state row
+/// are enabled.
+fn is_table_sync_enabled(conn: &mut SqliteConnection, table_name: &str) -> Result<bool> {
+    let enabled = sync_table_state::table
+        .find(table_name)
+        .select(sync_table_state::enabled)
+        .first::<i32>(conn)
+        .optional()
+        .map_err(StorageError::from)?;
+    Ok(enabled.is_none_or(|value| value != 0))
+}
+
 #[derive(Clone)]
 struct PayloadColumnCatalog {
     writable: HashSet<String>,
     readonly: HashSet<String>,
+    /// Type affinity of each writable column, from its declared type.
+    affinities: HashMap<String, ColumnAffinity>,
+    /// Writable columns explicitly declared as `BLOB`.
+    blob_columns: HashSet<String>,
+}
+
+/// SQLite column type affinity, derived from the declared column type using
+/// SQLite's own rules (https://www.sqlite.org/datatype3.html#affname).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnAffinity {
+    Integer,
+    Text,
+    Blob,
+    Real,
+    Numeric,
+}
+
+impl ColumnAffinity {
+    fn from_declared_type(declared: &str) -> Self {
+        let declared = declared.to_ascii_uppercase();
+        if declared.contains("INT") {
+            ColumnAffinity::Integer
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|t| declared.contains(t))
+        {
+            ColumnAffinity::Text
+        } else if declared.is_empty() || declared.contains("BLOB") {
+            ColumnAffinity::Blob
+        } else if ["REAL", "FLOA", "DOUB"]
+            .iter()
+            .any(|t| declared.contains(t))
+        {
+            ColumnAffinity::Real
+        } else {
+            ColumnAffinity::Numeric
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ColumnAffinity::Integer => "INTEGER",
+            ColumnAffinity::Text => "TEXT",
+            ColumnAffinity::Blob => "BLOB",
+            ColumnAffinity::Real => "REAL",
+            ColumnAffinity::Numeric => "NUMERIC",
+        }
+    }
 }
 
 fn payload_column_catalog_cache() -> &'static Mutex<HashMap<String, PayloadColumnCatalog>> {
@@ -73,10 +141,21 @@ struct PragmaTableInfoRow {
 struct PragmaTableXInfoRow {
     #[diesel(sql_type = diesel::sql_types::Text)]
     name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "type")]
+    declared_type: String,
     #[diesel(sql_type = diesel::sql_types::Integer)]
     hidden: i32,
 }
 
+#[derive(diesel::QueryableByName)]
+struct PragmaDatabaseListRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    file: String,
+}
+
 #[derive(diesel::QueryableByName)]
 struct TableRowCountResult {
     #[diesel(sql_type = diesel::sql_types::BigInt)]
@@ -89,12 +168,629 @@ pub struct SyncTableRowCount {
     pub rows: i64,
 }
 
+#[derive(diesel::QueryableByName)]
+struct RowTextResult {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    row_text: String,
+}
+
+/// A synced table whose contents changed since its baseline with no applied
+/// remote event or local outbox event to account for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncTableDrift {
+    pub table: String,
+    pub baseline_checksum: String,
+    pub current_checksum: String,
+    pub baseline_rows: i64,
+    pub current_rows: i64,
+}
+
+/// A payload field whose value differs between a pending local edit and the
+/// conflicting remote event. `None` means the field is absent on that side.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    pub field: String,
+    pub local: Option<serde_json::Value>,
+    pub remote: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SyncLocalDataSummary {
     pub total_rows: i64,
     pub non_empty_tables: Vec<SyncTableRowCount>,
 }
 
+/// Entity ids of one sync entity captured in a delta snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaSnapshotEntities {
+    pub entity: SyncEntity,
+    pub entity_ids: Vec<String>,
+}
+
+/// Describes what a delta snapshot image contains. Listed ids that have no
+/// row in the image were deleted after `since_seq`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaSnapshotManifest {
+    pub since_seq: i64,
+    /// Highest `last_seq` among the included entities; `since_seq` when empty.
+    pub max_seq: i64,
+    /// Changed entities in `APP_SYNC_TABLES` order.
+    pub entities: Vec<DeltaSnapshotEntities>,
+}
+
+impl DeltaSnapshotManifest {
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// A SQLite image holding only rows changed after a sequence, plus its manifest.
+#[derive(Debug, Clone)]
+pub struct DeltaSnapshot {
+    pub image: Vec<u8>,
+    pub manifest: DeltaSnapshotManifest,
+}
+
+/// What a sync cycle would do right now, computed without pushing, applying
+/// remote events or moving the cursor.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDryRunSummary {
+    /// Outbox events the next push would send.
+    pub pending_push: i64,
+    pub local_cursor: i64,
+    pub server_cursor: i64,
+    /// Sequence numbers the next pull would cover. Server sequences can have
+    /// gaps, so this is an upper bound on the events to apply.
+    pub remote_ahead: i64,
+}
+
+/// How far one sync entity is behind: local edits waiting to be pushed and
+/// the newest remote sequence applied to it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEntityLag {
+    pub entity: SyncEntity,
+    pub pending_outbox: i64,
+    /// Highest `last_seq` recorded for the entity; `None` if nothing was applied.
+    pub last_applied_seq: Option<i64>,
+}
+
+/// Outbox row as exposed for diagnostics. Omits the encrypted payload and the
+/// free-form error message, which may echo payload content.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncOutboxDiagnostic {
+    pub event_id: String,
+    pub entity: String,
+    pub entity_id: String,
+    pub op: String,
+    pub status: String,
+    pub payload_key_version: i32,
+    pub retry_count: i32,
+    pub next_retry_at: Option<String>,
+    pub last_error_code: Option<String>,
+    pub created_at: String,
+}
+
+fn count_table_rows(
+    conn: &mut SqliteConnection,
+    schema_ident: &str,
+    table_ident: &str,
+) -> Result<i64> {
+    let count_sql = format!("SELECT COUNT(*) AS count FROM {schema_ident}.{table_ident}");
+    let row = diesel::sql_query(count_sql)
+        .get_result::<TableRowCountResult>(conn)
+        .map_err(StorageError::from)?;
+    Ok(row.count)
+}
+
+/// SHA-256 over every row of `table`, independent of physical row order.
+/// Returns the hex digest and the row count.
+fn compute_table_checksum(conn: &mut SqliteConnection, table: &str) -> Result<(String, i64)> {
+    let columns = load_table_columns(conn, "main", table)?;
+    if columns.is_empty() {
+        return Err(Error::Database(DatabaseError::Internal(format!(
+            "Table '{}' has no columns to checksum",
+            table
+        ))));
+    }
+    let row_expr = columns
+        .iter()
+        .map(|column| format!("quote({})", quote_identifier(column)))
+        .collect::<Vec<_>>()
+        .join(" || '|' || ");
+    let sql = format!(
+        "SELECT {row_expr} AS row_text FROM main.{} ORDER BY row_text",
+        quote_identifier(table)
+    );
+    let rows = diesel::sql_query(sql)
+        .load::<RowTextResult>(conn)
+        .map_err(StorageError::from)?;
+
+    let mut hasher = Sha256::new();
+    for row in &rows {
+        hasher.update(row.row_text.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok((format!("{:x}", hasher.finalize()), rows.len() as i64))
+}
+
+fn record_table_baseline(
+    conn: &mut SqliteConnection,
+    table: &str,
+    computed_at: &str,
+) -> Result<()> {
+    let (checksum, row_count) = compute_table_checksum(conn, table)?;
+    let row = SyncTableBaselineDB {
+        table_name: table.to_string(),
+        checksum,
+        row_count,
+        computed_at: computed_at.to_string(),
+    };
+    diesel::insert_into(sync_table_baseline::table)
+        .values(&row)
+        .on_conflict(sync_table_baseline::table_name)
+        .do_update()
+        .set(&row)
+        .execute(conn)
+        .map_err(StorageError::from)?;
+    Ok(())
+}
+
+/// Remote event as replayed by `apply_remote_events_lww_batch`:
+/// (entity, entity_id, op, event_id, client_timestamp, seq, payload, origin),
+/// where `origin` is the emitting device and its per-device sequence, when the
+/// event carried one.
+pub type ReplayEvent = (
+    SyncEntity,
+    String,
+    SyncOperation,
+    String,
+    String,
+    i64,
+    serde_json::Value,
+    Option<(String, i64)>,
+);
+
+/// Per-entity conflict strategies; entities not listed use the default
+/// (last-writer-wins).
+type ConflictStrategies = HashMap<SyncEntity, ConflictStrategy>;
+
+/// Entities whose tables only reference each other, never the FK-coupled
+/// financial tables, so they can be replayed in a separate transaction.
+fn is_independent_replay_entity(entity: SyncEntity) -> bool {
+    matches!(
+        entity,
+        SyncEntity::AiThread | SyncEntity::AiMessage | SyncEntity::AiThreadTag
+    )
+}
+
+/// Apply a replay batch in the writer's transaction with FK checks deferred
+/// to commit, since events may arrive out of dependency order (e.g. an
+/// activity before its account).
+fn apply_replay_batch_tx(
+    conn: &mut SqliteConnection,
+    events: Vec<ReplayEvent>,
+    strategies: &ConflictStrategies,
+    rejected: &mut Option<RejectedReplayEvent>,
+) -> Result<usize> {
+    // Note: writer actor wraps jobs in a transaction, and SQLite ignores
+    // PRAGMA foreign_keys toggles inside active transactions.
+    // defer_foreign_keys applies to the current transaction and lets
+    // constraints validate at commit time.
+    diesel::sql_query("PRAGMA defer_foreign_keys = ON")
+        .execute(conn)
+        .map_err(StorageError::from)?;
+
+    // SQLite clears defer_foreign_keys at commit. It is deliberately not turned
+    // off here: doing so resets the pending violation count and skips the
+    // commit-time check.
+    let allowed_accounts = local_synced_account_ids(conn)?;
+    let mut applied = 0usize;
+    for (entity, entity_id, op, event_id, client_timestamp, seq, payload, origin) in events {
+        if apply_remote_event_lww_tx(
+            conn,
+            entity,
+            strategies.get(&entity).copied().unwrap_or_default(),
+            entity_id.clone(),
+            op,
+            event_id.clone(),
+            client_timestamp.clone(),
+            seq,
+            payload,
+            origin,
+            allowed_accounts.as_ref(),
+        )
+        .map_err(|err| {
+            let message = format!(
+                "Replay apply failed for entity={:?} entity_id={} op={:?} event_id={} seq={}: {}",
+                entity, entity_id, op, event_id, seq, err
+            );
+            *rejected = Some(RejectedReplayEvent {
+                entity,
+                entity_id: entity_id.clone(),
+                event_id: event_id.clone(),
+                reason: err.to_string(),
+            });
+            Error::Database(DatabaseError::Internal(message))
+        })? {
+            applied += 1;
+        }
+    }
+    Ok(applied)
+}
+
+/// Re-applies a batch whose commit failed the deferred foreign-key check and
+/// returns the rows it leaves with dangling references, so the caller can
+/// quarantine just those events. The re-run happens in a savepoint that is
+/// always rolled back.
+///
+/// Violations that already existed before the batch are not reported: the
+/// commit-time check only counts references the batch itself broke.
+fn replay_foreign_key_violations_tx(
+    conn: &mut SqliteConnection,
+    events: Vec<ReplayEvent>,
+    strategies: &ConflictStrategies,
+) -> Result<Vec<String>> {
+    let mut introduced = Ok(Vec::new());
+    let _ = conn.transaction::<(), StorageError, _>(|tx| {
+        introduced = (|| {
+            let existing = find_foreign_key_violations(tx)?
+                .into_iter()
+                .collect::<HashSet<_>>();
+            apply_replay_batch_tx(tx, events, strategies, &mut None)?;
+            Ok(find_foreign_key_violations(tx)?
+                .into_iter()
+                .filter(|violation| !existing.contains(violation))
+                .collect())
+        })();
+        Err(StorageError::from(
+            diesel::result::Error::RollbackTransaction,
+        ))
+    });
+    introduced
+}
+
+fn is_foreign_key_failure(err: &Error) -> bool {
+    match err {
+        Error::Database(DatabaseError::ForeignKeyViolation(_)) => true,
+        other => other.to_string().contains("FOREIGN KEY constraint failed"),
+    }
+}
+
+#[derive(diesel::QueryableByName)]
+struct ForeignKeyCheckRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    table: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    rowid: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    parent: String,
+}
+
+/// Rows of the synced tables that reference a missing parent, formatted as
+/// `entity:entity_id -> parent_table`. Scans every synced table, since a
+/// parent delete can strand children in tables the batch never touched.
+fn find_foreign_key_violations(conn: &mut SqliteConnection) -> Result<Vec<String>> {
+    let mut violations = Vec::new();
+    for entity in REPLAY_ENTITIES {
+        let Some((table, pk_name)) = entity_storage_mapping(&entity) else {
+            continue;
+        };
+        let rows = diesel::sql_query(format!(
+            "PRAGMA main.foreign_key_check('{}')",
+            escape_sqlite_str(table)
+        ))
+        .load::<ForeignKeyCheckRow>(conn)
+        .map_err(StorageError::from)?;
+        for row in rows.into_iter().filter(|row| row.table == table) {
+            let entity_id = match row.rowid {
+                Some(rowid) => diesel::sql_query(format!(
+                    "SELECT CAST({} AS TEXT) AS row_text FROM {} WHERE rowid = {}",
+                    quote_identifier(pk_name),
+                    quote_identifier(table),
+                    rowid
+                ))
+                .get_result::<RowTextResult>(conn)
+                .optional()
+                .map_err(StorageError::from)?
+                .map(|found| found.row_text),
+                None => None,
+            };
+            violations.push(format!(
+                "{}:{} -> {}",
+                enum_to_db(&entity)?,
+                entity_id.as_deref().unwrap_or("?"),
+                row.parent
+            ));
+        }
+    }
+    violations.dedup();
+    Ok(violations)
+}
+
+const REPLAY_NOT_ALLOWED_REASON: &str = "Entity is not in the replay allowlist";
+
+/// A remote event that failed to apply.
+struct RejectedReplayEvent {
+    entity: SyncEntity,
+    entity_id: String,
+    event_id: String,
+    reason: String,
+}
+
+/// Run `job` in a savepoint. If it fails on an event, the savepoint is rolled
+/// back and only a `RejectedValidation` audit row for that event is written,
+/// so the caller's transaction can still commit the audit trail. The job's
+/// own result is returned as the inner value.
+fn with_rejection_audit<T>(
+    conn: &mut SqliteConnection,
+    job: impl FnOnce(&mut SqliteConnection, &mut Option<RejectedReplayEvent>) -> Result<T>,
+) -> Result<Result<T>> {
+    let mut rejected = None;
+    let result = conn
+        .transaction::<_, StorageError, _>(|tx| job(tx, &mut rejected).map_err(StorageError::from))
+        .map_err(Error::from);
+    if result.is_err() {
+        if let Some(event) = rejected {
+            record_audit_tx(
+                conn,
+                event.entity,
+                &event.entity_id,
+                &event.event_id,
+                SyncAuditOutcome::RejectedValidation,
+                Some(event.reason),
+            )?;
+        }
+    }
+    Ok(result)
+}
+
+fn record_audit_tx(
+    conn: &mut SqliteConnection,
+    entity: SyncEntity,
+    entity_id_value: &str,
+    event_id_value: &str,
+    outcome: SyncAuditOutcome,
+    reason: Option<String>,
+) -> Result<()> {
+    diesel::insert_into(sync_event_audit::table)
+        .values(SyncEventAuditDB {
+            id: Uuid::now_v7().to_string(),
+            entity: enum_to_db(&entity)?,
+            entity_id: entity_id_value.to_string(),
+            event_id: event_id_value.to_string(),
+            outcome: enum_to_db(&outcome)?,
+            reason,
+            recorded_at: Utc::now().to_rfc3339(),
+        })
+        .execute(conn)
+        .map_err(StorageError::from)?;
+    Ok(())
+}
+
+/// First 16 bytes of every SQLite database file.
+const SQLITE_HEADER_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+/// Reject files that are not SQLite databases before they are attached, so a
+/// truncated download or an HTML error page fails with a useful message.
+fn ensure_sqlite_image(path: &str) -> Result<()> {
+    use std::io::Read;
+
+    let mut header = [0u8; 16];
+    let has_magic = std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok()
+        && &header == SQLITE_HEADER_MAGIC;
+    if has_magic {
+        return Ok(());
+    }
+    Err(Error::Database(DatabaseError::RestoreFailed(format!(
+        "Downloaded snapshot is not a valid SQLite database: {} does not start with the SQLite header magic (truncated or non-SQLite download?)",
+        path
+    ))))
+}
+
+/// The later of two RFC 3339 retry times. `current` is dropped once it has
+/// passed so an old backoff never outlives the cycle that replaced it.
+fn later_retry_at(current: Option<String>, requested: Option<String>) -> Option<String> {
+    let parse = |value: &str| chrono::DateTime::parse_from_rfc3339(value).ok();
+    let current = current.filter(|value| parse(value).is_some_and(|at| at > Utc::now()));
+    match (current, requested) {
+        (Some(current), Some(requested)) => match (parse(&current), parse(&requested)) {
+            (Some(current_at), Some(requested_at)) if current_at > requested_at => Some(current),
+            _ => Some(requested),
+        },
+        (current, requested) => requested.or(current),
+    }
+}
+
+/// Forget when each table was last restored or incrementally applied, while
+/// keeping its `enabled` flag.
+fn clear_table_state_timestamps_tx(conn: &mut SqliteConnection) -> Result<()> {
+    diesel::update(sync_table_state::table)
+        .set((
+            sync_table_state::last_snapshot_restore_at.eq(None::<String>),
+            sync_table_state::last_incremental_apply_at.eq(None::<String>),
+        ))
+        .execute(conn)
+        .map_err(StorageError::from)?;
+    Ok(())
+}
+
+fn set_cursor_tx(conn: &mut SqliteConnection, cursor_value: i64) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    let row = SyncCursorDB {
+        id: 1,
+        cursor: cursor_value,
+        updated_at: now.clone(),
+    };
+
+    diesel::insert_into(sync_cursor::table)
+        .values(&row)
+        .on_conflict(sync_cursor::id)
+        .do_update()
+        .set((
+            sync_cursor::cursor.eq(cursor_value),
+            sync_cursor::updated_at.eq(now),
+        ))
+        .execute(conn)
+        .map_err(StorageError::from)?;
+
+    Ok(())
+}
+
+/// Per-table WHERE filters applied during snapshot export.
+/// Tables not listed here are exported unfiltered.
+const SYNC_TABLE_EXPORT_FILTERS: &[(&str, &str)] = &[
+    (
+        "holdings_snapshots",
+        "source IN ('MANUAL_ENTRY', 'CSV_IMPORT', 'SYNTHETIC', 'BROKER_IMPORTED')",
+    ),
+    ("quotes", "source = 'MANUAL'"),
+];
+
+/// Synced tables whose rows belong to an account through `account_id`.
+const ACCOUNT_SCOPED_SYNC_TABLES: &[&str] = &[
+    "import_runs",
+    "activities",
+    "activity_import_profiles",
+    "goals_allocation",
+    "holdings_snapshots",
+];
+
+/// Options for snapshot export. The default exports every synced row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotExportOptions {
+    /// Leave out archived accounts and the rows that belong to them.
+    pub exclude_archived_accounts: bool,
+}
+
+/// File name prefix of the temporary SQLite image written by a snapshot
+/// export. The file is removed once read back; a crash mid-export leaves it
+/// behind.
+const SNAPSHOT_EXPORT_TEMP_PREFIX: &str = "wf_snapshot_export_";
+
+/// Whether `name` is a snapshot export temp file (or its SQLite journal).
+fn is_snapshot_export_temp_file(name: &str) -> bool {
+    name.strip_prefix(SNAPSHOT_EXPORT_TEMP_PREFIX)
+        .and_then(|rest| {
+            rest.strip_suffix(".db")
+                .or_else(|| rest.strip_suffix(".db-journal"))
+        })
+        .is_some_and(|id| Uuid::parse_str(id).is_ok())
+}
+
+/// Remove snapshot export temp files older than `max_age` from the system
+/// temp directory. Meant to run at startup to clear orphans left by a crash
+/// mid-export; returns how many files were removed.
+pub fn cleanup_orphaned_snapshot_temp_files(max_age: std::time::Duration) -> usize {
+    cleanup_orphaned_snapshot_temp_files_in(&std::env::temp_dir(), max_age)
+}
+
+fn cleanup_orphaned_snapshot_temp_files_in(
+    dir: &std::path::Path,
+    max_age: std::time::Duration,
+) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let now = std::time::SystemTime::now();
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        if !entry
+            .file_name()
+            .to_str()
+            .is_some_and(is_snapshot_export_temp_file)
+        {
+            continue;
+        }
+        // `DirEntry::metadata` does not follow symlinks, so only plain files
+        // we wrote ourselves are candidates.
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let is_stale = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age >= max_age);
+        if !metadata.is_file() || !is_stale {
+            continue;
+        }
+        match std::fs::remove_file(entry.path()) {
+            Ok(()) => removed += 1,
+            Err(err) => log::warn!(
+                "Failed to remove orphaned snapshot temp file {}: {}",
+                entry.path().display(),
+                err
+            ),
+        }
+    }
+    if removed > 0 {
+        log::info!("Removed {} orphaned snapshot temp file(s)", removed);
+    }
+    removed
+}
+
+/// WHERE clause for exporting `table`, or `None` to export it unfiltered.
+fn snapshot_export_filter(table: &str, options: SnapshotExportOptions) -> Option<String> {
+    let mut clauses = SYNC_TABLE_EXPORT_FILTERS
+        .iter()
+        .filter(|(t, _)| *t == table)
+        .map(|(_, f)| format!("({f})"))
+        .collect::<Vec<_>>();
+    if options.exclude_archived_accounts {
+        if table == "accounts" {
+            clauses.push("is_archived = 0".to_string());
+        } else if ACCOUNT_SCOPED_SYNC_TABLES.contains(&table) {
+            clauses.push(
+                "account_id NOT IN (SELECT id FROM main.accounts WHERE is_archived = 1)"
+                    .to_string(),
+            );
+        }
+    }
+    (!clauses.is_empty()).then(|| clauses.join(" AND "))
+}
+
+fn sql_string_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("'{}'", escape_sqlite_str(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn main_database_file_size(conn: &mut SqliteConnection) -> Option<u64> {
+    let file = diesel::sql_query("PRAGMA database_list")
+        .load::<PragmaDatabaseListRow>(conn)
+        .ok()?
+        .into_iter()
+        .find(|row| row.name == "main")?
+        .file;
+    std::fs::metadata(file).ok().map(|metadata| metadata.len())
+}
+
+/// Rebuild the database file to drop the free pages left behind by a
+/// restore. `VACUUM` cannot run inside a transaction, so this uses its own
+/// pooled connection rather than the writer.
+fn vacuum_database(conn: &mut SqliteConnection) -> Result<()> {
+    let size_before = main_database_file_size(conn);
+    // In WAL mode the rebuilt pages land in the WAL until a checkpoint.
+    conn.batch_execute("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")
+        .map_err(StorageError::from)?;
+    log::debug!(
+        "[AppSync] Vacuumed database after snapshot restore: {:?} -> {:?} bytes",
+        size_before,
+        main_database_file_size(conn)
+    );
+    Ok(())
+}
+
 fn load_table_columns(
     conn: &mut SqliteConnection,
     db_name: &str,
@@ -159,20 +855,36 @@ fn load_payload_column_catalog(
         Ok(rows) => {
             let mut writable = HashSet::new();
             let mut readonly = HashSet::new();
+            let mut affinities = HashMap::new();
+            let mut blob_columns = HashSet::new();
             for row in rows {
                 if row.hidden == 0 {
+                    if row.declared_type.to_ascii_uppercase().contains("BLOB") {
+                        blob_columns.insert(row.name.clone());
+                    }
+                    affinities.insert(
+                        row.name.clone(),
+                        ColumnAffinity::from_declared_type(&row.declared_type),
+                    );
                     writable.insert(row.name);
                 } else {
                     readonly.insert(row.name);
                 }
             }
-            PayloadColumnCatalog { writable, readonly }
+            PayloadColumnCatalog {
+                writable,
+                readonly,
+                affinities,
+                blob_columns,
+            }
         }
         Err(_) => PayloadColumnCatalog {
             writable: load_table_columns(conn, "main", table_name)?
                 .into_iter()
                 .collect::<HashSet<_>>(),
             readonly: HashSet::new(),
+            affinities: HashMap::new(),
+            blob_columns: HashSet::new(),
         },
     };
 
@@ -298,6 +1010,110 @@ fn normalize_payload_fields(
     Ok(normalized_fields)
 }
 
+/// Whether `value` can be stored in a column of `affinity` without SQLite
+/// silently keeping it as text. Only INTEGER and REAL columns are checked:
+/// NUMERIC columns also hold dates and decimals stored as strings.
+fn payload_value_fits_affinity(value: &serde_json::Value, affinity: ColumnAffinity) -> bool {
+    match affinity {
+        ColumnAffinity::Integer | ColumnAffinity::Real => match value {
+            serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {
+                true
+            }
+            serde_json::Value::String(v) => v.trim().parse::<f64>().is_ok(),
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => false,
+        },
+        ColumnAffinity::Text | ColumnAffinity::Blob | ColumnAffinity::Numeric => true,
+    }
+}
+
+fn json_value_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+/// Reject payload values that obviously do not match their column's type
+/// affinity. Expects fields already resolved by `normalize_payload_fields`.
+fn validate_payload_types(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    fields: &[(String, serde_json::Value)],
+) -> Result<()> {
+    let catalog = load_payload_column_catalog(conn, table_name)?;
+    for (column, value) in fields {
+        let Some(affinity) = catalog.affinities.get(column) else {
+            continue;
+        };
+        if !payload_value_fits_affinity(value, *affinity) {
+            return Err(Error::Database(DatabaseError::Internal(format!(
+                "Sync payload column '{}' for table '{}' expects {} affinity, got {} {}",
+                column,
+                table_name,
+                affinity.as_str(),
+                json_value_kind(value),
+                value
+            ))));
+        }
+    }
+    Ok(())
+}
+
+/// Entities whose payloads may carry binary data for `BLOB` columns. Their
+/// blob values travel as base64 strings and are decoded on apply; every other
+/// entity keeps plain JSON semantics for all columns.
+fn entity_allows_blob_columns(entity: &SyncEntity) -> bool {
+    matches!(entity, SyncEntity::Snapshot | SyncEntity::AiMessage)
+}
+
+fn payload_blob_columns(
+    conn: &mut SqliteConnection,
+    entity: &SyncEntity,
+    table_name: &str,
+) -> Result<HashSet<String>> {
+    if !entity_allows_blob_columns(entity) {
+        return Ok(HashSet::new());
+    }
+    Ok(load_payload_column_catalog(conn, table_name)?.blob_columns)
+}
+
+/// Decodes a base64 payload value into a SQLite blob literal (`X'..'`).
+fn blob_value_to_sql_literal(
+    table_name: &str,
+    column: &str,
+    value: &serde_json::Value,
+) -> Result<String> {
+    let encoded = match value {
+        serde_json::Value::Null => return Ok("NULL".to_string()),
+        serde_json::Value::String(encoded) => encoded,
+        other => {
+            return Err(Error::Database(DatabaseError::Internal(format!(
+                "Sync payload column '{}' for table '{}' expects base64 blob data, got {}",
+                column,
+                table_name,
+                json_value_kind(other)
+            ))))
+        }
+    };
+    let bytes = BASE64.decode(encoded).map_err(|err| {
+        Error::Database(DatabaseError::Internal(format!(
+            "Sync payload column '{}' for table '{}' has invalid base64 blob data: {}",
+            column, table_name, err
+        )))
+    })?;
+    let mut literal = String::with_capacity(bytes.len() * 2 + 3);
+    literal.push_str("X'");
+    for byte in bytes {
+        literal.push_str(&format!("{:02X}", byte));
+    }
+    literal.push('\'');
+    Ok(literal)
+}
+
 fn normalize_outbox_payload(payload: serde_json::Value) -> Result<serde_json::Value> {
     let serde_json::Value::Object(fields) = payload else {
         return Ok(payload);
@@ -328,6 +1144,24 @@ fn normalize_outbox_payload(payload: serde_json::Value) -> Result<serde_json::Va
     Ok(serde_json::Value::Object(normalized))
 }
 
+/// Every entity replay can write to a local table.
+const REPLAY_ENTITIES: [SyncEntity; 14] = [
+    SyncEntity::Platform,
+    SyncEntity::Account,
+    SyncEntity::Asset,
+    SyncEntity::Quote,
+    SyncEntity::AssetTaxonomyAssignment,
+    SyncEntity::Activity,
+    SyncEntity::ActivityImportProfile,
+    SyncEntity::Goal,
+    SyncEntity::GoalsAllocation,
+    SyncEntity::AiThread,
+    SyncEntity::AiMessage,
+    SyncEntity::AiThreadTag,
+    SyncEntity::ContributionLimit,
+    SyncEntity::Snapshot,
+];
+
 fn entity_storage_mapping(entity: &SyncEntity) -> Option<(&'static str, &'static str)> {
     match entity {
         SyncEntity::Account => Some(("accounts", "id")),
@@ -347,6 +1181,35 @@ fn entity_storage_mapping(entity: &SyncEntity) -> Option<(&'static str, &'static
     }
 }
 
+/// Render a JSON number so SQLite stores the value the payload carried.
+///
+/// Integers (including integral floats such as `100.0`) become integer
+/// literals. Anything SQLite would round as a REAL literal — fractions and
+/// integers beyond `i64` — is written as a quoted plain decimal string: a
+/// TEXT column (where amounts live) keeps it exactly, and REAL or NUMERIC
+/// columns still convert it to a number.
+fn json_number_to_sql_literal(number: &serde_json::Number) -> String {
+    if let Some(v) = number.as_i64() {
+        return v.to_string();
+    }
+    if let Some(v) = number.as_u64() {
+        return format!("'{}'", v);
+    }
+    let Some(v) = number.as_f64() else {
+        return number.to_string();
+    };
+    if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 {
+        return (v as i64).to_string();
+    }
+    // serde_json prints the shortest string that round-trips the f64;
+    // rust_decimal drops any exponent notation from it.
+    let shortest = number.to_string();
+    rust_decimal::Decimal::from_str_exact(&shortest)
+        .or_else(|_| rust_decimal::Decimal::from_scientific(&shortest))
+        .map(|decimal| format!("'{}'", decimal.normalize()))
+        .unwrap_or(shortest)
+}
+
 fn json_value_to_sql_literal(value: &serde_json::Value) -> String {
     match value {
         serde_json::Value::Null => "NULL".to_string(),
@@ -357,7 +1220,7 @@ fn json_value_to_sql_literal(value: &serde_json::Value) -> String {
                 "0".to_string()
             }
         }
-        serde_json::Value::Number(v) => v.to_string(),
+        serde_json::Value::Number(v) => json_number_to_sql_literal(v),
         serde_json::Value::String(v) => format!("'{}'", escape_sqlite_str(v)),
         serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
             format!(
@@ -399,6 +1262,17 @@ impl OutboxWriteRequest {
     }
 }
 
+/// Ordering of pending updates for the same entity, newest last. The device
+/// sequence breaks timestamp ties the same way last-writer-wins does.
+fn outbox_update_order(row: &SyncOutboxEventDB) -> (&str, Option<i64>, &str, &str) {
+    (
+        &row.client_timestamp,
+        row.device_seq,
+        &row.created_at,
+        &row.event_id,
+    )
+}
+
 fn resolve_payload_key_version(conn: &mut SqliteConnection, requested_version: i32) -> Result<i32> {
     if requested_version > 0 {
         return Ok(requested_version);
@@ -428,10 +1302,74 @@ fn resolve_local_device_id(conn: &mut SqliteConnection) -> Option<String> {
         .unwrap_or(None)
 }
 
+/// Account allow-list of the local device, or `None` when it syncs every
+/// account.
+fn local_synced_account_ids(conn: &mut SqliteConnection) -> Result<Option<HashSet<String>>> {
+    let Some(raw) = local_synced_account_ids_raw(conn)? else {
+        return Ok(None);
+    };
+    let account_ids: Vec<String> = serde_json::from_str(&raw)?;
+    Ok((!account_ids.is_empty()).then(|| account_ids.into_iter().collect()))
+}
+
+/// Stored `synced_account_ids` JSON of the local device config row.
+fn local_synced_account_ids_raw(conn: &mut SqliteConnection) -> Result<Option<String>> {
+    Ok(sync_device_config::table
+        .filter(sync_device_config::trust_state.eq("trusted"))
+        .order(sync_device_config::last_bootstrap_at.desc())
+        .select(sync_device_config::synced_account_ids)
+        .first::<Option<String>>(conn)
+        .optional()
+        .map_err(StorageError::from)?
+        .flatten())
+}
+
+/// Account an account-scoped entity belongs to, when the event tells us.
+fn entity_account_id(
+    entity: &SyncEntity,
+    entity_id: &str,
+    payload: &serde_json::Value,
+) -> Option<String> {
+    match entity {
+        SyncEntity::Account => Some(entity_id.to_string()),
+        SyncEntity::Activity | SyncEntity::GoalsAllocation => payload
+            .get("account_id")
+            .or_else(|| payload.get("accountId"))
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Account of `entity` that the allow-list filters out, or `None` when the
+/// entity syncs. Entities that are not account-scoped, or whose account is
+/// unknown, always sync.
+fn filtered_entity_account(
+    allowed: Option<&HashSet<String>>,
+    entity: &SyncEntity,
+    entity_id: &str,
+    payload: &serde_json::Value,
+) -> Option<String> {
+    let allowed = allowed?;
+    entity_account_id(entity, entity_id, payload).filter(|account_id| !allowed.contains(account_id))
+}
+
+/// Allocate the next per-device emission sequence.
+fn next_device_seq(conn: &mut SqliteConnection) -> Result<i64> {
+    diesel::update(sync_device_seq::table.find(1))
+        .set(sync_device_seq::last_seq.eq(sync_device_seq::last_seq + 1))
+        .returning(sync_device_seq::last_seq)
+        .get_result::<i64>(conn)
+        .map_err(|err| StorageError::from(err).into())
+}
+
+/// Write a pending outbox row for a local mutation. Returns the event id, or
+/// `None` when sync is disabled for the entity's table or the entity belongs
+/// to an account this device does not sync.
 pub fn insert_outbox_event(
     conn: &mut SqliteConnection,
     request: OutboxWriteRequest,
-) -> Result<String> {
+) -> Result<Option<String>> {
     let OutboxWriteRequest {
         event_id,
         entity,
@@ -442,8 +1380,20 @@ pub fn insert_outbox_event(
         payload_key_version,
     } = request;
 
+    if let Some((table_name, _)) = entity_storage_mapping(&entity) {
+        if !is_table_sync_enabled(conn, table_name)? {
+            return Ok(None);
+        }
+    }
+
+    let payload = normalize_outbox_payload(payload)?;
+    let allowed_accounts = local_synced_account_ids(conn)?;
+    if filtered_entity_account(allowed_accounts.as_ref(), &entity, &entity_id, &payload).is_some() {
+        return Ok(None);
+    }
+
     let event_id = event_id.unwrap_or_else(|| Uuid::now_v7().to_string());
-    let payload = serde_json::to_string(&normalize_outbox_payload(payload)?)?;
+    let payload = serde_json::to_string(&payload)?;
     let now = Utc::now().to_rfc3339();
 
     let payload_key_version = resolve_payload_key_version(conn, payload_key_version)?;
@@ -464,6 +1414,7 @@ pub fn insert_outbox_event(
         last_error_code: None,
         device_id,
         created_at: now,
+        device_seq: Some(next_device_seq(conn)?),
     };
 
     diesel::insert_into(sync_outbox::table)
@@ -471,7 +1422,7 @@ pub fn insert_outbox_event(
         .execute(conn)
         .map_err(StorageError::from)?;
 
-    Ok(event_id)
+    Ok(Some(event_id))
 }
 
 fn to_outbox_event(row: SyncOutboxEventDB) -> Result<SyncOutboxEvent> {
@@ -490,6 +1441,18 @@ fn to_outbox_event(row: SyncOutboxEventDB) -> Result<SyncOutboxEvent> {
         last_error: row.last_error,
         last_error_code: row.last_error_code,
         created_at: row.created_at,
+        device_seq: row.device_seq,
+    })
+}
+
+fn to_event_audit(row: SyncEventAuditDB) -> Result<SyncEventAudit> {
+    Ok(SyncEventAudit {
+        entity: enum_from_db(&row.entity)?,
+        entity_id: row.entity_id,
+        event_id: row.event_id,
+        outcome: enum_from_db(&row.outcome)?,
+        reason: row.reason,
+        recorded_at: row.recorded_at,
     })
 }
 
@@ -500,6 +1463,8 @@ fn to_entity_metadata(row: SyncEntityMetadataDB) -> Result<SyncEntityMetadata> {
         last_event_id: row.last_event_id,
         last_client_timestamp: row.last_client_timestamp,
         last_seq: row.last_seq,
+        last_device_id: row.last_device_id,
+        last_device_seq: row.last_device_seq,
     })
 }
 
@@ -507,12 +1472,15 @@ fn to_entity_metadata(row: SyncEntityMetadataDB) -> Result<SyncEntityMetadata> {
 fn apply_remote_event_lww_tx(
     conn: &mut SqliteConnection,
     entity: SyncEntity,
+    strategy: ConflictStrategy,
     entity_id_value: String,
     op: SyncOperation,
     event_id_value: String,
     client_timestamp_value: String,
     seq_value: i64,
     payload_json: serde_json::Value,
+    origin: Option<(String, i64)>,
+    allowed_accounts: Option<&HashSet<String>>,
 ) -> Result<bool> {
     let already_applied = sync_applied_events::table
         .find(&event_id_value)
@@ -525,6 +1493,43 @@ fn apply_remote_event_lww_tx(
     }
 
     let entity_db = enum_to_db(&entity)?;
+    let table_enabled = match entity_storage_mapping(&entity) {
+        Some((table_name, _)) => is_table_sync_enabled(conn, table_name)?,
+        None => true,
+    };
+    if let Some(account_id) =
+        filtered_entity_account(allowed_accounts, &entity, &entity_id_value, &payload_json)
+    {
+        // Not marked applied: widening the filter pulls it again.
+        diesel::insert_into(sync_filtered_events::table)
+            .values((
+                sync_filtered_events::event_id.eq(event_id_value),
+                sync_filtered_events::account_id.eq(account_id),
+                sync_filtered_events::seq.eq(seq_value),
+            ))
+            .on_conflict(sync_filtered_events::event_id)
+            .do_nothing()
+            .execute(conn)
+            .map_err(StorageError::from)?;
+        return Ok(false);
+    }
+    if !table_enabled {
+        // Record the event so it is not replayed, but leave local data alone.
+        diesel::insert_into(sync_applied_events::table)
+            .values(SyncAppliedEventDB {
+                event_id: event_id_value,
+                seq: seq_value,
+                entity: entity_db,
+                entity_id: entity_id_value,
+                applied_at: Utc::now().to_rfc3339(),
+            })
+            .on_conflict(sync_applied_events::event_id)
+            .do_nothing()
+            .execute(conn)
+            .map_err(StorageError::from)?;
+        return Ok(false);
+    }
+
     let metadata_row = sync_entity_metadata::table
         .filter(sync_entity_metadata::entity.eq(&entity_db))
         .filter(sync_entity_metadata::entity_id.eq(&entity_id_value))
@@ -532,15 +1537,19 @@ fn apply_remote_event_lww_tx(
         .optional()
         .map_err(StorageError::from)?;
 
-    let should_apply = match metadata_row.as_ref() {
-        Some(meta) => should_apply_lww(
-            &meta.last_client_timestamp,
-            &meta.last_event_id,
-            &client_timestamp_value,
-            &event_id_value,
-        ),
-        None => true,
-    };
+    let local_version = metadata_row.as_ref().map(|meta| {
+        let version = LwwVersion::new(&meta.last_client_timestamp, &meta.last_event_id);
+        match (meta.last_device_id.as_deref(), meta.last_device_seq) {
+            (Some(device_id), Some(device_seq)) => version.with_device_seq(device_id, device_seq),
+            _ => version,
+        }
+    });
+    let mut remote_version = LwwVersion::new(&client_timestamp_value, &event_id_value);
+    if let Some((device_id, device_seq)) = origin.as_ref() {
+        remote_version = remote_version.with_device_seq(device_id, *device_seq);
+    }
+    let should_apply = strategy.should_apply(local_version.as_ref(), &remote_version);
+    let (origin_device_id, origin_device_seq) = origin.clone().unzip();
 
     if should_apply {
         if let Some((table_name, pk_name)) = entity_storage_mapping(&entity) {
@@ -568,6 +1577,7 @@ fn apply_remote_event_lww_tx(
                         .map(|(k, v)| (k.clone(), v.clone()))
                         .collect();
                     let mut fields = normalize_payload_fields(conn, table_name, fields)?;
+                    validate_payload_types(conn, table_name, &fields)?;
                     if let Some((_, payload_pk)) = fields.iter().find(|(k, _)| k == pk_name) {
                         if !payload_value_matches_entity_id(payload_pk, &entity_id_value) {
                             return Err(Error::Database(DatabaseError::Internal(format!(
@@ -587,10 +1597,17 @@ fn apply_remote_event_lww_tx(
                         .map(|(k, _)| quote_identifier(k))
                         .collect::<Vec<_>>()
                         .join(", ");
+                    let blob_columns = payload_blob_columns(conn, &entity, table_name)?;
                     let values = fields
                         .iter()
-                        .map(|(_, v)| json_value_to_sql_literal(v))
-                        .collect::<Vec<_>>()
+                        .map(|(k, v)| {
+                            if blob_columns.contains(k) {
+                                blob_value_to_sql_literal(table_name, k, v)
+                            } else {
+                                Ok(json_value_to_sql_literal(v))
+                            }
+                        })
+                        .collect::<Result<Vec<_>>>()?
                         .join(", ");
                     let upserts = fields
                         .iter()
@@ -623,10 +1640,7 @@ fn apply_remote_event_lww_tx(
                 })
                 .on_conflict(sync_table_state::table_name)
                 .do_update()
-                .set((
-                    sync_table_state::enabled.eq(1),
-                    sync_table_state::last_incremental_apply_at.eq(Some(now)),
-                ))
+                .set(sync_table_state::last_incremental_apply_at.eq(Some(now)))
                 .execute(conn)
                 .map_err(StorageError::from)?;
         }
@@ -638,6 +1652,8 @@ fn apply_remote_event_lww_tx(
                 last_event_id: event_id_value.clone(),
                 last_client_timestamp: client_timestamp_value.clone(),
                 last_seq: seq_value,
+                last_device_id: origin_device_id.clone(),
+                last_device_seq: origin_device_seq,
             })
             .on_conflict((
                 sync_entity_metadata::entity,
@@ -648,11 +1664,42 @@ fn apply_remote_event_lww_tx(
                 sync_entity_metadata::last_event_id.eq(event_id_value.clone()),
                 sync_entity_metadata::last_client_timestamp.eq(client_timestamp_value.clone()),
                 sync_entity_metadata::last_seq.eq(seq_value),
+                sync_entity_metadata::last_device_id.eq(origin_device_id),
+                sync_entity_metadata::last_device_seq.eq(origin_device_seq),
             ))
             .execute(conn)
             .map_err(StorageError::from)?;
     }
 
+    let skip_reason = (!should_apply).then(|| match metadata_row.as_ref() {
+        Some(meta) => format!(
+            "Local version {} ({}) kept under {:?}",
+            meta.last_client_timestamp, meta.last_event_id, strategy
+        ),
+        None => format!("Remote event dropped under {:?}", strategy),
+    });
+    record_audit_tx(
+        conn,
+        entity,
+        &entity_id_value,
+        &event_id_value,
+        if should_apply {
+            SyncAuditOutcome::Applied
+        } else {
+            SyncAuditOutcome::SkippedLww
+        },
+        skip_reason,
+    )?;
+
+    record_conflict_if_pending(
+        conn,
+        &entity_db,
+        &entity_id_value,
+        &event_id_value,
+        &client_timestamp_value,
+        &payload_json,
+    )?;
+
     diesel::insert_into(sync_applied_events::table)
         .values(SyncAppliedEventDB {
             event_id: event_id_value,
@@ -669,9 +1716,86 @@ fn apply_remote_event_lww_tx(
     Ok(should_apply)
 }
 
+/// Keep the remote payload when the entity still has an unsent local edit, so
+/// the two versions can be diffed later.
+fn record_conflict_if_pending(
+    conn: &mut SqliteConnection,
+    entity_db: &str,
+    entity_id_value: &str,
+    event_id_value: &str,
+    client_timestamp_value: &str,
+    payload_json: &serde_json::Value,
+) -> Result<()> {
+    let has_pending_edit = sync_outbox::table
+        .filter(sync_outbox::entity.eq(entity_db))
+        .filter(sync_outbox::entity_id.eq(entity_id_value))
+        .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Pending)?))
+        .filter(sync_outbox::sent.eq(0))
+        .select(sync_outbox::event_id)
+        .first::<String>(conn)
+        .optional()
+        .map_err(StorageError::from)?
+        .is_some();
+    if !has_pending_edit {
+        return Ok(());
+    }
+
+    let remote_payload = serde_json::to_string(&normalize_outbox_payload(payload_json.clone())?)?;
+    let row = SyncConflictDB {
+        entity: entity_db.to_string(),
+        entity_id: entity_id_value.to_string(),
+        remote_event_id: event_id_value.to_string(),
+        remote_client_timestamp: client_timestamp_value.to_string(),
+        remote_payload,
+        detected_at: Utc::now().to_rfc3339(),
+    };
+    diesel::insert_into(sync_conflicts::table)
+        .values(&row)
+        .on_conflict((sync_conflicts::entity, sync_conflicts::entity_id))
+        .do_update()
+        .set(&row)
+        .execute(conn)
+        .map_err(StorageError::from)?;
+    Ok(())
+}
+
+/// Fields present on either side whose values differ, ordered by field name.
+fn diff_payload_fields(local: &serde_json::Value, remote: &serde_json::Value) -> Vec<FieldDiff> {
+    let empty = serde_json::Map::new();
+    let local_fields = local.as_object().unwrap_or(&empty);
+    let remote_fields = remote.as_object().unwrap_or(&empty);
+    let fields: BTreeSet<&String> = local_fields.keys().chain(remote_fields.keys()).collect();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let local_value = local_fields.get(field);
+            let remote_value = remote_fields.get(field);
+            (local_value != remote_value).then(|| FieldDiff {
+                field: field.clone(),
+                local: local_value.cloned(),
+                remote: remote_value.cloned(),
+            })
+        })
+        .collect()
+}
+
 pub struct AppSyncRepository {
     pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
     writer: WriteHandle,
+    /// Replay FK-independent entities in a separate transaction from the
+    /// FK-coupled group. Both run on the single writer, one after the other.
+    /// Off by default.
+    split_replay_groups: bool,
+    /// Tables remote events may write to. Events for other entities are
+    /// rejected, so a misbehaving server cannot touch unexpected tables.
+    replay_allowlist: Arc<HashSet<String>>,
+    /// Conflict strategy overrides per entity.
+    conflict_strategies: Arc<ConflictStrategies>,
+    /// Run `VACUUM` after a successful snapshot restore.
+    vacuum_after_restore: bool,
+    /// Newest snapshot schema version the local tables can restore.
+    local_schema_version: i32,
 }
 
 impl AppSyncRepository {
@@ -679,7 +1803,113 @@ impl AppSyncRepository {
         pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
         writer: WriteHandle,
     ) -> Self {
-        Self { pool, writer }
+        Self {
+            pool,
+            writer,
+            split_replay_groups: false,
+            replay_allowlist: Arc::new(APP_SYNC_TABLES.iter().map(|t| t.to_string()).collect()),
+            conflict_strategies: Arc::new(HashMap::new()),
+            vacuum_after_restore: true,
+            local_schema_version: SNAPSHOT_SCHEMA_VERSION,
+        }
+    }
+
+    /// Restrict the tables remote events may be applied to. Defaults to all
+    /// of `APP_SYNC_TABLES`.
+    pub fn with_replay_allowlist(mut self, tables: &[&str]) -> Self {
+        self.replay_allowlist = Arc::new(tables.iter().map(|t| t.to_string()).collect());
+        self
+    }
+
+    /// Resolve conflicts for `entity` with `strategy` instead of
+    /// last-writer-wins.
+    pub fn with_conflict_strategy(
+        mut self,
+        entity: SyncEntity,
+        strategy: ConflictStrategy,
+    ) -> Self {
+        Arc::make_mut(&mut self.conflict_strategies).insert(entity, strategy);
+        self
+    }
+
+    /// Conflict strategy applied to remote events for `entity`.
+    pub fn conflict_strategy(&self, entity: SyncEntity) -> ConflictStrategy {
+        self.conflict_strategies
+            .get(&entity)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether remote events for `entity` may be applied locally.
+    fn is_replay_allowed(&self, entity: &SyncEntity) -> bool {
+        entity_storage_mapping(entity)
+            .is_some_and(|(table_name, _)| self.replay_allowlist.contains(table_name))
+    }
+
+    fn reject_replay_event(entity: &SyncEntity, entity_id: &str, event_id: &str) {
+        log::warn!(
+            "[Replay] Rejected remote event for non-allowlisted entity={:?} entity_id={} event_id={}",
+            entity,
+            entity_id,
+            event_id
+        );
+    }
+
+    /// Drop (and log and audit) events for tables outside the replay allowlist.
+    async fn filter_replay_allowed(&self, events: Vec<ReplayEvent>) -> Result<Vec<ReplayEvent>> {
+        let (allowed, rejected): (Vec<_>, Vec<_>) = events
+            .into_iter()
+            .partition(|event| self.is_replay_allowed(&event.0));
+        if rejected.is_empty() {
+            return Ok(allowed);
+        }
+
+        let rejected = rejected
+            .into_iter()
+            .map(|(entity, entity_id, _, event_id, ..)| {
+                Self::reject_replay_event(&entity, &entity_id, &event_id);
+                (entity, entity_id, event_id)
+            })
+            .collect::<Vec<_>>();
+        self.writer
+            .exec(move |conn| {
+                for (entity, entity_id, event_id) in rejected {
+                    record_audit_tx(
+                        conn,
+                        entity,
+                        &entity_id,
+                        &event_id,
+                        SyncAuditOutcome::RejectedValidation,
+                        Some(REPLAY_NOT_ALLOWED_REASON.to_string()),
+                    )?;
+                }
+                Ok(())
+            })
+            .await?;
+        Ok(allowed)
+    }
+
+    /// Enable or disable splitting replay batches into an FK-coupled group and
+    /// an independent group (AI threads, messages and tags) that commit in
+    /// separate transactions. Disabled by default, which keeps each batch in
+    /// a single transaction.
+    pub fn with_split_replay_groups(mut self, enabled: bool) -> Self {
+        self.split_replay_groups = enabled;
+        self
+    }
+
+    /// Enable or disable compacting the database file after a snapshot
+    /// restore. Enabled by default.
+    pub fn with_vacuum_after_restore(mut self, enabled: bool) -> Self {
+        self.vacuum_after_restore = enabled;
+        self
+    }
+
+    /// Override the local schema version snapshots are checked against.
+    /// Defaults to `SNAPSHOT_SCHEMA_VERSION`.
+    pub fn with_local_schema_version(mut self, version: i32) -> Self {
+        self.local_schema_version = version;
+        self
     }
 
     pub fn get_cursor(&self) -> Result<i64> {
@@ -694,27 +1924,7 @@ impl AppSyncRepository {
 
     pub async fn set_cursor(&self, cursor_value: i64) -> Result<()> {
         self.writer
-            .exec(move |conn| {
-                let now = Utc::now().to_rfc3339();
-                let row = SyncCursorDB {
-                    id: 1,
-                    cursor: cursor_value,
-                    updated_at: now.clone(),
-                };
-
-                diesel::insert_into(sync_cursor::table)
-                    .values(&row)
-                    .on_conflict(sync_cursor::id)
-                    .do_update()
-                    .set((
-                        sync_cursor::cursor.eq(cursor_value),
-                        sync_cursor::updated_at.eq(now),
-                    ))
-                    .execute(conn)
-                    .map_err(StorageError::from)?;
-
-                Ok(())
-            })
+            .exec(move |conn| set_cursor_tx(conn, cursor_value))
             .await
     }
 
@@ -735,7 +1945,10 @@ impl AppSyncRepository {
             consecutive_failures: engine.as_ref().map(|s| s.consecutive_failures).unwrap_or(0),
             next_retry_at: engine.as_ref().and_then(|s| s.next_retry_at.clone()),
             last_cycle_status: engine.as_ref().and_then(|s| s.last_cycle_status.clone()),
-            last_cycle_duration_ms: engine.and_then(|s| s.last_cycle_duration_ms),
+            last_cycle_duration_ms: engine.as_ref().and_then(|s| s.last_cycle_duration_ms),
+            last_cycle_pushed_bytes: engine.as_ref().and_then(|s| s.last_cycle_pushed_bytes),
+            last_cycle_pulled_bytes: engine.as_ref().and_then(|s| s.last_cycle_pulled_bytes),
+            last_cycle_events_applied: engine.and_then(|s| s.last_cycle_events_applied),
         })
     }
 
@@ -802,6 +2015,7 @@ impl AppSyncRepository {
                     trust_state: trust_state_value.clone(),
                     last_bootstrap_at: None,
                     min_snapshot_created_at: None,
+                    synced_account_ids: None,
                 };
 
                 diesel::insert_into(sync_device_config::table)
@@ -834,9 +2048,21 @@ impl AppSyncRepository {
                 diesel::delete(sync_applied_events::table)
                     .execute(conn)
                     .map_err(StorageError::from)?;
+                diesel::delete(sync_event_audit::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                diesel::delete(sync_filtered_events::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
                 diesel::delete(sync_table_state::table)
                     .execute(conn)
                     .map_err(StorageError::from)?;
+                diesel::delete(sync_table_baseline::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                diesel::delete(sync_conflicts::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
                 diesel::delete(sync_device_config::table)
                     .execute(conn)
                     .map_err(StorageError::from)?;
@@ -867,6 +2093,9 @@ impl AppSyncRepository {
                         next_retry_at: None,
                         last_cycle_status: None,
                         last_cycle_duration_ms: None,
+                        last_cycle_pushed_bytes: None,
+                        last_cycle_pulled_bytes: None,
+                        last_cycle_events_applied: None,
                     })
                     .on_conflict(sync_engine_state::id)
                     .do_update()
@@ -879,6 +2108,57 @@ impl AppSyncRepository {
                         sync_engine_state::next_retry_at.eq::<Option<String>>(None),
                         sync_engine_state::last_cycle_status.eq::<Option<String>>(None),
                         sync_engine_state::last_cycle_duration_ms.eq::<Option<i64>>(None),
+                        sync_engine_state::last_cycle_pushed_bytes.eq::<Option<i64>>(None),
+                        sync_engine_state::last_cycle_pulled_bytes.eq::<Option<i64>>(None),
+                        sync_engine_state::last_cycle_events_applied.eq::<Option<i64>>(None),
+                    ))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+
+                Ok(())
+            })
+            .await
+    }
+
+    /// Discard local replication state so the next bootstrap restores from a
+    /// fresh snapshot. Unlike `reset_local_sync_session` the device stays
+    /// paired: device config is kept with `last_bootstrap_at` cleared, and
+    /// tables the user disabled stay disabled. App data tables are never
+    /// touched.
+    pub async fn reset_sync_state(&self) -> Result<()> {
+        self.writer
+            .exec(move |conn| {
+                diesel::delete(sync_outbox::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                diesel::delete(sync_entity_metadata::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                diesel::delete(sync_applied_events::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                diesel::delete(sync_event_audit::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                diesel::delete(sync_filtered_events::table)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                clear_table_state_timestamps_tx(conn)?;
+
+                diesel::update(sync_device_config::table)
+                    .set(sync_device_config::last_bootstrap_at.eq::<Option<String>>(None))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+
+                set_cursor_tx(conn, 0)?;
+
+                // Let the bootstrap run right away instead of waiting out a backoff.
+                diesel::update(sync_engine_state::table)
+                    .set((
+                        sync_engine_state::last_error.eq::<Option<String>>(None),
+                        sync_engine_state::consecutive_failures.eq(0),
+                        sync_engine_state::next_retry_at.eq::<Option<String>>(None),
+                        sync_engine_state::last_cycle_status.eq::<Option<String>>(None),
                     ))
                     .execute(conn)
                     .map_err(StorageError::from)?;
@@ -893,6 +2173,12 @@ impl AppSyncRepository {
         device_id_value: String,
         key_version_value: Option<i32>,
     ) -> Result<()> {
+        // The reset drops device config; keep the account allow-list the user
+        // chose across re-bootstraps.
+        let synced_account_ids = {
+            let mut conn = get_connection(&self.pool)?;
+            local_synced_account_ids_raw(&mut conn)?
+        };
         self.reset_local_sync_session().await?;
 
         self.writer
@@ -906,6 +2192,7 @@ impl AppSyncRepository {
                         trust_state: "trusted".to_string(),
                         last_bootstrap_at: Some(now.clone()),
                         min_snapshot_created_at: None,
+                        synced_account_ids: synced_account_ids.clone(),
                     })
                     .on_conflict(sync_device_config::device_id)
                     .do_update()
@@ -914,6 +2201,7 @@ impl AppSyncRepository {
                         sync_device_config::trust_state.eq("trusted"),
                         sync_device_config::last_bootstrap_at.eq(Some(now.clone())),
                         sync_device_config::min_snapshot_created_at.eq(None::<String>),
+                        sync_device_config::synced_account_ids.eq(&synced_account_ids),
                     ))
                     .execute(conn)
                     .map_err(StorageError::from)?;
@@ -939,6 +2227,7 @@ impl AppSyncRepository {
                         trust_state: "untrusted".to_string(),
                         last_bootstrap_at: None,
                         min_snapshot_created_at: Some(value.clone()),
+                        synced_account_ids: None,
                     })
                     .on_conflict(sync_device_config::device_id)
                     .do_update()
@@ -950,6 +2239,72 @@ impl AppSyncRepository {
             .await
     }
 
+    /// Restrict which accounts this device syncs. An empty list syncs every
+    /// account. When the list widens, the cursor is rewound to the oldest
+    /// event skipped for a newly synced account so the next cycle pulls it.
+    pub async fn set_synced_account_ids(&self, account_ids: Vec<String>) -> Result<()> {
+        let value = if account_ids.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&account_ids)?)
+        };
+        self.writer
+            .exec(move |conn| {
+                let Some(device_id_value) = resolve_local_device_id(conn) else {
+                    return Err(Error::Database(DatabaseError::Internal(
+                        "Cannot set synced accounts before this device is paired".to_string(),
+                    )));
+                };
+                diesel::update(sync_device_config::table.find(&device_id_value))
+                    .set(sync_device_config::synced_account_ids.eq(&value))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+
+                let mut unfiltered = sync_filtered_events::table.into_boxed();
+                if !account_ids.is_empty() {
+                    unfiltered =
+                        unfiltered.filter(sync_filtered_events::account_id.eq_any(&account_ids));
+                }
+                let oldest_seq = unfiltered
+                    .select(diesel::dsl::min(sync_filtered_events::seq))
+                    .first::<Option<i64>>(conn)
+                    .map_err(StorageError::from)?;
+                let Some(oldest_seq) = oldest_seq else {
+                    return Ok(());
+                };
+
+                let mut released = diesel::delete(sync_filtered_events::table).into_boxed();
+                if !account_ids.is_empty() {
+                    released =
+                        released.filter(sync_filtered_events::account_id.eq_any(&account_ids));
+                }
+                released.execute(conn).map_err(StorageError::from)?;
+
+                let cursor_value = sync_cursor::table
+                    .find(1)
+                    .select(sync_cursor::cursor)
+                    .first::<i64>(conn)
+                    .optional()
+                    .map_err(StorageError::from)?
+                    .unwrap_or(0);
+                if oldest_seq <= cursor_value {
+                    set_cursor_tx(conn, oldest_seq - 1)?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    /// Accounts this device syncs; empty means all of them.
+    pub fn get_synced_account_ids(&self) -> Result<Vec<String>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut account_ids = local_synced_account_ids(&mut conn)?
+            .map(|ids| ids.into_iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+        account_ids.sort();
+        Ok(account_ids)
+    }
+
     /// Read the bootstrap freshness gate for a device.
     pub fn get_min_snapshot_created_at(&self, device_id_value: &str) -> Result<Option<String>> {
         let mut conn = get_connection(&self.pool)?;
@@ -1023,6 +2378,144 @@ impl AppSyncRepository {
         rows.into_iter().map(to_outbox_event).collect()
     }
 
+    /// Number of local changes the server has not accepted yet, including
+    /// events still waiting out a retry backoff.
+    pub fn count_unpushed_outbox(&self) -> Result<i64> {
+        let mut conn = get_connection(&self.pool)?;
+        sync_outbox::table
+            .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Pending)?))
+            .filter(sync_outbox::sent.eq(0))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .map_err(|e| StorageError::from(e).into())
+    }
+
+    /// Number of outbox events `list_pending_outbox` would return without a limit.
+    pub fn count_pending_outbox(&self) -> Result<i64> {
+        let mut conn = get_connection(&self.pool)?;
+        let now = Utc::now().to_rfc3339();
+        sync_outbox::table
+            .filter(
+                sync_outbox::status
+                    .eq(enum_to_db(&SyncOutboxStatus::Pending)?)
+                    .and(sync_outbox::sent.eq(0)),
+            )
+            .filter(
+                sync_outbox::next_retry_at
+                    .is_null()
+                    .or(sync_outbox::next_retry_at.le(now)),
+            )
+            .count()
+            .get_result::<i64>(&mut conn)
+            .map_err(|e| StorageError::from(e).into())
+    }
+
+    /// Summarize the pending push and pull against `server_cursor`. Reads only.
+    pub fn sync_dry_run(&self, server_cursor: i64) -> Result<SyncDryRunSummary> {
+        let local_cursor = self.get_cursor()?;
+        Ok(SyncDryRunSummary {
+            pending_push: self.count_pending_outbox()?,
+            local_cursor,
+            server_cursor,
+            remote_ahead: (server_cursor - local_cursor).max(0),
+        })
+    }
+
+    /// Pending outbox counts and last applied sequence per entity, for every
+    /// entity that has either. Reads only.
+    pub fn entity_sync_lag(&self) -> Result<Vec<SyncEntityLag>> {
+        let mut conn = get_connection(&self.pool)?;
+        let pending = sync_outbox::table
+            .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Pending)?))
+            .filter(sync_outbox::sent.eq(0))
+            .group_by(sync_outbox::entity)
+            .select((sync_outbox::entity, diesel::dsl::count_star()))
+            .load::<(String, i64)>(&mut conn)
+            .map_err(StorageError::from)?;
+        let applied = sync_entity_metadata::table
+            .group_by(sync_entity_metadata::entity)
+            .select((
+                sync_entity_metadata::entity,
+                diesel::dsl::max(sync_entity_metadata::last_seq),
+            ))
+            .load::<(String, Option<i64>)>(&mut conn)
+            .map_err(StorageError::from)?;
+
+        let mut lag_by_entity: BTreeMap<String, (i64, Option<i64>)> = BTreeMap::new();
+        for (entity, count) in pending {
+            lag_by_entity.entry(entity).or_default().0 = count;
+        }
+        for (entity, last_seq) in applied {
+            lag_by_entity.entry(entity).or_default().1 = last_seq;
+        }
+        lag_by_entity
+            .into_iter()
+            .map(|(entity, (pending_outbox, last_applied_seq))| {
+                Ok(SyncEntityLag {
+                    entity: enum_from_db(&entity)?,
+                    pending_outbox,
+                    last_applied_seq,
+                })
+            })
+            .collect()
+    }
+
+    /// Dead-lettered outbox events, oldest first.
+    pub fn list_dead_outbox(&self, limit_value: i64) -> Result<Vec<SyncOutboxEvent>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = sync_outbox::table
+            .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Dead)?))
+            .order(sync_outbox::created_at.asc())
+            .limit(limit_value)
+            .load::<SyncOutboxEventDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        rows.into_iter().map(to_outbox_event).collect()
+    }
+
+    /// Most recent outbox rows across all statuses, newest first, without payloads.
+    pub fn list_outbox_diagnostics(&self, limit_value: i64) -> Result<Vec<SyncOutboxDiagnostic>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = sync_outbox::table
+            .order(sync_outbox::created_at.desc())
+            .limit(limit_value)
+            .load::<SyncOutboxEventDB>(&mut conn)
+            .map_err(StorageError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SyncOutboxDiagnostic {
+                event_id: row.event_id,
+                entity: row.entity,
+                entity_id: row.entity_id,
+                op: row.op,
+                status: row.status,
+                payload_key_version: row.payload_key_version,
+                retry_count: row.retry_count,
+                next_retry_at: row.next_retry_at,
+                last_error_code: row.last_error_code,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    pub fn list_table_states(&self) -> Result<Vec<SyncTableStateDB>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = sync_table_state::table
+            .order(sync_table_state::table_name.asc())
+            .load::<SyncTableStateDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        Ok(rows)
+    }
+
+    pub fn list_device_configs(&self) -> Result<Vec<SyncDeviceConfigDB>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = sync_device_config::table
+            .order(sync_device_config::device_id.asc())
+            .load::<SyncDeviceConfigDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        Ok(rows)
+    }
+
     pub async fn mark_outbox_sent(&self, event_ids: Vec<String>) -> Result<()> {
         if event_ids.is_empty() {
             return Ok(());
@@ -1090,6 +2583,8 @@ impl AppSyncRepository {
                     last_event_id: metadata.last_event_id.clone(),
                     last_client_timestamp: metadata.last_client_timestamp.clone(),
                     last_seq: metadata.last_seq,
+                    last_device_id: metadata.last_device_id.clone(),
+                    last_device_seq: metadata.last_device_seq,
                 };
 
                 diesel::insert_into(sync_entity_metadata::table)
@@ -1104,6 +2599,8 @@ impl AppSyncRepository {
                         sync_entity_metadata::last_client_timestamp
                             .eq(row.last_client_timestamp.clone()),
                         sync_entity_metadata::last_seq.eq(row.last_seq),
+                        sync_entity_metadata::last_device_id.eq(row.last_device_id.clone()),
+                        sync_entity_metadata::last_device_seq.eq(row.last_device_seq),
                     ))
                     .execute(conn)
                     .map_err(StorageError::from)?;
@@ -1128,6 +2625,40 @@ impl AppSyncRepository {
         row.map(to_entity_metadata).transpose()
     }
 
+    /// Field-level differences between the latest pending local edit of an
+    /// entity and the remote event that conflicted with it. Empty when there is
+    /// no pending edit or no recorded conflict.
+    pub fn conflict_field_diff(
+        &self,
+        entity: SyncEntity,
+        entity_id_value: &str,
+    ) -> Result<Vec<FieldDiff>> {
+        let mut conn = get_connection(&self.pool)?;
+        let entity_value = enum_to_db(&entity)?;
+        let local_payload = sync_outbox::table
+            .filter(sync_outbox::entity.eq(&entity_value))
+            .filter(sync_outbox::entity_id.eq(entity_id_value))
+            .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Pending)?))
+            .filter(sync_outbox::sent.eq(0))
+            .order((sync_outbox::created_at.desc(), sync_outbox::event_id.desc()))
+            .select(sync_outbox::payload)
+            .first::<String>(&mut conn)
+            .optional()
+            .map_err(StorageError::from)?;
+        let conflict = sync_conflicts::table
+            .find((&entity_value, entity_id_value))
+            .first::<SyncConflictDB>(&mut conn)
+            .optional()
+            .map_err(StorageError::from)?;
+
+        let (Some(local_payload), Some(conflict)) = (local_payload, conflict) else {
+            return Ok(Vec::new());
+        };
+        let local: serde_json::Value = serde_json::from_str(&local_payload)?;
+        let remote: serde_json::Value = serde_json::from_str(&conflict.remote_payload)?;
+        Ok(diff_payload_fields(&local, &remote))
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn apply_remote_event_lww(
         &self,
@@ -1138,84 +2669,178 @@ impl AppSyncRepository {
         client_timestamp_value: String,
         seq_value: i64,
         payload_json: serde_json::Value,
+        origin: Option<(String, i64)>,
     ) -> Result<bool> {
+        if !self.is_replay_allowed(&entity) {
+            Self::reject_replay_event(&entity, &entity_id_value, &event_id_value);
+            self.record_audit(
+                entity,
+                entity_id_value,
+                event_id_value,
+                SyncAuditOutcome::RejectedValidation,
+                Some(REPLAY_NOT_ALLOWED_REASON.to_string()),
+            )
+            .await?;
+            return Ok(false);
+        }
+        let strategy = self.conflict_strategy(entity);
         self.writer
             .exec(move |conn| {
-                apply_remote_event_lww_tx(
-                    conn,
-                    entity,
-                    entity_id_value,
-                    op,
-                    event_id_value,
-                    client_timestamp_value,
-                    seq_value,
-                    payload_json,
-                )
+                with_rejection_audit(conn, |tx, rejected| {
+                    let allowed_accounts = local_synced_account_ids(tx)?;
+                    apply_remote_event_lww_tx(
+                        tx,
+                        entity,
+                        strategy,
+                        entity_id_value.clone(),
+                        op,
+                        event_id_value.clone(),
+                        client_timestamp_value,
+                        seq_value,
+                        payload_json,
+                        origin,
+                        allowed_accounts.as_ref(),
+                    )
+                    .inspect_err(|err| {
+                        *rejected = Some(RejectedReplayEvent {
+                            entity,
+                            entity_id: entity_id_value,
+                            event_id: event_id_value,
+                            reason: err.to_string(),
+                        });
+                    })
+                })
             })
-            .await
+            .await?
+    }
+
+    pub async fn apply_remote_events_lww_batch(&self, events: Vec<ReplayEvent>) -> Result<usize> {
+        self.apply_replay_batch(events, None).await
     }
 
-    pub async fn apply_remote_events_lww_batch(
+    /// Apply a replay batch and advance the sync cursor to `new_cursor` in
+    /// the same writer transaction, so a crash cannot leave the cursor behind
+    /// applied events. With split replay groups the cursor commits with the
+    /// FK-coupled group, after the independent group has committed.
+    pub async fn apply_remote_events_lww_batch_with_cursor(
         &self,
-        events: Vec<(
-            SyncEntity,
-            String,
-            SyncOperation,
-            String,
-            String,
-            i64,
-            serde_json::Value,
-        )>,
+        events: Vec<ReplayEvent>,
+        new_cursor: i64,
     ) -> Result<usize> {
-        if events.is_empty() {
-            return Ok(0);
+        self.apply_replay_batch(events, Some(new_cursor)).await
+    }
+
+    async fn apply_replay_batch(
+        &self,
+        events: Vec<ReplayEvent>,
+        new_cursor: Option<i64>,
+    ) -> Result<usize> {
+        let events = self.filter_replay_allowed(events).await?;
+        if !self.split_replay_groups {
+            return self.apply_replay_group(events, new_cursor).await;
         }
 
-        self.writer
-            .exec(move |conn| {
-                // Defer FK checks during batch replay — events may arrive
-                // out of dependency order (e.g. activity before its account).
-                // Note: writer actor wraps jobs in a transaction, and SQLite
-                // ignores PRAGMA foreign_keys toggles inside active transactions.
-                // defer_foreign_keys applies to the current transaction and lets
-                // constraints validate at commit time.
-                diesel::sql_query("PRAGMA defer_foreign_keys = ON")
-                    .execute(conn)
-                    .map_err(StorageError::from)?;
+        // The FK-coupled group keeps single-transaction semantics; the
+        // independent group commits separately so a large AI history does not
+        // hold up (or get rolled back with) the financial entities. It commits
+        // first so the cursor never gets ahead of it.
+        let (independent, coupled): (Vec<_>, Vec<_>) = events
+            .into_iter()
+            .partition(|event| is_independent_replay_entity(event.0));
+        let independent_applied = self.apply_replay_group(independent, None).await?;
+        Ok(independent_applied + self.apply_replay_group(coupled, new_cursor).await?)
+    }
 
-                let result = (|| -> Result<usize> {
-                    let mut applied = 0usize;
-                    for (entity, entity_id, op, event_id, client_timestamp, seq, payload) in events
-                    {
-                        if apply_remote_event_lww_tx(
-                            conn,
-                            entity,
-                            entity_id.clone(),
-                            op,
-                            event_id.clone(),
-                            client_timestamp.clone(),
-                            seq,
-                            payload,
-                        )
-                        .map_err(|err| {
-                            Error::Database(DatabaseError::Internal(format!(
-                                "Replay apply failed for entity={:?} entity_id={} op={:?} event_id={} seq={}: {}",
-                                entity, entity_id, op, event_id, seq, err
-                            )))
-                        })?
-                        {
-                            applied += 1;
-                        }
+    /// Apply `events` in one writer transaction, moving the cursor to
+    /// `new_cursor` in the same transaction when given.
+    async fn apply_replay_group(
+        &self,
+        events: Vec<ReplayEvent>,
+        new_cursor: Option<i64>,
+    ) -> Result<usize> {
+        if events.is_empty() && new_cursor.is_none() {
+            return Ok(0);
+        }
+        let strategies = self.conflict_strategies.clone();
+        let retry_events = events.clone();
+        let result = self
+            .writer
+            .exec(move |conn| {
+                with_rejection_audit(conn, |tx, rejected| {
+                    let applied = apply_replay_batch_tx(tx, events, &strategies, rejected)?;
+                    if let Some(new_cursor) = new_cursor {
+                        set_cursor_tx(tx, new_cursor)?;
                     }
                     Ok(applied)
-                })();
+                })
+            })
+            .await;
+        match result {
+            Err(err) if is_foreign_key_failure(&err) => {
+                Err(self.diagnose_replay_foreign_keys(retry_events, err).await)
+            }
+            result => result?,
+        }
+    }
+
+    /// Explain a replay commit that failed deferred foreign-key checks by
+    /// naming the rows the batch left dangling. Falls back to `commit_err`
+    /// when the diagnostic re-run fails or finds nothing.
+    async fn diagnose_replay_foreign_keys(
+        &self,
+        events: Vec<ReplayEvent>,
+        commit_err: Error,
+    ) -> Error {
+        let strategies = self.conflict_strategies.clone();
+        match self
+            .writer
+            .exec(move |conn| replay_foreign_key_violations_tx(conn, events, &strategies))
+            .await
+        {
+            Ok(violations) if !violations.is_empty() => {
+                Error::Database(DatabaseError::ForeignKeyViolation(format!(
+                    "Replay batch left dangling references: {}",
+                    violations.join("; ")
+                )))
+            }
+            _ => commit_err,
+        }
+    }
 
-                let _ = diesel::sql_query("PRAGMA defer_foreign_keys = OFF").execute(conn);
-                result
+    /// Record what the replay path did with a remote event.
+    pub async fn record_audit(
+        &self,
+        entity: SyncEntity,
+        entity_id_value: String,
+        event_id_value: String,
+        outcome: SyncAuditOutcome,
+        reason: Option<String>,
+    ) -> Result<()> {
+        self.writer
+            .exec(move |conn| {
+                record_audit_tx(
+                    conn,
+                    entity,
+                    &entity_id_value,
+                    &event_id_value,
+                    outcome,
+                    reason,
+                )
             })
             .await
     }
 
+    /// Most recent audit entries, newest first.
+    pub fn list_recent_audit(&self, limit_value: i64) -> Result<Vec<SyncEventAudit>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = sync_event_audit::table
+            .order(sync_event_audit::id.desc())
+            .limit(limit_value)
+            .load::<SyncEventAuditDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        rows.into_iter().map(to_event_audit).collect()
+    }
+
     pub async fn acquire_cycle_lock(&self) -> Result<i64> {
         self.writer
             .exec(move |conn| {
@@ -1237,6 +2862,9 @@ impl AppSyncRepository {
                         next_retry_at: None,
                         last_cycle_status: None,
                         last_cycle_duration_ms: None,
+                        last_cycle_pushed_bytes: None,
+                        last_cycle_pulled_bytes: None,
+                        last_cycle_events_applied: None,
                     })
                     .on_conflict(sync_engine_state::id)
                     .do_update()
@@ -1276,6 +2904,9 @@ impl AppSyncRepository {
                         next_retry_at: None,
                         last_cycle_status: None,
                         last_cycle_duration_ms: None,
+                        last_cycle_pushed_bytes: None,
+                        last_cycle_pulled_bytes: None,
+                        last_cycle_events_applied: None,
                     })
                     .on_conflict(sync_engine_state::id)
                     .do_update()
@@ -1307,6 +2938,9 @@ impl AppSyncRepository {
                         next_retry_at: None,
                         last_cycle_status: None,
                         last_cycle_duration_ms: None,
+                        last_cycle_pushed_bytes: None,
+                        last_cycle_pulled_bytes: None,
+                        last_cycle_events_applied: None,
                     })
                     .on_conflict(sync_engine_state::id)
                     .do_update()
@@ -1323,9 +2957,20 @@ impl AppSyncRepository {
             .await
     }
 
+    /// Record a failed cycle and push `next_retry_at` out exponentially with
+    /// the number of consecutive failures.
     pub async fn mark_engine_error(&self, error_message: String) -> Result<()> {
         self.writer
             .exec(move |conn| {
+                let consecutive_failures = sync_engine_state::table
+                    .find(1)
+                    .select(sync_engine_state::consecutive_failures)
+                    .first::<i32>(conn)
+                    .optional()
+                    .map_err(StorageError::from)?
+                    .unwrap_or(0)
+                    .saturating_add(1);
+                let next_retry_at = compute_next_retry(consecutive_failures).to_rfc3339();
                 diesel::insert_into(sync_engine_state::table)
                     .values(SyncEngineStateDB {
                         id: 1,
@@ -1333,17 +2978,20 @@ impl AppSyncRepository {
                         last_push_at: None,
                         last_pull_at: None,
                         last_error: Some(error_message.clone()),
-                        consecutive_failures: 1,
-                        next_retry_at: None,
+                        consecutive_failures,
+                        next_retry_at: Some(next_retry_at.clone()),
                         last_cycle_status: Some("error".to_string()),
                         last_cycle_duration_ms: None,
+                        last_cycle_pushed_bytes: None,
+                        last_cycle_pulled_bytes: None,
+                        last_cycle_events_applied: None,
                     })
                     .on_conflict(sync_engine_state::id)
                     .do_update()
                     .set((
                         sync_engine_state::last_error.eq(Some(error_message)),
-                        sync_engine_state::consecutive_failures
-                            .eq(sync_engine_state::consecutive_failures + 1),
+                        sync_engine_state::consecutive_failures.eq(consecutive_failures),
+                        sync_engine_state::next_retry_at.eq(Some(next_retry_at)),
                         sync_engine_state::last_cycle_status.eq(Some("error")),
                     ))
                     .execute(conn)
@@ -1404,6 +3052,48 @@ impl AppSyncRepository {
             .await
     }
 
+    /// Deletes applied-event rows recorded before `cutoff_rfc3339`. Rows at or
+    /// above the current cursor are kept regardless of age, since replay still
+    /// relies on them to skip duplicates. Audit entries older than the cutoff
+    /// are dropped in the same transaction; the count covers applied events only.
+    pub async fn prune_applied_events_older_than(&self, cutoff_rfc3339: String) -> Result<usize> {
+        let cutoff = chrono::DateTime::parse_from_rfc3339(&cutoff_rfc3339)
+            .map_err(|err| {
+                Error::Database(DatabaseError::Internal(format!(
+                    "Invalid applied-event prune cutoff '{}': {}",
+                    cutoff_rfc3339, err
+                )))
+            })?
+            .with_timezone(&Utc)
+            .to_rfc3339();
+        self.writer
+            .exec(move |conn| {
+                let cursor_value = sync_cursor::table
+                    .find(1)
+                    .select(sync_cursor::cursor)
+                    .first::<i64>(conn)
+                    .optional()
+                    .map_err(StorageError::from)?
+                    .unwrap_or(0);
+                // `applied_at` is always written as UTC RFC 3339, so the
+                // normalized cutoff compares correctly as text.
+                let deleted = diesel::delete(
+                    sync_applied_events::table
+                        .filter(sync_applied_events::applied_at.lt(cutoff.clone()))
+                        .filter(sync_applied_events::seq.lt(cursor_value)),
+                )
+                .execute(conn)
+                .map_err(StorageError::from)?;
+                diesel::delete(
+                    sync_event_audit::table.filter(sync_event_audit::recorded_at.lt(cutoff)),
+                )
+                .execute(conn)
+                .map_err(StorageError::from)?;
+                Ok(deleted)
+            })
+            .await
+    }
+
     pub async fn mark_table_incremental_applied(&self, table_name_value: String) -> Result<()> {
         validate_sync_table(&table_name_value)?;
         self.writer
@@ -1418,10 +3108,44 @@ impl AppSyncRepository {
                     })
                     .on_conflict(sync_table_state::table_name)
                     .do_update()
-                    .set((
-                        sync_table_state::enabled.eq(1),
-                        sync_table_state::last_incremental_apply_at.eq(Some(now)),
-                    ))
+                    .set(sync_table_state::last_incremental_apply_at.eq(Some(now)))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Turn syncing of `table_name` on or off. Disabled tables get no outbox
+    /// rows for local edits, and remote events for them are acknowledged
+    /// without being applied. Enabling a table re-baselines it, so edits made
+    /// while it was disabled are not reported as drift.
+    pub async fn set_table_enabled(&self, table_name_value: String, enabled: bool) -> Result<()> {
+        validate_sync_table(&table_name_value)?;
+        let enabled_value = i32::from(enabled);
+        self.writer
+            .exec(move |conn| {
+                if enabled {
+                    let has_baseline = sync_table_baseline::table
+                        .find(&table_name_value)
+                        .count()
+                        .get_result::<i64>(conn)
+                        .map_err(StorageError::from)?
+                        > 0;
+                    if has_baseline {
+                        record_table_baseline(conn, &table_name_value, &Utc::now().to_rfc3339())?;
+                    }
+                }
+                diesel::insert_into(sync_table_state::table)
+                    .values(SyncTableStateDB {
+                        table_name: table_name_value,
+                        enabled: enabled_value,
+                        last_snapshot_restore_at: None,
+                        last_incremental_apply_at: None,
+                    })
+                    .on_conflict(sync_table_state::table_name)
+                    .do_update()
+                    .set(sync_table_state::enabled.eq(enabled_value))
                     .execute(conn)
                     .map_err(StorageError::from)?;
                 Ok(())
@@ -1454,6 +3178,88 @@ impl AppSyncRepository {
             .await
     }
 
+    /// Collapse pending updates that a newer pending update of the same entity
+    /// replaces. Update payloads carry the full row, so only the newest one per
+    /// `(entity, entity_id)` by `client_timestamp` is kept; older ones are
+    /// marked superseded. Creates and deletes are never touched. Returns the
+    /// number of superseded events.
+    pub async fn compact_pending_outbox(&self) -> Result<usize> {
+        self.writer
+            .exec(move |conn| {
+                let updates = sync_outbox::table
+                    .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Pending)?))
+                    .filter(sync_outbox::sent.eq(0))
+                    .filter(sync_outbox::op.eq(enum_to_db(&SyncOperation::Update)?))
+                    .load::<SyncOutboxEventDB>(conn)
+                    .map_err(StorageError::from)?;
+
+                let mut newest: HashMap<(String, String), &SyncOutboxEventDB> = HashMap::new();
+                for row in &updates {
+                    let key = (row.entity.clone(), row.entity_id.clone());
+                    match newest.get(&key) {
+                        Some(current)
+                            if outbox_update_order(current) >= outbox_update_order(row) => {}
+                        _ => {
+                            newest.insert(key, row);
+                        }
+                    }
+                }
+                let superseded_ids = updates
+                    .iter()
+                    .filter(|row| {
+                        newest
+                            .get(&(row.entity.clone(), row.entity_id.clone()))
+                            .is_some_and(|kept| kept.event_id != row.event_id)
+                    })
+                    .map(|row| row.event_id.clone())
+                    .collect::<Vec<_>>();
+                if superseded_ids.is_empty() {
+                    return Ok(0);
+                }
+
+                let mut superseded = 0;
+                for chunk in superseded_ids.chunks(500) {
+                    superseded += diesel::update(
+                        sync_outbox::table.filter(sync_outbox::event_id.eq_any(chunk)),
+                    )
+                    .set(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Superseded)?))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                }
+                Ok(superseded)
+            })
+            .await
+    }
+
+    /// Move dead-lettered events back to pending with a fresh retry budget.
+    /// Ids that are not dead are ignored. Returns the number of requeued events.
+    pub async fn requeue_dead_outbox(&self, event_ids: Vec<String>) -> Result<usize> {
+        if event_ids.is_empty() {
+            return Ok(0);
+        }
+
+        self.writer
+            .exec(move |conn| {
+                let requeued = diesel::update(
+                    sync_outbox::table
+                        .filter(sync_outbox::event_id.eq_any(event_ids))
+                        .filter(sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Dead)?)),
+                )
+                .set((
+                    sync_outbox::status.eq(enum_to_db(&SyncOutboxStatus::Pending)?),
+                    sync_outbox::sent.eq(0),
+                    sync_outbox::retry_count.eq(0),
+                    sync_outbox::next_retry_at.eq(None::<String>),
+                    sync_outbox::last_error.eq(None::<String>),
+                    sync_outbox::last_error_code.eq(None::<String>),
+                ))
+                .execute(conn)
+                .map_err(StorageError::from)?;
+                Ok(requeued)
+            })
+            .await
+    }
+
     pub async fn mark_cycle_outcome(
         &self,
         status_value: String,
@@ -1462,6 +3268,19 @@ impl AppSyncRepository {
     ) -> Result<()> {
         self.writer
             .exec(move |conn| {
+                // A failed cycle keeps any later backoff set by `mark_engine_error`.
+                let next_retry_at_value = if status_value == "ok" {
+                    next_retry_at_value
+                } else {
+                    let current = sync_engine_state::table
+                        .find(1)
+                        .select(sync_engine_state::next_retry_at)
+                        .first::<Option<String>>(conn)
+                        .optional()
+                        .map_err(StorageError::from)?
+                        .flatten();
+                    later_retry_at(current, next_retry_at_value)
+                };
                 diesel::insert_into(sync_engine_state::table)
                     .values(SyncEngineStateDB {
                         id: 1,
@@ -1473,6 +3292,9 @@ impl AppSyncRepository {
                         next_retry_at: next_retry_at_value.clone(),
                         last_cycle_status: Some(status_value.clone()),
                         last_cycle_duration_ms: Some(duration_ms_value),
+                        last_cycle_pushed_bytes: None,
+                        last_cycle_pulled_bytes: None,
+                        last_cycle_events_applied: None,
                     })
                     .on_conflict(sync_engine_state::id)
                     .do_update()
@@ -1497,17 +3319,55 @@ impl AppSyncRepository {
             .await
     }
 
+    /// Record how much data the last sync cycle moved.
+    pub async fn mark_cycle_transfer_stats(
+        &self,
+        pushed_bytes: i64,
+        pulled_bytes: i64,
+        events_applied: i64,
+    ) -> Result<()> {
+        self.writer
+            .exec(move |conn| {
+                diesel::insert_into(sync_engine_state::table)
+                    .values(SyncEngineStateDB {
+                        id: 1,
+                        lock_version: 0,
+                        last_push_at: None,
+                        last_pull_at: None,
+                        last_error: None,
+                        consecutive_failures: 0,
+                        next_retry_at: None,
+                        last_cycle_status: None,
+                        last_cycle_duration_ms: None,
+                        last_cycle_pushed_bytes: Some(pushed_bytes),
+                        last_cycle_pulled_bytes: Some(pulled_bytes),
+                        last_cycle_events_applied: Some(events_applied),
+                    })
+                    .on_conflict(sync_engine_state::id)
+                    .do_update()
+                    .set((
+                        sync_engine_state::last_cycle_pushed_bytes.eq(Some(pushed_bytes)),
+                        sync_engine_state::last_cycle_pulled_bytes.eq(Some(pulled_bytes)),
+                        sync_engine_state::last_cycle_events_applied.eq(Some(events_applied)),
+                    ))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                Ok(())
+            })
+            .await
+    }
+
     pub async fn export_snapshot_sqlite_image(&self, tables: Vec<String>) -> Result<Vec<u8>> {
-        /// Per-table WHERE filters applied during snapshot export.
-        /// Tables not listed here are exported unfiltered.
-        const SYNC_TABLE_EXPORT_FILTERS: &[(&str, &str)] = &[
-            (
-                "holdings_snapshots",
-                "source IN ('MANUAL_ENTRY', 'CSV_IMPORT', 'SYNTHETIC', 'BROKER_IMPORTED')",
-            ),
-            ("quotes", "source = 'MANUAL'"),
-        ];
+        self.export_snapshot_sqlite_image_with_options(tables, SnapshotExportOptions::default())
+            .await
+    }
 
+    /// Export a snapshot SQLite image, applying the opt-in filters in `options`.
+    pub async fn export_snapshot_sqlite_image_with_options(
+        &self,
+        tables: Vec<String>,
+        options: SnapshotExportOptions,
+    ) -> Result<Vec<u8>> {
         let pool = Arc::clone(&self.pool);
         tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
             let mut conn = get_connection(&pool)?;
@@ -1524,7 +3384,11 @@ impl AppSyncRepository {
             }
 
             let snapshot_path =
-                std::env::temp_dir().join(format!("wf_snapshot_export_{}.db", Uuid::now_v7()));
+                std::env::temp_dir().join(format!(
+                "{}{}.db",
+                SNAPSHOT_EXPORT_TEMP_PREFIX,
+                Uuid::now_v7()
+            ));
             let escaped_path = escape_sqlite_str(&snapshot_path.to_string_lossy());
             let snapshot_alias = format!("snapshot_export_{}", Uuid::now_v7().simple());
             let attach_sql = format!("ATTACH DATABASE '{}' AS {}", escaped_path, snapshot_alias);
@@ -1536,10 +3400,7 @@ impl AppSyncRepository {
                 let run_export = (|| -> Result<()> {
                     for table in &table_set {
                         let table_ident = quote_identifier(table);
-                        let filter = SYNC_TABLE_EXPORT_FILTERS
-                            .iter()
-                            .find(|(t, _)| *t == table.as_str())
-                            .map(|(_, f)| *f);
+                        let filter = snapshot_export_filter(table, options);
                         let copy_sql = match filter {
                             Some(where_clause) => format!(
                                 "CREATE TABLE {snapshot_alias}.{table_ident} AS SELECT * FROM main.{table_ident} WHERE {where_clause}"
@@ -1582,58 +3443,489 @@ impl AppSyncRepository {
         })?
     }
 
-    pub async fn restore_snapshot_tables_from_file(
+    /// Export a snapshot SQLite image and encode it (e.g. gzip) for upload.
+    pub async fn export_snapshot_sqlite_image_encoded(
         &self,
-        snapshot_db_path: String,
         tables: Vec<String>,
-        cursor_value: i64,
-        device_id_value: String,
-        key_version_value: Option<i32>,
-    ) -> Result<()> {
-        self.writer
-            .exec(move |conn| {
-                let table_set = if tables.is_empty() {
-                    APP_SYNC_TABLES
-                        .iter()
-                        .map(|t| t.to_string())
-                        .collect::<Vec<_>>()
-                } else {
-                    tables
+        encoding: SnapshotEncoding,
+    ) -> Result<Vec<u8>> {
+        let image = self.export_snapshot_sqlite_image(tables).await?;
+        tokio::task::spawn_blocking(move || encoding.encode(&image))
+            .await
+            .map_err(|e| {
+                Error::Database(DatabaseError::Internal(format!(
+                    "Snapshot encode worker failed: {}",
+                    e
+                )))
+            })?
+            .map_err(|e| {
+                Error::Database(DatabaseError::Internal(format!(
+                    "Failed to encode snapshot image: {}",
+                    e
+                )))
+            })
+    }
+
+    /// Write an unencrypted snapshot image of every synced table to `path`,
+    /// for an offline backup. Returns the number of bytes written. The file
+    /// is written next to `path` first and renamed, so a failed export never
+    /// leaves a truncated database behind.
+    pub async fn export_plaintext_snapshot_to_file(&self, path: String) -> Result<usize> {
+        let image = self.export_snapshot_sqlite_image(Vec::new()).await?;
+        tokio::task::spawn_blocking(move || -> Result<usize> {
+            let target = std::path::Path::new(&path);
+            let partial = target.with_extension("partial");
+            std::fs::write(&partial, &image)
+                .and_then(|_| std::fs::rename(&partial, target))
+                .map_err(|e| {
+                    let _ = std::fs::remove_file(&partial);
+                    Error::Database(DatabaseError::Internal(format!(
+                        "Failed to write snapshot backup to {}: {}",
+                        path, e
+                    )))
+                })?;
+            Ok(image.len())
+        })
+        .await
+        .map_err(|e| {
+            Error::Database(DatabaseError::Internal(format!(
+                "Snapshot backup worker failed: {}",
+                e
+            )))
+        })?
+    }
+
+    /// Export only the rows whose `sync_entity_metadata.last_seq` is greater
+    /// than `since_seq`. The image has one table per changed entity; tables
+    /// without changes are left out. An empty manifest comes with an empty image.
+    pub async fn export_delta_snapshot_since(
+        &self,
+        since_seq: i64,
+        tables: Vec<String>,
+    ) -> Result<DeltaSnapshot> {
+        let pool = Arc::clone(&self.pool);
+        tokio::task::spawn_blocking(move || -> Result<DeltaSnapshot> {
+            let mut conn = get_connection(&pool)?;
+            let table_set = if tables.is_empty() {
+                APP_SYNC_TABLES
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+            } else {
+                tables
+            };
+            for table in &table_set {
+                validate_sync_table(table)?;
+            }
+
+            let changed = sync_entity_metadata::table
+                .filter(sync_entity_metadata::last_seq.gt(since_seq))
+                .load::<SyncEntityMetadataDB>(&mut conn)
+                .map_err(StorageError::from)?;
+            let mut max_seq = since_seq;
+            let mut ids_by_table: HashMap<&'static str, (SyncEntity, BTreeSet<String>)> =
+                HashMap::new();
+            for row in changed {
+                let entity = enum_from_db::<SyncEntity>(&row.entity)?;
+                let Some((table, _)) = entity_storage_mapping(&entity) else {
+                    continue;
                 };
-                for table in &table_set {
-                    validate_sync_table(table)?;
+                if !table_set.iter().any(|t| t == table) {
+                    continue;
                 }
+                max_seq = max_seq.max(row.last_seq);
+                ids_by_table
+                    .entry(table)
+                    .or_insert_with(|| (entity, BTreeSet::new()))
+                    .1
+                    .insert(row.entity_id);
+            }
+            let entities = APP_SYNC_TABLES
+                .iter()
+                .filter_map(|table| ids_by_table.remove(table))
+                .map(|(entity, ids)| DeltaSnapshotEntities {
+                    entity,
+                    entity_ids: ids.into_iter().collect(),
+                })
+                .collect::<Vec<_>>();
+            let manifest = DeltaSnapshotManifest {
+                since_seq,
+                max_seq,
+                entities,
+            };
+            if manifest.is_empty() {
+                return Ok(DeltaSnapshot {
+                    image: Vec::new(),
+                    manifest,
+                });
+            }
 
-                let now = Utc::now().to_rfc3339();
-                let escaped_path = escape_sqlite_str(&snapshot_db_path);
-                let snapshot_alias = format!("snapshot_{}", Uuid::new_v4().simple());
-                let attach_sql =
-                    format!("ATTACH DATABASE '{}' AS {}", escaped_path, snapshot_alias);
-
-                // Note: PRAGMA foreign_keys cannot be changed inside a transaction
-                // (SQLite silently ignores it). Instead, APP_SYNC_TABLES is ordered
-                // to respect FK dependencies (parent tables before children).
-                diesel::sql_query(attach_sql)
-                    .execute(conn)
+            let snapshot_path = std::env::temp_dir().join(format!(
+                "{}{}.db",
+                SNAPSHOT_EXPORT_TEMP_PREFIX,
+                Uuid::now_v7()
+            ));
+            let escaped_path = escape_sqlite_str(&snapshot_path.to_string_lossy());
+            let snapshot_alias = format!("delta_export_{}", Uuid::now_v7().simple());
+            let attach_sql = format!("ATTACH DATABASE '{}' AS {}", escaped_path, snapshot_alias);
+            let tx_result = conn.immediate_transaction::<_, StorageError, _>(|tx| {
+                diesel::sql_query(attach_sql.clone())
+                    .execute(tx)
                     .map_err(StorageError::from)?;
 
-                let restore_result = (|| -> Result<()> {
-                    // Bootstrap reset: clear control-plane sync state so stale events/metadata
-                    // never leak into the newly restored snapshot baseline.
-                    diesel::delete(sync_outbox::table)
-                        .execute(conn)
-                        .map_err(StorageError::from)?;
+                let run_export = (|| -> Result<()> {
+                    for included in &manifest.entities {
+                        let Some((table, pk_name)) = entity_storage_mapping(&included.entity)
+                        else {
+                            continue;
+                        };
+                        let table_ident = quote_identifier(table);
+                        let pk_ident = quote_identifier(pk_name);
+                        let id_list = sql_string_list(&included.entity_ids);
+                        let where_clause = match snapshot_export_filter(
+                            table,
+                            SnapshotExportOptions::default(),
+                        ) {
+                            Some(filter) => format!("{pk_ident} IN ({id_list}) AND {filter}"),
+                            None => format!("{pk_ident} IN ({id_list})"),
+                        };
+                        diesel::sql_query(format!(
+                            "CREATE TABLE {snapshot_alias}.{table_ident} AS SELECT * FROM main.{table_ident} WHERE {where_clause}"
+                        ))
+                        .execute(tx)
+                        .map_err(StorageError::from)?;
+                    }
+                    Ok(())
+                })();
+
+                let detach_sql = format!("DETACH DATABASE {}", snapshot_alias);
+                let _ = diesel::sql_query(detach_sql).execute(tx);
+                run_export.map_err(StorageError::from)
+            });
+            if let Err(err) = tx_result {
+                let _ = std::fs::remove_file(&snapshot_path);
+                return Err(Error::from(err));
+            }
+
+            let image = std::fs::read(&snapshot_path).map_err(|e| {
+                Error::Database(DatabaseError::Internal(format!(
+                    "Failed reading exported delta snapshot: {}",
+                    e
+                )))
+            })?;
+            let _ = std::fs::remove_file(snapshot_path);
+            Ok(DeltaSnapshot { image, manifest })
+        })
+        .await
+        .map_err(|e| {
+            Error::Database(DatabaseError::Internal(format!(
+                "Delta snapshot export worker failed: {}",
+                e
+            )))
+        })?
+    }
+
+    /// Apply a delta snapshot on top of the local tables. Rows in the image are
+    /// upserted by primary key; manifest ids missing from the image are deleted.
+    /// Unlike a full restore, other rows and the sync control-plane state are
+    /// left untouched.
+    pub async fn restore_delta_snapshot(
+        &self,
+        snapshot_db_path: String,
+        manifest: DeltaSnapshotManifest,
+    ) -> Result<()> {
+        if manifest.is_empty() {
+            return Ok(());
+        }
+
+        self.writer
+            .exec(move |conn| {
+                let mut targets = Vec::with_capacity(manifest.entities.len());
+                for included in &manifest.entities {
+                    let (table, pk_name) =
+                        entity_storage_mapping(&included.entity).ok_or_else(|| {
+                            Error::Database(DatabaseError::Internal(format!(
+                                "Delta snapshot entity '{:?}' has no storage mapping",
+                                included.entity
+                            )))
+                        })?;
+                    validate_sync_table(table)?;
+                    targets.push((table, pk_name, &included.entity_ids));
+                }
+                // `APP_SYNC_TABLES` lists parents before children; the manifest
+                // order is whatever the exporting device sent.
+                targets.sort_by_key(|(table, _, _)| {
+                    APP_SYNC_TABLES
+                        .iter()
+                        .position(|candidate| candidate == table)
+                        .unwrap_or(usize::MAX)
+                });
+
+                let now = Utc::now().to_rfc3339();
+                let escaped_path = escape_sqlite_str(&snapshot_db_path);
+                let snapshot_alias = format!("delta_{}", Uuid::new_v4().simple());
+                let attach_sql =
+                    format!("ATTACH DATABASE '{}' AS {}", escaped_path, snapshot_alias);
+                diesel::sql_query(attach_sql)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+
+                let restore_result = (|| -> Result<()> {
+                    let alias_ident = quote_identifier(&snapshot_alias);
+                    // Upsert parents before children, then delete children before parents.
+                    for (table, pk_name, _) in &targets {
+                        let target_columns = load_table_columns(conn, "main", table)?;
+                        let source_column_set = load_table_columns(conn, &snapshot_alias, table)?
+                            .into_iter()
+                            .collect::<HashSet<String>>();
+                        let common_columns = target_columns
+                            .into_iter()
+                            .filter(|column| source_column_set.contains(column))
+                            .collect::<Vec<_>>();
+                        if !common_columns.iter().any(|column| column == pk_name) {
+                            return Err(Error::Database(DatabaseError::Internal(format!(
+                                "Delta snapshot table '{}' is missing primary key '{}'",
+                                table, pk_name
+                            ))));
+                        }
+
+                        let table_ident = quote_identifier(table);
+                        let pk_ident = quote_identifier(pk_name);
+                        let columns_sql = common_columns
+                            .iter()
+                            .map(|column| quote_identifier(column))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let updates_sql = common_columns
+                            .iter()
+                            .filter(|column| column.as_str() != *pk_name)
+                            .map(|column| {
+                                let ident = quote_identifier(column);
+                                format!("{ident} = excluded.{ident}")
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let conflict_sql = if updates_sql.is_empty() {
+                            "DO NOTHING".to_string()
+                        } else {
+                            format!("DO UPDATE SET {updates_sql}")
+                        };
+                        // `WHERE true` keeps SQLite from parsing ON CONFLICT as a join clause.
+                        let upsert_sql = format!(
+                            "INSERT INTO main.{table_ident} ({columns_sql}) SELECT {columns_sql} FROM {alias_ident}.{table_ident} WHERE true ON CONFLICT({pk_ident}) {conflict_sql}"
+                        );
+                        diesel::sql_query(upsert_sql)
+                            .execute(conn)
+                            .map_err(StorageError::from)?;
+                    }
+
+                    for (table, pk_name, entity_ids) in targets.iter().rev() {
+                        let table_ident = quote_identifier(table);
+                        let pk_ident = quote_identifier(pk_name);
+                        let delete_sql = format!(
+                            "DELETE FROM main.{table_ident} WHERE {pk_ident} IN ({}) AND {pk_ident} NOT IN (SELECT {pk_ident} FROM {alias_ident}.{table_ident})",
+                            sql_string_list(entity_ids)
+                        );
+                        diesel::sql_query(delete_sql)
+                            .execute(conn)
+                            .map_err(StorageError::from)?;
+                    }
+
+                    for (table, _, _) in &targets {
+                        record_table_baseline(conn, table, &now)?;
+                        diesel::insert_into(sync_table_state::table)
+                            .values(&SyncTableStateDB {
+                                table_name: table.to_string(),
+                                enabled: 1,
+                                last_snapshot_restore_at: None,
+                                last_incremental_apply_at: Some(now.clone()),
+                            })
+                            .on_conflict(sync_table_state::table_name)
+                            .do_update()
+                            .set(sync_table_state::last_incremental_apply_at.eq(Some(now.clone())))
+                            .execute(conn)
+                            .map_err(StorageError::from)?;
+                    }
+                    Ok(())
+                })();
+
+                let detach_sql = format!("DETACH DATABASE {}", snapshot_alias);
+                let _ = diesel::sql_query(detach_sql).execute(conn);
+                restore_result
+            })
+            .await
+    }
+
+    /// Recompute checksums for every table with a recorded baseline and report
+    /// the ones that changed without an applied remote event or a local outbox
+    /// event since the baseline. Tables whose changes are accounted for are
+    /// re-baselined.
+    pub async fn detect_drift(&self) -> Result<Vec<SyncTableDrift>> {
+        self.writer
+            .exec(move |conn| {
+                let baselines = sync_table_baseline::table
+                    .order(sync_table_baseline::table_name.asc())
+                    .load::<SyncTableBaselineDB>(conn)
+                    .map_err(StorageError::from)?;
+                let Some(oldest_baseline) = baselines
+                    .iter()
+                    .map(|baseline| baseline.computed_at.clone())
+                    .min()
+                else {
+                    return Ok(Vec::new());
+                };
+
+                let last_applied_at = sync_table_state::table
+                    .load::<SyncTableStateDB>(conn)
+                    .map_err(StorageError::from)?
+                    .into_iter()
+                    .filter_map(|state| {
+                        state
+                            .last_incremental_apply_at
+                            .map(|applied_at| (state.table_name, applied_at))
+                    })
+                    .collect::<HashMap<_, _>>();
+                let local_changes = sync_outbox::table
+                    .filter(sync_outbox::created_at.ge(&oldest_baseline))
+                    .select((sync_outbox::entity, sync_outbox::created_at))
+                    .load::<(String, String)>(conn)
+                    .map_err(StorageError::from)?
+                    .into_iter()
+                    .filter_map(|(entity, created_at)| {
+                        let entity = enum_from_db::<SyncEntity>(&entity).ok()?;
+                        let (table_name, _) = entity_storage_mapping(&entity)?;
+                        Some((table_name, created_at))
+                    })
+                    .collect::<Vec<_>>();
+
+                // Local writes to disabled tables, and to account-scoped
+                // tables while an account allow-list is set, may leave no
+                // outbox row. Such tables are re-baselined instead of checked.
+                let account_scoped_tables: HashSet<&str> =
+                    if local_synced_account_ids(conn)?.is_some() {
+                        [
+                            SyncEntity::Account,
+                            SyncEntity::Activity,
+                            SyncEntity::GoalsAllocation,
+                        ]
+                        .iter()
+                        .filter_map(|entity| entity_storage_mapping(entity))
+                        .map(|(table_name, _)| table_name)
+                        .collect()
+                    } else {
+                        HashSet::new()
+                    };
+
+                let now = Utc::now().to_rfc3339();
+                let mut drifted = Vec::new();
+                for baseline in baselines {
+                    let table = baseline.table_name.as_str();
+                    let (checksum, rows) = compute_table_checksum(conn, table)?;
+                    if checksum == baseline.checksum {
+                        continue;
+                    }
+                    if !is_table_sync_enabled(conn, table)? || account_scoped_tables.contains(table)
+                    {
+                        record_table_baseline(conn, table, &now)?;
+                        continue;
+                    }
+
+                    let remote_applied = last_applied_at
+                        .get(table)
+                        .is_some_and(|applied_at| *applied_at >= baseline.computed_at);
+                    let local_written = local_changes.iter().any(|(table_name, created_at)| {
+                        *table_name == table && *created_at >= baseline.computed_at
+                    });
+                    if remote_applied || local_written {
+                        record_table_baseline(conn, table, &now)?;
+                        continue;
+                    }
+
+                    drifted.push(SyncTableDrift {
+                        table: baseline.table_name,
+                        baseline_checksum: baseline.checksum,
+                        current_checksum: checksum,
+                        baseline_rows: baseline.row_count,
+                        current_rows: rows,
+                    });
+                }
+                Ok(drifted)
+            })
+            .await
+    }
+
+    /// Replace the synced tables with the contents of a snapshot file.
+    /// Snapshots written with a newer schema than the local one are rejected,
+    /// since restoring them would silently drop the columns this build lacks.
+    /// Older snapshots restore the columns both schemas share.
+    pub async fn restore_snapshot_tables_from_file(
+        &self,
+        snapshot_db_path: String,
+        snapshot_schema_version: i32,
+        tables: Vec<String>,
+        cursor_value: i64,
+        device_id_value: String,
+        key_version_value: Option<i32>,
+    ) -> Result<()> {
+        if snapshot_schema_version > self.local_schema_version {
+            return Err(Error::Database(DatabaseError::RestoreFailed(format!(
+                "Snapshot schema version {} is newer than local version {}",
+                snapshot_schema_version, self.local_schema_version
+            ))));
+        }
+        ensure_sqlite_image(&snapshot_db_path)?;
+
+        self.writer
+            .exec(move |conn| {
+                let table_set = if tables.is_empty() {
+                    APP_SYNC_TABLES
+                        .iter()
+                        .map(|t| t.to_string())
+                        .collect::<Vec<_>>()
+                } else {
+                    tables
+                };
+                for table in &table_set {
+                    validate_sync_table(table)?;
+                }
+
+                let now = Utc::now().to_rfc3339();
+                let escaped_path = escape_sqlite_str(&snapshot_db_path);
+                let snapshot_alias = format!("snapshot_{}", Uuid::new_v4().simple());
+                let attach_sql =
+                    format!("ATTACH DATABASE '{}' AS {}", escaped_path, snapshot_alias);
+
+                // Note: PRAGMA foreign_keys cannot be changed inside a transaction
+                // (SQLite silently ignores it). Instead, APP_SYNC_TABLES is ordered
+                // to respect FK dependencies (parent tables before children).
+                diesel::sql_query(attach_sql)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+
+                let restore_result = (|| -> Result<()> {
+                    // Bootstrap reset: clear control-plane sync state so stale events/metadata
+                    // never leak into the newly restored snapshot baseline.
+                    diesel::delete(sync_outbox::table)
+                        .execute(conn)
+                        .map_err(StorageError::from)?;
                     diesel::delete(sync_entity_metadata::table)
                         .execute(conn)
                         .map_err(StorageError::from)?;
                     diesel::delete(sync_applied_events::table)
                         .execute(conn)
                         .map_err(StorageError::from)?;
-                    diesel::delete(sync_table_state::table)
+                    // Keep the per-table enabled flags the user set.
+                    clear_table_state_timestamps_tx(conn)?;
+                    diesel::delete(sync_table_baseline::table)
+                        .execute(conn)
+                        .map_err(StorageError::from)?;
+                    diesel::delete(sync_conflicts::table)
                         .execute(conn)
                         .map_err(StorageError::from)?;
                     // Remove stale device config rows from previous enrollment cycles so
                     // resolve_payload_key_version never picks an outdated key_version.
+                    // The account allow-list moves to the new row.
+                    let synced_account_ids = local_synced_account_ids_raw(conn)?;
                     diesel::delete(
                         sync_device_config::table
                             .filter(sync_device_config::device_id.ne(&device_id_value)),
@@ -1675,6 +3967,19 @@ impl AppSyncRepository {
                             .execute(conn)
                             .map_err(StorageError::from)?;
 
+                        // Verify the copy landed every snapshot row; triggers or constraint
+                        // conflict clauses can otherwise drop rows without an error.
+                        let expected_rows = count_table_rows(conn, &alias_ident, &table_ident)?;
+                        let restored_rows =
+                            count_table_rows(conn, &quote_identifier("main"), &table_ident)?;
+                        if restored_rows != expected_rows {
+                            return Err(Error::Database(DatabaseError::Internal(format!(
+                                "Snapshot restore verification failed for table '{}': expected {} rows, restored {}",
+                                table, expected_rows, restored_rows
+                            ))));
+                        }
+                        record_table_baseline(conn, table, &now)?;
+
                         let state_row = SyncTableStateDB {
                             table_name: table.clone(),
                             enabled: 1,
@@ -1685,10 +3990,7 @@ impl AppSyncRepository {
                             .values(&state_row)
                             .on_conflict(sync_table_state::table_name)
                             .do_update()
-                            .set((
-                                sync_table_state::enabled.eq(1),
-                                sync_table_state::last_snapshot_restore_at.eq(Some(now.clone())),
-                            ))
+                            .set(sync_table_state::last_snapshot_restore_at.eq(Some(now.clone())))
                             .execute(conn)
                             .map_err(StorageError::from)?;
                     }
@@ -1715,6 +4017,7 @@ impl AppSyncRepository {
                             trust_state: "trusted".to_string(),
                             last_bootstrap_at: Some(now.clone()),
                             min_snapshot_created_at: None,
+                            synced_account_ids,
                         })
                         .on_conflict(sync_device_config::device_id)
                         .do_update()
@@ -1737,6 +4040,9 @@ impl AppSyncRepository {
                             next_retry_at: None,
                             last_cycle_status: Some("ok".to_string()),
                             last_cycle_duration_ms: None,
+                            last_cycle_pushed_bytes: None,
+                            last_cycle_pulled_bytes: None,
+                            last_cycle_events_applied: None,
                         })
                         .on_conflict(sync_engine_state::id)
                         .do_update()
@@ -1757,7 +4063,25 @@ impl AppSyncRepository {
                 let _ = diesel::sql_query(detach_sql).execute(conn);
                 restore_result
             })
-            .await
+            .await?;
+
+        if self.vacuum_after_restore {
+            let pool = Arc::clone(&self.pool);
+            let vacuum_result = tokio::task::spawn_blocking(move || -> Result<()> {
+                let mut conn = get_connection(&pool)?;
+                vacuum_database(&mut conn)
+            })
+            .await;
+            // The restore is already committed; a failed VACUUM only costs disk space.
+            match vacuum_result {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    log::warn!("[AppSync] VACUUM after snapshot restore failed: {}", err)
+                }
+                Err(err) => log::warn!("[AppSync] VACUUM worker failed: {}", err),
+            }
+        }
+        Ok(())
     }
 }
 
@@ -1766,15 +4090,48 @@ mod tests {
     use super::*;
     use diesel::dsl::count_star;
     use diesel::Connection;
-    use std::collections::BTreeSet;
     use tempfile::tempdir;
 
     use crate::db::{create_pool, get_connection, init, run_migrations, write_actor::spawn_writer};
     use crate::schema::{
-        accounts, activity_import_profiles, assets, goals, platforms, sync_applied_events,
-        sync_device_config, sync_entity_metadata, sync_outbox,
+        accounts, activities, activity_import_profiles, assets, goals, platforms,
+        sync_applied_events, sync_device_config, sync_entity_metadata, sync_outbox,
     };
 
+    #[test]
+    fn cleanup_removes_only_old_snapshot_export_orphans() {
+        let dir = tempdir().expect("tempdir");
+        let old_orphan = dir.path().join(format!(
+            "{}{}.db",
+            SNAPSHOT_EXPORT_TEMP_PREFIX,
+            Uuid::now_v7()
+        ));
+        let recent = dir.path().join(format!(
+            "{}{}.db",
+            SNAPSHOT_EXPORT_TEMP_PREFIX,
+            Uuid::now_v7()
+        ));
+        let unrelated = dir.path().join("wf_snapshot_export_notes.db");
+        let two_days_ago =
+            std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 24 * 60 * 60);
+        for path in [&old_orphan, &recent, &unrelated] {
+            let file = std::fs::File::create(path).expect("create file");
+            if path != &recent {
+                file.set_modified(two_days_ago).expect("backdate file");
+            }
+        }
+
+        let removed = cleanup_orphaned_snapshot_temp_files_in(
+            dir.path(),
+            std::time::Duration::from_secs(24 * 60 * 60),
+        );
+
+        assert_eq!(removed, 1);
+        assert!(!old_orphan.exists());
+        assert!(recent.exists());
+        assert!(unrelated.exists(), "non-UUID names are never touched");
+    }
+
     fn setup_db() -> (
         Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
         WriteHandle,
@@ -2001,6 +4358,7 @@ mod tests {
 
         repo.restore_snapshot_tables_from_file(
             snapshot_path.clone(),
+            SNAPSHOT_SCHEMA_VERSION,
             vec!["accounts".to_string()],
             88,
             "device-1".to_string(),
@@ -2014,6 +4372,7 @@ mod tests {
 
         repo.restore_snapshot_tables_from_file(
             snapshot_path,
+            SNAPSHOT_SCHEMA_VERSION,
             vec!["accounts".to_string()],
             88,
             "device-1".to_string(),
@@ -2027,57 +4386,191 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn snapshot_restore_error_keeps_existing_cursor() {
+    async fn snapshot_restore_keeps_disabled_tables_disabled() {
         let (pool, writer) = setup_db();
-        let repo = AppSyncRepository::new(pool, writer);
-        repo.set_cursor(15).await.expect("set cursor");
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        repo.set_table_enabled("accounts".to_string(), false)
+            .await
+            .expect("disable accounts");
+        repo.set_table_enabled("ai_threads".to_string(), false)
+            .await
+            .expect("disable ai_threads");
+        let snapshot_path = create_snapshot_db_with_account("acc-disabled-table");
 
-        let broken_snapshot_path = tempdir()
-            .expect("tempdir")
-            .keep()
-            .join("broken_snapshot.db");
-        std::fs::write(&broken_snapshot_path, b"not-a-sqlite-db").expect("write broken file");
+        repo.restore_snapshot_tables_from_file(
+            snapshot_path,
+            SNAPSHOT_SCHEMA_VERSION,
+            vec!["accounts".to_string()],
+            12,
+            "device-1".to_string(),
+            Some(1),
+        )
+        .await
+        .expect("restore snapshot");
 
-        let result = repo
+        let mut conn = get_connection(&pool).expect("conn");
+        assert!(!is_table_sync_enabled(&mut conn, "accounts").expect("accounts state"));
+        assert!(!is_table_sync_enabled(&mut conn, "ai_threads").expect("ai_threads state"));
+        let restored_at = sync_table_state::table
+            .find("accounts")
+            .select(sync_table_state::last_snapshot_restore_at)
+            .first::<Option<String>>(&mut conn)
+            .expect("accounts state row");
+        assert!(restored_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn snapshot_restore_rejects_newer_schema_version() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer).with_local_schema_version(2);
+        repo.set_cursor(15).await.expect("set cursor");
+        let snapshot_path = create_snapshot_db_with_account("acc-newer-schema");
+
+        let err = repo
             .restore_snapshot_tables_from_file(
-                broken_snapshot_path.to_string_lossy().to_string(),
+                snapshot_path,
+                3,
                 vec!["accounts".to_string()],
                 22,
-                "device-2".to_string(),
+                "device-1".to_string(),
                 Some(1),
             )
-            .await;
-        assert!(result.is_err(), "restore should fail for invalid snapshot");
+            .await
+            .expect_err("newer snapshot schema should be rejected");
+        assert!(matches!(
+            err,
+            Error::Database(DatabaseError::RestoreFailed(ref message))
+                if message.contains("schema version 3 is newer than local version 2")
+        ));
         assert_eq!(repo.get_cursor().expect("cursor"), 15);
+        let mut conn = get_connection(&pool).expect("conn");
+        let accounts_count: i64 = accounts::table
+            .select(count_star())
+            .first(&mut conn)
+            .expect("count accounts");
+        assert_eq!(accounts_count, 0);
     }
 
     #[tokio::test]
-    async fn needs_bootstrap_when_last_cycle_is_stale_cursor() {
+    async fn snapshot_restore_accepts_older_schema_version() {
         let (pool, writer) = setup_db();
-        let repo = AppSyncRepository::new(pool, writer);
-
-        repo.reset_and_mark_bootstrap_complete("device-1".to_string(), Some(1))
-            .await
-            .expect("mark bootstrap complete");
-        assert!(
-            !repo.needs_bootstrap("device-1").expect("needs bootstrap"),
-            "bootstrap should not be required immediately after completion"
-        );
+        let repo = AppSyncRepository::new(pool.clone(), writer).with_local_schema_version(2);
+        let snapshot_path = create_snapshot_db_with_account("acc-older-schema");
 
-        repo.mark_cycle_outcome("stale_cursor".to_string(), 42, None)
-            .await
-            .expect("mark stale cursor cycle");
-        assert!(
-            repo.needs_bootstrap("device-1").expect("needs bootstrap"),
-            "bootstrap should be required after stale cursor cycle"
-        );
+        repo.restore_snapshot_tables_from_file(
+            snapshot_path,
+            1,
+            vec!["accounts".to_string()],
+            22,
+            "device-1".to_string(),
+            Some(1),
+        )
+        .await
+        .expect("older snapshot schema should restore");
+        assert_eq!(repo.get_cursor().expect("cursor"), 22);
+        let mut conn = get_connection(&pool).expect("conn");
+        let restored = accounts::table
+            .select(accounts::id)
+            .load::<String>(&mut conn)
+            .expect("load accounts");
+        assert_eq!(restored, vec!["acc-older-schema".to_string()]);
     }
 
     #[tokio::test]
-    async fn local_sync_data_summary_reports_non_empty_tables() {
+    async fn snapshot_restore_fails_when_table_row_count_mismatches() {
         let (pool, writer) = setup_db();
         let repo = AppSyncRepository::new(pool.clone(), writer);
-        let baseline = repo
+        repo.set_cursor(15).await.expect("set cursor");
+        let snapshot_path = create_snapshot_db_with_account("acc-dropped");
+
+        // Simulate a silent partial copy: the row is inserted, then discarded.
+        let mut conn = get_connection(&pool).expect("conn");
+        diesel::sql_query(
+            "CREATE TRIGGER drop_restored_account AFTER INSERT ON accounts \
+             BEGIN DELETE FROM accounts WHERE id = NEW.id; END",
+        )
+        .execute(&mut conn)
+        .expect("create trigger");
+        drop(conn);
+
+        let err = repo
+            .restore_snapshot_tables_from_file(
+                snapshot_path,
+                SNAPSHOT_SCHEMA_VERSION,
+                vec!["accounts".to_string()],
+                22,
+                "device-1".to_string(),
+                Some(1),
+            )
+            .await
+            .expect_err("restore should fail verification");
+        let message = err.to_string();
+        assert!(
+            message.contains("'accounts'"),
+            "unexpected error: {message}"
+        );
+        assert!(message.contains("expected 1 rows, restored 0"));
+        assert_eq!(repo.get_cursor().expect("cursor"), 15);
+    }
+
+    #[tokio::test]
+    async fn snapshot_restore_error_keeps_existing_cursor() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer);
+        repo.set_cursor(15).await.expect("set cursor");
+
+        let broken_snapshot_path = tempdir()
+            .expect("tempdir")
+            .keep()
+            .join("broken_snapshot.db");
+        std::fs::write(&broken_snapshot_path, b"not-a-sqlite-db").expect("write broken file");
+
+        let result = repo
+            .restore_snapshot_tables_from_file(
+                broken_snapshot_path.to_string_lossy().to_string(),
+                SNAPSHOT_SCHEMA_VERSION,
+                vec!["accounts".to_string()],
+                22,
+                "device-2".to_string(),
+                Some(1),
+            )
+            .await;
+        let err = result.expect_err("restore should fail for invalid snapshot");
+        assert!(
+            err.to_string().contains("SQLite header magic"),
+            "unexpected error: {}",
+            err
+        );
+        assert_eq!(repo.get_cursor().expect("cursor"), 15);
+    }
+
+    #[tokio::test]
+    async fn needs_bootstrap_when_last_cycle_is_stale_cursor() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer);
+
+        repo.reset_and_mark_bootstrap_complete("device-1".to_string(), Some(1))
+            .await
+            .expect("mark bootstrap complete");
+        assert!(
+            !repo.needs_bootstrap("device-1").expect("needs bootstrap"),
+            "bootstrap should not be required immediately after completion"
+        );
+
+        repo.mark_cycle_outcome("stale_cursor".to_string(), 42, None)
+            .await
+            .expect("mark stale cursor cycle");
+        assert!(
+            repo.needs_bootstrap("device-1").expect("needs bootstrap"),
+            "bootstrap should be required after stale cursor cycle"
+        );
+    }
+
+    #[tokio::test]
+    async fn local_sync_data_summary_reports_non_empty_tables() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let baseline = repo
             .get_local_sync_data_summary()
             .expect("baseline sync summary");
 
@@ -2145,6 +4638,71 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn engine_errors_back_off_exponentially_until_push_succeeds() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer);
+        let retry_in_secs = |status: &SyncEngineStatus| {
+            let at = chrono::DateTime::parse_from_rfc3339(
+                status.next_retry_at.as_deref().expect("next_retry_at"),
+            )
+            .expect("rfc3339");
+            (at.with_timezone(&Utc) - Utc::now()).num_seconds()
+        };
+
+        repo.mark_engine_error("boom".to_string())
+            .await
+            .expect("mark error");
+        let status = repo.get_engine_status().expect("status");
+        assert_eq!(status.consecutive_failures, 1);
+        assert!(retry_in_secs(&status) <= 5);
+
+        // The error path also records the cycle outcome; it must not clear the backoff.
+        repo.mark_cycle_outcome("pull_error".to_string(), 3, None)
+            .await
+            .expect("mark outcome");
+        assert!(repo.get_engine_status().unwrap().next_retry_at.is_some());
+
+        for _ in 1..10 {
+            repo.mark_engine_error("boom".to_string())
+                .await
+                .expect("mark error");
+        }
+        let status = repo.get_engine_status().expect("status");
+        assert_eq!(status.consecutive_failures, 10);
+        let max_delay = wealthfolio_device_sync::engine::backoff_seconds(i32::MAX);
+        assert!(retry_in_secs(&status) > max_delay - 60);
+        assert!(retry_in_secs(&status) <= max_delay);
+
+        repo.mark_push_completed().await.expect("mark push");
+        let status = repo.get_engine_status().expect("status");
+        assert_eq!(status.consecutive_failures, 0);
+        assert_eq!(status.next_retry_at, None);
+    }
+
+    #[tokio::test]
+    async fn cycle_transfer_stats_are_reported_in_engine_status() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer);
+        assert_eq!(
+            repo.get_engine_status().unwrap().last_cycle_events_applied,
+            None
+        );
+
+        repo.mark_cycle_transfer_stats(1_024, 4_096, 12)
+            .await
+            .expect("mark stats");
+        repo.mark_cycle_outcome("ok".to_string(), 7, None)
+            .await
+            .expect("mark ok");
+
+        let status = repo.get_engine_status().expect("status");
+        assert_eq!(status.last_cycle_pushed_bytes, Some(1_024));
+        assert_eq!(status.last_cycle_pulled_bytes, Some(4_096));
+        assert_eq!(status.last_cycle_events_applied, Some(12));
+        assert_eq!(status.last_cycle_status.as_deref(), Some("ok"));
+    }
+
     #[tokio::test]
     async fn snapshot_restore_handles_source_with_extra_columns() {
         let (pool, writer) = setup_db();
@@ -2153,6 +4711,7 @@ mod tests {
 
         repo.restore_snapshot_tables_from_file(
             snapshot_path,
+            SNAPSHOT_SCHEMA_VERSION,
             vec!["assets".to_string()],
             19,
             "device-1".to_string(),
@@ -2190,6 +4749,8 @@ mod tests {
             last_event_id: "evt-local".to_string(),
             last_client_timestamp: chrono::Utc::now().to_rfc3339(),
             last_seq: 123,
+            last_device_id: None,
+            last_device_seq: None,
         })
         .await
         .expect("upsert metadata");
@@ -2204,6 +4765,7 @@ mod tests {
 
         repo.restore_snapshot_tables_from_file(
             snapshot_path,
+            SNAPSHOT_SCHEMA_VERSION,
             vec!["accounts".to_string()],
             200,
             "device-1".to_string(),
@@ -2231,6 +4793,79 @@ mod tests {
         assert_eq!(applied_count, 0);
     }
 
+    #[tokio::test]
+    async fn prune_applied_events_older_than_keeps_recent_and_uncursored_events() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let old_at = (Utc::now() - Duration::days(45)).to_rfc3339();
+        let recent_at = (Utc::now() - Duration::days(2)).to_rfc3339();
+        {
+            let mut conn = get_connection(&pool).expect("conn");
+            for (event_id, seq, applied_at) in [
+                ("evt-old-1", 10, &old_at),
+                ("evt-old-2", 11, &old_at),
+                ("evt-recent", 12, &recent_at),
+                ("evt-old-at-cursor", 20, &old_at),
+                ("evt-old-ahead", 21, &old_at),
+            ] {
+                diesel::insert_into(sync_applied_events::table)
+                    .values(SyncAppliedEventDB {
+                        event_id: event_id.to_string(),
+                        seq,
+                        entity: "account".to_string(),
+                        entity_id: "acc-prune".to_string(),
+                        applied_at: applied_at.clone(),
+                    })
+                    .execute(&mut conn)
+                    .expect("insert applied event");
+            }
+            for (event_id, recorded_at) in
+                [("evt-audit-old", &old_at), ("evt-audit-recent", &recent_at)]
+            {
+                diesel::insert_into(sync_event_audit::table)
+                    .values(SyncEventAuditDB {
+                        id: Uuid::now_v7().to_string(),
+                        entity: "account".to_string(),
+                        entity_id: "acc-prune".to_string(),
+                        event_id: event_id.to_string(),
+                        outcome: "applied".to_string(),
+                        reason: None,
+                        recorded_at: recorded_at.clone(),
+                    })
+                    .execute(&mut conn)
+                    .expect("insert audit entry");
+            }
+        }
+        repo.set_cursor(20).await.expect("set cursor");
+
+        let cutoff = (Utc::now() - Duration::days(30)).to_rfc3339();
+        let deleted = repo
+            .prune_applied_events_older_than(cutoff)
+            .await
+            .expect("prune by age");
+        assert_eq!(deleted, 2);
+
+        let mut conn = get_connection(&pool).expect("conn");
+        let mut remaining: Vec<String> = sync_applied_events::table
+            .select(sync_applied_events::event_id)
+            .load(&mut conn)
+            .expect("load applied events");
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec!["evt-old-ahead", "evt-old-at-cursor", "evt-recent"]
+        );
+        let audit = repo.list_recent_audit(10).expect("list audit");
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].event_id, "evt-audit-recent");
+
+        assert!(repo
+            .prune_applied_events_older_than("not-a-date".to_string())
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn reset_local_sync_session_clears_control_plane_and_zeroes_cursors() {
         let (pool, writer) = setup_db();
@@ -2257,6 +4892,8 @@ mod tests {
             last_event_id: "evt-dirty".to_string(),
             last_client_timestamp: chrono::Utc::now().to_rfc3339(),
             last_seq: 42,
+            last_device_id: None,
+            last_device_seq: None,
         })
         .await
         .expect("upsert metadata");
@@ -2276,6 +4913,15 @@ mod tests {
             .await
             .expect("mark engine error");
 
+        repo.record_audit(
+            SyncEntity::Account,
+            "acc-keep".to_string(),
+            "evt-audited".to_string(),
+            SyncAuditOutcome::Applied,
+            None,
+        )
+        .await
+        .expect("record audit");
         repo.reset_local_sync_session()
             .await
             .expect("reset local sync session");
@@ -2308,12 +4954,120 @@ mod tests {
             "app data must remain"
         );
         assert_eq!(repo.get_cursor().expect("cursor"), 0);
+        assert!(repo.list_recent_audit(10).expect("list audit").is_empty());
 
         let status = repo.get_engine_status().expect("engine status");
         assert_eq!(status.last_error, None);
         assert_eq!(status.last_cycle_status, None);
     }
 
+    #[tokio::test]
+    async fn reset_sync_state_clears_only_control_plane_tables() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        {
+            let mut conn = get_connection(&pool).expect("conn");
+            insert_account_for_test(&mut conn, "acc-keep").expect("insert account");
+            insert_outbox_event(
+                &mut conn,
+                OutboxWriteRequest::new(
+                    SyncEntity::Account,
+                    "acc-keep",
+                    SyncOperation::Update,
+                    serde_json::json!({ "id": "acc-keep", "name": "dirty" }),
+                ),
+            )
+            .expect("insert outbox");
+            let table_state_count: i64 = sync_table_state::table
+                .select(count_star())
+                .first(&mut conn)
+                .expect("count table state");
+            assert!(table_state_count > 0);
+        }
+        repo.upsert_entity_metadata(SyncEntityMetadata {
+            entity: SyncEntity::Account,
+            entity_id: "acc-keep".to_string(),
+            last_event_id: "evt-keep".to_string(),
+            last_client_timestamp: chrono::Utc::now().to_rfc3339(),
+            last_seq: 42,
+            last_device_id: None,
+            last_device_seq: None,
+        })
+        .await
+        .expect("upsert metadata");
+        repo.mark_applied_event(
+            "evt-applied".to_string(),
+            43,
+            SyncEntity::Account,
+            "acc-keep".to_string(),
+        )
+        .await
+        .expect("mark applied");
+        repo.reset_and_mark_bootstrap_complete("device-1".to_string(), Some(3))
+            .await
+            .expect("mark bootstrap complete");
+        repo.set_cursor(15).await.expect("set cursor");
+        repo.set_table_enabled("ai_threads".to_string(), false)
+            .await
+            .expect("disable ai_threads");
+        assert!(!repo.needs_bootstrap("device-1").expect("needs bootstrap"));
+
+        repo.record_audit(
+            SyncEntity::Account,
+            "acc-keep".to_string(),
+            "evt-audited".to_string(),
+            SyncAuditOutcome::Applied,
+            None,
+        )
+        .await
+        .expect("record audit");
+        repo.reset_sync_state().await.expect("reset sync state");
+
+        let mut conn = get_connection(&pool).expect("conn");
+        let outbox_count: i64 = sync_outbox::table
+            .select(count_star())
+            .first(&mut conn)
+            .expect("count outbox");
+        let metadata_count: i64 = sync_entity_metadata::table
+            .select(count_star())
+            .first(&mut conn)
+            .expect("count metadata");
+        let applied_count: i64 = sync_applied_events::table
+            .select(count_star())
+            .first(&mut conn)
+            .expect("count applied");
+        let table_states = sync_table_state::table
+            .load::<SyncTableStateDB>(&mut conn)
+            .expect("load table state");
+        assert_eq!(outbox_count, 0);
+        assert_eq!(metadata_count, 0);
+        assert_eq!(applied_count, 0);
+        assert!(table_states.iter().all(|state| {
+            state.last_snapshot_restore_at.is_none() && state.last_incremental_apply_at.is_none()
+        }));
+        assert!(
+            !is_table_sync_enabled(&mut conn, "ai_threads").expect("ai_threads state"),
+            "disabled tables stay disabled"
+        );
+        assert_eq!(repo.get_cursor().expect("cursor"), 0);
+        assert!(repo.list_recent_audit(10).expect("list audit").is_empty());
+
+        let configs = sync_device_config::table
+            .load::<SyncDeviceConfigDB>(&mut conn)
+            .expect("load device configs");
+        assert_eq!(configs.len(), 1, "device stays paired");
+        assert_eq!(configs[0].trust_state, "trusted");
+        assert_eq!(configs[0].last_bootstrap_at, None);
+        assert!(repo.needs_bootstrap("device-1").expect("needs bootstrap"));
+
+        assert_eq!(
+            count_account_rows(&pool, "acc-keep"),
+            1,
+            "app data must remain"
+        );
+    }
+
     #[tokio::test]
     async fn reset_and_mark_bootstrap_complete_recreates_current_device_config() {
         let (pool, writer) = setup_db();
@@ -2375,345 +5129,2015 @@ mod tests {
         assert_eq!(pending[0].payload_key_version, 3);
     }
 
-    #[test]
-    fn normalize_outbox_payload_keys_to_snake_case() {
-        let payload = normalize_outbox_payload(serde_json::json!({
-            "id": "goal-outbox-camel",
-            "targetAmount": 5000.0,
-            "isAchieved": false
-        }))
-        .expect("normalize payload");
-        assert!(payload.get("target_amount").is_some());
-        assert!(payload.get("is_achieved").is_some());
-        assert!(payload.get("targetAmount").is_none());
-        assert!(payload.get("isAchieved").is_none());
-    }
+    #[tokio::test]
+    async fn outbox_diagnostics_omit_payload() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
 
-    #[test]
-    fn normalize_outbox_payload_rejects_conflicting_aliases() {
-        let result = normalize_outbox_payload(serde_json::json!({
-            "id": "goal-outbox-conflict",
-            "isAchieved": false,
-            "is_achieved": true
-        }));
-        assert!(
-            result.is_err(),
-            "expected conflicting payload aliases to be rejected"
-        );
+        writer
+            .exec(|conn| {
+                insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::Account,
+                        "acc-diag",
+                        SyncOperation::Create,
+                        serde_json::json!({ "id": "acc-diag", "name": "secret-account-name" }),
+                    ),
+                )?;
+                Ok(())
+            })
+            .await
+            .expect("write outbox");
+
+        let rows = repo.list_outbox_diagnostics(10).expect("list diagnostics");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity_id, "acc-diag");
+        let serialized = serde_json::to_string(&rows).expect("serialize");
+        assert!(!serialized.contains("secret-account-name"));
     }
 
     #[tokio::test]
-    async fn payload_normalization_supports_camel_case_for_all_sync_tables() {
-        let (pool, _writer) = setup_db();
-        let mut conn = get_connection(&pool).expect("conn");
+    async fn compact_pending_outbox_keeps_only_newest_update() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
 
-        for table_name in APP_SYNC_TABLES {
-            let catalog = load_payload_column_catalog(&mut conn, table_name).expect("catalog");
+        let write = |op: SyncOperation, entity_id: &'static str, at: &'static str| {
+            let writer = writer.clone();
+            async move {
+                writer
+                    .exec(move |conn| {
+                        let mut request = OutboxWriteRequest::new(
+                            SyncEntity::Account,
+                            entity_id,
+                            op,
+                            serde_json::json!({ "id": entity_id, "name": at }),
+                        );
+                        request.client_timestamp = at.to_string();
+                        insert_outbox_event(conn, request)
+                    })
+                    .await
+                    .expect("write outbox")
+                    .expect("event written")
+            }
+        };
 
-            let camel_case_fields = catalog
-                .writable
-                .iter()
-                .map(|column| {
-                    (
-                        snake_to_camel_case(column),
-                        serde_json::Value::String("v".to_string()),
-                    )
+        let create = write(SyncOperation::Create, "acc-compact", "2026-02-15T00:00:00Z").await;
+        write(SyncOperation::Update, "acc-compact", "2026-02-15T00:00:01Z").await;
+        let newest = write(SyncOperation::Update, "acc-compact", "2026-02-15T00:00:03Z").await;
+        write(SyncOperation::Update, "acc-compact", "2026-02-15T00:00:02Z").await;
+        let other = write(SyncOperation::Update, "acc-other", "2026-02-15T00:00:01Z").await;
+        let delete = write(SyncOperation::Delete, "acc-compact", "2026-02-15T00:00:04Z").await;
+
+        let superseded = repo.compact_pending_outbox().await.expect("compact");
+        assert_eq!(superseded, 2);
+
+        let mut pending = repo
+            .list_pending_outbox(10)
+            .expect("list pending")
+            .into_iter()
+            .map(|event| event.event_id)
+            .collect::<Vec<_>>();
+        pending.sort();
+        let mut expected = vec![create, newest, other, delete];
+        expected.sort();
+        assert_eq!(pending, expected);
+
+        assert_eq!(repo.compact_pending_outbox().await.expect("compact"), 0);
+    }
+
+    #[tokio::test]
+    async fn outbox_events_carry_increasing_device_seq() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+
+        for name in ["First", "Second", "Third"] {
+            writer
+                .exec(move |conn| {
+                    let mut request = OutboxWriteRequest::new(
+                        SyncEntity::Account,
+                        "acc-seq",
+                        SyncOperation::Update,
+                        serde_json::json!({ "id": "acc-seq", "name": name }),
+                    );
+                    request.client_timestamp = "2026-02-15T00:00:00Z".to_string();
+                    insert_outbox_event(conn, request)
                 })
-                .collect::<Vec<_>>();
+                .await
+                .expect("write outbox")
+                .expect("event written");
+        }
 
-            let normalized = normalize_payload_fields(&mut conn, table_name, camel_case_fields)
-                .unwrap_or_else(|err| {
-                    panic!("normalize failed for table '{}': {}", table_name, err)
-                });
+        let mut seqs = repo
+            .list_pending_outbox(10)
+            .expect("list pending")
+            .into_iter()
+            .map(|event| event.device_seq.expect("device seq"))
+            .collect::<Vec<_>>();
+        seqs.sort();
+        assert_eq!(seqs, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn requeued_dead_events_return_to_pending() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+
+        let event_id = writer
+            .exec(|conn| {
+                insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::Account,
+                        "acc-dead",
+                        SyncOperation::Create,
+                        serde_json::json!({ "id": "acc-dead" }),
+                    ),
+                )
+            })
+            .await
+            .expect("write outbox")
+            .expect("event written");
+
+        repo.mark_outbox_dead(
+            vec![event_id.clone()],
+            Some("payload rejected".to_string()),
+            Some("invalid_payload".to_string()),
+        )
+        .await
+        .expect("mark dead");
+        assert!(repo
+            .list_pending_outbox(10)
+            .expect("list pending")
+            .is_empty());
+        let dead = repo.list_dead_outbox(10).expect("list dead");
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].last_error_code.as_deref(), Some("invalid_payload"));
+
+        let requeued = repo
+            .requeue_dead_outbox(vec![event_id.clone(), "not-dead".to_string()])
+            .await
+            .expect("requeue");
+        assert_eq!(requeued, 1);
+        assert!(repo.list_dead_outbox(10).expect("list dead").is_empty());
+        let pending = repo.list_pending_outbox(10).expect("list pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].event_id, event_id);
+        assert_eq!(pending[0].status, SyncOutboxStatus::Pending);
+        assert_eq!(pending[0].retry_count, 0);
+        assert_eq!(pending[0].last_error, None);
+        assert_eq!(pending[0].last_error_code, None);
+    }
+
+    #[tokio::test]
+    async fn entity_sync_lag_groups_pending_outbox_and_applied_seq() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+
+        let event_ids = writer
+            .exec(|conn| {
+                let mut ids = Vec::new();
+                for (entity, id) in [
+                    (SyncEntity::Account, "acc-lag-1"),
+                    (SyncEntity::Account, "acc-lag-2"),
+                    (SyncEntity::Account, "acc-lag-3"),
+                    (SyncEntity::Goal, "goal-lag-1"),
+                ] {
+                    ids.push(insert_outbox_event(
+                        conn,
+                        OutboxWriteRequest::new(
+                            entity,
+                            id,
+                            SyncOperation::Create,
+                            serde_json::json!({ "id": id }),
+                        ),
+                    )?);
+                }
+                Ok(ids)
+            })
+            .await
+            .expect("write outbox")
+            .into_iter()
+            .map(|id| id.expect("event written"))
+            .collect::<Vec<_>>();
+        repo.mark_outbox_sent(vec![event_ids[0].clone()])
+            .await
+            .expect("mark sent");
+        for (entity_id, last_seq) in [("acc-lag-1", 7), ("acc-lag-2", 12)] {
+            repo.upsert_entity_metadata(SyncEntityMetadata {
+                entity: SyncEntity::Account,
+                entity_id: entity_id.to_string(),
+                last_event_id: format!("evt-{}", entity_id),
+                last_client_timestamp: Utc::now().to_rfc3339(),
+                last_seq,
+                last_device_id: None,
+                last_device_seq: None,
+            })
+            .await
+            .expect("upsert metadata");
+        }
+
+        assert_eq!(
+            repo.entity_sync_lag().expect("entity lag"),
+            vec![
+                SyncEntityLag {
+                    entity: SyncEntity::Account,
+                    pending_outbox: 2,
+                    last_applied_seq: Some(12),
+                },
+                SyncEntityLag {
+                    entity: SyncEntity::Goal,
+                    pending_outbox: 1,
+                    last_applied_seq: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_counts_due_pending_events_and_remote_lag() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+        repo.set_cursor(40).await.expect("set cursor");
+
+        let event_ids = writer
+            .exec(|conn| {
+                let mut ids = Vec::new();
+                for id in ["acc-dry-1", "acc-dry-2", "acc-dry-3", "acc-dry-4"] {
+                    ids.push(insert_outbox_event(
+                        conn,
+                        OutboxWriteRequest::new(
+                            SyncEntity::Account,
+                            id,
+                            SyncOperation::Create,
+                            serde_json::json!({ "id": id }),
+                        ),
+                    )?);
+                }
+                Ok(ids)
+            })
+            .await
+            .expect("write outbox")
+            .into_iter()
+            .map(|id| id.expect("event written"))
+            .collect::<Vec<_>>();
+        repo.mark_outbox_sent(vec![event_ids[0].clone()])
+            .await
+            .expect("mark sent");
+        repo.mark_outbox_dead(vec![event_ids[1].clone()], None, None)
+            .await
+            .expect("mark dead");
+
+        let summary = repo.sync_dry_run(55).expect("dry run");
+        assert_eq!(
+            summary,
+            SyncDryRunSummary {
+                pending_push: 2,
+                local_cursor: 40,
+                server_cursor: 55,
+                remote_ahead: 15,
+            }
+        );
+        assert_eq!(repo.sync_dry_run(30).expect("dry run").remote_ahead, 0);
+        assert_eq!(repo.get_cursor().expect("cursor"), 40);
+        assert_eq!(repo.list_pending_outbox(10).expect("pending").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn disabled_table_gets_no_outbox_rows() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+        repo.set_table_enabled("ai_threads".to_string(), false)
+            .await
+            .expect("disable ai_threads");
+
+        let event_ids = writer
+            .exec(|conn| {
+                let skipped = insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::AiThread,
+                        "thread-disabled",
+                        SyncOperation::Create,
+                        serde_json::json!({ "id": "thread-disabled" }),
+                    ),
+                )?;
+                let written = insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::Account,
+                        "acc-enabled",
+                        SyncOperation::Create,
+                        serde_json::json!({ "id": "acc-enabled" }),
+                    ),
+                )?;
+                Ok((skipped, written))
+            })
+            .await
+            .expect("write outbox");
+
+        assert_eq!(event_ids.0, None);
+        assert!(event_ids.1.is_some());
+        let pending = repo.list_pending_outbox(10).expect("list pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].entity, SyncEntity::Account);
+    }
+
+    #[tokio::test]
+    async fn disabled_table_skips_replay_but_records_event() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        repo.set_table_enabled("ai_threads".to_string(), false)
+            .await
+            .expect("disable ai_threads");
+        repo.set_table_enabled("ai_messages".to_string(), false)
+            .await
+            .expect("disable ai_messages");
+
+        let applied = repo
+            .apply_remote_events_lww_batch(ai_replay_events("thread-off", "msg-off"))
+            .await
+            .expect("apply batch");
+
+        assert_eq!(applied, 0);
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-off"), 0);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-off"), 0);
+        for event in ai_replay_events("thread-off", "msg-off") {
+            assert!(repo.has_applied_event(&event.3).expect("applied lookup"));
+        }
+        assert!(repo
+            .get_entity_metadata(SyncEntity::AiThread, "thread-off")
+            .expect("metadata")
+            .is_none());
+
+        // Re-enabling lets later events through.
+        repo.set_table_enabled("ai_threads".to_string(), true)
+            .await
+            .expect("enable ai_threads");
+        repo.set_table_enabled("ai_messages".to_string(), true)
+            .await
+            .expect("enable ai_messages");
+        let applied = repo
+            .apply_remote_events_lww_batch(ai_replay_events("thread-on", "msg-on"))
+            .await
+            .expect("apply batch");
+        assert_eq!(applied, 2);
+        assert!(repo
+            .list_table_states()
+            .expect("table states")
+            .iter()
+            .all(|state| state.enabled == 1));
+    }
+
+    #[tokio::test]
+    async fn account_filter_skips_outbox_rows_for_unsynced_accounts() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+        assert!(repo
+            .set_synced_account_ids(vec!["acc-allowed".to_string()])
+            .await
+            .is_err());
+        repo.reset_and_mark_bootstrap_complete("device-1".to_string(), Some(1))
+            .await
+            .expect("bootstrap");
+        repo.set_synced_account_ids(vec!["acc-allowed".to_string()])
+            .await
+            .expect("set allow-list");
+        // Re-bootstrapping keeps the allow-list on the device config row.
+        repo.reset_and_mark_bootstrap_complete("device-1".to_string(), Some(2))
+            .await
+            .expect("re-bootstrap");
+        assert_eq!(
+            repo.get_synced_account_ids().expect("allow-list"),
+            vec!["acc-allowed".to_string()]
+        );
+
+        let event_ids = writer
+            .exec(|conn| {
+                let skipped_account = insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::Account,
+                        "acc-other",
+                        SyncOperation::Update,
+                        serde_json::json!({ "id": "acc-other" }),
+                    ),
+                )?;
+                let skipped_activity = insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::Activity,
+                        "act-other",
+                        SyncOperation::Create,
+                        serde_json::json!({ "id": "act-other", "accountId": "acc-other" }),
+                    ),
+                )?;
+                let written = insert_outbox_event(
+                    conn,
+                    OutboxWriteRequest::new(
+                        SyncEntity::Account,
+                        "acc-allowed",
+                        SyncOperation::Update,
+                        serde_json::json!({ "id": "acc-allowed" }),
+                    ),
+                )?;
+                Ok((skipped_account, skipped_activity, written))
+            })
+            .await
+            .expect("write outbox");
+
+        assert_eq!(event_ids.0, None);
+        assert_eq!(event_ids.1, None);
+        assert!(event_ids.2.is_some());
+        let pending = repo.list_pending_outbox(10).expect("list pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].entity_id, "acc-allowed");
+
+        // Clearing the allow-list syncs every account again.
+        repo.set_synced_account_ids(Vec::new())
+            .await
+            .expect("clear allow-list");
+        assert!(repo
+            .get_synced_account_ids()
+            .expect("allow-list")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn account_filter_skips_replay_for_unsynced_accounts() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        repo.reset_and_mark_bootstrap_complete("device-1".to_string(), Some(1))
+            .await
+            .expect("bootstrap");
+        repo.set_synced_account_ids(vec!["acc-allowed".to_string()])
+            .await
+            .expect("set allow-list");
+
+        let account_payload = |id: &str| {
+            serde_json::json!({
+                "id": id,
+                "name": "Remote",
+                "account_type": "cash",
+                "group": serde_json::Value::Null,
+                "currency": "USD",
+                "is_default": false,
+                "is_active": true,
+                "platform_id": serde_json::Value::Null,
+                "account_number": serde_json::Value::Null,
+                "meta": serde_json::Value::Null,
+                "provider": serde_json::Value::Null,
+                "provider_account_id": serde_json::Value::Null,
+                "is_archived": false,
+                "tracking_mode": "portfolio"
+            })
+        };
+
+        let skipped = repo
+            .apply_remote_event_lww(
+                SyncEntity::Account,
+                "acc-other".to_string(),
+                SyncOperation::Create,
+                "evt-acc-other".to_string(),
+                "2026-03-16T00:00:00Z".to_string(),
+                1,
+                account_payload("acc-other"),
+                None,
+            )
+            .await
+            .expect("apply filtered event");
+        let applied = repo
+            .apply_remote_event_lww(
+                SyncEntity::Account,
+                "acc-allowed".to_string(),
+                SyncOperation::Create,
+                "evt-acc-allowed".to_string(),
+                "2026-03-16T00:00:01Z".to_string(),
+                2,
+                account_payload("acc-allowed"),
+                None,
+            )
+            .await
+            .expect("apply allowed event");
+
+        assert!(!skipped);
+        assert!(applied);
+        assert_eq!(count_account_rows(&pool, "acc-other"), 0);
+        assert_eq!(count_account_rows(&pool, "acc-allowed"), 1);
+        assert!(!repo
+            .has_applied_event("evt-acc-other")
+            .expect("applied lookup"));
+
+        // Widening the filter rewinds the cursor so the skipped event is
+        // pulled and applied again.
+        repo.set_cursor(2).await.expect("set cursor");
+        repo.set_synced_account_ids(vec!["acc-allowed".to_string(), "acc-other".to_string()])
+            .await
+            .expect("widen allow-list");
+        assert_eq!(repo.get_cursor().expect("cursor"), 0);
+        let replayed = repo
+            .apply_remote_event_lww(
+                SyncEntity::Account,
+                "acc-other".to_string(),
+                SyncOperation::Create,
+                "evt-acc-other".to_string(),
+                "2026-03-16T00:00:00Z".to_string(),
+                1,
+                account_payload("acc-other"),
+                None,
+            )
+            .await
+            .expect("replay widened event");
+        assert!(replayed);
+        assert_eq!(count_account_rows(&pool, "acc-other"), 1);
+
+        // Nothing is left filtered out, so clearing the list keeps the cursor.
+        repo.set_cursor(2).await.expect("set cursor");
+        repo.set_synced_account_ids(Vec::new())
+            .await
+            .expect("clear allow-list");
+        assert_eq!(repo.get_cursor().expect("cursor"), 2);
+    }
+
+    #[tokio::test]
+    async fn set_table_enabled_rejects_unknown_tables() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer);
+        assert!(repo
+            .set_table_enabled("sync_outbox".to_string(), false)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn conflict_field_diff_lists_only_differing_fields() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer.clone());
+        let platform_id = "platform-conflict-1".to_string();
+
+        writer
+            .exec({
+                let platform_id = platform_id.clone();
+                move |conn| {
+                    insert_outbox_event(
+                        conn,
+                        OutboxWriteRequest::new(
+                            SyncEntity::Platform,
+                            platform_id.clone(),
+                            SyncOperation::Update,
+                            serde_json::json!({
+                                "id": platform_id,
+                                "name": "Local Name",
+                                "url": "https://broker.example",
+                                "externalId": "ext-1",
+                                "kind": "BROKERAGE",
+                                "websiteUrl": "https://local.example",
+                            }),
+                        ),
+                    )
+                }
+            })
+            .await
+            .expect("insert pending edit");
+
+        repo.apply_remote_event_lww(
+            SyncEntity::Platform,
+            platform_id.clone(),
+            SyncOperation::Update,
+            "evt-remote-conflict".to_string(),
+            "2026-02-16T00:00:00Z".to_string(),
+            1,
+            serde_json::json!({
+                "id": platform_id,
+                "name": "Remote Name",
+                "url": "https://broker.example",
+                "external_id": "ext-1",
+                "kind": "BROKERAGE",
+                "website_url": "https://remote.example",
+            }),
+            None,
+        )
+        .await
+        .expect("apply remote event");
+
+        let diff = repo
+            .conflict_field_diff(SyncEntity::Platform, &platform_id)
+            .expect("conflict diff");
+        assert_eq!(
+            diff,
+            vec![
+                FieldDiff {
+                    field: "name".to_string(),
+                    local: Some(serde_json::json!("Local Name")),
+                    remote: Some(serde_json::json!("Remote Name")),
+                },
+                FieldDiff {
+                    field: "website_url".to_string(),
+                    local: Some(serde_json::json!("https://local.example")),
+                    remote: Some(serde_json::json!("https://remote.example")),
+                },
+            ]
+        );
+        assert!(repo
+            .conflict_field_diff(SyncEntity::Platform, "platform-without-conflict")
+            .expect("empty diff")
+            .is_empty());
+    }
+
+    #[test]
+    fn normalize_outbox_payload_keys_to_snake_case() {
+        let payload = normalize_outbox_payload(serde_json::json!({
+            "id": "goal-outbox-camel",
+            "targetAmount": 5000.0,
+            "isAchieved": false
+        }))
+        .expect("normalize payload");
+        assert!(payload.get("target_amount").is_some());
+        assert!(payload.get("is_achieved").is_some());
+        assert!(payload.get("targetAmount").is_none());
+        assert!(payload.get("isAchieved").is_none());
+    }
+
+    #[test]
+    fn normalize_outbox_payload_rejects_conflicting_aliases() {
+        let result = normalize_outbox_payload(serde_json::json!({
+            "id": "goal-outbox-conflict",
+            "isAchieved": false,
+            "is_achieved": true
+        }));
+        assert!(
+            result.is_err(),
+            "expected conflicting payload aliases to be rejected"
+        );
+    }
+
+    #[tokio::test]
+    async fn payload_normalization_supports_camel_case_for_all_sync_tables() {
+        let (pool, _writer) = setup_db();
+        let mut conn = get_connection(&pool).expect("conn");
+
+        for table_name in APP_SYNC_TABLES {
+            let catalog = load_payload_column_catalog(&mut conn, table_name).expect("catalog");
+
+            let camel_case_fields = catalog
+                .writable
+                .iter()
+                .map(|column| {
+                    (
+                        snake_to_camel_case(column),
+                        serde_json::Value::String("v".to_string()),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let normalized = normalize_payload_fields(&mut conn, table_name, camel_case_fields)
+                .unwrap_or_else(|err| {
+                    panic!("normalize failed for table '{}': {}", table_name, err)
+                });
+
+            let normalized_columns = normalized
+                .iter()
+                .map(|(column, _)| column.clone())
+                .collect::<BTreeSet<_>>();
+            let expected_columns = catalog.writable.iter().cloned().collect::<BTreeSet<_>>();
+            assert_eq!(
+                normalized_columns, expected_columns,
+                "normalized columns mismatch for table '{}'",
+                table_name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn entity_mapping_targets_valid_tables_and_primary_keys() {
+        let (pool, _writer) = setup_db();
+        let mut conn = get_connection(&pool).expect("conn");
+
+        let entities = [
+            SyncEntity::Account,
+            SyncEntity::Asset,
+            SyncEntity::Quote,
+            SyncEntity::AssetTaxonomyAssignment,
+            SyncEntity::Activity,
+            SyncEntity::ActivityImportProfile,
+            SyncEntity::Goal,
+            SyncEntity::GoalsAllocation,
+            SyncEntity::AiThread,
+            SyncEntity::AiMessage,
+            SyncEntity::AiThreadTag,
+            SyncEntity::ContributionLimit,
+            SyncEntity::Platform,
+            SyncEntity::Snapshot,
+        ];
+
+        for entity in entities {
+            let (table_name, pk_name) =
+                entity_storage_mapping(&entity).expect("entity storage mapping");
+            assert!(
+                APP_SYNC_TABLES.contains(&table_name),
+                "entity {:?} mapped to non-sync table '{}'",
+                entity,
+                table_name
+            );
+
+            let catalog = load_payload_column_catalog(&mut conn, table_name).expect("catalog");
+            assert!(
+                catalog.writable.contains(pk_name) || catalog.readonly.contains(pk_name),
+                "entity {:?} PK '{}' not found in table '{}'",
+                entity,
+                pk_name,
+                table_name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_rejects_payload_with_mismatched_pk() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool, writer);
+
+        let result = repo
+            .apply_remote_event_lww(
+                SyncEntity::Account,
+                "account-entity-id".to_string(),
+                SyncOperation::Update,
+                "evt-1".to_string(),
+                "2026-02-12T00:00:00Z".to_string(),
+                1,
+                serde_json::json!({
+                    "id": "different-account-id"
+                }),
+                None,
+            )
+            .await;
+
+        assert!(result.is_err(), "expected PK mismatch to be rejected");
+    }
+
+    #[tokio::test]
+    async fn replay_applies_platform_create_then_update() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let platform_id = "platform-sync-1".to_string();
+
+        let created = repo
+            .apply_remote_event_lww(
+                SyncEntity::Platform,
+                platform_id.clone(),
+                SyncOperation::Create,
+                "evt-platform-create".to_string(),
+                "2026-02-16T00:00:00Z".to_string(),
+                1,
+                serde_json::json!({
+                    "id": platform_id,
+                    "name": "Initial Platform",
+                    "url": "https://broker.example/initial",
+                    "external_id": "ext-platform-1",
+                    "kind": "BROKERAGE",
+                    "website_url": "https://broker.example",
+                    "logo_url": "https://broker.example/logo.png"
+                }),
+                None,
+            )
+            .await
+            .expect("apply platform create");
+        assert!(created, "expected platform create to apply");
+
+        let updated = repo
+            .apply_remote_event_lww(
+                SyncEntity::Platform,
+                "platform-sync-1".to_string(),
+                SyncOperation::Update,
+                "evt-platform-update".to_string(),
+                "2026-02-16T00:00:01Z".to_string(),
+                2,
+                serde_json::json!({
+                    "id": "platform-sync-1",
+                    "name": "Renamed Platform",
+                    "url": "https://broker.example/updated",
+                    "external_id": "ext-platform-1",
+                    "kind": "BROKERAGE",
+                    "website_url": "https://broker.example/updated",
+                    "logo_url": "https://broker.example/logo-v2.png"
+                }),
+                None,
+            )
+            .await
+            .expect("apply platform update");
+        assert!(updated, "expected platform update to apply");
+        assert_eq!(count_platform_rows(&pool, "platform-sync-1"), 1);
+
+        let mut conn = get_connection(&pool).expect("conn");
+        let (name_value, url_value): (Option<String>, String) = platforms::table
+            .filter(platforms::id.eq("platform-sync-1"))
+            .select((platforms::name, platforms::url))
+            .first(&mut conn)
+            .expect("platform row");
+        assert_eq!(name_value.as_deref(), Some("Renamed Platform"));
+        assert_eq!(url_value, "https://broker.example/updated");
+    }
+
+    #[tokio::test]
+    async fn replay_accepts_camel_case_goal_payload() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let applied = repo
+            .apply_remote_event_lww(
+                SyncEntity::Goal,
+                "goal-camel-case".to_string(),
+                SyncOperation::Create,
+                "evt-goal-camel".to_string(),
+                "2026-02-19T00:00:00Z".to_string(),
+                1,
+                serde_json::json!({
+                    "id": "goal-camel-case",
+                    "title": "Emergency Fund",
+                    "description": "6 months expenses",
+                    "targetAmount": 50000.0,
+                    "isAchieved": true
+                }),
+                None,
+            )
+            .await
+            .expect("apply goal create");
+        assert!(applied, "expected goal create to apply");
+
+        let mut conn = get_connection(&pool).expect("conn");
+        let (target_amount_value, is_achieved_value): (f64, bool) = goals::table
+            .filter(goals::id.eq("goal-camel-case"))
+            .select((goals::target_amount, goals::is_achieved))
+            .first(&mut conn)
+            .expect("goal row");
+        assert_eq!(target_amount_value, 50000.0);
+        assert!(is_achieved_value);
+    }
+
+    fn goal_event(event_id: &str, client_timestamp: &str, title: &str) -> ReplayEvent {
+        (
+            SyncEntity::Goal,
+            "goal-strategy".to_string(),
+            SyncOperation::Update,
+            event_id.to_string(),
+            client_timestamp.to_string(),
+            1,
+            serde_json::json!({
+                "id": "goal-strategy",
+                "title": title,
+                "targetAmount": 1000.0,
+                "isAchieved": false
+            }),
+            None,
+        )
+    }
+
+    fn goal_title(pool: &Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>) -> String {
+        let mut conn = get_connection(pool).expect("conn");
+        goals::table
+            .filter(goals::id.eq("goal-strategy"))
+            .select(goals::title)
+            .first(&mut conn)
+            .expect("goal row")
+    }
+
+    #[tokio::test]
+    async fn same_timestamp_events_from_one_device_apply_in_sequence_order() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let from_device = |event_id: &str, title: &str, device_id: &str, device_seq: i64| {
+            let mut event = goal_event(event_id, "2026-02-19T00:00:00Z", title);
+            event.7 = Some((device_id.to_string(), device_seq));
+            event
+        };
+
+        // The later emission arrives first and has the lower event id.
+        repo.apply_remote_events_lww_batch(vec![from_device("evt-0000", "Second", "dev-a", 2)])
+            .await
+            .expect("apply second emission");
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![from_device("evt-ffff", "First", "dev-a", 1)])
+            .await
+            .expect("apply first emission");
+        assert_eq!(applied, 0);
+        assert_eq!(goal_title(&pool), "Second");
+        let metadata = repo
+            .get_entity_metadata(SyncEntity::Goal, "goal-strategy")
+            .expect("metadata")
+            .expect("metadata row");
+        assert_eq!(metadata.last_device_id.as_deref(), Some("dev-a"));
+        assert_eq!(metadata.last_device_seq, Some(2));
+
+        // A tie with another device still falls back to the event id.
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![from_device("evt-1111", "Other", "dev-b", 1)])
+            .await
+            .expect("apply other device");
+        assert_eq!(applied, 1);
+        assert_eq!(goal_title(&pool), "Other");
+    }
+
+    #[tokio::test]
+    async fn local_wins_skips_remote_update_when_local_metadata_exists() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer)
+            .with_conflict_strategy(SyncEntity::Goal, ConflictStrategy::LocalWins);
+        assert_eq!(
+            repo.conflict_strategy(SyncEntity::Goal),
+            ConflictStrategy::LocalWins
+        );
+        assert_eq!(
+            repo.conflict_strategy(SyncEntity::ContributionLimit),
+            ConflictStrategy::LastWriterWins
+        );
+
+        // No local metadata yet: the first remote version is taken.
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![goal_event(
+                "evt-goal-1",
+                "2026-02-19T00:00:00Z",
+                "Local",
+            )])
+            .await
+            .expect("apply first goal event");
+        assert_eq!(applied, 1);
+
+        // A newer remote update loses to the existing local row.
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![goal_event(
+                "evt-goal-2",
+                "2026-02-19T00:00:05Z",
+                "Remote",
+            )])
+            .await
+            .expect("apply second goal event");
+        assert_eq!(applied, 0);
+        assert_eq!(goal_title(&pool), "Local");
+        assert!(repo
+            .has_applied_event("evt-goal-2")
+            .expect("applied lookup"));
+        let metadata = repo
+            .get_entity_metadata(SyncEntity::Goal, "goal-strategy")
+            .expect("metadata")
+            .expect("metadata row");
+        assert_eq!(metadata.last_event_id, "evt-goal-1");
+    }
+
+    #[tokio::test]
+    async fn lww_skipped_event_is_audited() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        repo.apply_remote_events_lww_batch(vec![goal_event(
+            "evt-goal-new",
+            "2026-02-19T00:00:05Z",
+            "Newer",
+        )])
+        .await
+        .expect("apply newer goal event");
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![goal_event(
+                "evt-goal-old",
+                "2026-02-19T00:00:00Z",
+                "Older",
+            )])
+            .await
+            .expect("apply older goal event");
+        assert_eq!(applied, 0);
+        assert_eq!(goal_title(&pool), "Newer");
+
+        let audit = repo.list_recent_audit(10).expect("list audit");
+        assert_eq!(audit.len(), 2);
+        assert_eq!(audit[0].event_id, "evt-goal-old");
+        assert_eq!(audit[0].entity, SyncEntity::Goal);
+        assert_eq!(audit[0].outcome, SyncAuditOutcome::SkippedLww);
+        assert!(audit[0]
+            .reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("evt-goal-new")));
+        assert_eq!(audit[1].event_id, "evt-goal-new");
+        assert_eq!(audit[1].outcome, SyncAuditOutcome::Applied);
+        assert_eq!(audit[1].reason, None);
+    }
+
+    #[tokio::test]
+    async fn rejected_event_audit_survives_batch_rollback() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let result = repo
+            .apply_remote_events_lww_batch(vec![
+                goal_event("evt-goal-ok", "2026-02-19T00:00:00Z", "Valid"),
+                (
+                    SyncEntity::Account,
+                    "account-entity-id".to_string(),
+                    SyncOperation::Update,
+                    "evt-bad-pk".to_string(),
+                    "2026-02-19T00:00:01Z".to_string(),
+                    2,
+                    serde_json::json!({ "id": "different-account-id" }),
+                    None,
+                ),
+            ])
+            .await;
+        assert!(result.is_err(), "expected PK mismatch to be rejected");
+        assert_eq!(count_rows(&pool, "goals", "goal-strategy"), 0);
+
+        let audit = repo.list_recent_audit(10).expect("list audit");
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].event_id, "evt-bad-pk");
+        assert_eq!(audit[0].outcome, SyncAuditOutcome::RejectedValidation);
+        assert!(audit[0]
+            .reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("does not match entity_id")));
+    }
+
+    #[tokio::test]
+    async fn remote_wins_applies_older_remote_update() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer)
+            .with_conflict_strategy(SyncEntity::Goal, ConflictStrategy::RemoteWins);
+
+        repo.apply_remote_events_lww_batch(vec![goal_event(
+            "evt-goal-new",
+            "2026-02-19T00:00:05Z",
+            "Newer",
+        )])
+        .await
+        .expect("apply newer goal event");
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![goal_event(
+                "evt-goal-old",
+                "2026-02-19T00:00:00Z",
+                "Older",
+            )])
+            .await
+            .expect("apply older goal event");
+
+        assert_eq!(applied, 1);
+        assert_eq!(goal_title(&pool), "Older");
+    }
+
+    #[tokio::test]
+    async fn replay_accepts_camel_case_non_id_primary_key_payload() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let mut conn = get_connection(&pool).expect("conn");
+        insert_account_for_test(&mut conn, "acc-import-profile").expect("insert account");
+
+        let applied = repo
+            .apply_remote_event_lww(
+                SyncEntity::ActivityImportProfile,
+                "acc-import-profile".to_string(),
+                SyncOperation::Create,
+                "evt-import-profile-camel".to_string(),
+                "2026-02-19T00:00:00Z".to_string(),
+                1,
+                serde_json::json!({
+                    "accountId": "acc-import-profile",
+                    "name": "Broker Mapping",
+                    "config": "{\"rules\":[]}",
+                    "createdAt": "2026-02-19 00:00:00",
+                    "updatedAt": "2026-02-19 00:00:00"
+                }),
+                None,
+            )
+            .await
+            .expect("apply import profile create");
+        assert!(applied, "expected import profile create to apply");
+
+        let name_value: String = activity_import_profiles::table
+            .filter(activity_import_profiles::account_id.eq("acc-import-profile"))
+            .select(activity_import_profiles::name)
+            .first(&mut conn)
+            .expect("import profile row");
+        assert_eq!(name_value, "Broker Mapping");
+    }
+
+    #[tokio::test]
+    async fn replay_batch_applies_out_of_order_account_and_platform_events() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let applied = repo
+            .apply_remote_events_lww_batch(vec![
+                (
+                    SyncEntity::Account,
+                    "acc-batch-platform".to_string(),
+                    SyncOperation::Create,
+                    "evt-account-create".to_string(),
+                    "2026-02-17T00:00:00Z".to_string(),
+                    10,
+                    serde_json::json!({
+                        "id": "acc-batch-platform",
+                        "name": "Batch Account",
+                        "account_type": "cash",
+                        "group": serde_json::Value::Null,
+                        "currency": "USD",
+                        "is_default": false,
+                        "is_active": true,
+                        "platform_id": "platform-batch",
+                        "account_number": serde_json::Value::Null,
+                        "meta": serde_json::Value::Null,
+                        "provider": serde_json::Value::Null,
+                        "provider_account_id": serde_json::Value::Null,
+                        "is_archived": false,
+                        "tracking_mode": "portfolio"
+                    }),
+                    None,
+                ),
+                (
+                    SyncEntity::Platform,
+                    "platform-batch".to_string(),
+                    SyncOperation::Create,
+                    "evt-platform-create".to_string(),
+                    "2026-02-17T00:00:01Z".to_string(),
+                    11,
+                    serde_json::json!({
+                        "id": "platform-batch",
+                        "name": "Batch Platform",
+                        "url": "https://batch.example",
+                        "external_id": serde_json::Value::Null,
+                        "kind": "BROKERAGE",
+                        "website_url": serde_json::Value::Null,
+                        "logo_url": serde_json::Value::Null
+                    }),
+                    None,
+                ),
+            ])
+            .await
+            .expect("apply replay batch");
+
+        assert_eq!(applied, 2, "both events should apply in one batch");
+        assert_eq!(count_account_rows(&pool, "acc-batch-platform"), 1);
+        assert_eq!(count_platform_rows(&pool, "platform-batch"), 1);
+
+        let mut conn = get_connection(&pool).expect("conn");
+        let account_platform_id: Option<String> = accounts::table
+            .filter(accounts::id.eq("acc-batch-platform"))
+            .select(accounts::platform_id)
+            .first(&mut conn)
+            .expect("account row");
+        assert_eq!(account_platform_id.as_deref(), Some("platform-batch"));
+    }
+
+    fn ai_replay_events(thread_id: &str, message_id: &str) -> Vec<ReplayEvent> {
+        vec![
+            (
+                SyncEntity::AiMessage,
+                message_id.to_string(),
+                SyncOperation::Create,
+                format!("evt-{message_id}"),
+                "2026-02-17T00:00:02Z".to_string(),
+                20,
+                serde_json::json!({
+                    "id": message_id,
+                    "thread_id": thread_id,
+                    "role": "user",
+                    "content_json": "{}",
+                    "created_at": "2026-02-17T00:00:02Z"
+                }),
+                None,
+            ),
+            (
+                SyncEntity::AiThread,
+                thread_id.to_string(),
+                SyncOperation::Create,
+                format!("evt-{thread_id}"),
+                "2026-02-17T00:00:01Z".to_string(),
+                21,
+                serde_json::json!({
+                    "id": thread_id,
+                    "title": "Replay Thread",
+                    "created_at": "2026-02-17T00:00:01Z",
+                    "updated_at": "2026-02-17T00:00:01Z",
+                    "config_snapshot": serde_json::Value::Null,
+                    "is_pinned": 0
+                }),
+                None,
+            ),
+        ]
+    }
+
+    fn count_rows(
+        pool: &Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        table: &str,
+        id: &str,
+    ) -> i64 {
+        let mut conn = get_connection(pool).expect("conn");
+        diesel::sql_query(format!(
+            "SELECT COUNT(*) AS count FROM {} WHERE id = '{}'",
+            quote_identifier(table),
+            escape_sqlite_str(id)
+        ))
+        .get_result::<TableRowCountResult>(&mut conn)
+        .expect("count rows")
+        .count
+    }
+
+    #[tokio::test]
+    async fn replay_batch_applies_mixed_entity_groups() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer).with_split_replay_groups(true);
+        let mut conn = get_connection(&pool).expect("conn");
+        insert_account_for_test(&mut conn, "acc-mixed").expect("insert account");
+        drop(conn);
+
+        let mut events = ai_replay_events("thread-mixed", "msg-mixed");
+        events.push((
+            SyncEntity::Account,
+            "acc-mixed".to_string(),
+            SyncOperation::Update,
+            "evt-account-mixed".to_string(),
+            "2026-02-17T00:00:03Z".to_string(),
+            22,
+            serde_json::json!({
+                "id": "acc-mixed",
+                "name": "Renamed",
+                "account_type": "cash",
+                "group": serde_json::Value::Null,
+                "currency": "USD",
+                "is_default": false,
+                "is_active": true,
+                "platform_id": serde_json::Value::Null,
+                "account_number": serde_json::Value::Null,
+                "meta": serde_json::Value::Null,
+                "provider": serde_json::Value::Null,
+                "provider_account_id": serde_json::Value::Null,
+                "is_archived": false,
+                "tracking_mode": "portfolio"
+            }),
+            None,
+        ));
+
+        let applied = repo
+            .apply_remote_events_lww_batch(events)
+            .await
+            .expect("apply mixed batch");
+
+        assert_eq!(applied, 3);
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-mixed"), 1);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-mixed"), 1);
+        let mut conn = get_connection(&pool).expect("conn");
+        let name: String = accounts::table
+            .filter(accounts::id.eq("acc-mixed"))
+            .select(accounts::name)
+            .first(&mut conn)
+            .expect("account row");
+        assert_eq!(name, "Renamed");
+    }
+
+    #[tokio::test]
+    async fn replay_batch_is_one_transaction_by_default() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let mut events = ai_replay_events("thread-single", "msg-single");
+        events.push((
+            SyncEntity::GoalsAllocation,
+            "alloc-single".to_string(),
+            SyncOperation::Create,
+            "evt-alloc-single".to_string(),
+            "2026-02-17T00:00:03Z".to_string(),
+            22,
+            serde_json::json!({
+                "id": "alloc-single",
+                "percent_allocation": 50,
+                "goal_id": "goal-missing",
+                "account_id": "acc-missing"
+            }),
+            None,
+        ));
+
+        assert!(repo.apply_remote_events_lww_batch(events).await.is_err());
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-single"), 0);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-single"), 0);
+    }
+
+    #[tokio::test]
+    async fn replay_batch_coupled_group_still_validates_foreign_keys_at_commit() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer).with_split_replay_groups(true);
+
+        let mut events = ai_replay_events("thread-fk", "msg-fk");
+        events.push((
+            SyncEntity::GoalsAllocation,
+            "alloc-orphan".to_string(),
+            SyncOperation::Create,
+            "evt-alloc-orphan".to_string(),
+            "2026-02-17T00:00:03Z".to_string(),
+            22,
+            serde_json::json!({
+                "id": "alloc-orphan",
+                "percent_allocation": 50,
+                "goal_id": "goal-missing",
+                "account_id": "acc-missing"
+            }),
+            None,
+        ));
+
+        let result = repo.apply_remote_events_lww_batch(events).await;
+        assert!(
+            result.is_err(),
+            "dangling allocation FKs must fail at commit"
+        );
+        assert_eq!(count_rows(&pool, "goals_allocation", "alloc-orphan"), 0);
+        // The independent AI group committed in its own transaction.
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-fk"), 1);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-fk"), 1);
+    }
+
+    #[tokio::test]
+    async fn replay_batch_names_rows_with_dangling_foreign_keys() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let events = vec![(
+            SyncEntity::Activity,
+            "act-orphan".to_string(),
+            SyncOperation::Create,
+            "evt-act-orphan".to_string(),
+            "2026-02-17T00:00:01Z".to_string(),
+            30,
+            serde_json::json!({
+                "id": "act-orphan",
+                "account_id": "acc-never-arrives",
+                "activity_type": "DEPOSIT",
+                "activity_date": "2026-02-17",
+                "amount": "100",
+                "currency": "USD",
+                "created_at": "2026-02-17T00:00:01Z",
+                "updated_at": "2026-02-17T00:00:01Z"
+            }),
+            None,
+        )];
+
+        let err = repo
+            .apply_remote_events_lww_batch(events)
+            .await
+            .expect_err("dangling account reference must fail");
+        let message = err.to_string();
+        assert!(
+            message.contains("Foreign key violation")
+                && message.contains("activity:act-orphan -> accounts"),
+            "unexpected error: {message}"
+        );
+        assert_eq!(count_rows(&pool, "activities", "act-orphan"), 0);
+    }
 
-            let normalized_columns = normalized
-                .iter()
-                .map(|(column, _)| column.clone())
-                .collect::<BTreeSet<_>>();
-            let expected_columns = catalog.writable.iter().cloned().collect::<BTreeSet<_>>();
-            assert_eq!(
-                normalized_columns, expected_columns,
-                "normalized columns mismatch for table '{}'",
-                table_name
-            );
+    #[tokio::test]
+    async fn replay_batch_ignores_dangling_rows_that_predate_it() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        {
+            let mut conn = get_connection(&pool).expect("conn");
+            insert_account_for_test(&mut conn, "acc-fk-ok").expect("insert account");
+            diesel::sql_query("PRAGMA foreign_keys = OFF")
+                .execute(&mut conn)
+                .expect("disable fks");
+            diesel::sql_query(
+                "INSERT INTO goals_allocation (id, percent_allocation, goal_id, account_id) \
+                 VALUES ('alloc-stale', 10, 'goal-gone', 'acc-gone')",
+            )
+            .execute(&mut conn)
+            .expect("insert dangling allocation");
+            diesel::sql_query("PRAGMA foreign_keys = ON")
+                .execute(&mut conn)
+                .expect("enable fks");
         }
+
+        let events = vec![
+            (
+                SyncEntity::Goal,
+                "goal-fk-ok".to_string(),
+                SyncOperation::Create,
+                "evt-goal-fk-ok".to_string(),
+                "2026-02-17T00:00:01Z".to_string(),
+                40,
+                serde_json::json!({
+                    "id": "goal-fk-ok",
+                    "title": "Emergency Fund",
+                    "target_amount": 1000.0,
+                    "is_achieved": false
+                }),
+                None,
+            ),
+            (
+                SyncEntity::GoalsAllocation,
+                "alloc-fk-ok".to_string(),
+                SyncOperation::Create,
+                "evt-alloc-fk-ok".to_string(),
+                "2026-02-17T00:00:02Z".to_string(),
+                41,
+                serde_json::json!({
+                    "id": "alloc-fk-ok",
+                    "percent_allocation": 50,
+                    "goal_id": "goal-fk-ok",
+                    "account_id": "acc-fk-ok"
+                }),
+                None,
+            ),
+        ];
+
+        let applied = repo
+            .apply_remote_events_lww_batch(events)
+            .await
+            .expect("pre-existing dangling rows must not block replay");
+        assert_eq!(applied, 2);
+        assert_eq!(count_rows(&pool, "goals_allocation", "alloc-fk-ok"), 1);
     }
 
     #[tokio::test]
-    async fn entity_mapping_targets_valid_tables_and_primary_keys() {
-        let (pool, _writer) = setup_db();
-        let mut conn = get_connection(&pool).expect("conn");
+    async fn replay_batch_with_cursor_commits_events_and_cursor_together() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        repo.set_cursor(20).await.expect("set cursor");
 
-        let entities = [
-            SyncEntity::Account,
-            SyncEntity::Asset,
-            SyncEntity::Quote,
-            SyncEntity::AssetTaxonomyAssignment,
-            SyncEntity::Activity,
-            SyncEntity::ActivityImportProfile,
-            SyncEntity::Goal,
+        let applied = repo
+            .apply_remote_events_lww_batch_with_cursor(
+                ai_replay_events("thread-cur", "msg-cur"),
+                25,
+            )
+            .await
+            .expect("apply batch with cursor");
+
+        assert_eq!(applied, 2);
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-cur"), 1);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-cur"), 1);
+        assert_eq!(repo.get_cursor().expect("cursor"), 25);
+    }
+
+    #[tokio::test]
+    async fn replay_batch_with_cursor_rolls_back_cursor_on_failure() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        repo.set_cursor(20).await.expect("set cursor");
+
+        let mut events = ai_replay_events("thread-cur-fk", "msg-cur-fk");
+        events.push((
             SyncEntity::GoalsAllocation,
-            SyncEntity::AiThread,
-            SyncEntity::AiMessage,
-            SyncEntity::AiThreadTag,
-            SyncEntity::ContributionLimit,
-            SyncEntity::Platform,
-            SyncEntity::Snapshot,
-        ];
+            "alloc-cur-orphan".to_string(),
+            SyncOperation::Create,
+            "evt-alloc-cur-orphan".to_string(),
+            "2026-02-17T00:00:03Z".to_string(),
+            22,
+            serde_json::json!({
+                "id": "alloc-cur-orphan",
+                "percent_allocation": 50,
+                "goal_id": "goal-missing",
+                "account_id": "acc-missing"
+            }),
+            None,
+        ));
 
-        for entity in entities {
-            let (table_name, pk_name) =
-                entity_storage_mapping(&entity).expect("entity storage mapping");
-            assert!(
-                APP_SYNC_TABLES.contains(&table_name),
-                "entity {:?} mapped to non-sync table '{}'",
-                entity,
-                table_name
-            );
+        let result = repo
+            .apply_remote_events_lww_batch_with_cursor(events, 25)
+            .await;
 
-            let catalog = load_payload_column_catalog(&mut conn, table_name).expect("catalog");
-            assert!(
-                catalog.writable.contains(pk_name) || catalog.readonly.contains(pk_name),
-                "entity {:?} PK '{}' not found in table '{}'",
-                entity,
-                pk_name,
-                table_name
-            );
-        }
+        assert!(
+            result.is_err(),
+            "dangling allocation FKs must fail at commit"
+        );
+        assert_eq!(repo.get_cursor().expect("cursor"), 20);
+        // The whole batch shares the cursor's transaction, so nothing landed.
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-cur-fk"), 0);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-cur-fk"), 0);
+        assert!(!repo
+            .has_applied_event("evt-alloc-cur-orphan")
+            .expect("applied lookup"));
     }
 
     #[tokio::test]
-    async fn replay_rejects_payload_with_mismatched_pk() {
+    async fn split_replay_commits_cursor_with_coupled_group() {
         let (pool, writer) = setup_db();
-        let repo = AppSyncRepository::new(pool, writer);
+        let repo = AppSyncRepository::new(pool.clone(), writer).with_split_replay_groups(true);
+        repo.set_cursor(20).await.expect("set cursor");
+
+        let mut events = ai_replay_events("thread-split-cur", "msg-split-cur");
+        events.push((
+            SyncEntity::GoalsAllocation,
+            "alloc-split-cur".to_string(),
+            SyncOperation::Create,
+            "evt-alloc-split-cur".to_string(),
+            "2026-02-17T00:00:03Z".to_string(),
+            22,
+            serde_json::json!({
+                "id": "alloc-split-cur",
+                "percent_allocation": 50,
+                "goal_id": "goal-missing",
+                "account_id": "acc-missing"
+            }),
+            None,
+        ));
 
         let result = repo
-            .apply_remote_event_lww(
-                SyncEntity::Account,
-                "account-entity-id".to_string(),
-                SyncOperation::Update,
-                "evt-1".to_string(),
-                "2026-02-12T00:00:00Z".to_string(),
-                1,
-                serde_json::json!({
-                    "id": "different-account-id"
-                }),
-            )
+            .apply_remote_events_lww_batch_with_cursor(events, 25)
             .await;
 
-        assert!(result.is_err(), "expected PK mismatch to be rejected");
+        assert!(result.is_err(), "dangling allocation FKs must fail");
+        // The independent group committed, but the cursor stays with the
+        // rolled-back coupled group.
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-split-cur"), 1);
+        assert_eq!(repo.get_cursor().expect("cursor"), 20);
+
+        let applied = repo
+            .apply_remote_events_lww_batch_with_cursor(Vec::new(), 25)
+            .await
+            .expect("advance cursor");
+        assert_eq!(applied, 0);
+        assert_eq!(repo.get_cursor().expect("cursor"), 25);
     }
 
     #[tokio::test]
-    async fn replay_applies_platform_create_then_update() {
+    async fn replay_rejects_entities_outside_allowlist() {
         let (pool, writer) = setup_db();
-        let repo = AppSyncRepository::new(pool.clone(), writer);
-        let platform_id = "platform-sync-1".to_string();
+        let allowlist: Vec<&str> = APP_SYNC_TABLES
+            .iter()
+            .copied()
+            .filter(|table| *table != "ai_messages")
+            .collect();
+        let repo = AppSyncRepository::new(pool.clone(), writer).with_replay_allowlist(&allowlist);
 
-        let created = repo
+        let mut events = ai_replay_events("thread-allow", "msg-denied");
+        events.push((
+            SyncEntity::Account,
+            "acc-allowed".to_string(),
+            SyncOperation::Create,
+            "evt-account-allowed".to_string(),
+            "2026-02-17T00:00:03Z".to_string(),
+            22,
+            serde_json::json!({
+                "id": "acc-allowed",
+                "name": "Allowed",
+                "account_type": "cash",
+                "group": serde_json::Value::Null,
+                "currency": "USD",
+                "is_default": false,
+                "is_active": true,
+                "platform_id": serde_json::Value::Null,
+                "account_number": serde_json::Value::Null,
+                "meta": serde_json::Value::Null,
+                "provider": serde_json::Value::Null,
+                "provider_account_id": serde_json::Value::Null,
+                "is_archived": false,
+                "tracking_mode": "portfolio"
+            }),
+            None,
+        ));
+
+        let applied = repo
+            .apply_remote_events_lww_batch(events)
+            .await
+            .expect("apply batch");
+
+        assert_eq!(applied, 2);
+        assert_eq!(count_account_rows(&pool, "acc-allowed"), 1);
+        assert_eq!(count_rows(&pool, "ai_threads", "thread-allow"), 1);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-denied"), 0);
+
+        let single = repo
             .apply_remote_event_lww(
-                SyncEntity::Platform,
-                platform_id.clone(),
+                SyncEntity::AiMessage,
+                "msg-denied-single".to_string(),
                 SyncOperation::Create,
-                "evt-platform-create".to_string(),
-                "2026-02-16T00:00:00Z".to_string(),
-                1,
+                "evt-msg-denied-single".to_string(),
+                "2026-02-17T00:00:04Z".to_string(),
+                23,
                 serde_json::json!({
-                    "id": platform_id,
-                    "name": "Initial Platform",
-                    "url": "https://broker.example/initial",
-                    "external_id": "ext-platform-1",
-                    "kind": "BROKERAGE",
-                    "website_url": "https://broker.example",
-                    "logo_url": "https://broker.example/logo.png"
+                    "id": "msg-denied-single",
+                    "thread_id": "thread-allow",
+                    "role": "user",
+                    "content_json": "{}",
+                    "created_at": "2026-02-17T00:00:04Z"
                 }),
+                None,
             )
             .await
-            .expect("apply platform create");
-        assert!(created, "expected platform create to apply");
+            .expect("apply single");
+        assert!(!single);
+        assert_eq!(count_rows(&pool, "ai_messages", "msg-denied-single"), 0);
+    }
 
-        let updated = repo
+    #[tokio::test]
+    async fn drift_detection_flags_out_of_band_edits_only() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let snapshot_path = create_snapshot_db_with_account("acc-baseline");
+
+        repo.restore_snapshot_tables_from_file(
+            snapshot_path,
+            SNAPSHOT_SCHEMA_VERSION,
+            vec!["accounts".to_string()],
+            10,
+            "device-1".to_string(),
+            Some(1),
+        )
+        .await
+        .expect("restore snapshot");
+        assert!(repo.detect_drift().await.expect("detect").is_empty());
+
+        // A replayed remote event explains its change.
+        let applied = repo
             .apply_remote_event_lww(
-                SyncEntity::Platform,
-                "platform-sync-1".to_string(),
+                SyncEntity::Account,
+                "acc-baseline".to_string(),
                 SyncOperation::Update,
-                "evt-platform-update".to_string(),
-                "2026-02-16T00:00:01Z".to_string(),
-                2,
+                "evt-baseline-rename".to_string(),
+                "2026-02-17T00:00:00Z".to_string(),
+                11,
                 serde_json::json!({
-                    "id": "platform-sync-1",
-                    "name": "Renamed Platform",
-                    "url": "https://broker.example/updated",
-                    "external_id": "ext-platform-1",
-                    "kind": "BROKERAGE",
-                    "website_url": "https://broker.example/updated",
-                    "logo_url": "https://broker.example/logo-v2.png"
+                    "id": "acc-baseline",
+                    "name": "Renamed By Sync",
+                    "account_type": "cash",
+                    "group": serde_json::Value::Null,
+                    "currency": "USD",
+                    "is_default": true,
+                    "is_active": true,
+                    "platform_id": serde_json::Value::Null,
+                    "account_number": serde_json::Value::Null,
+                    "meta": serde_json::Value::Null,
+                    "provider": serde_json::Value::Null,
+                    "provider_account_id": serde_json::Value::Null,
+                    "is_archived": false,
+                    "tracking_mode": "portfolio"
                 }),
+                None,
             )
             .await
-            .expect("apply platform update");
-        assert!(updated, "expected platform update to apply");
-        assert_eq!(count_platform_rows(&pool, "platform-sync-1"), 1);
+            .expect("apply event");
+        assert!(applied);
+        assert!(repo.detect_drift().await.expect("detect").is_empty());
 
+        // A direct edit with no sync event is drift.
         let mut conn = get_connection(&pool).expect("conn");
-        let (name_value, url_value): (Option<String>, String) = platforms::table
-            .filter(platforms::id.eq("platform-sync-1"))
-            .select((platforms::name, platforms::url))
-            .first(&mut conn)
-            .expect("platform row");
-        assert_eq!(name_value.as_deref(), Some("Renamed Platform"));
-        assert_eq!(url_value, "https://broker.example/updated");
+        diesel::sql_query("UPDATE accounts SET name = 'Tampered' WHERE id = 'acc-baseline'")
+            .execute(&mut conn)
+            .expect("manual edit");
+        drop(conn);
+
+        let drift = repo.detect_drift().await.expect("detect");
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].table, "accounts");
+        assert_eq!(drift[0].baseline_rows, 1);
+        assert_eq!(drift[0].current_rows, 1);
+        assert_ne!(drift[0].baseline_checksum, drift[0].current_checksum);
     }
 
     #[tokio::test]
-    async fn replay_accepts_camel_case_goal_payload() {
+    async fn drift_detection_skips_tables_whose_local_edits_are_not_synced() {
         let (pool, writer) = setup_db();
         let repo = AppSyncRepository::new(pool.clone(), writer);
+        let snapshot_path = create_snapshot_db_with_account("acc-unsynced");
 
-        let applied = repo
-            .apply_remote_event_lww(
-                SyncEntity::Goal,
-                "goal-camel-case".to_string(),
-                SyncOperation::Create,
-                "evt-goal-camel".to_string(),
-                "2026-02-19T00:00:00Z".to_string(),
-                1,
-                serde_json::json!({
-                    "id": "goal-camel-case",
-                    "title": "Emergency Fund",
-                    "description": "6 months expenses",
-                    "targetAmount": 50000.0,
-                    "isAchieved": true
-                }),
+        repo.restore_snapshot_tables_from_file(
+            snapshot_path,
+            SNAPSHOT_SCHEMA_VERSION,
+            vec!["accounts".to_string()],
+            10,
+            "device-1".to_string(),
+            Some(1),
+        )
+        .await
+        .expect("restore snapshot");
+
+        let edit = |name: &str| {
+            let mut conn = get_connection(&pool).expect("conn");
+            diesel::sql_query(format!(
+                "UPDATE accounts SET name = '{name}' WHERE id = 'acc-unsynced'"
+            ))
+            .execute(&mut conn)
+            .expect("local edit");
+        };
+
+        // A disabled table writes no outbox rows, so its edits are not drift.
+        repo.set_table_enabled("accounts".to_string(), false)
+            .await
+            .expect("disable accounts");
+        edit("Edited While Disabled");
+        assert!(repo.detect_drift().await.expect("detect").is_empty());
+
+        // Re-enabling re-baselines, even when no check ran while disabled.
+        edit("Edited Again While Disabled");
+        repo.set_table_enabled("accounts".to_string(), true)
+            .await
+            .expect("enable accounts");
+        assert!(repo.detect_drift().await.expect("detect").is_empty());
+
+        // Nor are edits to account-scoped tables under an allow-list.
+        repo.set_synced_account_ids(vec!["acc-other".to_string()])
+            .await
+            .expect("set allow-list");
+        edit("Edited While Filtered");
+        assert!(repo.detect_drift().await.expect("detect").is_empty());
+    }
+
+    #[tokio::test]
+    async fn snapshot_export_returns_sqlite_image() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let mut conn = get_connection(&pool).expect("conn");
+        insert_account_for_test(&mut conn, "acc-export").expect("insert account");
+
+        let payload = repo
+            .export_snapshot_sqlite_image(vec!["accounts".to_string()])
+            .await
+            .expect("export snapshot");
+        assert!(
+            payload.starts_with(b"SQLite format 3\0"),
+            "expected exported payload to be sqlite image"
+        );
+    }
+
+    #[tokio::test]
+    async fn plaintext_snapshot_export_writes_openable_database() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let mut conn = get_connection(&pool).expect("conn");
+        for id in ["acc-backup-1", "acc-backup-2"] {
+            insert_account_for_test(&mut conn, id).expect("insert account");
+        }
+        drop(conn);
+
+        let path = tempdir().expect("tempdir").keep().join("backup.db");
+        let written = repo
+            .export_plaintext_snapshot_to_file(path.to_string_lossy().to_string())
+            .await
+            .expect("export backup");
+        assert_eq!(
+            written as u64,
+            std::fs::metadata(&path).expect("backup file").len()
+        );
+        assert!(!path.with_extension("partial").exists());
+
+        let mut backup_conn =
+            SqliteConnection::establish(path.to_string_lossy().as_ref()).expect("open backup");
+        let ids = diesel::sql_query("SELECT id AS row_text FROM accounts ORDER BY id")
+            .load::<RowTextResult>(&mut backup_conn)
+            .expect("load accounts")
+            .into_iter()
+            .map(|row| row.row_text)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["acc-backup-1", "acc-backup-2"]);
+    }
+
+    #[tokio::test]
+    async fn gzip_snapshot_export_round_trips_through_restore() {
+        let (source_pool, source_writer) = setup_db();
+        let source = AppSyncRepository::new(source_pool.clone(), source_writer);
+        let mut conn = get_connection(&source_pool).expect("conn");
+        for id in ["acc-gzip-1", "acc-gzip-2", "acc-gzip-3"] {
+            insert_account_for_test(&mut conn, id).expect("insert account");
+        }
+        drop(conn);
+
+        let raw = source
+            .export_snapshot_sqlite_image(vec!["accounts".to_string()])
+            .await
+            .expect("export raw snapshot");
+        let encoded = source
+            .export_snapshot_sqlite_image_encoded(
+                vec!["accounts".to_string()],
+                SnapshotEncoding::Gzip,
+            )
+            .await
+            .expect("export gzip snapshot");
+        assert!(encoded.len() < raw.len(), "gzip should shrink the image");
+
+        let image = SnapshotEncoding::Gzip.decode(&encoded).expect("decode");
+        assert!(image.starts_with(b"SQLite format 3\0"));
+        let snapshot_path = tempdir().expect("tempdir").keep().join("gzip_snapshot.db");
+        std::fs::write(&snapshot_path, image).expect("write image");
+
+        let (target_pool, target_writer) = setup_db();
+        let target = AppSyncRepository::new(target_pool.clone(), target_writer);
+        target
+            .restore_snapshot_tables_from_file(
+                snapshot_path.to_string_lossy().to_string(),
+                SNAPSHOT_SCHEMA_VERSION,
+                vec!["accounts".to_string()],
+                42,
+                "device-1".to_string(),
+                Some(1),
             )
             .await
-            .expect("apply goal create");
-        assert!(applied, "expected goal create to apply");
-
-        let mut conn = get_connection(&pool).expect("conn");
-        let (target_amount_value, is_achieved_value): (f64, bool) = goals::table
-            .filter(goals::id.eq("goal-camel-case"))
-            .select((goals::target_amount, goals::is_achieved))
-            .first(&mut conn)
-            .expect("goal row");
-        assert_eq!(target_amount_value, 50000.0);
-        assert!(is_achieved_value);
+            .expect("restore snapshot");
+
+        let account_rows = |pool: &Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>| {
+            let mut conn = get_connection(pool).expect("conn");
+            accounts::table
+                .order(accounts::id.asc())
+                .select((accounts::id, accounts::name, accounts::currency))
+                .load::<(String, String, String)>(&mut conn)
+                .expect("load accounts")
+        };
+        let restored = account_rows(&target_pool);
+        assert_eq!(restored.len(), 3);
+        assert_eq!(restored, account_rows(&source_pool));
+        assert_eq!(target.get_cursor().expect("cursor"), 42);
     }
 
     #[tokio::test]
-    async fn replay_accepts_camel_case_non_id_primary_key_payload() {
-        let (pool, writer) = setup_db();
-        let repo = AppSyncRepository::new(pool.clone(), writer);
-        let mut conn = get_connection(&pool).expect("conn");
-        insert_account_for_test(&mut conn, "acc-import-profile").expect("insert account");
+    async fn delta_snapshot_carries_only_changed_rows_and_keeps_untouched_ones() {
+        let account_metadata = |id: &str, seq: i64| SyncEntityMetadata {
+            entity: SyncEntity::Account,
+            entity_id: id.to_string(),
+            last_event_id: format!("evt-{id}"),
+            last_client_timestamp: chrono::Utc::now().to_rfc3339(),
+            last_seq: seq,
+            last_device_id: None,
+            last_device_seq: None,
+        };
+        let account_rows = |pool: &Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>| {
+            let mut conn = get_connection(pool).expect("conn");
+            accounts::table
+                .order(accounts::id.asc())
+                .select((accounts::id, accounts::name))
+                .load::<(String, String)>(&mut conn)
+                .expect("load accounts")
+        };
 
-        let applied = repo
-            .apply_remote_event_lww(
-                SyncEntity::ActivityImportProfile,
-                "acc-import-profile".to_string(),
-                SyncOperation::Create,
-                "evt-import-profile-camel".to_string(),
-                "2026-02-19T00:00:00Z".to_string(),
-                1,
-                serde_json::json!({
-                    "accountId": "acc-import-profile",
-                    "name": "Broker Mapping",
-                    "config": "{\"rules\":[]}",
-                    "createdAt": "2026-02-19 00:00:00",
-                    "updatedAt": "2026-02-19 00:00:00"
-                }),
-            )
+        let (source_pool, source_writer) = setup_db();
+        let source = AppSyncRepository::new(source_pool.clone(), source_writer);
+        let mut conn = get_connection(&source_pool).expect("conn");
+        for id in ["acc-changed", "acc-stale"] {
+            insert_account_for_test(&mut conn, id).expect("insert account");
+        }
+        diesel::sql_query("UPDATE accounts SET name = 'Renamed' WHERE id = 'acc-changed'")
+            .execute(&mut conn)
+            .expect("rename account");
+        drop(conn);
+        for metadata in [
+            account_metadata("acc-stale", 3),
+            account_metadata("acc-changed", 8),
+            account_metadata("acc-deleted", 9),
+        ] {
+            source
+                .upsert_entity_metadata(metadata)
+                .await
+                .expect("upsert metadata");
+        }
+
+        let delta = source
+            .export_delta_snapshot_since(5, Vec::new())
             .await
-            .expect("apply import profile create");
-        assert!(applied, "expected import profile create to apply");
+            .expect("export delta");
+        assert_eq!(
+            delta.manifest,
+            DeltaSnapshotManifest {
+                since_seq: 5,
+                max_seq: 9,
+                entities: vec![DeltaSnapshotEntities {
+                    entity: SyncEntity::Account,
+                    entity_ids: vec!["acc-changed".to_string(), "acc-deleted".to_string()],
+                }],
+            }
+        );
+        let snapshot_path = tempdir().expect("tempdir").keep().join("delta_snapshot.db");
+        std::fs::write(&snapshot_path, &delta.image).expect("write image");
+
+        let (target_pool, target_writer) = setup_db();
+        let target = AppSyncRepository::new(target_pool.clone(), target_writer);
+        let mut conn = get_connection(&target_pool).expect("conn");
+        for id in ["acc-changed", "acc-deleted", "acc-untouched"] {
+            insert_account_for_test(&mut conn, id).expect("insert account");
+        }
+        drop(conn);
+        target.set_cursor(7).await.expect("set cursor");
 
-        let name_value: String = activity_import_profiles::table
-            .filter(activity_import_profiles::account_id.eq("acc-import-profile"))
-            .select(activity_import_profiles::name)
-            .first(&mut conn)
-            .expect("import profile row");
-        assert_eq!(name_value, "Broker Mapping");
+        target
+            .restore_delta_snapshot(snapshot_path.to_string_lossy().to_string(), delta.manifest)
+            .await
+            .expect("restore delta");
+
+        assert_eq!(
+            account_rows(&target_pool),
+            vec![
+                ("acc-changed".to_string(), "Renamed".to_string()),
+                ("acc-untouched".to_string(), "Sync Test".to_string()),
+            ]
+        );
+        assert_eq!(target.get_cursor().expect("cursor"), 7);
+
+        let empty = source
+            .export_delta_snapshot_since(9, Vec::new())
+            .await
+            .expect("export empty delta");
+        assert!(empty.manifest.is_empty());
+        assert!(empty.image.is_empty());
     }
 
     #[tokio::test]
-    async fn replay_batch_applies_out_of_order_account_and_platform_events() {
-        let (pool, writer) = setup_db();
-        let repo = AppSyncRepository::new(pool.clone(), writer);
+    async fn delta_snapshot_restores_parents_first_regardless_of_manifest_order() {
+        let (source_pool, source_writer) = setup_db();
+        let source = AppSyncRepository::new(source_pool.clone(), source_writer);
+        let mut conn = get_connection(&source_pool).expect("conn");
+        insert_account_for_test(&mut conn, "acc-delta-parent").expect("insert account");
+        diesel::sql_query(
+            "INSERT INTO activities (id, account_id, activity_type, activity_date, amount, currency, created_at, updated_at)
+             VALUES ('act-delta-child', 'acc-delta-parent', 'DEPOSIT', '2026-01-01', '100', 'USD', CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+        )
+        .execute(&mut conn)
+        .expect("insert activity");
+        drop(conn);
+        for (entity, entity_id) in [
+            (SyncEntity::Account, "acc-delta-parent"),
+            (SyncEntity::Activity, "act-delta-child"),
+        ] {
+            source
+                .upsert_entity_metadata(SyncEntityMetadata {
+                    entity,
+                    entity_id: entity_id.to_string(),
+                    last_event_id: format!("evt-{entity_id}"),
+                    last_client_timestamp: chrono::Utc::now().to_rfc3339(),
+                    last_seq: 4,
+                    last_device_id: None,
+                    last_device_seq: None,
+                })
+                .await
+                .expect("upsert metadata");
+        }
 
-        let applied = repo
-            .apply_remote_events_lww_batch(vec![
-                (
-                    SyncEntity::Account,
-                    "acc-batch-platform".to_string(),
-                    SyncOperation::Create,
-                    "evt-account-create".to_string(),
-                    "2026-02-17T00:00:00Z".to_string(),
-                    10,
-                    serde_json::json!({
-                        "id": "acc-batch-platform",
-                        "name": "Batch Account",
-                        "account_type": "cash",
-                        "group": serde_json::Value::Null,
-                        "currency": "USD",
-                        "is_default": false,
-                        "is_active": true,
-                        "platform_id": "platform-batch",
-                        "account_number": serde_json::Value::Null,
-                        "meta": serde_json::Value::Null,
-                        "provider": serde_json::Value::Null,
-                        "provider_account_id": serde_json::Value::Null,
-                        "is_archived": false,
-                        "tracking_mode": "portfolio"
-                    }),
-                ),
-                (
-                    SyncEntity::Platform,
-                    "platform-batch".to_string(),
-                    SyncOperation::Create,
-                    "evt-platform-create".to_string(),
-                    "2026-02-17T00:00:01Z".to_string(),
-                    11,
-                    serde_json::json!({
-                        "id": "platform-batch",
-                        "name": "Batch Platform",
-                        "url": "https://batch.example",
-                        "external_id": serde_json::Value::Null,
-                        "kind": "BROKERAGE",
-                        "website_url": serde_json::Value::Null,
-                        "logo_url": serde_json::Value::Null
-                    }),
-                ),
-            ])
+        let mut delta = source
+            .export_delta_snapshot_since(0, Vec::new())
             .await
-            .expect("apply replay batch");
-
-        assert_eq!(applied, 2, "both events should apply in one batch");
-        assert_eq!(count_account_rows(&pool, "acc-batch-platform"), 1);
-        assert_eq!(count_platform_rows(&pool, "platform-batch"), 1);
+            .expect("export delta");
+        delta.manifest.entities.reverse();
+        assert_eq!(delta.manifest.entities[0].entity, SyncEntity::Activity);
+        let snapshot_path = tempdir().expect("tempdir").keep().join("delta_snapshot.db");
+        std::fs::write(&snapshot_path, &delta.image).expect("write image");
+
+        let (target_pool, target_writer) = setup_db();
+        let target = AppSyncRepository::new(target_pool.clone(), target_writer);
+        target
+            .restore_delta_snapshot(snapshot_path.to_string_lossy().to_string(), delta.manifest)
+            .await
+            .expect("restore delta");
 
-        let mut conn = get_connection(&pool).expect("conn");
-        let account_platform_id: Option<String> = accounts::table
-            .filter(accounts::id.eq("acc-batch-platform"))
-            .select(accounts::platform_id)
-            .first(&mut conn)
-            .expect("account row");
-        assert_eq!(account_platform_id.as_deref(), Some("platform-batch"));
+        let mut conn = get_connection(&target_pool).expect("conn");
+        let restored = activities::table
+            .select((activities::id, activities::account_id))
+            .load::<(String, String)>(&mut conn)
+            .expect("load activities");
+        assert_eq!(
+            restored,
+            vec![(
+                "act-delta-child".to_string(),
+                "acc-delta-parent".to_string()
+            )]
+        );
     }
 
     #[tokio::test]
-    async fn snapshot_export_returns_sqlite_image() {
+    async fn snapshot_restore_vacuums_free_pages() {
+        let export_accounts = |count: usize| async move {
+            let (pool, writer) = setup_db();
+            let repo = AppSyncRepository::new(pool.clone(), writer);
+            let mut conn = get_connection(&pool).expect("conn");
+            for i in 0..count {
+                insert_account_for_test(&mut conn, &format!("acc-vacuum-{i}"))
+                    .expect("insert account");
+            }
+            diesel::sql_query(format!("UPDATE accounts SET name = '{}'", "x".repeat(2000)))
+                .execute(&mut conn)
+                .expect("pad account names");
+            drop(conn);
+            let image = repo
+                .export_snapshot_sqlite_image(vec!["accounts".to_string()])
+                .await
+                .expect("export snapshot");
+            let path = tempdir().expect("tempdir").keep().join("snapshot.db");
+            std::fs::write(&path, image).expect("write image");
+            path.to_string_lossy().to_string()
+        };
+        let large = export_accounts(2000).await;
+        let small = export_accounts(1).await;
+
         let (pool, writer) = setup_db();
         let repo = AppSyncRepository::new(pool.clone(), writer);
-        let mut conn = get_connection(&pool).expect("conn");
-        insert_account_for_test(&mut conn, "acc-export").expect("insert account");
+        let restore = |path: String| {
+            repo.restore_snapshot_tables_from_file(
+                path,
+                SNAPSHOT_SCHEMA_VERSION,
+                vec!["accounts".to_string()],
+                1,
+                "device-1".to_string(),
+                Some(1),
+            )
+        };
+        let file_size = || {
+            let mut conn = get_connection(&pool).expect("conn");
+            main_database_file_size(&mut conn).expect("file size")
+        };
 
-        let payload = repo
-            .export_snapshot_sqlite_image(vec!["accounts".to_string()])
-            .await
-            .expect("export snapshot");
+        restore(large).await.expect("restore large snapshot");
+        let size_after_large = file_size();
+        restore(small).await.expect("restore small snapshot");
+        let size_after_small = file_size();
         assert!(
-            payload.starts_with(b"SQLite format 3\0"),
-            "expected exported payload to be sqlite image"
+            size_after_small < size_after_large / 2,
+            "expected {} to shrink well below {}",
+            size_after_small,
+            size_after_large
         );
     }
 
@@ -2812,6 +7236,77 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn snapshot_export_can_exclude_archived_accounts() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        let mut conn = get_connection(&pool).expect("conn");
+        for id in ["acc-active", "acc-archived"] {
+            insert_account_for_test(&mut conn, id).expect("insert account");
+        }
+        diesel::sql_query("UPDATE accounts SET is_archived = 1 WHERE id = 'acc-archived'")
+            .execute(&mut conn)
+            .expect("archive account");
+        diesel::sql_query(
+            "INSERT INTO activities (id, account_id, activity_type, activity_date, amount, currency, created_at, updated_at)
+             VALUES
+             ('act-active', 'acc-active', 'DEPOSIT', '2026-01-01', '100', 'USD', CURRENT_TIMESTAMP, CURRENT_TIMESTAMP),
+             ('act-archived', 'acc-archived', 'DEPOSIT', '2026-01-01', '100', 'USD', CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+        )
+        .execute(&mut conn)
+        .expect("insert activities");
+        drop(conn);
+
+        let exported_ids = |payload: Vec<u8>| {
+            let path = tempdir().expect("tempdir").keep().join("snapshot.db");
+            std::fs::write(&path, payload).expect("write snapshot db");
+            let mut exported_conn = SqliteConnection::establish(path.to_string_lossy().as_ref())
+                .expect("open snapshot db");
+            let load = |conn: &mut SqliteConnection, table: &str| {
+                diesel::sql_query(format!("SELECT id AS row_text FROM {table} ORDER BY id"))
+                    .load::<RowTextResult>(conn)
+                    .expect("load ids")
+                    .into_iter()
+                    .map(|row| row.row_text)
+                    .collect::<Vec<_>>()
+            };
+            (
+                load(&mut exported_conn, "accounts"),
+                load(&mut exported_conn, "activities"),
+            )
+        };
+        let tables = vec!["accounts".to_string(), "activities".to_string()];
+
+        let everything = repo
+            .export_snapshot_sqlite_image(tables.clone())
+            .await
+            .expect("default export");
+        assert_eq!(
+            exported_ids(everything),
+            (
+                vec!["acc-active".to_string(), "acc-archived".to_string()],
+                vec!["act-active".to_string(), "act-archived".to_string()],
+            )
+        );
+
+        let filtered = repo
+            .export_snapshot_sqlite_image_with_options(
+                tables,
+                SnapshotExportOptions {
+                    exclude_archived_accounts: true,
+                },
+            )
+            .await
+            .expect("filtered export");
+        assert_eq!(
+            exported_ids(filtered),
+            (
+                vec!["acc-active".to_string()],
+                vec!["act-active".to_string()],
+            )
+        );
+    }
+
     #[test]
     fn quote_identifier_escapes_backticks() {
         assert_eq!(quote_identifier("col`name"), "`col``name`");
@@ -2829,6 +7324,132 @@ mod tests {
         assert_eq!(sql, "'''; DROP TABLE accounts; --'");
     }
 
+    #[test]
+    fn json_number_literals_keep_integers_and_decimals_exact() {
+        let literal = |v: serde_json::Value| json_value_to_sql_literal(&v);
+        assert_eq!(
+            literal(serde_json::json!(9_007_199_254_740_993_i64)),
+            "9007199254740993"
+        );
+        assert_eq!(
+            literal(serde_json::json!(u64::MAX)),
+            "'18446744073709551615'"
+        );
+        assert_eq!(literal(serde_json::json!(100.0)), "100");
+        assert_eq!(literal(serde_json::json!(1234.56)), "'1234.56'");
+        assert_eq!(
+            literal(serde_json::json!(0.1 + 0.2)),
+            "'0.30000000000000004'"
+        );
+        assert_eq!(literal(serde_json::json!(1e-7)), "'0.0000001'");
+
+        #[derive(diesel::QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            id: i64,
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            amount: String,
+        }
+        let mut conn = SqliteConnection::establish(":memory:").expect("open db");
+        diesel::sql_query("CREATE TABLE t (id INTEGER, amount TEXT)")
+            .execute(&mut conn)
+            .expect("create table");
+        diesel::sql_query(format!(
+            "INSERT INTO t (id, amount) VALUES ({}, {})",
+            literal(serde_json::json!(9_007_199_254_740_993_i64)),
+            literal(serde_json::json!(123456789.12345679)),
+        ))
+        .execute(&mut conn)
+        .expect("insert");
+        let row: Row = diesel::sql_query("SELECT id, amount FROM t")
+            .get_result(&mut conn)
+            .expect("select");
+        assert_eq!(row.id, 9_007_199_254_740_993);
+        assert_eq!(row.amount, "123456789.12345679");
+    }
+
+    #[tokio::test]
+    async fn replay_decodes_base64_payload_into_blob_column() {
+        let (pool, writer) = setup_db();
+        {
+            let mut conn = get_connection(&pool).expect("conn");
+            diesel::sql_query("ALTER TABLE holdings_snapshots ADD COLUMN positions_packed BLOB")
+                .execute(&mut conn)
+                .expect("add blob column");
+            load_payload_column_catalog(&mut conn, "holdings_snapshots").expect("catalog");
+            // The catalog cache is shared across test databases; extend the
+            // cached entry in place instead of evicting it.
+            let mut cache = payload_column_catalog_cache().lock().expect("cache");
+            let catalog = cache.get_mut("holdings_snapshots").expect("cached catalog");
+            catalog.writable.insert("positions_packed".to_string());
+            catalog
+                .affinities
+                .insert("positions_packed".to_string(), ColumnAffinity::Blob);
+            catalog.blob_columns.insert("positions_packed".to_string());
+        }
+
+        let payload: Vec<u8> = vec![0x00, 0xff, 0x10, b'{', 0x7f];
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+        repo.apply_remote_event_lww(
+            SyncEntity::Snapshot,
+            "snap-blob".to_string(),
+            SyncOperation::Create,
+            "evt-snap-blob".to_string(),
+            "2026-02-15T00:00:00Z".to_string(),
+            1,
+            serde_json::json!({
+                "id": "snap-blob",
+                "account_id": "acc-blob",
+                "snapshot_date": "2026-02-14",
+                "currency": "USD",
+                "positions_packed": BASE64.encode(&payload),
+            }),
+            None,
+        )
+        .await
+        .expect("apply snapshot with blob");
+
+        #[derive(diesel::QueryableByName)]
+        struct Row {
+            #[diesel(sql_type = diesel::sql_types::Binary)]
+            positions_packed: Vec<u8>,
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            kind: String,
+        }
+        let mut conn = get_connection(&pool).expect("conn");
+        let row: Row = diesel::sql_query(
+            "SELECT positions_packed, typeof(positions_packed) AS kind \
+             FROM holdings_snapshots WHERE id = 'snap-blob'",
+        )
+        .get_result(&mut conn)
+        .expect("select blob");
+        assert_eq!(row.kind, "blob");
+        assert_eq!(row.positions_packed, payload);
+
+        let invalid = repo
+            .apply_remote_event_lww(
+                SyncEntity::Snapshot,
+                "snap-blob-invalid".to_string(),
+                SyncOperation::Create,
+                "evt-snap-blob-invalid".to_string(),
+                "2026-02-15T00:00:01Z".to_string(),
+                2,
+                serde_json::json!({
+                    "id": "snap-blob-invalid",
+                    "account_id": "acc-blob",
+                    "snapshot_date": "2026-02-15",
+                    "currency": "USD",
+                    "positions_packed": "not base64!",
+                }),
+                None,
+            )
+            .await;
+        let err_msg = invalid
+            .expect_err("invalid base64 should be rejected")
+            .to_string();
+        assert!(err_msg.contains("invalid base64"), "{}", err_msg);
+    }
+
     #[tokio::test]
     async fn replay_rejects_unknown_columns() {
         let (pool, writer) = setup_db();
@@ -2846,6 +7467,7 @@ mod tests {
                     "id": "acc-unknown-col",
                     "nonexistent_column": "value"
                 }),
+                None,
             )
             .await;
 
@@ -2858,6 +7480,85 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn replay_rejects_mistyped_integer_column() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let result = repo
+            .apply_remote_event_lww(
+                SyncEntity::Account,
+                "acc-mistyped".to_string(),
+                SyncOperation::Create,
+                "evt-mistyped".to_string(),
+                "2026-02-15T00:00:00Z".to_string(),
+                1,
+                serde_json::json!({
+                    "id": "acc-mistyped",
+                    "name": "Mistyped",
+                    "account_type": "cash",
+                    "currency": "USD",
+                    "is_default": false,
+                    "is_active": true,
+                    "is_archived": "not-a-number",
+                    "tracking_mode": "portfolio"
+                }),
+                None,
+            )
+            .await;
+
+        let err_msg = result.expect_err("mistyped column rejected").to_string();
+        assert!(
+            err_msg.contains("'is_archived'") && err_msg.contains("INTEGER"),
+            "error should name the column and affinity: {}",
+            err_msg
+        );
+        assert_eq!(count_account_rows(&pool, "acc-mistyped"), 0);
+    }
+
+    #[test]
+    fn column_affinity_follows_sqlite_rules() {
+        assert_eq!(
+            ColumnAffinity::from_declared_type("BIGINT"),
+            ColumnAffinity::Integer
+        );
+        assert_eq!(
+            ColumnAffinity::from_declared_type("varchar(255)"),
+            ColumnAffinity::Text
+        );
+        assert_eq!(ColumnAffinity::from_declared_type(""), ColumnAffinity::Blob);
+        assert_eq!(
+            ColumnAffinity::from_declared_type("DOUBLE"),
+            ColumnAffinity::Real
+        );
+        assert_eq!(
+            ColumnAffinity::from_declared_type("TIMESTAMP"),
+            ColumnAffinity::Numeric
+        );
+
+        let integer = ColumnAffinity::Integer;
+        assert!(payload_value_fits_affinity(
+            &serde_json::json!(" 42 "),
+            integer
+        ));
+        assert!(payload_value_fits_affinity(
+            &serde_json::json!(true),
+            integer
+        ));
+        assert!(!payload_value_fits_affinity(
+            &serde_json::json!("abc"),
+            integer
+        ));
+        assert!(!payload_value_fits_affinity(
+            &serde_json::json!([1]),
+            integer
+        ));
+        assert!(payload_value_fits_affinity(
+            &serde_json::json!("2026-02-15T00:00:00Z"),
+            ColumnAffinity::Numeric
+        ));
+    }
+
     #[tokio::test]
     async fn replay_rejects_conflicting_alias_columns() {
         let (pool, writer) = setup_db();
@@ -2879,6 +7580,7 @@ mod tests {
                     "isAchieved": false,
                     "is_achieved": true
                 }),
+                None,
             )
             .await;
 
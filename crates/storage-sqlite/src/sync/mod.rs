@@ -24,8 +24,10 @@ pub mod broker_ingest {
 // Re-export for convenience
 pub(crate) use app_sync::flush_projected_outbox;
 pub use app_sync::{
-    insert_outbox_event, AppSyncRepository, OutboxWriteRequest, SqliteSyncEngineDbPorts,
-    SyncLocalDataSummary, SyncTableRowCount,
+    cleanup_orphaned_snapshot_temp_files, insert_outbox_event, AppSyncRepository, DeltaSnapshot,
+    DeltaSnapshotEntities, DeltaSnapshotManifest, FieldDiff, OutboxWriteRequest,
+    SnapshotExportOptions, SqliteSyncEngineDbPorts, SyncDryRunSummary, SyncEntityLag,
+    SyncLocalDataSummary, SyncOutboxDiagnostic, SyncTableDrift, SyncTableRowCount,
 };
 pub use import_run::{ImportRunDB, ImportRunRepository};
 pub use platform::{Platform, PlatformDB, PlatformRepository};
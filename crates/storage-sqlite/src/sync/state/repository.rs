@@ -339,6 +339,53 @@ impl BrokerSyncStateRepository {
             .await
     }
 
+    /// Replace the checkpoint, creating the sync state if needed
+    pub async fn upsert_checkpoint(
+        &self,
+        account_id: String,
+        provider: String,
+        checkpoint: serde_json::Value,
+    ) -> Result<()> {
+        self.writer
+            .exec(move |conn| {
+                let now_str = Utc::now().to_rfc3339();
+                let checkpoint_json = serde_json::to_string(&checkpoint)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+
+                let updated =
+                    diesel::update(brokers_sync_state::table.find((&account_id, &provider)))
+                        .set((
+                            brokers_sync_state::checkpoint_json.eq(&checkpoint_json),
+                            brokers_sync_state::updated_at.eq(&now_str),
+                        ))
+                        .execute(conn)
+                        .map_err(StorageError::from)?;
+
+                if updated == 0 {
+                    let new_state = BrokerSyncStateDB {
+                        account_id,
+                        provider,
+                        checkpoint_json: Some(checkpoint_json),
+                        last_attempted_at: None,
+                        last_successful_at: None,
+                        last_error: None,
+                        last_run_id: None,
+                        sync_status: "IDLE".to_string(),
+                        created_at: now_str.clone(),
+                        updated_at: now_str,
+                    };
+
+                    diesel::insert_into(brokers_sync_state::table)
+                        .values(&new_state)
+                        .execute(conn)
+                        .map_err(StorageError::from)?;
+                }
+
+                Ok(())
+            })
+            .await
+    }
+
     /// Get all sync states for an account
     pub fn get_for_account(&self, account_id: &str) -> Result<Vec<BrokerSyncState>> {
         let mut conn = get_connection(&self.pool)?;
@@ -432,6 +479,15 @@ impl ConnectBrokerSyncStateRepositoryTrait for BrokerSyncStateRepository {
         .await
     }
 
+    async fn upsert_checkpoint(
+        &self,
+        account_id: String,
+        provider: String,
+        checkpoint: serde_json::Value,
+    ) -> Result<()> {
+        BrokerSyncStateRepository::upsert_checkpoint(self, account_id, provider, checkpoint).await
+    }
+
     fn get_all(&self) -> Result<Vec<BrokerSyncState>> {
         BrokerSyncStateRepository::get_all(self)
     }